@@ -0,0 +1,477 @@
+//! Easy as ABC (also known as ABC End View) is a letter puzzle played on a square grid,
+//! with a clue at some ends of each row and column.
+//!
+//! Problem: fill some cells of the grid with the first few letters of the alphabet
+//! and leave the rest blank, such that each row and column contains each letter exactly once,
+//! and each clue is the first letter seen when looking into its row or column from the clue's end.
+//!
+//! For example, in the following puzzle with the letters A to C
+//! (with clues around the edges, `.` for ends without clues, `_` for cells to fill in,
+//! and `-` for blank cells):
+//! ```text
+//!   . A A . .
+//! . _ _ _ _ _ .
+//! A _ _ _ _ _ .
+//! . _ _ _ _ _ C
+//! A _ _ _ _ _ .
+//! . _ _ _ _ _ C
+//!   . . . . .
+//! ```
+//! the unique solution is:
+//! ```text
+//!   . A A . .
+//! . C - - B A .
+//! A - - A C B .
+//! . B A C - - C
+//! A A C B - - .
+//! . - B - A C C
+//!   . . . . .
+//! ```
+//!
+//! The solver keeps track of what each cell could still contain (a letter or a blank),
+//! and repeatedly narrows down each row and column to the contents allowed by
+//! at least one arrangement of the line's letters and blanks that agrees with its clues.
+//! When nothing more can be narrowed down, it guesses the contents of the most constrained cell,
+//! and backtracks if the guess leads to a contradiction.
+
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// An Easy as ABC puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbcBoard {
+    size: usize,
+    letters: usize,
+    row_clues: Vec<Vec<Option<char>>>,
+    col_clues: Vec<Vec<Option<char>>>,
+}
+
+impl AbcBoard {
+    /// Creates a `size` by `size` puzzle using the first `letters` letters of the alphabet,
+    /// with the given clues:
+    /// `row_clues[y]` is `[left, right]` for row `y`, and `col_clues[x]` is `[top, bottom]`
+    /// for column `x`, with None for an end without a clue.
+    ///
+    /// Returns Err(clue) if `clue` isn't one of the letters used by the puzzle.
+    /// If there are multiple such clues, the first one in the row clues,
+    /// or else the first one in the column clues, is returned.
+    ///
+    /// Panics if there are more letters than cells in a line or letters in the alphabet,
+    /// or if there aren't two clues (or lack of clues) for each row and column.
+    pub fn new(
+        size: usize,
+        letters: usize,
+        row_clues: Vec<Vec<Option<char>>>,
+        col_clues: Vec<Vec<Option<char>>>,
+    ) -> Result<AbcBoard, char> {
+        assert!(
+            letters <= size && letters <= 26,
+            "can't use {letters} letters in a puzzle of size {size}"
+        );
+        for clues in [&row_clues, &col_clues] {
+            assert!(
+                clues.len() == size && clues.iter().all(|ends| ends.len() == 2),
+                "there must be two clues for each of the {size} rows and columns"
+            );
+        }
+        let invalid = row_clues
+            .iter()
+            .chain(&col_clues)
+            .flatten()
+            .flatten()
+            .find(|&&clue| letter_number(clue).is_none_or(|number| number > letters));
+        if let Some(&clue) = invalid {
+            return Err(clue);
+        }
+        Ok(AbcBoard {
+            size,
+            letters,
+            row_clues,
+            col_clues,
+        })
+    }
+
+    /// The number of cells in each row and column.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of letters used, starting from A.
+    pub fn letters(&self) -> usize {
+        self.letters
+    }
+
+    /// The `[left, right]` clues for each row.
+    pub fn row_clues(&self) -> &[Vec<Option<char>>] {
+        &self.row_clues
+    }
+
+    /// The `[top, bottom]` clues for each column.
+    pub fn col_clues(&self) -> &[Vec<Option<char>>] {
+        &self.col_clues
+    }
+}
+
+/// Returns the position of the given letter in the alphabet, starting from 1 for A,
+/// or None if it isn't an uppercase letter.
+fn letter_number(letter: char) -> Option<usize> {
+    letter
+        .is_ascii_uppercase()
+        .then(|| usize::from(letter as u8 - b'A') + 1)
+}
+
+/// A set of possible contents for a cell, where bit 0 is set if the cell could be blank,
+/// and bit `k` is set if the cell could contain the `k`th letter of the alphabet.
+type Contents = u32;
+
+/// The contents of each cell of a row or column, as numbers:
+/// 0 for a blank cell, or the position of a letter in the alphabet.
+type Arrangement = Vec<u8>;
+
+/// A row or column of a puzzle, with the arrangements of it that agree with its clues.
+struct Line {
+    cells: Vec<CellIndex>,
+    arrangements: Vec<Arrangement>,
+}
+
+/// Returns every arrangement of `letters` letters and `size - letters` blanks.
+fn arrangements(size: usize, letters: usize) -> Vec<Arrangement> {
+    /// Adds every completion of the given partial arrangement to `result`.
+    fn extend(
+        arrangement: &mut Arrangement,
+        size: usize,
+        letters: usize,
+        result: &mut Vec<Arrangement>,
+    ) {
+        if arrangement.len() == size {
+            result.push(arrangement.clone());
+            return;
+        }
+        let blanks = arrangement
+            .iter()
+            .filter(|&&contents| contents == 0)
+            .count();
+        let choices = (0..=letters as u8).filter(|&contents| {
+            if contents == 0 {
+                blanks < size - letters
+            } else {
+                !arrangement.contains(&contents)
+            }
+        });
+        for contents in choices.collect::<Vec<_>>() {
+            arrangement.push(contents);
+            extend(arrangement, size, letters, result);
+            arrangement.pop();
+        }
+    }
+    let mut result = vec![];
+    extend(&mut vec![], size, letters, &mut result);
+    result
+}
+
+/// Returns the first letter among the given contents, as a number, or None if they're all blank.
+fn first_letter<'a>(mut contents: impl Iterator<Item = &'a u8>) -> Option<usize> {
+    contents
+        .find(|&&contents| contents != 0)
+        .map(|&contents| usize::from(contents))
+}
+
+/// Returns whether the given arrangement agrees with the given clues at its start and end.
+fn agrees_with(arrangement: &Arrangement, start: Option<char>, end: Option<char>) -> bool {
+    let agrees = |clue: Option<char>, seen| clue.is_none_or(|clue| letter_number(clue) == seen);
+    agrees(start, first_letter(arrangement.iter()))
+        && agrees(end, first_letter(arrangement.iter().rev()))
+}
+
+/// Returns a solution to the given puzzle, with None for blank cells,
+/// or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &AbcBoard) -> Option<Grid<Option<char>>> {
+    let AbcBoard {
+        size,
+        letters,
+        row_clues,
+        col_clues,
+    } = board;
+    let (size, letters) = (*size, *letters);
+    let all_arrangements = arrangements(size, letters);
+    let line = |cells: Vec<CellIndex>, clues: &[Option<char>]| Line {
+        cells,
+        arrangements: all_arrangements
+            .iter()
+            .filter(|arrangement| agrees_with(arrangement, clues[0], clues[1]))
+            .cloned()
+            .collect(),
+    };
+    let rows = (0..size).map(|y| line((0..size).map(|x| (x, y)).collect(), &row_clues[y]));
+    let cols = (0..size).map(|x| line((0..size).map(|y| (x, y)).collect(), &col_clues[x]));
+    let lines: Vec<Line> = rows.chain(cols).collect();
+    let all_contents: Contents = (1 << (letters + 1)) - 1;
+    let candidates = search(Grid::filled(all_contents, (size, size)), &lines)?;
+    Some(candidates.map(|contents| match contents.trailing_zeros() {
+        0 => None,
+        number => Some((b'A' + number as u8 - 1) as char),
+    }))
+    /*
+        Time complexity analysis:
+        Finding a solution can take exponential time in the size of the puzzle in the worst case.
+        Let `n` be the size of the puzzle and `k` be the number of letters.
+        There are `a = n! / (n - k)!` arrangements of each line,
+        so each round of narrowing down the `2 * n` lines takes `O(n^2 * a)` time,
+        and each round except the last removes one of the `O(n^2 * k)` possible contents,
+        so each guess takes `O(n^4 * k * a)` time to propagate in the very worst case.
+    */
+}
+
+/// Tries to narrow down the given candidates to a single possibility for each cell,
+/// such that every line has a valid arrangement.
+///
+/// Returns the narrowed-down candidates, or None if that's impossible.
+fn search(mut candidates: Grid<Contents>, lines: &[Line]) -> Option<Grid<Contents>> {
+    if !propagate(&mut candidates, lines) {
+        return None;
+    }
+    // Guess the contents of the cell with the fewest remaining possibilities (besides 1).
+    let undecided = candidates
+        .enumerate::<CellIndex>()
+        .filter(|&(_, &contents)| contents.count_ones() > 1)
+        .min_by_key(|&(_, &contents)| contents.count_ones());
+    let Some((index, &contents)) = undecided else {
+        return Some(candidates);
+    };
+    (0..Contents::BITS)
+        .filter(|&number| contents & (1 << number) != 0)
+        .find_map(|number| {
+            let mut candidates = candidates.clone();
+            candidates[index] = 1 << number;
+            search(candidates, lines)
+        })
+}
+
+/// Narrows down the candidates for each line to the contents allowed by
+/// at least one of the line's arrangements, until nothing more can be narrowed down.
+///
+/// Returns false if some line has no possible arrangements left.
+/// When every cell is narrowed down to a single possibility and this returns true,
+/// the candidates form a solution.
+fn propagate(candidates: &mut Grid<Contents>, lines: &[Line]) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for line in lines {
+            let mut allowed: Vec<Contents> = vec![0; line.cells.len()];
+            let possible = line.arrangements.iter().filter(|arrangement| {
+                line.cells
+                    .iter()
+                    .zip(arrangement.iter())
+                    .all(|(&cell, &contents)| candidates[cell] & (1 << contents) != 0)
+            });
+            let mut any_possible = false;
+            for arrangement in possible {
+                any_possible = true;
+                for (allowed, &contents) in allowed.iter_mut().zip(arrangement) {
+                    *allowed |= 1 << contents;
+                }
+            }
+            if !any_possible {
+                return false;
+            }
+            for (&cell, allowed) in line.cells.iter().zip(allowed) {
+                if candidates[cell] != allowed {
+                    candidates[cell] = allowed;
+                    changed = true;
+                }
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::abc_puzzle::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates clues or a solution for an Easy as ABC puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// abc![
+    ///     [<`.` for no clue or `-` for a blank cell, or a letter> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! abc {
+        (@cell .) => {None};
+        (@cell -) => {None};
+        (@cell $letter:ident) => {Some(stringify!($letter).chars().next().unwrap())};
+        ($([$($cell:tt)*])*) => {
+            vec![$(vec![$(abc!(@cell $cell)),*]),*]
+        };
+    }
+
+    /// Converts a 2D `Vec` from [`abc!`] into a grid.
+    fn grid(rows: Vec<Vec<Option<char>>>) -> Grid<Option<char>> {
+        let (width, height) = (rows.first().map_or(0, Vec::len), rows.len());
+        let mut result = Grid::filled(None, (width, height));
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, cell) in row.into_iter().enumerate() {
+                result[(x, y)] = cell;
+            }
+        }
+        result
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &AbcBoard, solution: &Grid<Option<char>>) {
+        let size = board.size();
+        assert_eq!(solution.dimensions(), (size, size));
+        let letters: Vec<char> = ('A'..='Z').take(board.letters()).collect();
+        let rows = (0..size).map(|y| {
+            let line: Vec<Option<char>> = (0..size).map(|x| solution[(x, y)]).collect();
+            (line, &board.row_clues()[y])
+        });
+        let cols = (0..size).map(|x| {
+            let line: Vec<Option<char>> = (0..size).map(|y| solution[(x, y)]).collect();
+            (line, &board.col_clues()[x])
+        });
+        for (line, clues) in rows.chain(cols) {
+            let mut line_letters: Vec<char> = line.iter().flatten().copied().collect();
+            line_letters.sort();
+            assert_eq!(
+                line_letters, letters,
+                "{line:?} doesn't contain each letter once"
+            );
+            let first_from_start = line.iter().flatten().next().copied();
+            let first_from_end = line.iter().rev().flatten().next().copied();
+            for (clue, seen) in [(clues[0], first_from_start), (clues[1], first_from_end)] {
+                if clue.is_some() {
+                    assert_eq!(seen, clue, "clue for {line:?} isn't satisfied");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let row_clues = abc![[. .] [A .] [. C] [A .] [. C]];
+        let col_clues = abc![[. .] [A .] [A .] [. .] [. .]];
+        let board = AbcBoard::new(5, 3, row_clues, col_clues).unwrap();
+        let expected = grid(abc![
+            [C - - B A]
+            [- - A C B]
+            [B A C - -]
+            [A C B - -]
+            [- B - A C]
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_letters_a_to_e() {
+        // With as many letters as cells in a line, there are no blank cells.
+        let row_clues = abc![[. A] [D .] [C .] [. D] [. .]];
+        let col_clues = abc![[. .] [. A] [. .] [B C] [. .]];
+        let board = AbcBoard::new(5, 5, row_clues, col_clues).unwrap();
+        let expected = grid(abc![
+            [E D C B A]
+            [D B E A C]
+            [C E A D B]
+            [A C B E D]
+            [B A D C E]
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_4_by_4() {
+        let row_clues = abc![[C .] [. C] [B .] [. .]];
+        let col_clues = abc![[. .] [. .] [C .] [. .]];
+        let board = AbcBoard::new(4, 3, row_clues, col_clues).unwrap();
+        let expected = grid(abc![
+            [C A - B]
+            [A B C -]
+            [B - A C]
+            [- C B A]
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::no_clues(4, 2, abc![[. .] [. .] [. .] [. .]], abc![[. .] [. .] [. .] [. .]])]
+    #[case::all_blank(3, 0, abc![[. .] [. .] [. .]], abc![[. .] [. .] [. .]])]
+    // The same letter can be seen from both ends of a line if it's the only letter.
+    #[case::one_letter(3, 1, abc![[A A] [. .] [. .]], abc![[. .] [A A] [. .]])]
+    fn test_solve(
+        #[case] size: usize,
+        #[case] letters: usize,
+        #[case] row_clues: Vec<Vec<Option<char>>>,
+        #[case] col_clues: Vec<Vec<Option<char>>>,
+    ) {
+        let board = AbcBoard::new(size, letters, row_clues, col_clues).unwrap();
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    // With more than one letter, each end of a row sees a different letter.
+    #[case::same_letter_both_ends(
+        5,
+        3,
+        abc![[A A] [. .] [. .] [. .] [. .]],
+        abc![[. .] [. .] [. .] [. .] [. .]],
+    )]
+    // With no blank cells, the top left cell must be both A and B.
+    #[case::corner(3, 3, abc![[A .] [. .] [. .]], abc![[B .] [. .] [. .]])]
+    // With no blank cells, every row would start with A, so the first column would be all As.
+    #[case::all_rows_start_with_a(3, 3, abc![[A .] [A .] [A .]], abc![[. .] [. .] [. .]])]
+    fn test_unsolvable(
+        #[case] size: usize,
+        #[case] letters: usize,
+        #[case] row_clues: Vec<Vec<Option<char>>>,
+        #[case] col_clues: Vec<Vec<Option<char>>>,
+    ) {
+        let board = AbcBoard::new(size, letters, row_clues, col_clues).unwrap();
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case::letter_not_used(abc![[. C] [. .]], abc![[. .] [. .]], 'C')]
+    #[case::not_a_letter(abc![[. .] [. .]], abc![[. .] [a .]], 'a')]
+    #[case::first_invalid(abc![[. .] [Z .]], abc![[Y .] [. .]], 'Z')]
+    fn test_invalid_board(
+        #[case] row_clues: Vec<Vec<Option<char>>>,
+        #[case] col_clues: Vec<Vec<Option<char>>>,
+        #[case] expected: char,
+    ) {
+        assert_eq!(AbcBoard::new(2, 2, row_clues, col_clues), Err(expected));
+    }
+
+    #[test]
+    #[should_panic(expected = "can't use 4 letters in a puzzle of size 3")]
+    fn test_too_many_letters() {
+        let _ = AbcBoard::new(3, 4, abc![[. .] [. .] [. .]], abc![[. .] [. .] [. .]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "there must be two clues for each of the 2 rows and columns")]
+    fn test_wrong_number_of_clues() {
+        let _ = AbcBoard::new(2, 1, abc![[. .] [. .]], abc![[. .] [.]]);
+    }
+
+    #[test]
+    fn test_empty_board() {
+        let board = AbcBoard::new(0, 0, vec![], vec![]).unwrap();
+        assert_eq!(solve(&board), Some(Grid::filled(None, (0, 0))));
+    }
+}
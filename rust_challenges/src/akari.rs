@@ -0,0 +1,473 @@
+//! Akari (or Light Up) is a puzzle played on a rectangular grid of black and white cells,
+//! where some black cells are numbered.
+//!
+//! Problem: place light bulbs on some of the white cells, such that:
+//! - every white cell is illuminated -- a bulb illuminates its own cell,
+//!   and every cell in the same row or column that it can see without a black cell in the way;
+//! - no bulb is illuminated by another bulb; and
+//! - each numbered black cell is orthogonally adjacent to exactly that many bulbs.
+//!
+//! For example, the following puzzle
+//! (`.` for white cells, `#` for black cells without numbers, and digits for numbered black cells):
+//! ```text
+//! . . . 1 .
+//! . . . . .
+//! 0 . . . .
+//! 0 # . 1 .
+//! . . 1 . .
+//! ```
+//! has the unique solution (`*` for bulbs):
+//! ```text
+//! . . * 1 .
+//! . * . . .
+//! 0 . . * .
+//! 0 # . 1 .
+//! . * 1 . *
+//! ```
+//!
+//! The solver keeps track of which white cells definitely have or don't have a bulb,
+//! and deduces more cells from the numbered black cells and from the cells that still need light.
+//! When no more cells can be deduced,
+//! it picks the unlit cell with the fewest cells that could still light it up,
+//! tries a bulb in each of them, and backtracks if that leads to a contradiction.
+
+use crate::grid::{Grid, ORTHOGONAL_OFFSETS};
+
+type CellIndex = (usize, usize);
+
+/// A cell of an Akari puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AkariCell {
+    /// A black cell, with an optional number of adjacent bulbs.
+    Black(Option<u8>),
+    White,
+}
+
+/// An Akari puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AkariBoard(pub Grid<AkariCell>);
+
+/// Returns, for each white cell, every white cell that a bulb there would illuminate
+/// (including the cell itself), or an empty list for black cells.
+fn lines_of_sight(grid: &Grid<AkariCell>) -> Grid<Vec<CellIndex>> {
+    let mut result = Grid::filled(vec![], grid.dimensions());
+    for (cell, &contents) in grid.enumerate::<CellIndex>() {
+        if contents != AkariCell::White {
+            continue;
+        }
+        let (x, y) = (cell.0 as i32, cell.1 as i32);
+        result[cell].push(cell);
+        for (dx, dy) in ORTHOGONAL_OFFSETS {
+            let (mut curr_x, mut curr_y) = (x + dx, y + dy);
+            while grid.get((curr_x, curr_y)) == Some(&AkariCell::White) {
+                result[cell].push((curr_x as usize, curr_y as usize));
+                (curr_x, curr_y) = (curr_x + dx, curr_y + dy);
+            }
+        }
+    }
+    result
+}
+
+/// Returns whether the given placement of bulbs (where `true` is a bulb)
+/// is a valid solution to the given puzzle.
+///
+/// Returns false if the dimensions of the placement don't match the puzzle.
+pub fn is_solution(board: &AkariBoard, bulbs: &Grid<bool>) -> bool {
+    let AkariBoard(grid) = board;
+    if bulbs.dimensions() != grid.dimensions() {
+        return false;
+    }
+    let sight = lines_of_sight(grid);
+    grid.enumerate::<CellIndex>()
+        .all(|(cell, &contents)| match contents {
+            AkariCell::White => {
+                let num_lighting = sight[cell].iter().filter(|&&other| bulbs[other]).count();
+                // A bulb must only be lit by itself; any other cell must be lit by something.
+                if bulbs[cell] {
+                    num_lighting == 1
+                } else {
+                    num_lighting > 0
+                }
+            }
+            AkariCell::Black(number) => {
+                let num_adjacent = bulbs
+                    .orthogonal_neighbors(cell)
+                    .filter(|&(_, &bulb)| bulb)
+                    .count();
+                !bulbs[cell] && number.is_none_or(|number| num_adjacent == usize::from(number))
+            }
+        })
+}
+
+/// Returns a placement of bulbs solving the given puzzle, where `true` is a bulb,
+/// or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &AkariBoard) -> Option<Grid<bool>> {
+    let AkariBoard(grid) = board;
+    let sight = lines_of_sight(grid);
+    // Black cells never have bulbs.
+    let state = grid.clone().map(|contents| match contents {
+        AkariCell::Black(_) => Some(false),
+        AkariCell::White => None,
+    });
+    let state = search(state, grid, &sight)?;
+    Some(state.map(|cell| cell == Some(true)))
+    /*
+        Time complexity analysis:
+        The puzzle is NP-complete in general,
+        and the search can take exponential time in the number of cells in the worst case.
+        Let `w` and `h` be the width and height of the grid, and `c = w * h`.
+        Each round of deductions takes `O(c * (w + h))` time,
+        since each cell can see `O(w + h)` other cells.
+    */
+}
+
+/// Tries to complete the given partial solution
+/// (where `Some(true)` is a bulb, `Some(false)` is no bulb, and None is undecided).
+///
+/// Undecided cells in the returned solution don't have bulbs.
+/// Returns None if there is no solution.
+fn search(
+    mut state: Grid<Option<bool>>,
+    grid: &Grid<AkariCell>,
+    sight: &Grid<Vec<CellIndex>>,
+) -> Option<Grid<Option<bool>>> {
+    if !propagate(&mut state, grid, sight) {
+        return None;
+    }
+    // Light up the unlit cell with the fewest cells that could light it.
+    let unlit = grid
+        .enumerate::<CellIndex>()
+        .filter(|&(cell, &contents)| contents == AkariCell::White && !is_lit(&state, sight, cell))
+        .map(|(cell, _)| {
+            let candidates: Vec<CellIndex> = sight[cell]
+                .iter()
+                .copied()
+                .filter(|&other| state[other].is_none())
+                .collect();
+            candidates
+        })
+        .min_by_key(|candidates| candidates.len());
+    let Some(candidates) = unlit else {
+        // Every cell is lit, so the remaining cells can be left empty if that satisfies the numbers.
+        let state = state.map(|cell| Some(cell == Some(true)));
+        return numbers_satisfied(&state, grid).then_some(state);
+    };
+    candidates.into_iter().find_map(|cell| {
+        let mut state = state.clone();
+        state[cell] = Some(true);
+        search(state, grid, sight)
+    })
+}
+
+/// Returns whether the given cell is illuminated by a bulb in the given partial solution.
+fn is_lit(state: &Grid<Option<bool>>, sight: &Grid<Vec<CellIndex>>, cell: CellIndex) -> bool {
+    sight[cell].iter().any(|&other| state[other] == Some(true))
+}
+
+/// Returns whether every numbered black cell is adjacent to exactly that many bulbs,
+/// assuming that every cell of the given partial solution is decided.
+fn numbers_satisfied(state: &Grid<Option<bool>>, grid: &Grid<AkariCell>) -> bool {
+    grid.enumerate::<CellIndex>().all(|(cell, &contents)| {
+        let AkariCell::Black(Some(number)) = contents else {
+            return true;
+        };
+        let num_bulbs = state
+            .orthogonal_neighbors(cell)
+            .filter(|&(_, &other)| other == Some(true))
+            .count();
+        num_bulbs == usize::from(number)
+    })
+}
+
+/// Decides cells of the given partial solution until no more cells can be decided:
+/// - cells that a bulb illuminates can't have bulbs;
+/// - the undecided neighbors of a numbered black cell all have bulbs
+///   if they're needed to reach the number, and all don't if the number has been reached; and
+/// - an unlit cell that can only be lit by one undecided cell forces a bulb there.
+///
+/// Returns false if the partial solution is found to break some rule.
+fn propagate(
+    state: &mut Grid<Option<bool>>,
+    grid: &Grid<AkariCell>,
+    sight: &Grid<Vec<CellIndex>>,
+) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (cell, &contents) in grid.enumerate::<CellIndex>() {
+            match contents {
+                AkariCell::White if state[cell] == Some(true) => {
+                    for &other in &sight[cell][1..] {
+                        match state[other] {
+                            Some(true) => return false,
+                            Some(false) => {}
+                            None => {
+                                state[other] = Some(false);
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+                AkariCell::White => {
+                    if is_lit(state, sight, cell) {
+                        continue;
+                    }
+                    let mut candidates =
+                        sight[cell].iter().filter(|&&other| state[other].is_none());
+                    match (candidates.next(), candidates.next()) {
+                        (None, _) => return false,
+                        (Some(&only), None) => {
+                            state[only] = Some(true);
+                            changed = true;
+                        }
+                        _ => {}
+                    }
+                }
+                AkariCell::Black(Some(number)) => {
+                    let number = usize::from(number);
+                    let num_bulbs = state
+                        .orthogonal_neighbors(cell)
+                        .filter(|&(_, &other)| other == Some(true))
+                        .count();
+                    let undecided: Vec<CellIndex> = state
+                        .orthogonal_neighbors(cell)
+                        .filter(|(_, other)| other.is_none())
+                        .map(|(other, _)| other)
+                        .collect();
+                    if num_bulbs > number || num_bulbs + undecided.len() < number {
+                        return false;
+                    }
+                    let fill = if num_bulbs == number {
+                        Some(false)
+                    } else if num_bulbs + undecided.len() == number {
+                        Some(true)
+                    } else {
+                        None
+                    };
+                    if let Some(fill) = fill {
+                        for other in undecided {
+                            state[other] = Some(fill);
+                            changed = true;
+                        }
+                    }
+                }
+                AkariCell::Black(None) => {}
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::akari::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates an Akari puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// akari![
+    ///     [<`.` for white, `#` for black, a digit for a numbered black cell> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! akari {
+        (@cell .) => {AkariCell::White};
+        (@cell #) => {AkariCell::Black(None)};
+        (@cell $number:literal) => {AkariCell::Black(Some($number))};
+        ($([$($cell:tt)*])*) => {
+            AkariBoard(Grid::from_2d_array([$([$(akari!(@cell $cell)),*]),*]))
+        };
+    }
+
+    /// Returns a placement with bulbs exactly at the given cells.
+    fn bulbs_at(dimensions: (usize, usize), cells: &[CellIndex]) -> Grid<bool> {
+        let mut bulbs = Grid::filled(false, dimensions);
+        for &cell in cells {
+            bulbs[cell] = true;
+        }
+        bulbs
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &AkariBoard, bulbs: &Grid<bool>) {
+        let AkariBoard(grid) = board;
+        assert_eq!(bulbs.dimensions(), grid.dimensions());
+        let sight = lines_of_sight(grid);
+        for (cell, &contents) in grid.enumerate::<CellIndex>() {
+            match contents {
+                AkariCell::White => {
+                    assert!(
+                        sight[cell].iter().any(|&other| bulbs[other]),
+                        "{cell:?} not lit"
+                    );
+                    if bulbs[cell] {
+                        let num_lighting = sight[cell].iter().filter(|&&other| bulbs[other]);
+                        assert_eq!(num_lighting.count(), 1, "bulb at {cell:?} lit by another");
+                    }
+                }
+                AkariCell::Black(number) => {
+                    assert!(!bulbs[cell], "bulb on black cell {cell:?}");
+                    if let Some(number) = number {
+                        let num_adjacent =
+                            bulbs.orthogonal_neighbors(cell).filter(|&(_, &bulb)| bulb);
+                        assert_eq!(
+                            num_adjacent.count(),
+                            usize::from(number),
+                            "wrong number of bulbs next to {cell:?}"
+                        );
+                    }
+                }
+            }
+        }
+        assert!(is_solution(board, bulbs));
+    }
+
+    #[test]
+    fn test_lines_of_sight() {
+        let AkariBoard(grid) = akari![
+            [. . #]
+            [. 1 .]
+        ];
+        let sight = lines_of_sight(&grid);
+        assert_eq!(sight[(0, 0)], [(0, 0), (1, 0), (0, 1)]);
+        assert_eq!(sight[(2, 1)], [(2, 1)]);
+        assert_eq!(sight[(1, 1)], []);
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = akari![
+            [. . . 1 .]
+            [. . . . .]
+            [0 . . . .]
+            [0 # . 1 .]
+            [. . 1 . .]
+        ];
+        let expected = bulbs_at((5, 5), &[(2, 0), (1, 1), (3, 2), (1, 4), (4, 4)]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_7_by_7() {
+        let board = akari![
+            [. . 1 . 1 . .]
+            [. . . . . . .]
+            [1 . 3 . . . .]
+            [. 3 . . . 0 .]
+            [. . # . . . .]
+            [. . # . . . .]
+            [. . . 0 . . 1]
+        ];
+        let expected = bulbs_at(
+            (7, 7),
+            &[
+                (0, 0),
+                (5, 0),
+                (2, 1),
+                (1, 2),
+                (6, 2),
+                (2, 3),
+                (1, 4),
+                (3, 4),
+                (4, 5),
+                (0, 6),
+                (5, 6),
+            ],
+        );
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::all_white(akari![
+        [. . .]
+        [. . .]
+        [. . .]
+    ])]
+    #[case::all_black(akari![
+        [# 0]
+        [# #]
+    ])]
+    #[case::four(akari![
+        [. . .]
+        [. 4 .]
+        [. . .]
+    ])]
+    #[case::corridors(akari![
+        [. # . # .]
+        [. . . . .]
+        [. # 2 # .]
+        [. . . . .]
+        [. # . # .]
+    ])]
+    fn test_solve(#[case] board: AkariBoard) {
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    // A numbered cell in a corner has only 2 neighbors.
+    #[case::number_too_large(akari![
+        [3 .]
+        [. .]
+    ])]
+    // The bulbs below the 1s would light each other.
+    #[case::bulbs_see_each_other(akari![
+        [1 # # 1]
+        [. . . .]
+    ])]
+    // The white cells can only be lit by themselves, but the 0s can't have bulbs next to them.
+    #[case::unlit(akari![
+        [. 0]
+        [0 .]
+    ])]
+    // The 1 on the left needs a bulb in the middle, but the 0 can't have a bulb next to it.
+    #[case::conflicting_numbers(akari![
+        [# . 1]
+        [1 . .]
+        [# 0 #]
+    ])]
+    fn test_unsolvable(#[case] board: AkariBoard) {
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case::lit_bulb(&[(0, 0), (2, 0)])]
+    #[case::unlit_cell(&[(1, 0)])]
+    #[case::wrong_number(&[(1, 0), (0, 1)])]
+    #[case::bulb_on_black(&[(0, 1), (2, 0), (2, 1)])]
+    fn test_invalid_solution(#[case] cells: &[CellIndex]) {
+        let board = akari![
+            [. . .]
+            [. 1 #]
+        ];
+        assert!(!is_solution(&board, &bulbs_at((3, 2), cells)));
+    }
+
+    #[test]
+    fn test_valid_solution() {
+        let board = akari![
+            [. . .]
+            [. 1 #]
+        ];
+        assert!(is_solution(&board, &bulbs_at((3, 2), &[(0, 1), (2, 0)])));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = AkariBoard(Grid::filled(AkariCell::White, (width, height)));
+        assert_eq!(solve(&board), Some(Grid::filled(false, (width, height))));
+    }
+}
@@ -0,0 +1,355 @@
+//! Binairo (also known as Takuzu, or the binary puzzle) is a logic puzzle
+//! played on a rectangular grid, where some cells are already filled with 0s and 1s.
+//!
+//! Problem: fill the rest of the grid with 0s and 1s, such that:
+//! - no row or column contains three consecutive cells with the same value,
+//! - each row and column contains equally many 0s and 1s, and
+//! - no two rows are the same, and no two columns are the same.
+//!
+//! For example, the following puzzle (`.` for empty cells):
+//! ```text
+//! 1 . . 0
+//! . . 0 .
+//! . 0 . .
+//! 1 1 . 0
+//! ```
+//! has the unique solution:
+//! ```text
+//! 1 0 1 0
+//! 0 1 0 1
+//! 0 0 1 1
+//! 1 1 0 0
+//! ```
+//!
+//! The solver repeatedly fills in cells that are forced by the rules:
+//! - the cells next to two consecutive equal values, or between two equal values,
+//!   must have the opposite value,
+//! - once a row or column has as many of one value as it can,
+//!   its remaining cells must have the other value, and
+//! - if a row or column has exactly two empty cells and could otherwise turn out
+//!   the same as a filled row or column, that "naked pair" of cells must be filled the other way.
+//!
+//! When no more cells are forced, it guesses a value for an empty cell,
+//! and backtracks if the guess leads to a contradiction.
+
+use crate::grid::Grid;
+use std::collections::HashSet;
+
+/// A Binairo puzzle, or a (partial) solution to one,
+/// where `true` and `false` represent 1 and 0, and `None` represents an empty cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BinairoBoard(pub Grid<Option<bool>>);
+
+type CellIndex = (usize, usize);
+
+/// Returns the cells of each row of a grid with the given dimensions,
+/// and the cells of each column.
+fn lines((width, height): (usize, usize)) -> [Vec<Vec<CellIndex>>; 2] {
+    let rows = (0..height)
+        .map(|y| (0..width).map(|x| (x, y)).collect())
+        .collect();
+    let columns = (0..width)
+        .map(|x| (0..height).map(|y| (x, y)).collect())
+        .collect();
+    [rows, columns]
+}
+
+/// Fills in as many cells as possible that are forced by the cells already filled in.
+///
+/// Returns None if a contradiction is found
+/// (including if the cells already filled in break the rules).
+fn propagate(grid: &mut Grid<Option<bool>>) -> Option<()> {
+    let groups = lines(grid.dimensions());
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let mut fill = |grid: &mut Grid<Option<bool>>, index: CellIndex, value: bool| {
+            grid[index] = Some(value);
+            changed = true;
+        };
+        for line in groups.iter().flatten() {
+            for triple in line.windows(3) {
+                let known: Vec<_> = triple.iter().filter_map(|&index| grid[index]).collect();
+                match known[..] {
+                    [a, b, c] if a == b && b == c => return None,
+                    [a, b] if a == b => {
+                        let &empty = triple.iter().find(|&&index| grid[index].is_none())?;
+                        fill(grid, empty, !a);
+                    }
+                    _ => {}
+                }
+            }
+            for value in [false, true] {
+                let count = line
+                    .iter()
+                    .filter(|&&index| grid[index] == Some(value))
+                    .count();
+                if count > line.len() / 2 {
+                    return None;
+                }
+                if count == line.len() / 2 {
+                    for &index in line {
+                        if grid[index].is_none() {
+                            fill(grid, index, !value);
+                        }
+                    }
+                }
+            }
+        }
+        for group in &groups {
+            let filled: Vec<Vec<bool>> = group
+                .iter()
+                .filter_map(|line| line.iter().map(|&index| grid[index]).collect())
+                .collect();
+            if filled.iter().collect::<HashSet<_>>().len() < filled.len() {
+                return None;
+            }
+            for line in group {
+                let empty: Vec<_> = (0..line.len())
+                    .filter(|&i| grid[line[i]].is_none())
+                    .collect();
+                let &[i, j] = &empty[..] else {
+                    continue;
+                };
+                let matching = filled.iter().find(|other| {
+                    other[i] != other[j]
+                        && line
+                            .iter()
+                            .zip(other.iter())
+                            .all(|(&index, &value)| grid[index].is_none_or(|v| v == value))
+                });
+                if let Some(other) = matching {
+                    fill(grid, line[i], !other[i]);
+                    fill(grid, line[j], !other[j]);
+                }
+            }
+        }
+    }
+    Some(())
+}
+
+/// Returns a solution that extends the given partial solution, or None if there is none.
+fn search(mut grid: Grid<Option<bool>>) -> Option<Grid<Option<bool>>> {
+    propagate(&mut grid)?;
+    let Some((index, _)) = grid
+        .enumerate::<CellIndex>()
+        .find(|(_, cell)| cell.is_none())
+    else {
+        return Some(grid);
+    };
+    for value in [false, true] {
+        let mut guess = grid.clone();
+        guess[index] = Some(value);
+        if let Some(solution) = search(guess) {
+            return Some(solution);
+        }
+    }
+    None
+}
+
+/// Returns a solution to the given Binairo puzzle, with every cell filled in,
+/// or None if there is no solution.
+///
+/// If there are multiple solutions, any one of them may be returned.
+pub fn solve(board: &BinairoBoard) -> Option<BinairoBoard> {
+    let BinairoBoard(grid) = board;
+    let (width, height) = grid.dimensions();
+    if width % 2 != 0 || height % 2 != 0 {
+        return None;
+    }
+    search(grid.clone()).map(BinairoBoard)
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid, and `n = max(width, height)`.
+        This function completes in `O(2^c * c^2 * n)` time in the worst case,
+        though propagation usually prunes the search far more than this suggests.
+        - The search tree has `O(2^c)` nodes.
+        - At each node, propagation makes at most `c + 1` passes over the grid,
+          since each pass that doesn't finish fills in at least one cell.
+        - Each pass takes `O(c * n)` time: the rules about consecutive values and counts
+          take `O(c)` time in total, while the rule about naked pairs compares
+          each of the `O(n)` rows (or columns) with every filled row (or column).
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binairo::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Binairo board, with `0` and `1` for filled cells and `.` for empty cells.
+    macro_rules! binairo {
+        (@cell 0) => {Some(false)};
+        (@cell 1) => {Some(true)};
+        (@cell .) => {None};
+        ($([$($cell:tt)*])*) => {
+            BinairoBoard(Grid::from_2d_array([$([$(binairo!(@cell $cell)),*]),*]))
+        };
+    }
+
+    /// Checks that the given solution satisfies all the rules of Binairo
+    /// and keeps every cell that was filled in on the given board.
+    fn assert_valid(board: &BinairoBoard, solution: &BinairoBoard) {
+        let (BinairoBoard(grid), BinairoBoard(solution)) = (board, solution);
+        assert_eq!(grid.dimensions(), solution.dimensions());
+        for (index, cell) in grid.enumerate::<CellIndex>() {
+            assert!(solution[index].is_some(), "empty cell at {index:?}");
+            assert!(cell.is_none_or(|value| solution[index] == Some(value)));
+        }
+        for group in lines(solution.dimensions()) {
+            let values: Vec<Vec<_>> = group
+                .iter()
+                .map(|line| line.iter().map(|&index| solution[index]).collect())
+                .collect();
+            for line in &values {
+                assert!(line.windows(3).all(|t| t[0] != t[1] || t[1] != t[2]));
+                let num_ones = line.iter().filter(|&&value| value == Some(true)).count();
+                assert_eq!(num_ones * 2, line.len());
+            }
+            assert_eq!(values.iter().collect::<HashSet<_>>().len(), values.len());
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let board = BinairoBoard(Grid::filled(None, (0, 0)));
+        assert_eq!(solve(&board), Some(board));
+    }
+
+    #[rstest]
+    #[case::two_by_two(binairo![
+        [1 .]
+        [. .]
+    ], binairo![
+        [1 0]
+        [0 1]
+    ])]
+    #[case::example(binairo![
+        [1 . . 0]
+        [. . 0 .]
+        [. 0 . .]
+        [1 1 . 0]
+    ], binairo![
+        [1 0 1 0]
+        [0 1 0 1]
+        [0 0 1 1]
+        [1 1 0 0]
+    ])]
+    #[case::six_by_six(binairo![
+        [. . . 1 1 .]
+        [. 1 . . . .]
+        [. . 1 1 . 1]
+        [0 . 0 1 . .]
+        [. . . . . .]
+        [. . . . 0 .]
+    ], binairo![
+        [0 1 0 1 1 0]
+        [1 1 0 0 1 0]
+        [0 0 1 1 0 1]
+        [0 1 0 1 0 1]
+        [1 0 1 0 1 0]
+        [1 0 1 0 0 1]
+    ])]
+    #[case::ten_by_ten(binairo![
+        [. . . . . 1 . 1 1 .]
+        [. . 0 . . . 0 . 0 .]
+        [1 . . . . . . . . .]
+        [. . 1 . . . . 0 . 0]
+        [. . 0 0 . 1 . . . .]
+        [0 . . . . . 1 . . 0]
+        [. . 1 . . . . 0 . .]
+        [. . . . 0 . . . 1 1]
+        [. . 1 . . . 0 . . 0]
+        [0 . . 1 . . . . . .]
+    ], binairo![
+        [1 0 1 0 0 1 0 1 1 0]
+        [0 1 0 1 0 1 0 1 0 1]
+        [1 0 1 0 1 0 1 0 0 1]
+        [1 0 1 1 0 0 1 0 1 0]
+        [0 1 0 0 1 1 0 1 0 1]
+        [0 1 0 1 1 0 1 1 0 0]
+        [1 0 1 0 0 1 0 0 1 1]
+        [1 0 0 1 0 0 1 0 1 1]
+        [0 1 1 0 1 1 0 1 0 0]
+        [0 1 0 1 1 0 1 0 1 0]
+    ])]
+    fn test_solve(#[case] board: BinairoBoard, #[case] expected: BinairoBoard) {
+        let actual = solve(&board).unwrap();
+        assert_valid(&board, &actual);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::example(binairo![
+        [1 . . 0]
+        [. . 0 .]
+        [. 0 . .]
+        [1 1 . 0]
+    ], true)]
+    #[case::six_by_six(binairo![
+        [. . . 1 1 .]
+        [. 1 . . . .]
+        [. . 1 1 . 1]
+        [0 . 0 1 . .]
+        [. . . . . .]
+        [. . . . 0 .]
+    ], true)]
+    #[case::ten_by_ten(binairo![
+        [. . . . . 1 . 1 1 .]
+        [. . 0 . . . 0 . 0 .]
+        [1 . . . . . . . . .]
+        [. . 1 . . . . 0 . 0]
+        [. . 0 0 . 1 . . . .]
+        [0 . . . . . 1 . . 0]
+        [. . 1 . . . . 0 . .]
+        [. . . . 0 . . . 1 1]
+        [. . 1 . . . 0 . . 0]
+        [0 . . 1 . . . . . .]
+    ], false)]
+    fn test_propagate_alone(#[case] board: BinairoBoard, #[case] expected: bool) {
+        let BinairoBoard(mut grid) = board;
+        assert_eq!(propagate(&mut grid), Some(()));
+        let is_complete = grid
+            .enumerate::<CellIndex>()
+            .all(|(_, cell)| cell.is_some());
+        assert_eq!(is_complete, expected);
+    }
+
+    #[rstest]
+    #[case::odd_width(binairo![[. . .] [. . .]])]
+    #[case::three_in_a_row(binairo![
+        [1 1 1 .]
+        [. . . .]
+        [. . . .]
+        [. . . .]
+    ])]
+    #[case::too_many_ones(binairo![
+        [1 . 1 1]
+        [. . . .]
+        [. . . .]
+        [. . . .]
+    ])]
+    #[case::same_rows(binairo![
+        [1 .]
+        [1 .]
+    ])]
+    // There are only two possible columns of height 2.
+    #[case::same_columns(binairo![
+        [. . . .]
+        [. . . .]
+    ])]
+    // Nothing is wrong at first, but every way of filling in the grid
+    // eventually breaks one of the rules.
+    #[case::over_constrained(binairo![
+        [. . . . 0 .]
+        [1 . . . . 1]
+        [. 1 . . . .]
+        [. . . . . .]
+        [. . . . . .]
+        [1 . . . 0 .]
+    ])]
+    fn test_no_solution(#[case] board: BinairoBoard) {
+        assert_eq!(solve(&board), None);
+    }
+}
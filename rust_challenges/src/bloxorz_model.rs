@@ -5,13 +5,20 @@
 //! Problem: model a simplified version of Bloxorz.
 //!
 //! The only type of special tile included in the model is the fragile orange tile.
-//! Switches (and thus, bridges, as well as the ability to split the block) are not included.
+//! Switches (and thus, bridges) are not included.
+//! Instead of being split by a switch, a split block is modeled as a separate kind of game,
+//! which starts out with two blocks.
 
-// Dependencies (later modules depend on earlier ones): board -> block -> game
+// Dependencies (later modules depend on earlier ones):
+// board -> block -> game -> bloxorz_levels, split_game
 mod block;
+mod bloxorz_levels;
 mod board;
 mod game;
+mod split_game;
 
 pub use block::{Block, Direction, Orientation, DIRECTIONS};
-pub use board::{Board, Coordinates, Tile};
+pub use bloxorz_levels::{level, level_count};
+pub use board::{Board, Coordinates, ParseBoardError, Tile};
 pub use game::{ActiveGame, Game, Status};
+pub use split_game::{SplitActiveGame, SplitGame, SplitMove, SplitStatus, WhichBlock};
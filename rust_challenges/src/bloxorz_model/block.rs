@@ -75,7 +75,7 @@ impl Block {
     /// Returns the coordinates of both squares covered by the block.
     ///
     /// For upright blocks, returns the same pair of coordinates twice.
-    fn full_coordinates(self) -> [Coordinates; 2] {
+    pub fn full_coordinates(self) -> [Coordinates; 2] {
         let Block((x, y), orientation) = self;
         let (dx, dy) = match orientation {
             Orientation::Upright => (0, 0),
@@ -0,0 +1,146 @@
+//! Module for a library of built-in Bloxorz stages.
+//!
+//! The stages follow the first five levels of the original game as closely as the model allows.
+//! Since switches and bridges aren't part of the model,
+//! levels 2 and 5 (which rely on them in the original game) are reworked without them.
+
+use crate::bloxorz_board;
+use crate::bloxorz_model::block::{Block, Orientation};
+use crate::bloxorz_model::board::Board;
+
+/// The number of levels in the library.
+const LEVEL_COUNT: u8 = 5;
+
+/// The number of levels in the library, numbered from 1 to `level_count()`.
+pub fn level_count() -> u8 {
+    LEVEL_COUNT
+}
+
+/// Returns the board and starting block for the given level (starting from level 1),
+/// or None if there's no such level.
+pub fn level(n: u8) -> Option<(Board, Block)> {
+    let (board, start) = match n {
+        1 => (level_1(), (1, 1)),
+        2 => (level_2(), (1, 3)),
+        3 => (level_3(), (1, 3)),
+        4 => (level_4(), (1, 5)),
+        5 => (level_5(), (2, 0)),
+        _ => return None,
+    };
+    Some((board, Block(start, Orientation::Upright)))
+}
+
+/// Level 1: a single island, with the block starting near the top left corner
+/// and the goal near the bottom right corner.
+fn level_1() -> Board {
+    bloxorz_board![
+        [# # # . . . . . . .]
+        [# # # # # # . . . .]
+        [# # # # # # # # # .]
+        [. # # # # # # # # #]
+        [. . . . . # # $ # #]
+        [. . . . . . # # # .]
+    ]
+}
+
+/// Level 2: three islands connected by narrow paths,
+/// which replace the switch-controlled bridges of the original level.
+/// The block starts on the left island and the goal is on the right island.
+fn level_2() -> Board {
+    bloxorz_board![
+        [. . . . . . # # # # . . . . . .]
+        [# # # # . . # # # # . . # # # #]
+        [# # # # . . # # # # # . . # # #]
+        [# # # # # # # # # . # . . # $ #]
+        [# # # # . . # # # # . . # # # #]
+        [# # # # . . # . # # # # # # # #]
+        [. . . . . . # # # # . . . . . .]
+    ]
+}
+
+/// Level 3: the block starts on the left island,
+/// and has to take a detour across the top of the board to reach the goal on the right,
+/// squeezing through the narrow gaps along the way.
+fn level_3() -> Board {
+    bloxorz_board![
+        [. . . . . . # # # # # # # . .]
+        [# # # # . . # # # . . # # . .]
+        [# # # # # # # # # . . # # # #]
+        [# # # # . . . . . . . # # $ #]
+        [# # # # . . . . . . . # # # #]
+        [. . . . . . . . . . . . # # #]
+    ]
+}
+
+/// Level 4: the first level with fragile tiles.
+/// The block starts on the left, crosses the fragile area at the top while lying down,
+/// and then heads down the right side and back to the left to reach the goal at the bottom.
+fn level_4() -> Board {
+    bloxorz_board![
+        [. . . ! ! ! ! ! ! ! . . . .]
+        [. . . ! ! ! ! ! ! ! . . . .]
+        [# # # # . . . . . # # # . .]
+        [# # # . . . . . . . # # . .]
+        [# # # . . . . . . . # # . .]
+        [# # # . . # # # # # # # ! !]
+        [# # # . . # # # # # # # ! !]
+        [. . . . . # $ # . . ! ! ! .]
+        [. . . . . # # # . . ! ! ! .]
+    ]
+}
+
+/// Level 5: a narrow path with pairs of fragile tiles leads from the top left island
+/// to the goal at the top right, where the block can only stand up on the regular tiles.
+/// The area at the bottom right is a dead end.
+fn level_5() -> Board {
+    bloxorz_board![
+        [. # # # . . . . . . . # # #]
+        [. # # # . . . . . . . # $ #]
+        [. # # # # ! ! # ! ! # # # #]
+        [. . . . . . . . . . . # # #]
+        [. . . . . . . . . # # # # #]
+        [. . . . . . . . . # # ! ! .]
+        [. . . . . . . . . # # . . .]
+        [. . . . . . . . . # ! ! . .]
+        [. . . . . . . . . # # # # .]
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bloxorz_model::bloxorz_levels::*;
+    use crate::bloxorz_model::{Game, Status};
+    use crate::bloxorz_solver::solve;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::level_1(1, 7)]
+    #[case::level_2(2, 23)]
+    #[case::level_3(3, 19)]
+    #[case::level_4(4, 29)]
+    #[case::level_5(5, 17)]
+    fn test_solvable(#[case] n: u8, #[case] expected_moves: usize) {
+        let (board, block) = level(n).unwrap();
+        let game = Game {
+            board: &board,
+            block,
+        };
+        assert!(matches!(game.status(), Status::Active(_)));
+        let solution = solve(game).unwrap();
+        assert_eq!(solution.len(), expected_moves);
+    }
+
+    #[test]
+    fn test_level_count() {
+        assert_eq!(level_count(), 5);
+        assert!((1..=level_count()).all(|n| level(n).is_some()));
+    }
+
+    #[rstest]
+    #[case::zero(0)]
+    #[case::after_last(level_count() + 1)]
+    #[case::max(u8::MAX)]
+    fn test_no_level(#[case] n: u8) {
+        assert!(level(n).is_none());
+    }
+}
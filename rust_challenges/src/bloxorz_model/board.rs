@@ -1,6 +1,8 @@
 //! Module for specifying a Bloxorz stage.
 
 use crate::grid::Grid;
+use std::fmt;
+use std::str::FromStr;
 
 /// A square of terrain.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -15,11 +17,35 @@ pub enum Tile {
     Goal,
 }
 
+impl Tile {
+    /// The symbol for the tile, as used by `bloxorz_board!`.
+    pub fn symbol(self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Regular => '#',
+            Tile::Fragile => '!',
+            Tile::Goal => '$',
+        }
+    }
+
+    /// The tile with the given symbol, as used by `bloxorz_board!`, if there is one.
+    pub fn from_symbol(symbol: char) -> Option<Tile> {
+        match symbol {
+            '.' => Some(Tile::Empty),
+            '#' => Some(Tile::Regular),
+            '!' => Some(Tile::Fragile),
+            '$' => Some(Tile::Goal),
+            _ => None,
+        }
+    }
+}
+
 pub type Coordinates = (i32, i32);
 
 /// The terrain of a Bloxorz stage.
 ///
 /// Note that boards are allowed to have multiple goals, unlike in the actual game.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Board(pub Grid<Tile>);
 
 impl Board {
@@ -32,6 +58,59 @@ impl Board {
     }
 }
 
+/// The reason a board couldn't be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseBoardError {
+    /// A character isn't the symbol of a tile.
+    InvalidTile(char),
+    /// The rows don't all have the same length.
+    RaggedRows,
+}
+
+/// Draws the board with one line per row and one symbol per tile,
+/// using the same symbols as `bloxorz_board!` (without spaces).
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Board(grid) = self;
+        for y in 0..grid.height() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..grid.width() {
+                write!(f, "{}", grid[(x, y)].symbol())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a board in the format produced by its `Display` implementation.
+impl FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(s: &str) -> Result<Board, ParseBoardError> {
+        let rows = s
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| Tile::from_symbol(c).ok_or(ParseBoardError::InvalidTile(c)))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<Tile>>, ParseBoardError>>()?;
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(ParseBoardError::RaggedRows);
+        }
+        let mut grid = Grid::filled(Tile::Empty, (width, rows.len()));
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, tile) in row.into_iter().enumerate() {
+                grid[(x, y)] = tile;
+            }
+        }
+        Ok(Board(grid))
+    }
+}
+
 /// Creates a board for a Bloxorz stage.
 ///
 /// Syntax:
@@ -63,6 +142,7 @@ macro_rules! bloxorz_board {
 #[cfg(test)]
 mod tests {
     use crate::bloxorz_model::board::*;
+    use pretty_assertions::assert_eq;
     use rstest::rstest;
 
     fn dumbbell_board() -> Board {
@@ -74,6 +154,25 @@ mod tests {
         ]
     }
 
+    #[test]
+    fn test_display() {
+        let expected = "###...##$\n###!!!###\n###!!!###\n###...##$";
+        assert_eq!(dumbbell_board().to_string(), expected);
+        assert_eq!(Board(Grid::filled(Tile::Regular, (0, 0))).to_string(), "");
+    }
+
+    #[rstest]
+    #[case::dumbbell("###...##$\n###!!!###\n###!!!###\n###...##$", Ok(dumbbell_board()))]
+    #[case::trailing_newline("###...##$\n###!!!###\n###!!!###\n###...##$\n", Ok(dumbbell_board()))]
+    #[case::single_tile("$", Ok(bloxorz_board![[$]]))]
+    #[case::empty("", Ok(Board(Grid::filled(Tile::Empty, (0, 0)))))]
+    #[case::invalid_tile("##\n#x", Err(ParseBoardError::InvalidTile('x')))]
+    #[case::spaces("# #", Err(ParseBoardError::InvalidTile(' ')))]
+    #[case::ragged("###\n##", Err(ParseBoardError::RaggedRows))]
+    fn test_from_str(#[case] s: &str, #[case] expected: Result<Board, ParseBoardError>) {
+        assert_eq!(s.parse::<Board>(), expected);
+    }
+
     #[rstest]
     #[case::out_of_bounds_left((-5, 2), Tile::Empty)]
     #[case::out_of_bounds_down((4, 4),  Tile::Empty)]
@@ -0,0 +1,246 @@
+//! Module for the rules and state of games where the block has been split in two.
+
+use crate::bloxorz_model::block::{Block, Direction};
+use crate::bloxorz_model::board::Board;
+use crate::bloxorz_model::game::{Game, Status};
+
+/// One of the two blocks in a split game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WhichBlock {
+    /// The block that needs to reach a goal.
+    A,
+    /// The other block.
+    B,
+}
+
+impl WhichBlock {
+    /// The other block.
+    pub fn other(self) -> WhichBlock {
+        match self {
+            WhichBlock::A => WhichBlock::B,
+            WhichBlock::B => WhichBlock::A,
+        }
+    }
+}
+
+/// A move in a split game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitMove {
+    /// Moving the active block in the given direction.
+    Move(Direction),
+    /// Switching control to the other block.
+    Switch,
+}
+
+/// A game of Bloxorz in a specific state, where the player has two blocks,
+/// and controls one of them at a time.
+///
+/// Each block follows the usual rules for falling off the board and breaking fragile tiles,
+/// and the blocks can't overlap.
+/// The player wins when block A stands upright on a goal,
+/// while block B has to stay on the board, so it can't stand upright on a goal.
+#[derive(Clone, Copy)]
+pub struct SplitGame<'a> {
+    pub board: &'a Board,
+    pub block_a: Block,
+    pub block_b: Block,
+    /// The block that the player is currently controlling.
+    pub active: WhichBlock,
+}
+
+/// Information about the final outcome of a split game of Bloxorz.
+#[derive(Clone, Copy)]
+pub enum SplitStatus<'a> {
+    /// The player successfully completed the stage.
+    Win,
+    /// The player entered a fail state.
+    Loss,
+    /// The game is still ongoing.
+    Active(SplitActiveGame<'a>),
+}
+
+/// An ongoing split game of Bloxorz in which the player can still make moves.
+#[derive(Clone, Copy)]
+pub struct SplitActiveGame<'a> {
+    board: &'a Board,
+    block_a: Block,
+    block_b: Block,
+    active: WhichBlock,
+}
+
+impl<'a> SplitGame<'a> {
+    /// Evaluates the status of the game based on the current state,
+    /// following the rules for split games.
+    pub fn status(self) -> SplitStatus<'a> {
+        let SplitGame {
+            board,
+            block_a,
+            block_b,
+            active,
+        } = self;
+        let squares_b = block_b.full_coordinates();
+        if block_a
+            .full_coordinates()
+            .iter()
+            .any(|coordinates| squares_b.contains(coordinates))
+        {
+            return SplitStatus::Loss;
+        }
+        let status = |block| Game { board, block }.status();
+        if !matches!(status(block_b), Status::Active(_)) {
+            return SplitStatus::Loss;
+        }
+        match status(block_a) {
+            Status::Win => SplitStatus::Win,
+            Status::Loss => SplitStatus::Loss,
+            Status::Active(_) => SplitStatus::Active(SplitActiveGame {
+                board,
+                block_a,
+                block_b,
+                active,
+            }),
+        }
+    }
+}
+
+impl<'a> SplitActiveGame<'a> {
+    /// Returns the result of moving the active block in the given direction
+    /// in the current game state.
+    pub fn make_move(self, direction: Direction) -> SplitGame<'a> {
+        let mut result = SplitGame {
+            board: self.board,
+            block_a: self.block_a,
+            block_b: self.block_b,
+            active: self.active,
+        };
+        let block = match self.active {
+            WhichBlock::A => &mut result.block_a,
+            WhichBlock::B => &mut result.block_b,
+        };
+        *block = block.make_move(direction);
+        result
+    }
+
+    /// Returns the current game state with control switched to the other block.
+    pub fn switch_active(self) -> SplitActiveGame<'a> {
+        SplitActiveGame {
+            active: self.active.other(),
+            ..self
+        }
+    }
+
+    /// Returns the result of making the given move in the current game state.
+    pub fn make_split_move(self, split_move: SplitMove) -> SplitGame<'a> {
+        match split_move {
+            SplitMove::Move(direction) => self.make_move(direction),
+            SplitMove::Switch => {
+                let SplitActiveGame {
+                    board,
+                    block_a,
+                    block_b,
+                    active,
+                } = self.switch_active();
+                SplitGame {
+                    board,
+                    block_a,
+                    block_b,
+                    active,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bloxorz_board;
+    use crate::bloxorz_model::block::Orientation::*;
+    use crate::bloxorz_model::split_game::*;
+    use rstest::rstest;
+    use Direction::{Down as D, Left as L, Right as R, Up as U};
+    use SplitMove::{Move, Switch};
+
+    /// Returns the result of making multiple moves in the given split game.
+    ///
+    /// Panics if there are still moves to make after the game is won or lost.
+    fn play<'a>(mut game: SplitGame<'a>, moves: &[SplitMove]) -> SplitGame<'a> {
+        for (i, &split_move) in moves.iter().enumerate() {
+            let SplitStatus::Active(active_game) = game.status() else {
+                panic!("cannot make a move in a finished game: move {i} of {moves:?}")
+            };
+            game = active_game.make_split_move(split_move);
+        }
+        game
+    }
+
+    fn corridor_board() -> Board {
+        bloxorz_board![
+            [. . . # . . .]
+            [. . . # . . .]
+            [# # # # # # $]
+            [. . . # . . .]
+            [. . . # . . .]
+        ]
+    }
+
+    /// The game on the corridor board, with block B in the way of block A.
+    fn corridor_game(board: &Board) -> SplitGame<'_> {
+        SplitGame {
+            board,
+            block_a: Block((0, 2), Upright),
+            block_b: Block((3, 2), Upright),
+            active: WhichBlock::A,
+        }
+    }
+
+    #[test]
+    fn test_winning_play() {
+        let board = corridor_board();
+        let moves = [Switch, Move(U), Switch, Move(R), Move(R), Move(R), Move(R)];
+        let result = play(corridor_game(&board), &moves);
+        assert_eq!(result.block_a, Block((6, 2), Upright));
+        assert_eq!(result.block_b, Block((3, 0), Vertical));
+        assert_eq!(result.active, WhichBlock::A);
+        let SplitStatus::Win = result.status() else {
+            panic!("expected a win");
+        };
+    }
+
+    #[rstest]
+    #[case::collision(&[Move(R), Move(R)], Block((3, 2), Upright), Block((3, 2), Upright))]
+    #[case::collision_lying_down(&[Move(R), Switch, Move(L)], Block((1, 2), Horizontal),
+        Block((1, 2), Horizontal))]
+    #[case::a_falls_off(&[Move(U)], Block((0, 0), Vertical), Block((3, 2), Upright))]
+    #[case::b_falls_off(&[Switch, Move(D), Move(R)], Block((0, 2), Upright),
+        Block((4, 3), Vertical))]
+    #[case::b_falls_into_goal(&[Switch, Move(R), Move(R)], Block((0, 2), Upright),
+        Block((6, 2), Upright))]
+    fn test_losing_play(
+        #[case] moves: &[SplitMove],
+        #[case] final_block_a: Block,
+        #[case] final_block_b: Block,
+    ) {
+        let board = corridor_board();
+        let result = play(corridor_game(&board), moves);
+        assert_eq!(result.block_a, final_block_a);
+        assert_eq!(result.block_b, final_block_b);
+        let SplitStatus::Loss = result.status() else {
+            panic!("expected a loss");
+        };
+    }
+
+    #[test]
+    fn test_switch_active() {
+        let board = corridor_board();
+        let SplitStatus::Active(game) = corridor_game(&board).status() else {
+            panic!("expected an active game");
+        };
+        let switched = game.switch_active();
+        assert_eq!(switched.active, WhichBlock::B);
+        assert_eq!(switched.switch_active().active, WhichBlock::A);
+        // Only the active block moves.
+        let moved = switched.make_move(D);
+        assert_eq!(moved.block_a, Block((0, 2), Upright));
+        assert_eq!(moved.block_b, Block((3, 3), Vertical));
+    }
+}
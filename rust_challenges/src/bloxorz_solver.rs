@@ -1,11 +1,15 @@
 //! Follow-up challenge to Bloxorz Model.
 //!
 //! Problem: find the shortest solution to a Bloxorz level.
-use crate::bloxorz_model::{Block, Direction, Game, Status, DIRECTIONS};
+use crate::bloxorz_model::{
+    Block, Board, Coordinates, Direction, Game, Orientation, SplitGame, SplitMove, SplitStatus,
+    Status, Tile, WhichBlock, DIRECTIONS,
+};
 use std::collections::{
     hash_map::{Entry, HashMap},
-    VecDeque,
+    HashSet, VecDeque,
 };
+use std::hash::Hash;
 
 /// Returns the shortest list of moves needed to win the given game,
 /// or None if the game is unwinnable.
@@ -13,6 +17,31 @@ use std::collections::{
 /// If there are multiple shortest solutions, one of them will be returned;
 /// it's left unspecified which specific solution is returned.
 pub fn solve(game: Game) -> Option<Vec<Direction>> {
+    let (solution, _) = search(game, |_| true);
+    solution
+}
+
+/// Returns the same result as `solve`,
+/// but skips the block states from which the game can't be won.
+pub fn solve_pruned(game: Game) -> Option<Vec<Direction>> {
+    let (solution, _) = pruned_search(game);
+    solution
+}
+
+/// Searches for a shortest solution as in `solve_pruned`,
+/// returning the solution (if any) and the number of block states visited.
+fn pruned_search(game: Game) -> (Option<Vec<Direction>>, usize) {
+    let can_win = reachable_from_win(game.board);
+    if !can_win.contains(&game.block) {
+        return (None, 0);
+    }
+    search(game, |block| can_win.contains(&block))
+}
+
+/// Searches for a shortest solution with a breadth-first search,
+/// only visiting block states for which `should_visit` returns true,
+/// and returns the solution (if any) and the number of block states visited.
+fn search(game: Game, should_visit: impl Fn(Block) -> bool) -> (Option<Vec<Direction>>, usize) {
     let mut queue = VecDeque::from([game]);
     // Map from a block representing a state
     // to a (move from previous state to current state, block for previous state) tuple
@@ -21,11 +50,17 @@ pub fn solve(game: Game) -> Option<Vec<Direction>> {
     let mut visited = HashMap::from([(game.block, None)]);
     while let Some(curr) = queue.pop_front() {
         match curr.status() {
-            Status::Win => return Some(trace_moves(visited, curr.block)),
+            Status::Win => {
+                let visited_count = visited.len();
+                return (Some(trace_moves(visited, curr.block)), visited_count);
+            }
             Status::Loss => {}
             Status::Active(active_curr) => {
                 for &direction in &DIRECTIONS {
                     let next = active_curr.make_move(direction);
+                    if !should_visit(next.block) {
+                        continue;
+                    }
                     if let Entry::Vacant(entry_for_next) = visited.entry(next.block) {
                         queue.push_back(next);
                         entry_for_next.insert(Some((direction, curr.block)));
@@ -34,17 +69,237 @@ pub fn solve(game: Game) -> Option<Vec<Direction>> {
             }
         }
     }
+    (None, visited.len())
+}
+
+/// Returns the shortest list of moves needed to win the given split game,
+/// or None if the game is unwinnable.
+///
+/// Switching control to the other block counts as a move.
+/// If there are multiple shortest solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve_split(game: SplitGame) -> Option<Vec<SplitMove>> {
+    let state = |game: SplitGame| (game.block_a, game.block_b, game.active);
+    let split_moves = DIRECTIONS
+        .iter()
+        .map(|&direction| SplitMove::Move(direction))
+        .chain([SplitMove::Switch]);
+    let mut queue = VecDeque::from([game]);
+    // Map from a state to a (move from previous state to current state, previous state) tuple,
+    // as in `search`.
+    let mut visited: HashMap<(Block, Block, WhichBlock), _> = HashMap::from([(state(game), None)]);
+    while let Some(curr) = queue.pop_front() {
+        match curr.status() {
+            SplitStatus::Win => return Some(trace_moves(visited, state(curr))),
+            SplitStatus::Loss => {}
+            SplitStatus::Active(active_curr) => {
+                for split_move in split_moves.clone() {
+                    let next = active_curr.make_split_move(split_move);
+                    if let Entry::Vacant(entry_for_next) = visited.entry(state(next)) {
+                        queue.push_back(next);
+                        entry_for_next.insert(Some((split_move, state(curr))));
+                    }
+                }
+            }
+        }
+    }
     None
+    /*
+        Time complexity analysis:
+        Let `s` be the number of block states on the board that don't immediately lose.
+        There are `O(s^2)` reachable states of the split game,
+        each of which is processed in `O(1)` expected time,
+        so this function completes in `O(s^2)` expected time.
+    */
+}
+
+/// Returns the block states on the given board from which the game can be won,
+/// found with a breadth-first search backwards from the blocks standing on goals.
+pub fn reachable_from_win(board: &Board) -> HashSet<Block> {
+    moves_to_win(board).into_keys().collect()
+    /*
+        Time complexity analysis:
+        Let `c` be the number of tiles on the board and `s` be the number of block states
+        from which the game can be won.
+        Finding the goals takes `O(c)` time and the search takes `O(s)` time,
+        so this function completes in `O(c + s)` time.
+    */
+}
+
+/// Returns a map from each number of moves
+/// to the number of (non-losing) block states that are first reached after that many moves,
+/// as explored by a breadth-first search from the given game.
+///
+/// As with [`solve`], the search doesn't continue past the first winning state,
+/// though every state reached after the same number of moves as that state is counted.
+/// For winnable games, the largest number of moves in the map is thus the length of the
+/// shortest solution, while for unwinnable games, every reachable state is counted.
+/// If the game starts out lost, the map is empty.
+pub fn bfs_depth_profile(game: Game) -> HashMap<usize, usize> {
+    let mut result = HashMap::new();
+    if let Status::Loss = game.status() {
+        return result;
+    }
+    let mut visited = HashSet::from([game.block]);
+    let mut layer = vec![game];
+    let mut depth = 0;
+    while !layer.is_empty() {
+        result.insert(depth, layer.len());
+        let mut next_layer = vec![];
+        for curr in layer {
+            match curr.status() {
+                Status::Win => return result,
+                Status::Loss => {}
+                Status::Active(active_curr) => {
+                    for &direction in &DIRECTIONS {
+                        let next = active_curr.make_move(direction);
+                        if !matches!(next.status(), Status::Loss) && visited.insert(next.block) {
+                            next_layer.push(next);
+                        }
+                    }
+                }
+            }
+        }
+        layer = next_layer;
+        depth += 1;
+    }
+    result
+}
+
+/// Returns the number of different move sequences of at most `max_moves` moves
+/// that win the given game.
+///
+/// Panics if the number of sequences doesn't fit in a `u64`.
+pub fn count_solutions(game: Game, max_moves: usize) -> u64 {
+    let add = |a: u64, b: u64| {
+        a.checked_add(b)
+            .expect("the number of solutions doesn't fit in a u64")
+    };
+    let moves_to_win = moves_to_win(game.board);
+    // The number of sequences of the current number of moves leading to each block state,
+    // counting only states that can still be won within `max_moves` moves.
+    let mut counts = HashMap::from([(game.block, 1)]);
+    let mut result = 0;
+    for moves in 0..=max_moves {
+        let mut next_counts = HashMap::new();
+        for (block, count) in counts {
+            let curr = Game {
+                board: game.board,
+                block,
+            };
+            match curr.status() {
+                Status::Win => result = add(result, count),
+                Status::Loss => {}
+                Status::Active(active_curr) => {
+                    for &direction in &DIRECTIONS {
+                        let next = active_curr.make_move(direction).block;
+                        if moves_to_win
+                            .get(&next)
+                            .is_some_and(|&remaining| moves + 1 + remaining <= max_moves)
+                        {
+                            let entry = next_counts.entry(next).or_insert(0);
+                            *entry = add(*entry, count);
+                        }
+                    }
+                }
+            }
+        }
+        counts = next_counts;
+    }
+    result
+    /*
+        Time complexity analysis:
+        Let `s` be the number of block states that can reach a win.
+        Finding the states that can reach a win takes `O(s)` time (plus the time to find the goals),
+        and each of the `max_moves + 1` steps of the count takes `O(s)` time,
+        so this function completes in `O(s * max_moves)` time.
+    */
+}
+
+/// Returns the minimum number of moves needed to win from each block state on the given board
+/// that can reach a win.
+fn moves_to_win(board: &Board) -> HashMap<Block, usize> {
+    let Board(grid) = board;
+    let goals: Vec<Block> = grid
+        .enumerate::<Coordinates>()
+        .filter(|(_, &tile)| tile == Tile::Goal)
+        .map(|(coordinates, _)| Block(coordinates, Orientation::Upright))
+        .collect();
+    let mut result: HashMap<Block, usize> = goals.iter().map(|&goal| (goal, 0)).collect();
+    let mut queue = VecDeque::from(goals);
+    while let Some(curr) = queue.pop_front() {
+        for &direction in &DIRECTIONS {
+            // Moves can always be undone, so this is the state that reaches `curr` by moving
+            // in the opposite direction.
+            let prev = curr.make_move(direction);
+            let prev_game = Game { board, block: prev };
+            if matches!(prev_game.status(), Status::Active(_)) && !result.contains_key(&prev) {
+                result.insert(prev, result[&curr] + 1);
+                queue.push_back(prev);
+            }
+        }
+    }
+    result
+}
+
+/// Returns drawings of the states of the given game as the given moves are made,
+/// starting with the initial state (so there's one more drawing than there are moves).
+///
+/// Each drawing is the board as drawn by its `Display` implementation,
+/// with the block drawn over it: `B` for an upright block, and `H` or `V`
+/// for both squares of a horizontal or vertical block.
+/// Parts of the block outside the board aren't drawn.
+///
+/// Panics if a move is made after the game is won or lost.
+pub fn animate_solution(game: Game, moves: &[Direction]) -> Vec<String> {
+    let mut result = vec![draw(game)];
+    let mut curr = game;
+    for (i, &direction) in moves.iter().enumerate() {
+        let Status::Active(active_curr) = curr.status() else {
+            panic!("cannot make a move in a finished game: move {i} of {moves:?}")
+        };
+        curr = active_curr.make_move(direction);
+        result.push(draw(curr));
+    }
+    result
+}
+
+/// Draws the given game's board with its block drawn over it, as described in `animate_solution`.
+fn draw(game: Game) -> String {
+    let Game { board, block } = game;
+    let Block(_, orientation) = block;
+    let symbol = match orientation {
+        Orientation::Upright => 'B',
+        Orientation::Horizontal => 'H',
+        Orientation::Vertical => 'V',
+    };
+    let mut rows: Vec<Vec<char>> = board
+        .to_string()
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect();
+    for (x, y) in block.full_coordinates() {
+        let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+            continue;
+        };
+        if let Some(cell) = rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = symbol;
+        }
+    }
+    rows.into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-/// Reconstructs the moves needed to get to the state associated with the given block,
+/// Reconstructs the moves needed to get to the given state,
 /// based on the map of given states.
-fn trace_moves(
-    visited: HashMap<Block, Option<(Direction, Block)>>,
-    final_block: Block,
-) -> Vec<Direction> {
+fn trace_moves<S: Copy + Eq + Hash, M: Copy>(
+    visited: HashMap<S, Option<(M, S)>>,
+    final_state: S,
+) -> Vec<M> {
     let mut result = VecDeque::new();
-    let mut curr = final_block;
+    let mut curr = final_state;
     while let Some((direction, prev)) = visited[&curr] {
         result.push_front(direction);
         curr = prev;
@@ -55,7 +310,7 @@ fn trace_moves(
 #[cfg(test)]
 mod tests {
     use crate::bloxorz_board;
-    use crate::bloxorz_model::{Board, Orientation::*};
+    use crate::bloxorz_model::{level, Board, Orientation::*};
     use crate::bloxorz_solver::*;
     use rstest::rstest;
 
@@ -153,17 +408,494 @@ mod tests {
         };
         match optimal_solution_length {
             Some(length) => {
-                let solution = solve(game).unwrap();
-                assert_eq!(solution.len(), length, "incorrect length: {solution:?}");
-                let Status::Win = play(game, &solution).status() else {
-                    panic!("expected a win: {solution:?}");
-                };
+                for solution in [solve(game).unwrap(), solve_pruned(game).unwrap()] {
+                    assert_eq!(solution.len(), length, "incorrect length: {solution:?}");
+                    let Status::Win = play(game, &solution).status() else {
+                        panic!("expected a win: {solution:?}");
+                    };
+                }
             }
             None => {
                 if let Some(solution) = solve(game) {
                     panic!("expected no solution, got solution {solution:?}");
                 }
+                if let Some(solution) = solve_pruned(game) {
+                    panic!("expected no solution, got pruned solution {solution:?}");
+                }
+            }
+        }
+    }
+
+    /// Returns every non-losing block state reachable from the given game
+    /// without passing through a finished game.
+    fn reachable_states(game: Game) -> HashSet<Block> {
+        let mut result = HashSet::new();
+        let mut stack = vec![game];
+        while let Some(curr) = stack.pop() {
+            if matches!(curr.status(), Status::Loss) || !result.insert(curr.block) {
+                continue;
+            }
+            if let Status::Active(active_curr) = curr.status() {
+                stack.extend(DIRECTIONS.iter().map(|&d| active_curr.make_move(d)));
+            }
+        }
+        result
+    }
+
+    #[rstest]
+    #[case::separated(bloxorz_board![
+        [# # # . # # #]
+        [# # # . # $ #]
+        [# # # . # # #]
+    ], Block((1, 1), Vertical), None)]
+    #[case::no_goal(bloxorz_board![
+        [# # # # # #]
+        [# # # # # #]
+        [# # # # # #]
+    ], Block((2, 1), Horizontal), None)]
+    #[case::instant_win(bloxorz_board![[$]], Block((0, 0), Upright), Some(0))]
+    #[case::dumbbell(bloxorz_board![
+        [# # # . . . # # $]
+        [# # # ! ! ! # # #]
+        [# # # ! ! ! # # #]
+        [# # # . . . # # $]
+    ], Block((0, 0), Upright), Some(10))]
+    #[case::plain_square(bloxorz_board![
+        [# # # #]
+        [# # # #]
+        [# # # #]
+        [# # # $]
+    ], Block((0, 0), Upright), Some(4))]
+    #[case::tight_maneuvering(bloxorz_board![
+        [# # # #]
+        [. ! ! $]
+        [. # # #]
+    ], Block((0, 0), Horizontal), Some(7))]
+    fn test_bfs_depth_profile(
+        #[case] board: Board,
+        #[case] initial_block: Block,
+        #[case] optimal_solution_length: Option<usize>,
+    ) {
+        let game = Game {
+            board: &board,
+            block: initial_block,
+        };
+        let profile = bfs_depth_profile(game);
+        assert_eq!(profile[&0], 1);
+        assert!(profile.get(&1).copied().unwrap_or(0) <= 4);
+        let max_depth = *profile.keys().max().unwrap();
+        // Every depth up to the maximum has at least one state.
+        assert!((0..=max_depth).all(|depth| profile[&depth] > 0));
+        let total: usize = profile.values().sum();
+        let reachable = reachable_states(game).len();
+        match optimal_solution_length {
+            Some(length) => {
+                assert_eq!(max_depth, length);
+                assert!(total <= reachable);
+            }
+            None => assert_eq!(total, reachable),
+        }
+    }
+
+    #[rstest]
+    fn test_bfs_depth_profile_levels(#[values(1, 2, 3, 4, 5)] n: u8) {
+        let (board, block) = level(n).unwrap();
+        let game = Game {
+            board: &board,
+            block,
+        };
+        let profile = bfs_depth_profile(game);
+        assert_eq!(profile.keys().max(), Some(&solve(game).unwrap().len()));
+    }
+
+    #[test]
+    fn test_bfs_depth_profile_exact() {
+        // Along a single row, each move either goes back to the previous state
+        // or reaches exactly one new state.
+        let board = bloxorz_board![[# # # # # # $]];
+        let game = Game {
+            board: &board,
+            block: Block((0, 0), Upright),
+        };
+        assert_eq!(
+            bfs_depth_profile(game),
+            HashMap::from([(0, 1), (1, 1), (2, 1), (3, 1), (4, 1)])
+        );
+    }
+
+    #[test]
+    fn test_bfs_depth_profile_instant_loss() {
+        let board = bloxorz_board![[!]];
+        let game = Game {
+            board: &board,
+            block: Block((0, 0), Upright),
+        };
+        assert_eq!(bfs_depth_profile(game), HashMap::new());
+    }
+
+    #[rstest]
+    #[case::instant_win(bloxorz_board![[$]], Block((0, 0), Upright), 0, 1)]
+    #[case::instant_loss(bloxorz_board![[!]], Block((0, 0), Upright), 10, 0)]
+    #[case::no_goal(bloxorz_board![
+        [# # # # # #]
+        [# # # # # #]
+        [# # # # # #]
+    ], Block((2, 1), Horizontal), 100, 0)]
+    #[case::separated(bloxorz_board![
+        [# # # . # # #]
+        [# # # . # $ #]
+        [# # # . # # #]
+    ], Block((1, 1), Vertical), 100, 0)]
+    // The shortest solution is `U R`, to the top goal,
+    // and the next shortest is `L D D`, to the bottom left goal.
+    // Both solutions are only counted with a limit of 3 moves:
+    // with a limit of 2, only `U R` fits, so the count is 1, not 2.
+    #[case::many_paths(bloxorz_board![
+        [# # # $ . . .]
+        [# ! ! # . . .]
+        [! . . ! . . .]
+        [! . . ! . . .]
+        [$ ! ! # # # $]
+    ], Block((1, 1), Horizontal), 3, 2)]
+    #[case::many_paths_shortest(bloxorz_board![
+        [# # # $ . . .]
+        [# ! ! # . . .]
+        [! . . ! . . .]
+        [! . . ! . . .]
+        [$ ! ! # # # $]
+    ], Block((1, 1), Horizontal), 2, 1)]
+    #[case::many_paths_too_short(bloxorz_board![
+        [# # # $ . . .]
+        [# ! ! # . . .]
+        [! . . ! . . .]
+        [! . . ! . . .]
+        [$ ! ! # # # $]
+    ], Block((1, 1), Horizontal), 1, 0)]
+    // Along a single row, the only solutions are the shortest one, `R R R R`,
+    // and those that go back once along the way, such as `R L R R R R`.
+    #[case::row_too_short(bloxorz_board![[# # # # # # $]], Block((0, 0), Upright), 3, 0)]
+    #[case::row_shortest(bloxorz_board![[# # # # # # $]], Block((0, 0), Upright), 5, 1)]
+    #[case::row_detour(bloxorz_board![[# # # # # # $]], Block((0, 0), Upright), 6, 4)]
+    fn test_count_solutions(
+        #[case] board: Board,
+        #[case] initial_block: Block,
+        #[case] max_moves: usize,
+        #[case] expected: u64,
+    ) {
+        let game = Game {
+            board: &board,
+            block: initial_block,
+        };
+        assert_eq!(count_solutions(game, max_moves), expected);
+    }
+
+    #[test]
+    fn test_count_solutions_instant_win() {
+        // No moves can be made after winning.
+        let board = bloxorz_board![[# $ #]];
+        let game = Game {
+            board: &board,
+            block: Block((1, 0), Upright),
+        };
+        assert_eq!(count_solutions(game, 100), 1);
+    }
+
+    #[rstest]
+    fn test_count_solutions_levels(#[values(1, 2, 3, 4, 5)] n: u8) {
+        let (board, block) = level(n).unwrap();
+        let game = Game {
+            board: &board,
+            block,
+        };
+        let optimal = solve(game).unwrap().len();
+        assert_eq!(count_solutions(game, optimal - 1), 0);
+        let count = count_solutions(game, optimal);
+        assert!(count >= 1);
+        assert!(count_solutions(game, optimal + 2) > count);
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of solutions doesn't fit in a u64")]
+    fn test_count_solutions_overflow() {
+        let board = bloxorz_board![
+            [# # # # # #]
+            [# # # # # #]
+            [# # # # # #]
+            [# # # # # $]
+        ];
+        let game = Game {
+            board: &board,
+            block: Block((0, 0), Upright),
+        };
+        count_solutions(game, 1000);
+    }
+
+    /// Asserts that the given drawing is a drawing of the given board with the given block,
+    /// as described in `animate_solution`.
+    fn assert_drawing(drawing: &str, board: &Board, block: Block) {
+        let Board(grid) = board;
+        let mut restored = String::new();
+        for (y, line) in drawing.lines().enumerate() {
+            if y > 0 {
+                restored.push('\n');
+            }
+            for (x, c) in line.chars().enumerate() {
+                let coordinates = (x as i32, y as i32);
+                let covered = block.full_coordinates().contains(&coordinates);
+                assert_eq!("BHV".contains(c), covered, "{coordinates:?} in\n{drawing}");
+                restored.push(if covered {
+                    grid[coordinates].symbol()
+                } else {
+                    c
+                });
+            }
+        }
+        assert_eq!(restored.parse::<Board>().as_ref(), Ok(board));
+    }
+
+    #[test]
+    fn test_animate_solution() {
+        let board = bloxorz_board![
+            [# # # #]
+            [# # # #]
+            [# # # #]
+            [# # # $]
+        ];
+        let game = Game {
+            board: &board,
+            block: Block((0, 0), Upright),
+        };
+        let moves = solve(game).unwrap();
+        let frames = animate_solution(game, &moves);
+        assert_eq!(frames.len(), moves.len() + 1);
+        assert_eq!(frames[0], "B###\n####\n####\n###$");
+        assert_eq!(frames.last().unwrap(), "####\n####\n####\n###B");
+        let mut block = game.block;
+        assert_drawing(&frames[0], &board, block);
+        for (&direction, frame) in moves.iter().zip(&frames[1..]) {
+            block = block.make_move(direction);
+            assert_drawing(frame, &board, block);
+        }
+    }
+
+    #[test]
+    fn test_animate_solution_orientations() {
+        use Direction::*;
+        let board = bloxorz_board![
+            [# # # .]
+            [# # ! .]
+            [# # # $]
+        ];
+        let game = Game {
+            board: &board,
+            block: Block((0, 0), Upright),
+        };
+        // The last move goes off the top of the board, and only the part on the board is drawn.
+        let frames = animate_solution(game, &[Right, Down, Left, Up]);
+        let expected = [
+            "B##.\n##!.\n###$",
+            "#HH.\n##!.\n###$",
+            "###.\n#HH.\n###$",
+            "###.\nB#!.\n###$",
+            "V##.\n##!.\n###$",
+        ];
+        assert_eq!(frames, expected);
+        // The block falls off the bottom of the board entirely.
+        assert_eq!(
+            animate_solution(game, &[Down, Down]),
+            ["B##.\n##!.\n###$", "###.\nV#!.\nV##$", "###.\n##!.\n###$"]
+        );
+    }
+
+    #[test]
+    fn test_animate_level() {
+        let (board, block) = level(4).unwrap();
+        let game = Game {
+            board: &board,
+            block,
+        };
+        let moves = solve(game).unwrap();
+        let frames = animate_solution(game, &moves);
+        assert_eq!(frames.len(), moves.len() + 1);
+        let mut curr = block;
+        for (i, frame) in frames.iter().enumerate() {
+            assert_drawing(frame, &board, curr);
+            if let Some(&direction) = moves.get(i) {
+                curr = curr.make_move(direction);
             }
         }
+        // The block ends up standing on the goal.
+        assert!(curr.is_standing_on(Tile::Goal, &board));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot make a move in a finished game: move 1 of [Down, Up]")]
+    fn test_animate_after_loss() {
+        let board = bloxorz_board![[#] [#]];
+        let game = Game {
+            board: &board,
+            block: Block((0, 0), Upright),
+        };
+        animate_solution(game, &[Direction::Down, Direction::Up]);
+    }
+
+    #[test]
+    fn test_reachable_from_win_instant_win() {
+        let board = bloxorz_board![[$]];
+        assert_eq!(
+            reachable_from_win(&board),
+            HashSet::from([Block((0, 0), Upright)])
+        );
+    }
+
+    #[test]
+    fn test_reachable_from_win() {
+        let board = bloxorz_board![
+            [# # # . # # # # #]
+            [# # # . # # $ # #]
+            [# # # . # # # # #]
+        ];
+        let reachable = reachable_from_win(&board);
+        assert!(reachable.contains(&Block((6, 1), Upright)));
+        assert!(reachable.contains(&Block((4, 1), Horizontal)));
+        assert!(reachable.contains(&Block((4, 1), Upright)));
+        // Only the right island can reach the goal.
+        assert!(reachable
+            .iter()
+            .all(|block| block.full_coordinates().iter().all(|&(x, _)| x >= 4)));
+        for &block in &reachable {
+            let game = Game {
+                board: &board,
+                block,
+            };
+            assert!(solve(game).is_some(), "{block:?}");
+        }
+        assert_eq!(
+            reachable_from_win(&bloxorz_board![[# #] [# #]]),
+            HashSet::new()
+        );
+    }
+
+    #[test]
+    fn test_solve_pruned_explores_fewer_states() {
+        let board = bloxorz_board![
+            [! ! ! ! ! ! ! !]
+            [! ! ! ! ! ! ! !]
+            [. . # . . # ! !]
+            [! ! $ . . . ! !]
+            [! ! . . . . ! !]
+            [! ! # . . # ! !]
+            [! ! ! ! ! ! ! !]
+            [! ! ! ! ! ! ! !]
+        ];
+        let game = Game {
+            board: &board,
+            block: Block((2, 2), Upright),
+        };
+        let (solution, visited) = search(game, |_| true);
+        let (pruned_solution, pruned_visited) = pruned_search(game);
+        assert_eq!(solution.map(|s| s.len()), Some(19));
+        assert_eq!(pruned_solution.map(|s| s.len()), Some(19));
+        assert!(pruned_visited < visited, "{pruned_visited} >= {visited}");
+    }
+
+    #[test]
+    fn test_solve_pruned_unwinnable_start() {
+        // The search doesn't even start, since the starting state can't reach the goal.
+        let board = bloxorz_board![
+            [# # # . # # #]
+            [# # # . # $ #]
+            [# # # . # # #]
+        ];
+        let game = Game {
+            board: &board,
+            block: Block((1, 1), Vertical),
+        };
+        assert_eq!(pruned_search(game), (None, 0));
+        assert!(search(game, |_| true).1 > 0);
+    }
+
+    fn corridor_board() -> Board {
+        bloxorz_board![
+            [. . . # . . .]
+            [. . . # . . .]
+            [# # # # # # $]
+            [. . . # . . .]
+            [. . . # . . .]
+        ]
+    }
+
+    /// Returns the result of making multiple moves in the given split game,
+    /// panicking if there are still moves to make after the game is won or lost.
+    fn play_split<'a>(mut game: SplitGame<'a>, moves: &[SplitMove]) -> SplitGame<'a> {
+        for (i, &split_move) in moves.iter().enumerate() {
+            let SplitStatus::Active(active_game) = game.status() else {
+                panic!("cannot make a move in a finished game: move {i} of {moves:?}")
+            };
+            game = active_game.make_split_move(split_move);
+        }
+        game
+    }
+
+    #[test]
+    fn test_solve_split_requires_switching() {
+        // Block B is in the way of block A, so it has to be moved up or down out of the corridor,
+        // after which block A can roll to the goal.
+        let board = corridor_board();
+        let game = SplitGame {
+            board: &board,
+            block_a: Block((0, 2), Upright),
+            block_b: Block((3, 2), Upright),
+            active: WhichBlock::A,
+        };
+        let solution = solve_split(game).unwrap();
+        assert_eq!(solution.len(), 7, "incorrect length: {solution:?}");
+        let switches = solution
+            .iter()
+            .filter(|&&split_move| split_move == SplitMove::Switch)
+            .count();
+        assert_eq!(switches, 2, "{solution:?}");
+        let SplitStatus::Win = play_split(game, &solution).status() else {
+            panic!("expected a win: {solution:?}");
+        };
+    }
+
+    #[rstest]
+    #[case::a_active(WhichBlock::A, 4)]
+    #[case::b_active(WhichBlock::B, 5)]
+    fn test_solve_split_unobstructed(#[case] active: WhichBlock, #[case] expected_length: usize) {
+        let board = corridor_board();
+        let game = SplitGame {
+            board: &board,
+            block_a: Block((0, 2), Upright),
+            block_b: Block((3, 0), Vertical),
+            active,
+        };
+        let solution = solve_split(game).unwrap();
+        assert_eq!(solution.len(), expected_length, "{solution:?}");
+        let SplitStatus::Win = play_split(game, &solution).status() else {
+            panic!("expected a win: {solution:?}");
+        };
+    }
+
+    #[rstest]
+    #[case::blocked_corridor(bloxorz_board![[# # # # # # $]], Block((0, 0), Upright),
+        Block((3, 0), Upright))]
+    #[case::b_off_board(corridor_board(), Block((0, 2), Upright), Block((3, -1), Upright))]
+    #[case::b_on_goal(corridor_board(), Block((0, 2), Upright), Block((6, 2), Upright))]
+    fn test_solve_split_unwinnable(
+        #[case] board: Board,
+        #[case] block_a: Block,
+        #[case] block_b: Block,
+    ) {
+        let game = SplitGame {
+            board: &board,
+            block_a,
+            block_b,
+            active: WhichBlock::A,
+        };
+        if let Some(solution) = solve_split(game) {
+            panic!("expected no solution, got solution {solution:?}");
+        }
     }
 }
@@ -0,0 +1,226 @@
+//! Problem: given a Boggle board (a grid of letters) and a dictionary,
+//! find all dictionary words that can be spelled out on the board.
+//!
+//! A word is spelled out by a path of cells, where each cell is adjacent
+//! (orthogonally or diagonally) to the previous one, and no cell is used more than once.
+//!
+//! For example, on the following board:
+//! ```text
+//! C A T
+//! X R S
+//! ```
+//! the words `CAT`, `CATS`, `CAR`, `CARS`, `ART`, `ARTS`, `RAT`, `RATS`, and `STAR`
+//! can all be spelled out, but `TACT` can't (it would need to use `T` twice).
+//!
+//! The dictionary is stored in a trie,
+//! so that paths can be abandoned as soon as they stop being a prefix of any word.
+
+use crate::flood_fill::Connectivity;
+use crate::grid::Grid;
+use std::collections::{HashMap, HashSet};
+
+/// A set of words, stored as a tree of characters for efficient prefix lookups.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Trie {
+    children: HashMap<char, Trie>,
+    is_word: bool,
+}
+
+impl Trie {
+    /// Creates an empty trie.
+    pub fn new() -> Trie {
+        Trie::default()
+    }
+
+    /// Adds the given word to the trie.
+    pub fn insert(&mut self, word: &str) {
+        let mut node = self;
+        for c in word.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        node.is_word = true;
+    }
+
+    /// Returns whether the given word is in the trie.
+    pub fn contains(&self, word: &str) -> bool {
+        self.descendant(word).is_some_and(|node| node.is_word)
+    }
+
+    /// Returns whether any word in the trie starts with the given prefix.
+    ///
+    /// Every word counts as a prefix of itself, and the empty string is a prefix of every word.
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        self.descendant(prefix)
+            .is_some_and(|node| node.is_word || !node.children.is_empty())
+    }
+
+    /// Returns the subtrie for the words starting with the given prefix,
+    /// or None if the trie has no nodes for that prefix.
+    fn descendant(&self, prefix: &str) -> Option<&Trie> {
+        prefix
+            .chars()
+            .try_fold(self, |node, c| node.children.get(&c))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Trie {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(words: I) -> Trie {
+        let mut trie = Trie::new();
+        for word in words {
+            trie.insert(word);
+        }
+        trie
+    }
+}
+
+type CellIndex = (i32, i32);
+
+/// Returns all words in the given dictionary with at least `min_length` characters
+/// that can be spelled out on the given board.
+pub fn find_words(board: &Grid<char>, dictionary: &Trie, min_length: usize) -> HashSet<String> {
+    let mut found = HashSet::new();
+    let mut visited = Grid::filled(false, board.dimensions());
+    let mut word = String::new();
+    for (cell, _) in board.enumerate::<CellIndex>() {
+        extend_word(board, dictionary, cell, &mut visited, &mut word, &mut found);
+    }
+    found.retain(|word| word.chars().count() >= min_length);
+    found
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells on the board, and `l` the length of the longest word.
+        This function completes in `O(c * 8^l * l)` time in the worst case:
+        from each of the `c` starting cells, there are at most `8^l` paths
+        of length at most `l` that are prefixes of dictionary words,
+        and each word found takes `O(l)` time to add to the result.
+        In practice, the trie prunes almost all paths early.
+    */
+}
+
+/// Extends the word spelled out so far (whose trie node is `node`) with the letter at `cell`,
+/// adding every word found from there onwards to `found`.
+///
+/// `word` and `visited` are restored to their original states before returning.
+fn extend_word(
+    board: &Grid<char>,
+    node: &Trie,
+    cell: CellIndex,
+    visited: &mut Grid<bool>,
+    word: &mut String,
+    found: &mut HashSet<String>,
+) {
+    let (x, y) = cell;
+    let letter = board[cell];
+    let Some(node) = node.children.get(&letter) else {
+        return;
+    };
+    visited[cell] = true;
+    word.push(letter);
+    if node.is_word {
+        found.insert(word.clone());
+    }
+    for (dx, dy) in Connectivity::Eight.displacements() {
+        let neighbor = (x + dx, y + dy);
+        if visited.get(neighbor) == Some(&false) {
+            extend_word(board, node, neighbor, visited, word, found);
+        }
+    }
+    word.pop();
+    visited[cell] = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boggle::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Boggle board from a list of rows, each given as a string of letters.
+    fn board<const W: usize, const H: usize>(rows: [&str; H]) -> Grid<char> {
+        Grid::from_2d_array(rows.map(|row| {
+            let letters: Vec<char> = row.chars().collect();
+            <[char; W]>::try_from(letters).unwrap()
+        }))
+    }
+
+    /// Converts a list of words to a set of owned strings.
+    fn words(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn test_trie() {
+        let trie: Trie = ["CAT", "CATS", "DOG"].into_iter().collect();
+        assert!(trie.contains("CAT"));
+        assert!(trie.contains("CATS"));
+        assert!(!trie.contains("CA"));
+        assert!(!trie.contains("COW"));
+        assert!(trie.has_prefix("CA"));
+        assert!(trie.has_prefix("CATS"));
+        assert!(trie.has_prefix(""));
+        assert!(!trie.has_prefix("CATSS"));
+        assert!(!trie.has_prefix("X"));
+    }
+
+    #[test]
+    fn test_empty_trie() {
+        let trie = Trie::new();
+        assert!(!trie.contains(""));
+        assert!(!trie.has_prefix(""));
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let dictionary: Trie = [
+            "CAT", "CATS", "CAR", "CARS", "ART", "ARTS", "RAT", "RATS", "STAR", "TACT", "DOG",
+        ]
+        .into_iter()
+        .collect();
+        let actual = find_words(&board::<3, 2>(["CAT", "XRS"]), &dictionary, 0);
+        let expected = words(&[
+            "CAT", "CATS", "CAR", "CARS", "ART", "ARTS", "RAT", "RATS", "STAR",
+        ]);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::two_letters(0, &["AB", "BA", "AA", "BB", "ABA", "ABAB"])]
+    #[case::min_length_3(3, &["ABA", "ABAB"])]
+    #[case::min_length_5(5, &[])]
+    fn test_repeated_letters(#[case] min_length: usize, #[case] expected: &[&str]) {
+        let dictionary: Trie = ["AB", "BA", "AA", "BB", "ABA", "ABAB", "AAA", "ABABA", "C"]
+            .into_iter()
+            .collect();
+        let actual = find_words(&board::<2, 2>(["AA", "BB"]), &dictionary, min_length);
+        assert_eq!(actual, words(expected));
+    }
+
+    #[rstest]
+    // The only `A` would have to be used twice.
+    #[case::revisit("ABA")]
+    // `A` and `C` aren't adjacent.
+    #[case::not_adjacent("AC")]
+    #[case::missing_letter("ABD")]
+    fn test_not_found(#[case] word: &str) {
+        let dictionary: Trie = [word].into_iter().collect();
+        let actual = find_words(&board::<3, 1>(["ABC"]), &dictionary, 0);
+        assert_eq!(actual, words(&[]));
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let dictionary: Trie = ["AD", "BC", "ADBC"].into_iter().collect();
+        let actual = find_words(&board::<2, 2>(["AB", "CD"]), &dictionary, 0);
+        assert_eq!(actual, words(&["AD", "BC", "ADBC"]));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let dictionary: Trie = ["A", "AB"].into_iter().collect();
+        let actual = find_words(&Grid::filled('A', (width, height)), &dictionary, 0);
+        assert_eq!(actual, words(&[]));
+    }
+}
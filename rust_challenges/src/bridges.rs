@@ -0,0 +1,386 @@
+//! Hashiwokakero (also known as Bridges) is a logic puzzle played on a rectangular grid,
+//! where some cells contain islands, each labeled with a number.
+//!
+//! Problem: connect the islands with bridges, such that:
+//! - each bridge runs horizontally or vertically between two islands,
+//!   without passing over any other island,
+//! - each pair of islands is connected by at most two bridges,
+//! - no two bridges cross,
+//! - the number of bridges connected to each island is the number on the island, and
+//! - every island can be reached from every other island by following bridges.
+//!
+//! For example, the following puzzle (numbers for islands, `.` for empty cells):
+//! ```text
+//! 2 . 3 . 3
+//! . . . . .
+//! 1 . . . 3
+//! ```
+//! has the unique solution (`-` and `|` for single bridges, `=` and `‖` for double bridges):
+//! ```text
+//! 2 = 3 - 3
+//! . . . . ‖
+//! 1 - - - 3
+//! ```
+//!
+//! The solver keeps track of the bridge counts that each pair of neighboring islands
+//! could still have. It narrows down the counts based on the numbers on the islands
+//! and on which bridges would cross, and backtracks when it reaches a contradiction
+//! or when the islands can no longer be connected.
+
+use crate::grid::Grid;
+use std::collections::{HashMap, VecDeque};
+
+type CellIndex = (usize, usize);
+
+/// An island, with the number of bridges that must be connected to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IslandNode {
+    pub value: u8,
+    pub position: CellIndex,
+}
+
+/// A Hashiwokakero puzzle, with the islands placed on a grid of the given size (width, height).
+///
+/// Every island should be within the bounds of the grid, and no two islands should share a cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BridgesBoard {
+    pub islands: Vec<IslandNode>,
+    pub grid_size: (usize, usize),
+}
+
+/// A set of possible bridge counts between two islands, where bit `i` represents `i` bridges.
+type Counts = u8;
+
+/// Every possible bridge count between two islands: 0, 1, or 2.
+const ALL_COUNTS: Counts = 0b111;
+
+/// Returns the smallest count in the given (nonempty) set.
+fn min_count(counts: Counts) -> u8 {
+    counts.trailing_zeros() as u8
+}
+
+/// Returns the largest count in the given (nonempty) set.
+fn max_count(counts: Counts) -> u8 {
+    7 - counts.leading_zeros() as u8
+}
+
+/// Returns the set of counts from `low` to `high` inclusive,
+/// treating negative bounds as 0 and bounds above 2 as 2.
+fn count_range(low: i32, high: i32) -> Counts {
+    (low.max(0)..=high.min(2)).fold(0, |counts, i| counts | 1 << i)
+}
+
+/// The structure of a puzzle: which islands can be connected, and which connections would cross.
+struct Puzzle {
+    values: Vec<u8>,
+    /// The pairs of islands that could be connected by bridges, as indices into `values`,
+    /// with the smaller index first.
+    connections: Vec<(usize, usize)>,
+    /// For each island, the indices of the connections involving that island.
+    incident: Vec<Vec<usize>>,
+    /// For each connection, the indices of the connections that would cross it.
+    crossings: Vec<Vec<usize>>,
+}
+
+impl Puzzle {
+    /// Finds the possible connections between islands.
+    ///
+    /// Panics if an island is out of bounds.
+    fn new(board: &BridgesBoard) -> Puzzle {
+        let mut grid = Grid::filled(None, board.grid_size);
+        for (i, island) in board.islands.iter().enumerate() {
+            grid[island.position] = Some(i);
+        }
+        let mut connections = vec![];
+        let mut cells_between: Vec<Vec<CellIndex>> = vec![];
+        for (i, island) in board.islands.iter().enumerate() {
+            let (x, y) = island.position;
+            // Looking only right and down finds each connection exactly once.
+            for (dx, dy) in [(1, 0), (0, 1)] {
+                let mut cells = vec![];
+                let mut curr = (x + dx, y + dy);
+                while let Some(&cell) = grid.get(curr) {
+                    if let Some(j) = cell {
+                        connections.push((i.min(j), i.max(j)));
+                        cells_between.push(cells);
+                        break;
+                    }
+                    cells.push(curr);
+                    curr = (curr.0 + dx, curr.1 + dy);
+                }
+            }
+        }
+        let mut incident = vec![vec![]; board.islands.len()];
+        for (c, &(i, j)) in connections.iter().enumerate() {
+            incident[i].push(c);
+            incident[j].push(c);
+        }
+        // Two connections cross exactly when they pass over the same cell.
+        let mut connections_by_cell: HashMap<CellIndex, Vec<usize>> = HashMap::new();
+        for (c, cells) in cells_between.iter().enumerate() {
+            for &cell in cells {
+                connections_by_cell.entry(cell).or_default().push(c);
+            }
+        }
+        let mut crossings = vec![vec![]; connections.len()];
+        for crossing in connections_by_cell.values() {
+            if let &[c, d] = &crossing[..] {
+                crossings[c].push(d);
+                crossings[d].push(c);
+            }
+        }
+        Puzzle {
+            values: board.islands.iter().map(|island| island.value).collect(),
+            connections,
+            incident,
+            crossings,
+        }
+    }
+
+    /// Narrows down the possible bridge counts as much as possible.
+    ///
+    /// Returns None if a contradiction is found or the islands can no longer be connected.
+    fn propagate(&self, counts: &mut [Counts]) -> Option<()> {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let mut restrict = |counts: &mut [Counts], c: usize, allowed: Counts| {
+                let restricted = counts[c] & allowed;
+                if restricted == 0 {
+                    return None;
+                }
+                if restricted != counts[c] {
+                    counts[c] = restricted;
+                    changed = true;
+                }
+                Some(())
+            };
+            for (i, incident) in self.incident.iter().enumerate() {
+                let value = self.values[i] as i32;
+                let low_sum: i32 = incident.iter().map(|&c| min_count(counts[c]) as i32).sum();
+                let high_sum: i32 = incident.iter().map(|&c| max_count(counts[c]) as i32).sum();
+                if !(low_sum..=high_sum).contains(&value) {
+                    return None;
+                }
+                // Each connection must make up whatever the others can't.
+                for &c in incident {
+                    let low = value - (high_sum - max_count(counts[c]) as i32);
+                    let high = value - (low_sum - min_count(counts[c]) as i32);
+                    restrict(counts, c, count_range(low, high))?;
+                }
+            }
+            for (c, crossings) in self.crossings.iter().enumerate() {
+                if min_count(counts[c]) > 0 {
+                    for &d in crossings {
+                        restrict(counts, d, 0b001)?;
+                    }
+                }
+            }
+        }
+        self.is_connectable(counts).then_some(())
+    }
+
+    /// Returns whether every island can be reached from every other island
+    /// using connections that could still have bridges.
+    fn is_connectable(&self, counts: &[Counts]) -> bool {
+        let num_islands = self.values.len();
+        if num_islands == 0 {
+            return true;
+        }
+        let mut visited = vec![false; num_islands];
+        visited[0] = true;
+        let mut num_visited = 1;
+        let mut queue = VecDeque::from([0]);
+        while let Some(i) = queue.pop_front() {
+            for &c in &self.incident[i] {
+                let (a, b) = self.connections[c];
+                let j = if a == i { b } else { a };
+                if max_count(counts[c]) > 0 && !visited[j] {
+                    visited[j] = true;
+                    num_visited += 1;
+                    queue.push_back(j);
+                }
+            }
+        }
+        num_visited == num_islands
+    }
+
+    /// Returns a solution that narrows down the given possible counts, or None if there is none.
+    fn search(&self, mut counts: Vec<Counts>) -> Option<Vec<Counts>> {
+        self.propagate(&mut counts)?;
+        // Guess for the connection with the fewest possible counts.
+        let Some(c) = (0..counts.len())
+            .filter(|&c| counts[c].count_ones() > 1)
+            .min_by_key(|&c| counts[c].count_ones())
+        else {
+            return Some(counts);
+        };
+        for count in 0..=2 {
+            if counts[c] & 1 << count != 0 {
+                let mut guess = counts.clone();
+                guess[c] = 1 << count;
+                if let Some(solution) = self.search(guess) {
+                    return Some(solution);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Returns a solution to the given Hashiwokakero puzzle, or None if there is no solution.
+///
+/// The solution maps each pair of islands that could be connected by bridges
+/// (as indices into `board.islands`, with the smaller index first)
+/// to the number of bridges between them, including pairs that have 0 bridges.
+/// If there are multiple solutions, any one of them may be returned.
+///
+/// Panics if an island is out of bounds.
+pub fn solve(board: &BridgesBoard) -> Option<HashMap<(usize, usize), u8>> {
+    let puzzle = Puzzle::new(board);
+    let counts = puzzle.search(vec![ALL_COUNTS; puzzle.connections.len()])?;
+    let solution = puzzle
+        .connections
+        .iter()
+        .zip(counts)
+        .map(|(&connection, counts)| (connection, min_count(counts)))
+        .collect();
+    Some(solution)
+    /*
+        Time complexity analysis:
+        Let `n` be the number of islands, and `c` the number of cells in the grid.
+        There are at most `2n` possible connections (at most two per island, to the right and below).
+        This function completes in `O(c + 9^n * n^2)` time in the worst case,
+        though propagation usually prunes the search far more than this suggests.
+        - Finding the connections and crossings takes `O(c + n)` time,
+          since each cell is passed over by at most two connections.
+        - The search tree has `O(3^(2n)) = O(9^n)` nodes.
+        - At each node, propagation makes `O(n)` passes,
+          since each pass that doesn't finish removes at least one possible count.
+          Each pass takes `O(n)` time, since there are `O(n)` connections and crossings.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bridges::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a puzzle from a grid of cells, with numbers for islands and `.` for empty cells.
+    macro_rules! bridges {
+        ($([$($cell:tt)*])*) => {{
+            let rows: Vec<Vec<Option<u8>>> = vec![$(vec![$(bridges!(@cell $cell)),*]),*];
+            let mut islands = vec![];
+            for (y, row) in rows.iter().enumerate() {
+                for (x, &cell) in row.iter().enumerate() {
+                    if let Some(value) = cell {
+                        islands.push(IslandNode { value, position: (x, y) });
+                    }
+                }
+            }
+            let width = rows.first().map_or(0, |row| row.len());
+            BridgesBoard { islands, grid_size: (width, rows.len()) }
+        }};
+        (@cell .) => {None};
+        (@cell $value:literal) => {Some($value)};
+    }
+
+    /// Checks that the given solution satisfies all the rules of Hashiwokakero for the given board.
+    fn assert_valid(board: &BridgesBoard, solution: &HashMap<(usize, usize), u8>) {
+        let puzzle = Puzzle::new(board);
+        let counts: Vec<_> = puzzle
+            .connections
+            .iter()
+            .map(|connection| 1 << solution[connection])
+            .collect();
+        assert_eq!(solution.len(), puzzle.connections.len());
+        for (i, island) in board.islands.iter().enumerate() {
+            let num_bridges: u8 = puzzle.incident[i]
+                .iter()
+                .map(|&c| solution[&puzzle.connections[c]])
+                .sum();
+            assert_eq!(
+                num_bridges, island.value,
+                "wrong bridge count for island {i}"
+            );
+        }
+        for (c, crossings) in puzzle.crossings.iter().enumerate() {
+            for &d in crossings {
+                assert!(min_count(counts[c]) == 0 || min_count(counts[d]) == 0);
+            }
+        }
+        assert!(puzzle.is_connectable(&counts), "islands not connected");
+    }
+
+    #[rstest]
+    #[case::empty_0_0((0, 0))]
+    #[case::empty_3_0((3, 0))]
+    #[case::empty_0_3((0, 3))]
+    fn test_empty(#[case] grid_size: (usize, usize)) {
+        let board = BridgesBoard {
+            islands: vec![],
+            grid_size,
+        };
+        assert_eq!(solve(&board), Some(HashMap::new()));
+    }
+
+    #[rstest]
+    #[case::single_island(bridges![[. 0 .]], &[])]
+    #[case::double_bridge(bridges![[2 . 2]], &[((0, 1), 2)])]
+    #[case::example(bridges![
+        [2 . 3 . 3]
+        [. . . . .]
+        [1 . . . 3]
+    ], &[((0, 1), 2), ((1, 2), 1), ((0, 3), 0), ((2, 4), 2), ((3, 4), 1)])]
+    // The two connections in the middle would cross, but only one of them is needed.
+    #[case::avoid_crossing(bridges![
+        [2 2 .]
+        [1 . 1]
+        [. 2 2]
+    ], &[((0, 1), 1), ((0, 2), 1), ((1, 4), 1), ((2, 3), 0), ((3, 5), 1), ((4, 5), 1)])]
+    fn test_solve(#[case] board: BridgesBoard, #[case] expected: &[((usize, usize), u8)]) {
+        let actual = solve(&board);
+        if let Some(solution) = &actual {
+            assert_valid(&board, solution);
+        }
+        assert_eq!(actual, Some(expected.iter().copied().collect()));
+    }
+
+    #[test]
+    fn test_solve_larger() {
+        let board = bridges![
+            [3 . 5 . . 2]
+            [. . . . . .]
+            [. . . . . .]
+            [4 . 4 . 2 .]
+            [. . . . . .]
+            [3 . . . 4 3]
+        ];
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    #[case::wrong_total(bridges![[1 . 2]])]
+    #[case::too_many(bridges![[3 . 3]])]
+    #[case::no_neighbors(bridges![
+        [1 .]
+        [. 1]
+    ])]
+    // Every island needs its one bridge, but the two bridges would cross.
+    #[case::crossing(bridges![
+        [. 1 .]
+        [1 . 1]
+        [. 1 .]
+    ])]
+    // Each island can be paired up with a neighbor, but then the pairs can't be connected.
+    #[case::disconnected(bridges![
+        [1 1]
+        [. .]
+        [1 1]
+    ])]
+    fn test_no_solution(#[case] board: BridgesBoard) {
+        assert_eq!(solve(&board), None);
+    }
+}
@@ -0,0 +1,450 @@
+//! Helper module for number puzzles that are solved by keeping track of
+//! the numbers that each cell could still contain.
+//!
+//! Sets of candidate numbers are stored as bitsets ([`Numbers`]).
+//!
+//! [`solve_candidates`] solves a puzzle described by three kinds of constraints:
+//! - _arcs_, where the number in one cell restricts the number in another cell
+//!   (like two cells in the same row of a Latin square, or the two sides of an inequality);
+//! - _groups_ of `n` cells that must contain every number from 1 to `n`
+//!   (like rows and columns of a Latin square, or regions in Suguru); and
+//! - puzzle-specific narrowing rules, for constraints that don't fit either of the above
+//!   (like KenKen cages).
+//!
+//! Every pair of cells with an arc is kept _arc consistent_ using the AC-3 algorithm:
+//! every candidate for one cell of the pair must be compatible with some candidate for the other.
+//! A number that only one cell of a group can contain is placed in that cell,
+//! and the narrowing rules are applied until none of them make any progress.
+//! With the candidates consistent, the solver guesses a number for the most constrained cell,
+//! and backtracks if the guess leads to a contradiction.
+//!
+//! [`solve_regions`] uses it for puzzles where each region of `n` cells contains the numbers
+//! from 1 to `n`, and pairs of cells can't both contain certain numbers
+//! (like Suguru and Ripple Effect).
+
+use crate::grid::Grid;
+use std::collections::{HashMap, VecDeque};
+
+type CellIndex = (usize, usize);
+
+/// A set of numbers from 1 to 31, where bit `k` is set if number `k` is in the set.
+pub type Numbers = u32;
+
+/// Every number from 1 to 31.
+const ALL_NUMBERS: Numbers = !1;
+
+/// Returns the numbers in the given set, in increasing order.
+pub fn numbers_in(numbers: Numbers) -> impl Iterator<Item = u32> {
+    (1..Numbers::BITS).filter(move |&k| numbers & (1 << k) != 0)
+}
+
+/// The largest number of cells a region can have in [`solve_regions`].
+pub const MAX_REGION_SIZE: usize = 31;
+
+/// A constraint from one cell to another cell.
+pub trait ArcConstraint: Copy {
+    /// Returns the numbers that the first cell can contain
+    /// when the second cell can contain any of the given numbers.
+    ///
+    /// The result may also contain numbers that aren't candidates for either cell.
+    fn supported(self, numbers: Numbers) -> Numbers;
+
+    /// The same constraint, seen from the other cell.
+    fn reversed(self) -> Self {
+        self
+    }
+}
+
+/// The two cells can't contain the same number.
+impl ArcConstraint for () {
+    fn supported(self, numbers: Numbers) -> Numbers {
+        ALL_NUMBERS.supported(numbers)
+    }
+}
+
+/// The two cells can't both contain any of the numbers in the set.
+impl ArcConstraint for Numbers {
+    fn supported(self, numbers: Numbers) -> Numbers {
+        // A number is only unsupported if it's forbidden and the only candidate for the other cell.
+        if numbers.count_ones() == 1 {
+            !(numbers & self)
+        } else {
+            Numbers::MAX
+        }
+    }
+}
+
+/// A constraint from one cell to another cell, in one direction.
+pub type Arc<C> = (CellIndex, CellIndex, C);
+
+/// Returns the arc going the other way from the given one.
+fn reverse<C: ArcConstraint>((from, to, constraint): Arc<C>) -> Arc<C> {
+    (to, from, constraint.reversed())
+}
+
+/// A puzzle-specific way to narrow down the candidates for each cell, used by [`solve_candidates`].
+///
+/// Returns the cells whose candidates were narrowed down,
+/// or None if the puzzle is found to be unsolvable.
+pub type Rule<'a> = &'a dyn Fn(&mut Grid<Numbers>) -> Option<Vec<CellIndex>>;
+
+/// Returns the rows and columns of an `n`×`n` grid.
+pub fn lines(size: usize) -> Vec<Vec<CellIndex>> {
+    let rows = (0..size).map(|y| (0..size).map(|x| (x, y)).collect());
+    let columns = (0..size).map(|x| (0..size).map(|y| (x, y)).collect());
+    rows.chain(columns).collect()
+}
+
+/// Returns the arcs from every cell of an `n`×`n` grid
+/// to every other cell in the same row or column, all with the given constraint,
+/// grouped by the cell that the arc goes from.
+pub fn line_arcs<C: Copy>(size: usize, constraint: C) -> Grid<Vec<Arc<C>>> {
+    Grid::from_fn(size, size, |x, y| {
+        let row = (0..size).map(|i| (i, y));
+        let column = (0..size).map(|i| (x, i));
+        row.chain(column)
+            .filter(|&other| other != (x, y))
+            .map(|other| ((x, y), other, constraint))
+            .collect()
+    })
+}
+
+/// Returns the first cell (in row-major order) that is in a region with more than
+/// [`MAX_REGION_SIZE`] cells or is given a number that its region can't contain,
+/// or None if there's no such cell.
+pub fn first_invalid_cell(grid: &Grid<Option<u8>>, regions: &Grid<usize>) -> Option<CellIndex> {
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for (_, &id) in regions.enumerate::<CellIndex>() {
+        *sizes.entry(id).or_default() += 1;
+    }
+    grid.enumerate::<CellIndex>()
+        .find(|&(cell, &number)| {
+            let size = sizes[&regions[cell]];
+            size > MAX_REGION_SIZE
+                || number.is_some_and(|number| !(1..=size).contains(&usize::from(number)))
+        })
+        .map(|(cell, _)| cell)
+}
+
+/// Returns a solution to the puzzle with the given cells and the ID of the region containing
+/// each cell, where the arcs starting from each cell must also be satisfied,
+/// or None if the puzzle has no solution.
+///
+/// Every arc must have a counterpart going the other way.
+/// The regions are assumed to be valid, as checked by [`first_invalid_cell`].
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve_regions<C: ArcConstraint>(
+    grid: &Grid<Option<u8>>,
+    regions: &Grid<usize>,
+    arcs: &Grid<Vec<Arc<C>>>,
+) -> Option<Grid<u8>> {
+    let mut region_cells: HashMap<usize, Vec<CellIndex>> = HashMap::new();
+    for (cell, &id) in regions.enumerate::<CellIndex>() {
+        region_cells.entry(id).or_default().push(cell);
+    }
+    let region_cells: Vec<Vec<CellIndex>> = region_cells.into_values().collect();
+    let mut candidates = Grid::filled(0, grid.dimensions());
+    for cells in &region_cells {
+        let all_numbers: Numbers = ((1 << cells.len()) - 1) << 1;
+        for &cell in cells {
+            candidates[cell] = grid[cell].map_or(all_numbers, |number| 1 << number);
+        }
+    }
+    solve_candidates(candidates, arcs, &region_cells, &[])
+}
+
+/// Returns a solution to the puzzle with the given candidates for each cell,
+/// where the arcs starting from each cell must be satisfied,
+/// each group of `n` cells must contain every number from 1 to `n`,
+/// and the candidates must be left unchanged by every rule,
+/// or None if the puzzle has no solution.
+///
+/// Every arc must have a counterpart going the other way (as given by [`ArcConstraint::reversed`]).
+/// The rules must not rely on being applied in any particular order,
+/// and must reject any fully decided candidates that don't satisfy their constraints.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve_candidates<C: ArcConstraint>(
+    mut candidates: Grid<Numbers>,
+    arcs: &Grid<Vec<Arc<C>>>,
+    groups: &[Vec<CellIndex>],
+    rules: &[Rule],
+) -> Option<Grid<u8>> {
+    if candidates.any(|&numbers| numbers == 0) {
+        return None;
+    }
+    let queue = arcs
+        .enumerate::<CellIndex>()
+        .flat_map(|(_, arcs)| arcs.iter().copied())
+        .collect();
+    let constraints = Constraints {
+        arcs,
+        groups,
+        rules,
+    };
+    if !constraints.propagate(&mut candidates, queue) {
+        return None;
+    }
+    let candidates = constraints.search(candidates)?;
+    Some(candidates.map(|numbers| numbers.trailing_zeros() as u8))
+    /*
+        Time complexity analysis:
+        Finding a solution can take exponential time in the number of cells in the worst case.
+        Let `c` be the number of cells, `g` be the total size of the groups,
+        `n` be the largest number, and `a` be the number of arcs.
+        Each arc is revised in constant time (with bitsets),
+        and is only added back to the queue when a cell loses a candidate,
+        so AC-3 takes `O(a * n)` time.
+        Looking for numbers with only one possible cell takes `O(g * n)` time per round,
+        and there are at most `O(c * n)` rounds (since each one removes a candidate),
+        so each guess takes `O(a * n + c * g * n^2)` time to propagate in the very worst case,
+        plus the time taken by the rules.
+    */
+}
+
+/// The constraints of a puzzle, as given to [`solve_candidates`].
+struct Constraints<'a, C> {
+    arcs: &'a Grid<Vec<Arc<C>>>,
+    groups: &'a [Vec<CellIndex>],
+    rules: &'a [Rule<'a>],
+}
+
+impl<C: ArcConstraint> Constraints<'_, C> {
+    /// Tries to narrow down the given candidates (which must be consistent) to a solution.
+    ///
+    /// Returns None if there is no solution.
+    fn search(&self, candidates: Grid<Numbers>) -> Option<Grid<Numbers>> {
+        // Guess a number for the cell with the fewest remaining candidates (besides 1).
+        let undecided = candidates
+            .enumerate::<CellIndex>()
+            .filter(|&(_, &numbers)| numbers.count_ones() > 1)
+            .min_by_key(|&(_, &numbers)| numbers.count_ones());
+        let Some((index, &numbers)) = undecided else {
+            return Some(candidates);
+        };
+        numbers_in(numbers).find_map(|number| {
+            let mut candidates = candidates.clone();
+            candidates[index] = 1 << number;
+            // Only the arcs pointing at the guessed cell can be affected.
+            let queue = self.arcs[index].iter().copied().map(reverse).collect();
+            if !self.propagate(&mut candidates, queue) {
+                return None;
+            }
+            self.search(candidates)
+        })
+    }
+
+    /// Alternates between running AC-3 (starting with the given queue of arcs to revise),
+    /// placing numbers that only one cell of their group can contain, and applying the rules,
+    /// until none of them make any progress.
+    ///
+    /// Returns false if some cell has no candidates left, some group can't contain all its numbers,
+    /// or some rule finds the puzzle to be unsolvable.
+    fn propagate(&self, candidates: &mut Grid<Numbers>, mut queue: VecDeque<Arc<C>>) -> bool {
+        loop {
+            if !self.make_consistent(candidates, queue) {
+                return false;
+            }
+            let mut narrowed = vec![];
+            for cells in self.groups {
+                for number in 1..=cells.len() {
+                    let mut possible = cells
+                        .iter()
+                        .copied()
+                        .filter(|&cell| candidates[cell] & (1 << number) != 0);
+                    match (possible.next(), possible.next()) {
+                        (None, _) => return false,
+                        (Some(cell), None) if candidates[cell] != 1 << number => {
+                            candidates[cell] = 1 << number;
+                            narrowed.push(cell);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            for rule in self.rules {
+                let Some(cells) = rule(candidates) else {
+                    return false;
+                };
+                narrowed.extend(cells);
+            }
+            if narrowed.is_empty() {
+                return true;
+            }
+            queue = narrowed
+                .into_iter()
+                .flat_map(|cell| self.arcs[cell].iter().copied().map(reverse))
+                .collect();
+        }
+    }
+
+    /// Runs AC-3 on the given candidates, starting with the given queue of arcs to revise.
+    ///
+    /// Returns false if some cell has no candidates left.
+    fn make_consistent(&self, candidates: &mut Grid<Numbers>, mut queue: VecDeque<Arc<C>>) -> bool {
+        while let Some((from, to, constraint)) = queue.pop_front() {
+            let revised = candidates[from] & constraint.supported(candidates[to]);
+            if revised == candidates[from] {
+                continue;
+            }
+            if revised == 0 {
+                return false;
+            }
+            candidates[from] = revised;
+            // Every other cell constrained by `from` might have lost support.
+            for &arc in &self.arcs[from] {
+                if arc.1 != to {
+                    queue.push_back(reverse(arc));
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::candidate_numbers::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::empty(0, vec![])]
+    #[case::single(0b1000, vec![3])]
+    #[case::several(0b1010_0110, vec![1, 2, 5, 7])]
+    #[case::ignores_zero(0b11, vec![1])]
+    #[case::all(ALL_NUMBERS, (1..=31).collect())]
+    fn test_numbers_in(#[case] numbers: Numbers, #[case] expected: Vec<u32>) {
+        assert_eq!(numbers_in(numbers).collect::<Vec<_>>(), expected);
+    }
+
+    /// Returns the arcs of a puzzle where the given pairs of cells can't both contain
+    /// the given numbers, in both directions.
+    fn symmetric_arcs(
+        dimensions: (usize, usize),
+        pairs: &[(CellIndex, CellIndex, Numbers)],
+    ) -> Grid<Vec<Arc<Numbers>>> {
+        let mut arcs = Grid::filled(vec![], dimensions);
+        for &(a, b, forbidden) in pairs {
+            arcs[a].push((a, b, forbidden));
+            arcs[b].push((b, a, forbidden));
+        }
+        arcs
+    }
+
+    #[rstest]
+    #[case::same_single((), 0b100, !0b100)]
+    #[case::same_multiple((), 0b110, Numbers::MAX)]
+    #[case::forbidden_single(0b110, 0b100, !0b100)]
+    #[case::allowed_single(0b110, 0b1000, Numbers::MAX)]
+    #[case::forbidden_multiple(0b110, 0b110, Numbers::MAX)]
+    fn test_supported<C: ArcConstraint>(
+        #[case] constraint: C,
+        #[case] numbers: Numbers,
+        #[case] expected: Numbers,
+    ) {
+        assert_eq!(constraint.supported(numbers), expected);
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(
+            lines(2),
+            [
+                vec![(0, 0), (1, 0)],
+                vec![(0, 1), (1, 1)],
+                vec![(0, 0), (0, 1)],
+                vec![(1, 0), (1, 1)],
+            ]
+        );
+        assert_eq!(lines(0), Vec::<Vec<CellIndex>>::new());
+    }
+
+    #[test]
+    fn test_line_arcs() {
+        let arcs = line_arcs(3, 'x');
+        assert_eq!(
+            arcs[(1, 2)],
+            [
+                ((1, 2), (0, 2), 'x'),
+                ((1, 2), (2, 2), 'x'),
+                ((1, 2), (1, 0), 'x'),
+                ((1, 2), (1, 1), 'x'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_regions_forced() {
+        // Two regions of 2 cells, where the cells where the regions meet can't both contain 2.
+        let regions = Grid::from_2d_array([[0, 0, 1, 1]]);
+        let arcs = symmetric_arcs((4, 1), &[((1, 0), (2, 0), 0b100)]);
+        let given = Grid::from_2d_array([[Some(1), None, None, None]]);
+        let solution = solve_regions(&given, &regions, &arcs);
+        assert_eq!(solution, Some(Grid::from_2d_array([[1, 2, 1, 2]])));
+    }
+
+    #[test]
+    fn test_solve_regions_unsolvable() {
+        // Two single-cell regions that can't both contain 1.
+        let regions = Grid::from_2d_array([[0, 1]]);
+        let arcs = symmetric_arcs((2, 1), &[((0, 0), (1, 0), 0b10)]);
+        assert_eq!(
+            solve_regions(&Grid::filled(None, (2, 1)), &regions, &arcs),
+            None
+        );
+        // Without the constraint, both cells contain 1.
+        let arcs: Grid<Vec<Arc<()>>> = Grid::filled(vec![], (2, 1));
+        let solution = solve_regions(&Grid::filled(None, (2, 1)), &regions, &arcs);
+        assert_eq!(solution, Some(Grid::filled(1, (2, 1))));
+    }
+
+    #[test]
+    fn test_solve_candidates_rules() {
+        // A single group of 2 cells, where a rule keeps the first cell from containing 1.
+        let candidates = Grid::filled(0b110, (2, 1));
+        let arcs: Grid<Vec<Arc<()>>> = Grid::filled(vec![], (2, 1));
+        let groups = [vec![(0, 0), (1, 0)]];
+        let not_one = |candidates: &mut Grid<Numbers>| {
+            if candidates[(0, 0)] & 0b10 == 0 {
+                return Some(vec![]);
+            }
+            candidates[(0, 0)] &= !0b10;
+            Some(vec![(0, 0)])
+        };
+        let solution = solve_candidates(candidates.clone(), &arcs, &groups, &[&not_one]);
+        assert_eq!(solution, Some(Grid::from_2d_array([[2, 1]])));
+        let reject = |_: &mut Grid<Numbers>| None;
+        assert_eq!(
+            solve_candidates(candidates.clone(), &arcs, &groups, &[&not_one, &reject]),
+            None
+        );
+        // A cell with no candidates can't be filled.
+        let candidates = Grid::from_2d_array([[0b110, 0]]);
+        assert_eq!(solve_candidates(candidates, &arcs, &groups, &[]), None);
+    }
+
+    #[rstest]
+    #[case::valid([Some(2), None, Some(1)], None)]
+    #[case::too_large([None, Some(3), None], Some((1, 0)))]
+    #[case::zero([None, None, Some(0)], Some((2, 0)))]
+    #[case::first_invalid([Some(3), None, Some(2)], Some((0, 0)))]
+    fn test_first_invalid_cell(
+        #[case] given: [Option<u8>; 3],
+        #[case] expected: Option<CellIndex>,
+    ) {
+        let regions = Grid::from_2d_array([[0, 0, 1]]);
+        let given = Grid::from_2d_array([given]);
+        assert_eq!(first_invalid_cell(&given, &regions), expected);
+    }
+
+    #[test]
+    fn test_region_too_large() {
+        let regions = Grid::filled(0, (MAX_REGION_SIZE + 1, 1));
+        let given = Grid::filled(None, (MAX_REGION_SIZE + 1, 1));
+        assert_eq!(first_invalid_cell(&given, &regions), Some((0, 0)));
+    }
+}
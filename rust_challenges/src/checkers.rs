@@ -0,0 +1,595 @@
+//! Checkers (also known as English draughts) is a two-player game played on an 8×8 board,
+//! using only the dark squares. Each player starts with 12 pieces on the dark squares
+//! of the three rows closest to them, with black moving first.
+//! A piece moves diagonally forward to an adjacent empty square,
+//! or _jumps_ diagonally forward over an adjacent opposing piece to the empty square beyond it,
+//! capturing the jumped piece. After a jump, the same piece must keep jumping if it can,
+//! and if a player can jump at all, they must jump instead of making a simple move.
+//! A piece reaching the far row becomes a _king_, which can also move and jump backwards;
+//! a piece that becomes a king in the middle of a jump sequence stops there.
+//!
+//! Problem: model the moves of the game.
+//!
+//! For example (`b` for a black piece, `w` for a white piece, `.` for an empty square,
+//! and with black moving down the board), on this part of a board
+//! ```text
+//! . b . . .
+//! . . w . .
+//! . . . . .
+//! . . w . .
+//! . . . . .
+//! ```
+//! black has to jump over both white pieces, ending up at the bottom left.
+//!
+//! Jump sequences are found with a depth-first search from each piece,
+//! keeping track of the pieces captured so far (which stay on the board until the move is over,
+//! so they can't be jumped twice or landed on).
+
+use crate::grid::Grid;
+
+/// One of the two players.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Player {
+    /// The player who moves first, starting at the top of the board and moving down.
+    Black,
+    /// The player starting at the bottom of the board and moving up.
+    White,
+}
+
+impl Player {
+    /// The other player.
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::Black => Player::White,
+            Player::White => Player::Black,
+        }
+    }
+
+    /// The vertical direction that the player's pieces move in before becoming kings.
+    fn forward(self) -> isize {
+        match self {
+            Player::Black => 1,
+            Player::White => -1,
+        }
+    }
+}
+
+/// A piece on the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CheckersPiece {
+    pub player: Player,
+    /// Whether the piece is a king, which can move backwards.
+    pub king: bool,
+}
+
+/// A checkers board, where `None` indicates an empty square.
+///
+/// Boards created with `new` are 8×8, but the other functions in this module
+/// work with boards of any size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckersBoard(pub Grid<Option<CheckersPiece>>);
+
+/// The side length of a standard board.
+const SIZE: usize = 8;
+
+/// The number of rows each player starts with pieces in.
+const STARTING_ROWS: usize = 3;
+
+impl CheckersBoard {
+    /// Creates an 8×8 board with the standard starting position:
+    /// black pieces on the dark squares of the top three rows
+    /// and white pieces on the dark squares of the bottom three rows,
+    /// where the dark squares are those with an odd `x + y`.
+    pub fn new() -> CheckersBoard {
+        let mut grid = Grid::filled(None, (SIZE, SIZE));
+        for y in 0..SIZE {
+            for x in (0..SIZE).filter(|x| (x + y) % 2 == 1) {
+                let player = if y < STARTING_ROWS {
+                    Player::Black
+                } else if y >= SIZE - STARTING_ROWS {
+                    Player::White
+                } else {
+                    continue;
+                };
+                grid[(x, y)] = Some(CheckersPiece {
+                    player,
+                    king: false,
+                });
+            }
+        }
+        CheckersBoard(grid)
+    }
+}
+
+impl Default for CheckersBoard {
+    fn default() -> CheckersBoard {
+        CheckersBoard::new()
+    }
+}
+
+type CellIndex = (usize, usize);
+
+/// A move in checkers.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CheckersMove {
+    /// A piece moving to an adjacent empty square.
+    Simple { from: CellIndex, to: CellIndex },
+    /// A piece jumping over one or more opposing pieces,
+    /// landing on each square in `path` in turn.
+    Jump {
+        from: CellIndex,
+        path: Vec<CellIndex>,
+    },
+}
+
+/// The diagonal displacements, with the upward ones first.
+const DIAGONALS: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// Returns the directions that the given piece can move in.
+fn directions(piece: CheckersPiece) -> impl Iterator<Item = (isize, isize)> {
+    DIAGONALS
+        .into_iter()
+        .filter(move |&(_, dy)| piece.king || dy == piece.player.forward())
+}
+
+/// Returns the cell `distance` steps away from the given cell in the given direction,
+/// or None if it's out of bounds for the given grid.
+fn offset<T>(
+    grid: &Grid<T>,
+    (x, y): CellIndex,
+    (dx, dy): (isize, isize),
+    distance: isize,
+) -> Option<CellIndex> {
+    let cell = (
+        x.checked_add_signed(dx * distance)?,
+        y.checked_add_signed(dy * distance)?,
+    );
+    grid.get(cell).is_some().then_some(cell)
+}
+
+/// Returns whether the given piece becomes a king upon landing on the given cell.
+fn promotes(grid: &Grid<Option<CheckersPiece>>, piece: CheckersPiece, (_, y): CellIndex) -> bool {
+    let far_row = match piece.player {
+        Player::Black => grid.height() - 1,
+        Player::White => 0,
+    };
+    !piece.king && y == far_row
+}
+
+/// Adds every complete jump sequence that `piece` can make from `current` to `result`,
+/// given the squares landed on so far (`path`) and the pieces captured so far.
+///
+/// `grid` must not contain the moving piece, so that it can land on the square it started from.
+fn find_jumps(
+    grid: &Grid<Option<CheckersPiece>>,
+    (from, piece): (CellIndex, CheckersPiece),
+    current: CellIndex,
+    path: &mut Vec<CellIndex>,
+    captured: &mut Vec<CellIndex>,
+    result: &mut Vec<CheckersMove>,
+) {
+    let mut extended = false;
+    for direction in directions(piece) {
+        let (Some(over), Some(to)) = (
+            offset(grid, current, direction, 1),
+            offset(grid, current, direction, 2),
+        ) else {
+            continue;
+        };
+        let jumpable = matches!(grid[over], Some(other) if other.player != piece.player);
+        if !jumpable || captured.contains(&over) || grid[to].is_some() {
+            continue;
+        }
+        extended = true;
+        path.push(to);
+        captured.push(over);
+        if promotes(grid, piece, to) {
+            result.push(CheckersMove::Jump {
+                from,
+                path: path.clone(),
+            });
+        } else {
+            find_jumps(grid, (from, piece), to, path, captured, result);
+        }
+        path.pop();
+        captured.pop();
+    }
+    if !extended && !path.is_empty() {
+        result.push(CheckersMove::Jump {
+            from,
+            path: path.clone(),
+        });
+    }
+}
+
+/// Returns the valid moves for `player` on the given board:
+/// the complete jump sequences if there are any, and the simple moves otherwise.
+///
+/// Moves are grouped by the piece moving, in row-major order.
+pub fn valid_moves(board: &CheckersBoard, player: Player) -> Vec<CheckersMove> {
+    let CheckersBoard(grid) = board;
+    let pieces: Vec<(CellIndex, CheckersPiece)> = grid
+        .enumerate::<CellIndex>()
+        .filter_map(|(index, &cell)| Some((index, cell?)))
+        .filter(|(_, piece)| piece.player == player)
+        .collect();
+    let mut jumps = vec![];
+    let mut without_piece = grid.clone();
+    for &(from, piece) in &pieces {
+        without_piece[from] = None;
+        find_jumps(
+            &without_piece,
+            (from, piece),
+            from,
+            &mut vec![],
+            &mut vec![],
+            &mut jumps,
+        );
+        without_piece[from] = Some(piece);
+    }
+    if !jumps.is_empty() {
+        return jumps;
+    }
+    pieces
+        .into_iter()
+        .flat_map(|(from, piece)| {
+            directions(piece)
+                .filter_map(move |direction| offset(grid, from, direction, 1))
+                .filter(|&to| grid[to].is_none())
+                .map(move |to| CheckersMove::Simple { from, to })
+        })
+        .collect()
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells on the board.
+        Finding the pieces and simple moves takes `O(c)` time.
+        Each jump in a sequence captures a different piece and has at most 3 possible directions
+        (the way back is blocked by the piece just captured),
+        so the jump search takes `O(c * 3^p)` time in the worst case,
+        where `p` is the number of opposing pieces, though far less on realistic boards.
+    */
+}
+
+/// Returns the result of making the given move on the given board,
+/// removing any captured pieces and making the moving piece a king if it reaches the far row.
+///
+/// The move is assumed to be valid (such as one returned by [`valid_moves`]).
+///
+/// Panics if there's no piece at the start of the move.
+pub fn apply_move(board: &CheckersBoard, mv: &CheckersMove) -> CheckersBoard {
+    let CheckersBoard(grid) = board;
+    let mut grid = grid.clone();
+    let (from, path) = match mv {
+        CheckersMove::Simple { from, to } => (*from, vec![*to]),
+        CheckersMove::Jump { from, path } => (*from, path.clone()),
+    };
+    let mut piece = grid[from]
+        .take()
+        .unwrap_or_else(|| panic!("there's no piece at {from:?}"));
+    let mut current = from;
+    let is_jump = matches!(mv, CheckersMove::Jump { .. });
+    for &to in &path {
+        if is_jump {
+            // The captured piece is halfway between the squares jumped from and to.
+            grid[((current.0 + to.0) / 2, (current.1 + to.1) / 2)] = None;
+        }
+        piece.king |= promotes(&grid, piece, to);
+        current = to;
+    }
+    grid[current] = Some(piece);
+    CheckersBoard(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checkers::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a checkers board.
+    ///
+    /// Syntax:
+    /// ```text
+    /// checkers![
+    ///     [<`b`/`w` for black/white pieces, `B`/`W` for black/white kings, `.` for empty> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! checkers {
+        (@cell b) => {Some(CheckersPiece { player: Player::Black, king: false })};
+        (@cell B) => {Some(CheckersPiece { player: Player::Black, king: true })};
+        (@cell w) => {Some(CheckersPiece { player: Player::White, king: false })};
+        (@cell W) => {Some(CheckersPiece { player: Player::White, king: true })};
+        (@cell .) => {None};
+        ($([$($cell:tt)*])*) => {
+            CheckersBoard(Grid::from_2d_array([$([$(checkers!(@cell $cell)),*]),*]))
+        };
+    }
+
+    /// Creates a jump move.
+    fn jump(from: CellIndex, path: &[CellIndex]) -> CheckersMove {
+        CheckersMove::Jump {
+            from,
+            path: path.to_vec(),
+        }
+    }
+
+    /// Creates a simple move.
+    fn simple(from: CellIndex, to: CellIndex) -> CheckersMove {
+        CheckersMove::Simple { from, to }
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = checkers![
+            [. b . . .]
+            [. . w . .]
+            [. . . . .]
+            [. . w . .]
+            [. . . . .]
+        ];
+        let moves = valid_moves(&board, Player::Black);
+        assert_eq!(moves, [jump((1, 0), &[(3, 2), (1, 4)])]);
+        let expected = checkers![
+            [. . . . .]
+            [. . . . .]
+            [. . . . .]
+            [. . . . .]
+            [. B . . .]
+        ];
+        assert_eq!(apply_move(&board, &moves[0]), expected);
+    }
+
+    #[test]
+    fn test_new() {
+        let expected = checkers![
+            [. b . b . b . b]
+            [b . b . b . b .]
+            [. b . b . b . b]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [w . w . w . w .]
+            [. w . w . w . w]
+            [w . w . w . w .]
+        ];
+        assert_eq!(CheckersBoard::new(), expected);
+    }
+
+    #[rstest]
+    #[case::black(Player::Black, vec![
+        simple((1, 2), (0, 3)),
+        simple((1, 2), (2, 3)),
+        simple((3, 2), (2, 3)),
+        simple((3, 2), (4, 3)),
+        simple((5, 2), (4, 3)),
+        simple((5, 2), (6, 3)),
+        simple((7, 2), (6, 3)),
+    ])]
+    #[case::white(Player::White, vec![
+        simple((0, 5), (1, 4)),
+        simple((2, 5), (1, 4)),
+        simple((2, 5), (3, 4)),
+        simple((4, 5), (3, 4)),
+        simple((4, 5), (5, 4)),
+        simple((6, 5), (5, 4)),
+        simple((6, 5), (7, 4)),
+    ])]
+    fn test_opening_moves(#[case] player: Player, #[case] expected: Vec<CheckersMove>) {
+        assert_eq!(valid_moves(&CheckersBoard::new(), player), expected);
+    }
+
+    #[test]
+    fn test_jump_is_mandatory() {
+        // The black piece on the left could move forward, but the one on the right has to jump.
+        let board = checkers![
+            [. b . . . b . .]
+            [. . . . w . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+        ];
+        assert_eq!(
+            valid_moves(&board, Player::Black),
+            [jump((5, 0), &[(3, 2)])]
+        );
+        let expected = checkers![
+            [. b . . . . . .]
+            [. . . . . . . .]
+            [. . . b . . . .]
+            [. . . . . . . .]
+        ];
+        assert_eq!(apply_move(&board, &jump((5, 0), &[(3, 2)])), expected);
+    }
+
+    #[test]
+    fn test_multi_jump_is_mandatory() {
+        // Stopping after the first or second jump isn't allowed.
+        let board = checkers![
+            [. . . . . . . .]
+            [. . . . b . . .]
+            [. . . . . . . .]
+            [. . b . . . . .]
+            [. . . . . . . .]
+            [. . b . . . . .]
+            [. . . w . . . .]
+            [. . . . . . . .]
+        ];
+        let moves = valid_moves(&board, Player::White);
+        assert_eq!(moves, [jump((3, 6), &[(1, 4), (3, 2), (5, 0)])]);
+        let expected = checkers![
+            [. . . . . W . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+        ];
+        assert_eq!(apply_move(&board, &moves[0]), expected);
+    }
+
+    #[test]
+    fn test_branching_jumps() {
+        // After the first jump, the piece can continue either way, but has to continue.
+        let board = checkers![
+            [. . . . . . .]
+            [. . . . . . .]
+            [. . . . . . .]
+            [. . b . b . .]
+            [. . . . . . .]
+            [. . . . b . .]
+            [. . . . . w .]
+            [. . . . . . .]
+        ];
+        let expected = vec![
+            jump((5, 6), &[(3, 4), (1, 2)]),
+            jump((5, 6), &[(3, 4), (5, 2)]),
+        ];
+        assert_eq!(valid_moves(&board, Player::White), expected);
+    }
+
+    #[test]
+    fn test_promotion() {
+        let board = checkers![
+            [. . . .]
+            [. . . .]
+            [. b . .]
+            [. . . .]
+        ];
+        let moves = valid_moves(&board, Player::Black);
+        assert_eq!(moves, [simple((1, 2), (0, 3)), simple((1, 2), (2, 3))]);
+        let expected = checkers![
+            [. . . .]
+            [. . . .]
+            [. . . .]
+            [B . . .]
+        ];
+        assert_eq!(apply_move(&board, &moves[0]), expected);
+    }
+
+    #[test]
+    fn test_promotion_ends_jump_sequence() {
+        // As a king, the piece could jump again, but becoming a king ends the move.
+        let board = checkers![
+            [. . . . . .]
+            [. . b . b .]
+            [. w . . . .]
+            [. . . . . .]
+        ];
+        let moves = valid_moves(&board, Player::White);
+        assert_eq!(moves, [jump((1, 2), &[(3, 0)])]);
+        let expected = checkers![
+            [. . . W . .]
+            [. . . . b .]
+            [. . . . . .]
+            [. . . . . .]
+        ];
+        assert_eq!(apply_move(&board, &moves[0]), expected);
+    }
+
+    #[test]
+    fn test_king_moves_backwards() {
+        let board = checkers![
+            [. . . . .]
+            [. . . . .]
+            [. . B . .]
+            [. . . w .]
+            [. . . . .]
+        ];
+        // The king can jump backwards over the white piece.
+        assert_eq!(
+            valid_moves(&board, Player::Black),
+            [jump((2, 2), &[(4, 4)])]
+        );
+        let board = checkers![
+            [. . . . .]
+            [. . . . .]
+            [. . B . .]
+            [. . . . .]
+            [. . . . .]
+        ];
+        let expected = vec![
+            simple((2, 2), (1, 1)),
+            simple((2, 2), (3, 1)),
+            simple((2, 2), (1, 3)),
+            simple((2, 2), (3, 3)),
+        ];
+        assert_eq!(valid_moves(&board, Player::Black), expected);
+        let moved = checkers![
+            [. . . . .]
+            [. B . . .]
+            [. . . . .]
+            [. . . . .]
+            [. . . . .]
+        ];
+        assert_eq!(apply_move(&board, &expected[0]), moved);
+    }
+
+    #[test]
+    fn test_men_dont_move_backwards() {
+        // Neither piece can jump the other, since that would be a backwards jump.
+        let board = checkers![
+            [. . . . .]
+            [. w . . .]
+            [. . b . .]
+            [. . . . .]
+            [. . . . .]
+        ];
+        assert_eq!(
+            valid_moves(&board, Player::Black),
+            [simple((2, 2), (1, 3)), simple((2, 2), (3, 3))]
+        );
+        assert_eq!(
+            valid_moves(&board, Player::White),
+            [simple((1, 1), (0, 0)), simple((1, 1), (2, 0))]
+        );
+    }
+
+    #[test]
+    fn test_king_circles_back() {
+        // The king captures all four pieces and ends up back where it started,
+        // but can't jump any of them twice.
+        let board = checkers![
+            [. . . . . . .]
+            [. . w . w . .]
+            [. B . . . . .]
+            [. . w . w . .]
+            [. . . . . . .]
+        ];
+        let expected = vec![
+            jump((1, 2), &[(3, 0), (5, 2), (3, 4), (1, 2)]),
+            jump((1, 2), &[(3, 4), (5, 2), (3, 0), (1, 2)]),
+        ];
+        let moves = valid_moves(&board, Player::Black);
+        assert_eq!(moves, expected);
+        let after = checkers![
+            [. . . . . . .]
+            [. . . . . . .]
+            [. B . . . . .]
+            [. . . . . . .]
+            [. . . . . . .]
+        ];
+        assert_eq!(apply_move(&board, &moves[0]), after);
+    }
+
+    #[test]
+    fn test_no_moves() {
+        // Black is blocked by the edge of the board and by white pieces that can't be jumped.
+        let board = checkers![
+            [. . . b]
+            [. . w .]
+            [. w . .]
+        ];
+        assert_eq!(valid_moves(&board, Player::Black), []);
+        assert_eq!(
+            valid_moves(&board, Player::White),
+            [simple((2, 1), (1, 0)), simple((1, 2), (0, 1))]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "there's no piece at (0, 0)")]
+    fn test_apply_move_no_piece() {
+        apply_move(&CheckersBoard::new(), &simple((0, 0), (1, 1)));
+    }
+}
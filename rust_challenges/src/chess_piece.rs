@@ -0,0 +1,289 @@
+//! Problem: given a chess piece, its position on a board, and which squares are occupied,
+//! find every square that the piece covers -- the squares it can move to or attack.
+//!
+//! - A king moves one square in any of the 8 directions.
+//! - A queen slides any number of squares in any of the 8 directions.
+//! - A rook slides any number of squares orthogonally.
+//! - A bishop slides any number of squares diagonally.
+//! - A knight moves two squares in one orthogonal direction and one square in a perpendicular one,
+//!   jumping over anything in between.
+//!
+//! Sliding pieces are stopped by occupied squares, but still cover the first occupied square
+//! in each direction, since the piece on it could be captured.
+//!
+//! For example, a rook (`R`) with pieces (`#`) to its right and below
+//! covers the squares marked with `*`, along with both occupied squares:
+//! ```text
+//! . * . .
+//! * R * #
+//! . * . .
+//! . # . .
+//! ```
+
+use crate::flood_fill::Connectivity;
+use crate::grid::Grid;
+
+type Square = (usize, usize);
+
+/// A chess piece (other than a pawn, whose movement depends on its color and on captures).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChessPiece {
+    King,
+    Queen,
+    Rook,
+    Bishop,
+    Knight,
+}
+
+#[rustfmt::skip]
+const KNIGHT_DISPLACEMENTS: [(i32, i32); 8] = [
+    (-1, -2), (1, -2),
+    (-2, -1), (2, -1),
+    (-2,  1), (2,  1),
+    (-1,  2), (1,  2),
+];
+
+const DIAGONAL_DISPLACEMENTS: [(i32, i32); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+impl ChessPiece {
+    /// The displacements of a single step of the piece in each direction it can move in.
+    fn displacements(self) -> &'static [(i32, i32)] {
+        match self {
+            ChessPiece::King | ChessPiece::Queen => Connectivity::Eight.displacements(),
+            ChessPiece::Rook => Connectivity::Four.displacements(),
+            ChessPiece::Bishop => &DIAGONAL_DISPLACEMENTS,
+            ChessPiece::Knight => &KNIGHT_DISPLACEMENTS,
+        }
+    }
+
+    /// Whether the piece can repeat its step any number of times in the same direction.
+    fn slides(self) -> bool {
+        matches!(
+            self,
+            ChessPiece::Queen | ChessPiece::Rook | ChessPiece::Bishop
+        )
+    }
+}
+
+/// Returns every square covered by the given piece at the given position
+/// on a `board_width`×`board_height` board, in row-major order.
+///
+/// `true` cells of `blocked` are occupied: sliding pieces can't move past them,
+/// but they are still covered. The piece's own square is never covered.
+///
+/// Panics if `position` is out of bounds,
+/// or if the dimensions of `blocked` don't match the dimensions of the board.
+pub fn covered_squares(
+    piece: ChessPiece,
+    position: Square,
+    board_width: usize,
+    board_height: usize,
+    blocked: &Grid<bool>,
+) -> Vec<Square> {
+    let dimensions = (board_width, board_height);
+    if blocked.dimensions() != dimensions {
+        panic!(
+            "dimensions of blocked grid {:?} don't match board dimensions {dimensions:?}",
+            blocked.dimensions()
+        );
+    }
+    let (x, y) = position;
+    if x >= board_width || y >= board_height {
+        panic!("cell {position:?} out of bounds for dimensions {dimensions:?}");
+    }
+    let mut covered = vec![];
+    for &(dx, dy) in piece.displacements() {
+        let (mut curr_x, mut curr_y) = (x as i32, y as i32);
+        loop {
+            (curr_x, curr_y) = (curr_x + dx, curr_y + dy);
+            let Some(&occupied) = blocked.get((curr_x, curr_y)) else {
+                break;
+            };
+            covered.push((curr_x as usize, curr_y as usize));
+            if occupied || !piece.slides() {
+                break;
+            }
+        }
+    }
+    covered.sort_by_key(|&(x, y)| (y, x));
+    covered
+    /*
+        Time complexity analysis:
+        Let `n` be the larger of `board_width` and `board_height`.
+        This function completes in `O(n * log(n))` time in the worst case.
+        - Each of the at most 8 directions covers at most `n` squares.
+        - Sorting the `O(n)` covered squares takes `O(n * log(n))` time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chess_piece::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a grid of occupied squares.
+    ///
+    /// Syntax:
+    /// ```text
+    /// blocked![
+    ///     [<`.` for empty, `#` for occupied> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! blocked {
+        (@cell .) => {false};
+        (@cell #) => {true};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(blocked!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Returns the squares covered by the given piece on an empty 8×8 board.
+    fn covered_on_empty_board(piece: ChessPiece, position: Square) -> Vec<Square> {
+        covered_squares(piece, position, 8, 8, &Grid::filled(false, (8, 8)))
+    }
+
+    #[rstest]
+    #[case::king_center(ChessPiece::King, (3, 4), 8)]
+    #[case::king_corner(ChessPiece::King, (0, 0), 3)]
+    #[case::king_edge(ChessPiece::King, (7, 3), 5)]
+    #[case::queen_center(ChessPiece::Queen, (3, 4), 27)]
+    #[case::queen_corner(ChessPiece::Queen, (7, 7), 21)]
+    #[case::rook_center(ChessPiece::Rook, (3, 4), 14)]
+    #[case::rook_corner(ChessPiece::Rook, (0, 7), 14)]
+    #[case::bishop_center(ChessPiece::Bishop, (3, 4), 13)]
+    #[case::bishop_corner(ChessPiece::Bishop, (0, 0), 7)]
+    #[case::knight_center(ChessPiece::Knight, (3, 4), 8)]
+    #[case::knight_corner(ChessPiece::Knight, (0, 0), 2)]
+    #[case::knight_edge(ChessPiece::Knight, (0, 3), 4)]
+    fn test_empty_board(
+        #[case] piece: ChessPiece,
+        #[case] position: Square,
+        #[case] expected: usize,
+    ) {
+        assert_eq!(covered_on_empty_board(piece, position).len(), expected);
+    }
+
+    #[test]
+    fn test_knight_corner() {
+        assert_eq!(
+            covered_on_empty_board(ChessPiece::Knight, (0, 0)),
+            [(2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rook_example() {
+        let blocked = blocked![
+            [. . . .]
+            [. . . #]
+            [. . . .]
+            [. # . .]
+        ];
+        let expected = [(1, 0), (0, 1), (2, 1), (3, 1), (1, 2), (1, 3)];
+        assert_eq!(
+            covered_squares(ChessPiece::Rook, (1, 1), 4, 4, &blocked),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_queen_blocked() {
+        let blocked = blocked![
+            [. . . . .]
+            [. . . . .]
+            [# . . # .]
+            [. . . . .]
+            [. . # . .]
+        ];
+        // Every piece in the queen's lines is covered, but only the one at (3, 2) hides anything.
+        let expected = [
+            (0, 0),
+            (2, 0),
+            (4, 0),
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (0, 2),
+            (1, 2),
+            (3, 2),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+            (0, 4),
+            (2, 4),
+            (4, 4),
+        ];
+        assert_eq!(
+            covered_squares(ChessPiece::Queen, (2, 2), 5, 5, &blocked),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_pieces_between() {
+        // Only the knight can jump over the surrounding pieces.
+        let blocked = blocked![
+            [. . . . .]
+            [. # # # .]
+            [. # . # .]
+            [. # # # .]
+            [. . . . .]
+        ];
+        let pieces = [
+            (ChessPiece::King, 8),
+            (ChessPiece::Queen, 8),
+            (ChessPiece::Rook, 4),
+            (ChessPiece::Bishop, 4),
+            (ChessPiece::Knight, 8),
+        ];
+        for (piece, expected) in pieces {
+            let covered = covered_squares(piece, (2, 2), 5, 5, &blocked);
+            assert_eq!(covered.len(), expected, "{piece:?}");
+        }
+    }
+
+    #[rstest]
+    fn test_within_bounds(
+        #[values(
+            ChessPiece::King,
+            ChessPiece::Queen,
+            ChessPiece::Rook,
+            ChessPiece::Bishop,
+            ChessPiece::Knight
+        )]
+        piece: ChessPiece,
+        #[values((1, 1), (6, 2), (3, 4))] dimensions: (usize, usize),
+    ) {
+        let (width, height) = dimensions;
+        let mut blocked = Grid::filled(false, dimensions);
+        blocked[(0, 0)] = true;
+        for y in 0..height {
+            for x in 0..width {
+                let covered = covered_squares(piece, (x, y), width, height, &blocked);
+                for &(covered_x, covered_y) in &covered {
+                    assert!(covered_x < width && covered_y < height);
+                    assert_ne!((covered_x, covered_y), (x, y));
+                }
+                let mut deduplicated = covered.clone();
+                deduplicated.dedup();
+                assert_eq!(deduplicated, covered);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (8, 0) out of bounds for dimensions (8, 8)")]
+    fn test_out_of_bounds() {
+        covered_on_empty_board(ChessPiece::King, (8, 0));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "dimensions of blocked grid (3, 3) don't match board dimensions (8, 8)"
+    )]
+    fn test_mismatched_dimensions() {
+        covered_squares(ChessPiece::Rook, (0, 0), 8, 8, &Grid::filled(false, (3, 3)));
+    }
+}
@@ -0,0 +1,401 @@
+//! Connect Four is a two-player game played on a vertical board of columns.
+//! Players take turns dropping pieces into a column; each piece falls to the lowest empty cell.
+//! The first player to get four of their pieces in a row -- horizontally, vertically,
+//! or diagonally -- wins.
+//!
+//! Problem: model the game, and find a good move for a player by searching the game tree.
+//!
+//! For example, on the following board (`R` and `Y` for the two players' pieces, `.` for empty):
+//! ```text
+//! . . . . . . .
+//! . . . . . . .
+//! . . . . . . .
+//! . . . . . . .
+//! Y Y Y . . . .
+//! R R R . . . .
+//! ```
+//! Red wins by dropping a piece into column 3.
+//!
+//! The search uses negamax with alpha-beta pruning, limited to a given depth.
+//! Positions at the depth limit are scored by a heuristic
+//! that counts possible lines of four that are still open to each player.
+
+use crate::grid::Grid;
+
+/// One of the two players.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Player {
+    Red,
+    Yellow,
+}
+
+impl Player {
+    /// The other player.
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::Red => Player::Yellow,
+            Player::Yellow => Player::Red,
+        }
+    }
+}
+
+/// A Connect Four board, where `None` indicates an empty cell.
+///
+/// Row 0 is the top of the board; pieces fall towards the last row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectFourBoard(pub Grid<Option<Player>>);
+
+impl ConnectFourBoard {
+    /// Creates an empty board with the given number of columns and rows.
+    pub fn new(columns: usize, rows: usize) -> ConnectFourBoard {
+        ConnectFourBoard(Grid::filled(None, (columns, rows)))
+    }
+
+    /// Returns whether every cell on the board contains a piece.
+    pub fn is_full(&self) -> bool {
+        let ConnectFourBoard(grid) = self;
+        grid.enumerate::<(usize, usize)>()
+            .all(|(_, cell)| cell.is_some())
+    }
+}
+
+/// The number of pieces in a row needed to win.
+const WIN_LENGTH: i32 = 4;
+
+/// The directions in which a line of pieces can extend from its first piece.
+const LINE_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+/// Returns the result of `player` dropping a piece into the given column,
+/// or None if the column is full or doesn't exist.
+pub fn drop_piece(
+    board: &ConnectFourBoard,
+    column: usize,
+    player: Player,
+) -> Option<ConnectFourBoard> {
+    let ConnectFourBoard(grid) = board;
+    let row = (0..grid.height())
+        .rev()
+        .find(|&row| grid.get((column, row)) == Some(&None))?;
+    let mut grid = grid.clone();
+    grid[(column, row)] = Some(player);
+    Some(ConnectFourBoard(grid))
+}
+
+/// Returns every line of `WIN_LENGTH` cells on the given board,
+/// as the coordinates of each cell in the line.
+fn lines(grid: &Grid<Option<Player>>) -> impl Iterator<Item = [(i32, i32); 4]> + '_ {
+    grid.enumerate::<(i32, i32)>().flat_map(move |((x, y), _)| {
+        LINE_DIRECTIONS.iter().filter_map(move |&(dx, dy)| {
+            let end = (x + dx * (WIN_LENGTH - 1), y + dy * (WIN_LENGTH - 1));
+            grid.get(end)?;
+            Some([0, 1, 2, 3].map(|i| (x + dx * i, y + dy * i)))
+        })
+    })
+}
+
+/// Returns the player with four pieces in a row on the given board, if there is one.
+///
+/// If both players have four in a row (which can't happen in a real game),
+/// it's left unspecified which player is returned.
+pub fn check_winner(board: &ConnectFourBoard) -> Option<Player> {
+    let ConnectFourBoard(grid) = board;
+    lines(grid).find_map(|line| {
+        let first = grid[line[0]]?;
+        line.iter()
+            .all(|&index| grid[index] == Some(first))
+            .then_some(first)
+    })
+}
+
+/// The score of a won position, before adjusting for how quickly it was won.
+const WIN_SCORE: i32 = 1_000_000;
+
+/// Heuristically scores the given board (without a winner) from the perspective of `player`:
+/// each line containing only one player's pieces is worth the square of the number of pieces,
+/// positively for `player` and negatively for the opponent.
+fn evaluate(grid: &Grid<Option<Player>>, player: Player) -> i32 {
+    lines(grid)
+        .map(|line| {
+            let count = |target| {
+                line.iter()
+                    .filter(|&&index| grid[index] == Some(target))
+                    .count() as i32
+            };
+            match (count(player), count(player.opponent())) {
+                (own, 0) => own * own,
+                (0, opposing) => -opposing * opposing,
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+/// Returns the columns of the given board, ordered from the center outwards --
+/// central columns are part of more lines, so searching them first makes pruning more effective.
+fn move_order(board: &ConnectFourBoard) -> Vec<usize> {
+    let ConnectFourBoard(grid) = board;
+    let mut columns: Vec<usize> = (0..grid.width()).collect();
+    columns.sort_by_key(|&column| (2 * column).abs_diff(grid.width().saturating_sub(1)));
+    columns
+}
+
+/// Returns the negamax score of the given board from the perspective of `player`,
+/// who is about to move, searching up to `depth` moves ahead.
+///
+/// The result is exact if it lies strictly between `alpha` and `beta`;
+/// otherwise, it's only a bound in the corresponding direction.
+fn negamax(
+    board: &ConnectFourBoard,
+    player: Player,
+    depth: usize,
+    mut alpha: i32,
+    beta: i32,
+) -> i32 {
+    if check_winner(board).is_some() {
+        // The opponent just won; losing later (with less remaining depth) is better.
+        return -(WIN_SCORE + depth as i32);
+    }
+    if board.is_full() {
+        return 0;
+    }
+    if depth == 0 {
+        let ConnectFourBoard(grid) = board;
+        return evaluate(grid, player);
+    }
+    let mut best = i32::MIN + 1;
+    for column in move_order(board) {
+        let Some(child) = drop_piece(board, column, player) else {
+            continue;
+        };
+        let score = -negamax(&child, player.opponent(), depth - 1, -beta, -alpha);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// Returns the column that `player` should drop a piece into on the given board,
+/// according to a search of the game tree `depth` moves deep,
+/// or None if the game is already over.
+///
+/// A search depth of 0 is treated as a depth of 1
+/// (the moves available to `player` are always considered).
+/// If multiple moves are equally good, the one closest to the center of the board is returned.
+pub fn best_move(board: &ConnectFourBoard, player: Player, depth: usize) -> Option<usize> {
+    if check_winner(board).is_some() {
+        return None;
+    }
+    let mut best: Option<(usize, i32)> = None;
+    let mut alpha = i32::MIN + 1;
+    for column in move_order(board) {
+        let Some(child) = drop_piece(board, column, player) else {
+            continue;
+        };
+        let score = -negamax(
+            &child,
+            player.opponent(),
+            depth.saturating_sub(1),
+            i32::MIN + 1,
+            -alpha,
+        );
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((column, score));
+            alpha = score;
+        }
+    }
+    best.map(|(column, _)| column)
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells on the board, and `w` the number of columns.
+        This function completes in `O(w^d * c)` time in the worst case, where `d` is the depth --
+        alpha-beta pruning can reduce this to around `O(w^(d / 2) * c)` with good move ordering.
+        - The search visits `O(w^d)` positions.
+        - Checking for a winner, or evaluating a position, takes `O(c)` time,
+          since each cell starts up to 4 lines, each with 4 cells.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connect_four::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Connect Four board.
+    ///
+    /// Syntax:
+    /// ```text
+    /// connect_four![
+    ///     [<`R` for red, `Y` for yellow, `.` for empty> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! connect_four {
+        (@cell R) => {Some(Player::Red)};
+        (@cell Y) => {Some(Player::Yellow)};
+        (@cell .) => {None};
+        ($([$($cell:tt)*])*) => {
+            ConnectFourBoard(Grid::from_2d_array([$([$(connect_four!(@cell $cell)),*]),*]))
+        };
+    }
+
+    /// The board from the problem description.
+    fn problem_description_example() -> ConnectFourBoard {
+        connect_four![
+            [. . . . . . .]
+            [. . . . . . .]
+            [. . . . . . .]
+            [. . . . . . .]
+            [Y Y Y . . . .]
+            [R R R . . . .]
+        ]
+    }
+
+    /// A full board where neither player has four in a row.
+    fn drawn_board() -> ConnectFourBoard {
+        connect_four![
+            [R Y R Y R Y R]
+            [R Y R Y R Y R]
+            [Y R Y R Y R Y]
+            [Y R Y R Y R Y]
+            [R Y R Y R Y R]
+            [R Y R Y R Y R]
+        ]
+    }
+
+    #[rstest]
+    #[case::empty_board(ConnectFourBoard::new(4, 3), 1, Player::Red, connect_four![
+        [. . . .]
+        [. . . .]
+        [. R . .]
+    ])]
+    #[case::stacks(connect_four![
+        [. . . .]
+        [. Y . .]
+        [. R . .]
+    ], 1, Player::Yellow, connect_four![
+        [. Y . .]
+        [. Y . .]
+        [. R . .]
+    ])]
+    #[case::next_to_piece(connect_four![
+        [. . . .]
+        [. . . .]
+        [. R . .]
+    ], 0, Player::Yellow, connect_four![
+        [. . . .]
+        [. . . .]
+        [Y R . .]
+    ])]
+    fn test_drop_piece(
+        #[case] board: ConnectFourBoard,
+        #[case] column: usize,
+        #[case] player: Player,
+        #[case] expected: ConnectFourBoard,
+    ) {
+        assert_eq!(drop_piece(&board, column, player), Some(expected));
+    }
+
+    #[rstest]
+    #[case::full_column(connect_four![
+        [. Y . .]
+        [. Y . .]
+        [. R . .]
+    ], 1)]
+    #[case::nonexistent_column(ConnectFourBoard::new(4, 3), 4)]
+    #[case::full_board(drawn_board(), 3)]
+    fn test_drop_piece_invalid(#[case] board: ConnectFourBoard, #[case] column: usize) {
+        assert_eq!(drop_piece(&board, column, Player::Red), None);
+    }
+
+    #[rstest]
+    #[case::horizontal(connect_four![
+        [. . . . .]
+        [. Y Y Y .]
+        [. R R R R]
+    ], Some(Player::Red))]
+    #[case::vertical(connect_four![
+        [Y . . .]
+        [Y R . .]
+        [Y R . .]
+        [Y R . R]
+    ], Some(Player::Yellow))]
+    #[case::diagonal(connect_four![
+        [R . . . .]
+        [Y R . . .]
+        [Y Y R . .]
+        [R Y Y R .]
+    ], Some(Player::Red))]
+    #[case::anti_diagonal(connect_four![
+        [. . . . Y]
+        [. . . Y R]
+        [. . Y R R]
+        [. Y R R Y]
+    ], Some(Player::Yellow))]
+    #[case::three_in_a_row(problem_description_example(), None)]
+    #[case::broken_line(connect_four![
+        [R R Y R R]
+    ], None)]
+    #[case::full_board(drawn_board(), None)]
+    #[case::empty_board(ConnectFourBoard::new(7, 6), None)]
+    fn test_check_winner(#[case] board: ConnectFourBoard, #[case] expected: Option<Player>) {
+        assert_eq!(check_winner(&board), expected);
+    }
+
+    #[rstest]
+    #[case::horizontal_win(problem_description_example(), Player::Red, 1, 3)]
+    #[case::horizontal_win_deep(problem_description_example(), Player::Red, 5, 3)]
+    #[case::block_horizontal(problem_description_example(), Player::Yellow, 2, 3)]
+    #[case::vertical_win(connect_four![
+        [. . . . . . .]
+        [. . . . . . .]
+        [. . . . . . .]
+        [. . . . . Y .]
+        [. R . . . Y .]
+        [. R R . . Y .]
+    ], Player::Yellow, 3, 5)]
+    // Dropping a piece into column 2 or 5 creates two threats at once.
+    #[case::double_threat(connect_four![
+        [. . . . . . .]
+        [. . . . . . .]
+        [. . . . . . .]
+        [. . . . . . .]
+        [. . . . . . .]
+        [. . . R R . Y]
+    ], Player::Red, 3, 2)]
+    fn test_best_move(
+        #[case] board: ConnectFourBoard,
+        #[case] player: Player,
+        #[case] depth: usize,
+        #[case] expected: usize,
+    ) {
+        assert_eq!(best_move(&board, player, depth), Some(expected));
+    }
+
+    #[rstest]
+    #[case::already_won(connect_four![
+        [. . . . .]
+        [. Y Y Y .]
+        [. R R R R]
+    ])]
+    #[case::full_board(drawn_board())]
+    #[case::no_columns(ConnectFourBoard::new(0, 6))]
+    fn test_best_move_game_over(#[case] board: ConnectFourBoard) {
+        assert_eq!(best_move(&board, Player::Red, 4), None);
+    }
+
+    #[test]
+    fn test_self_play() {
+        // Neither player should ever pass up an immediate win or make an illegal move.
+        let mut board = ConnectFourBoard::new(7, 6);
+        let mut player = Player::Red;
+        while let Some(column) = best_move(&board, player, 4) {
+            board = drop_piece(&board, column, player).unwrap();
+            player = player.opponent();
+        }
+        assert!(check_winner(&board).is_some() || board.is_full());
+    }
+}
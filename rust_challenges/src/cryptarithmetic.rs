@@ -0,0 +1,243 @@
+//! A cryptarithm is an addition where each digit has been replaced by a letter.
+//!
+//! Problem: given some words to add up and a word for their sum,
+//! assign a digit to each letter (with different letters getting different digits)
+//! such that the addition is correct and no word with more than one letter starts with 0.
+//!
+//! For example, the classic puzzle
+//! ```text
+//!   SEND
+//! + MORE
+//! ------
+//!  MONEY
+//! ```
+//! has the unique solution `9567 + 1085 = 10652`.
+//!
+//! The solver works through the columns of the addition from right to left, like long addition,
+//! keeping track of the carry into each column.
+//! Each letter is assigned a digit when it's first seen, and at the end of each column,
+//! the letter of the sum must match the last digit of the column's total,
+//! which prunes most assignments long before every letter has a digit.
+
+use std::collections::{HashMap, HashSet};
+
+/// A column of the addition: the letters being added, and the letter of the sum.
+struct Column {
+    addends: Vec<char>,
+    result: char,
+}
+
+/// A partial assignment of digits to letters.
+struct Assignment {
+    digits: HashMap<char, u8>,
+    /// Whether each digit is assigned to a letter.
+    used: [bool; 10],
+}
+
+/// Returns an assignment of digits to letters such that the words in `lhs` add up to `rhs`,
+/// or None if there is no such assignment.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+///
+/// Panics if any word is empty.
+pub fn solve(lhs: &[&str], rhs: &str) -> Option<HashMap<char, u8>> {
+    assert!(
+        lhs.iter().chain([&rhs]).all(|word| !word.is_empty()),
+        "words can't be empty"
+    );
+    let letters: HashSet<char> = lhs
+        .iter()
+        .chain([&rhs])
+        .flat_map(|word| word.chars())
+        .collect();
+    if letters.len() > 10 {
+        return None;
+    }
+    let rhs: Vec<char> = rhs.chars().rev().collect();
+    let lhs: Vec<Vec<char>> = lhs
+        .iter()
+        .map(|word| word.chars().rev().collect())
+        .collect();
+    // Without leading zeros, a word with more letters than the sum is larger than the sum.
+    if lhs.iter().any(|word| word.len() > rhs.len()) {
+        return None;
+    }
+    let columns: Vec<Column> = (0..rhs.len())
+        .map(|i| Column {
+            addends: lhs.iter().filter_map(|word| word.get(i).copied()).collect(),
+            result: rhs[i],
+        })
+        .collect();
+    let leading: Vec<char> = lhs
+        .iter()
+        .chain([&rhs])
+        .filter(|word| word.len() > 1)
+        .map(|word| word[word.len() - 1])
+        .collect();
+    let mut assignment = Assignment {
+        digits: HashMap::new(),
+        used: [false; 10],
+    };
+    search(&columns, &leading, (0, 0), 0, &mut assignment).then_some(assignment.digits)
+    /*
+        Time complexity analysis:
+        Let `k` be the number of different letters (at most 10 after the initial check)
+        and `w` be the number of words.
+        There are at most `10! / (10 - k)!` assignments of digits to letters,
+        each of which is checked column by column in `O(w * l)` time,
+        where `l` is the length of the sum,
+        so this function completes in `O(10! / (10 - k)! * w * l)` time in the worst case,
+        though checking each column as soon as it's finished makes it much faster in practice.
+    */
+}
+
+/// Tries to complete the given assignment, starting from the given addend of the given column,
+/// with `total` being the sum of the carry into the column and the addends before that one.
+///
+/// Returns whether the assignment was completed; if not, it's left as it was.
+fn search(
+    columns: &[Column],
+    leading: &[char],
+    position: (usize, usize),
+    total: u32,
+    assignment: &mut Assignment,
+) -> bool {
+    let (column, addend) = position;
+    let Some(Column { addends, result }) = columns.get(column) else {
+        // There's nowhere for a final carry to go.
+        return total == 0;
+    };
+    let is_result = addend == addends.len();
+    let letter = if is_result { *result } else { addends[addend] };
+    let assigned = assignment.digits.get(&letter).copied();
+    // The sum's letter can only be the last digit of the column's total.
+    let digits: Vec<u8> = match assigned {
+        Some(digit) => vec![digit],
+        None if is_result => vec![(total % 10) as u8],
+        None => (0..10).collect(),
+    };
+    for digit in digits {
+        if is_result && u32::from(digit) != total % 10 {
+            continue;
+        }
+        let (next, next_total) = if is_result {
+            ((column + 1, 0), total / 10)
+        } else {
+            ((column, addend + 1), total + u32::from(digit))
+        };
+        if assigned.is_some() {
+            return search(columns, leading, next, next_total, assignment);
+        }
+        if assignment.used[usize::from(digit)] || (digit == 0 && leading.contains(&letter)) {
+            continue;
+        }
+        assignment.digits.insert(letter, digit);
+        assignment.used[usize::from(digit)] = true;
+        if search(columns, leading, next, next_total, assignment) {
+            return true;
+        }
+        assignment.digits.remove(&letter);
+        assignment.used[usize::from(digit)] = false;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cryptarithmetic::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Returns the value of the given word under the given assignment.
+    fn value(word: &str, assignment: &HashMap<char, u8>) -> u64 {
+        word.chars()
+            .fold(0, |acc, letter| acc * 10 + u64::from(assignment[&letter]))
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(lhs: &[&str], rhs: &str, solution: &HashMap<char, u8>) {
+        let letters: HashSet<char> = lhs
+            .iter()
+            .chain([&rhs])
+            .flat_map(|word| word.chars())
+            .collect();
+        assert_eq!(solution.keys().copied().collect::<HashSet<_>>(), letters);
+        let digits: HashSet<u8> = solution.values().copied().collect();
+        assert_eq!(digits.len(), solution.len(), "some letters share a digit");
+        assert!(digits.iter().all(|&digit| digit < 10));
+        for word in lhs.iter().chain([&rhs]) {
+            if word.len() > 1 {
+                let first = word.chars().next().unwrap();
+                assert_ne!(solution[&first], 0, "{word} has a leading zero");
+            }
+        }
+        let sum: u64 = lhs.iter().map(|word| value(word, solution)).sum();
+        assert_eq!(sum, value(rhs, solution), "the addition is wrong");
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let solution = solve(&["SEND", "MORE"], "MONEY").unwrap();
+        assert_valid(&["SEND", "MORE"], "MONEY", &solution);
+        let expected: HashMap<char, u8> = [
+            ('S', 9),
+            ('E', 5),
+            ('N', 6),
+            ('D', 7),
+            ('M', 1),
+            ('O', 0),
+            ('R', 8),
+            ('Y', 2),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_cross_roads_danger() {
+        let solution = solve(&["CROSS", "ROADS"], "DANGER").unwrap();
+        assert_valid(&["CROSS", "ROADS"], "DANGER", &solution);
+        assert_eq!(value("CROSS", &solution), 96233);
+        assert_eq!(value("ROADS", &solution), 62513);
+        assert_eq!(value("DANGER", &solution), 158746);
+    }
+
+    #[rstest]
+    #[case::three_words(&["SIX", "SEVEN", "SEVEN"], "TWENTY")]
+    #[case::repeated_word(&["TO", "TO", "TO", "TO"], "GO")]
+    #[case::same_length(&["AB", "CD"], "EF")]
+    #[case::one_word(&["ABC"], "ABC")]
+    fn test_solve(#[case] lhs: &[&str], #[case] rhs: &str) {
+        let solution = solve(lhs, rhs).unwrap();
+        assert_valid(lhs, rhs, &solution);
+    }
+
+    #[test]
+    fn test_single_letter_zero() {
+        // `A` has to be 0, which is allowed since it's only one letter long.
+        let solution = solve(&["A", "BC"], "BC").unwrap();
+        assert_valid(&["A", "BC"], "BC", &solution);
+        assert_eq!(solution[&'A'], 0);
+    }
+
+    #[rstest]
+    // Two 2-digit numbers add up to at least 20.
+    #[case::sum_too_small(&["AB", "CD"], "E")]
+    // Two 1-digit numbers add up to at most 18.
+    #[case::sum_too_large(&["A", "B"], "CDE")]
+    // `A + A = A` would need A to be 0, but it's the first letter of `AB`.
+    #[case::leading_zero(&["AB", "AB"], "AB")]
+    #[case::too_many_letters(&["ABCDE", "FGHIJ"], "KLMNOP")]
+    #[case::different_letters_same_digit(&["A"], "B")]
+    fn test_unsolvable(#[case] lhs: &[&str], #[case] rhs: &str) {
+        assert_eq!(solve(lhs, rhs), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "words can't be empty")]
+    fn test_empty_word() {
+        solve(&["A", ""], "B");
+    }
+}
@@ -0,0 +1,221 @@
+//! A domino tiling of a rectangular grid covers every cell of the grid with dominoes,
+//! each covering two orthogonally adjacent cells, without any dominoes overlapping.
+//!
+//! Problem: count the domino tilings of a grid with the given dimensions, and find one of them.
+//!
+//! For example, a 3 by 2 grid has 3 tilings:
+//! ```text
+//! |||  |==  ==|
+//! |||  |==  ==|
+//! ```
+//! where `|` is half of a vertical domino and `==` is a horizontal domino.
+//!
+//! The tilings are counted one column at a time, from left to right.
+//! The _profile_ of a column is the set of its cells that are already covered
+//! by horizontal dominoes sticking out of the previous column.
+//! For each profile of a column, the number of ways to tile everything before the column
+//! (with the profile's cells covered) is known,
+//! and each way to fill the rest of the column with vertical dominoes and horizontal dominoes
+//! sticking out into the next column gives a profile of the next column.
+//! The columns are taken along the longer side of the grid, so that profiles are as small as possible.
+
+/// A domino covering two orthogonally adjacent cells, as `(x, y)` coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DominoPlacement {
+    pub cell_a: (usize, usize),
+    pub cell_b: (usize, usize),
+}
+
+/// The largest supported length of the shorter side of the grid when counting tilings.
+pub const MAX_PROFILE_SIZE: usize = 20;
+
+/// Returns the number of domino tilings of a grid with the given dimensions.
+///
+/// A grid with no cells has exactly one tiling (with no dominoes).
+///
+/// Panics if both dimensions are greater than [`MAX_PROFILE_SIZE`],
+/// or the number of tilings doesn't fit in a `u64`.
+pub fn count_tilings(width: usize, height: usize) -> u64 {
+    // Transposing a grid doesn't change its number of tilings.
+    let (length, size) = (width.max(height), width.min(height));
+    assert!(
+        size <= MAX_PROFILE_SIZE,
+        "both dimensions of a {width} by {height} grid are greater than {MAX_PROFILE_SIZE}"
+    );
+    // `counts[profile]` is the number of ways to tile the columns before the current one,
+    // along with the cells of the current column in `profile`, and nothing else.
+    let mut counts = vec![0u64; 1 << size];
+    counts[0] = 1;
+    for _ in 0..length {
+        let mut next_counts = vec![0u64; 1 << size];
+        for (profile, &count) in counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            fill_column(size, profile, 0, 0, &mut |next_profile| {
+                next_counts[next_profile] = next_counts[next_profile]
+                    .checked_add(count)
+                    .expect("the number of tilings doesn't fit in a u64");
+            });
+        }
+        counts = next_counts;
+    }
+    // No dominoes can stick out past the last column.
+    counts[0]
+    /*
+        Time complexity analysis:
+        Let `s` be the shorter side of the grid and `l` be the longer side.
+        For each of the `l` columns, each of the `2^s` profiles has at most `2^s` next profiles
+        (one for each set of horizontal dominoes sticking out), each found in `O(s)` time,
+        so this function completes in `O(l * s * 4^s)` time in the worst case,
+        though far fewer next profiles are actually possible.
+    */
+}
+
+/// Calls `visit` with the profile of the next column for each way to fill in the rows
+/// of the current column from `y` onwards,
+/// where `filled` is the set of cells of the current column that are already covered,
+/// and `next` is the set of cells of the next column covered so far.
+fn fill_column(size: usize, filled: usize, y: usize, next: usize, visit: &mut impl FnMut(usize)) {
+    if y >= size {
+        visit(next);
+        return;
+    }
+    if filled & (1 << y) != 0 {
+        fill_column(size, filled, y + 1, next, visit);
+        return;
+    }
+    // A horizontal domino sticking out into the next column.
+    fill_column(size, filled, y + 1, next | (1 << y), visit);
+    // A vertical domino covering this cell and the one below it.
+    if y + 1 < size && filled & (1 << (y + 1)) == 0 {
+        fill_column(size, filled, y + 2, next, visit);
+    }
+}
+
+/// Returns a domino tiling of a grid with the given dimensions,
+/// or None if there isn't one (which is exactly when the grid has an odd number of cells).
+///
+/// If there are multiple tilings, one of them will be returned;
+/// it's left unspecified which specific tiling is returned.
+pub fn find_tiling(width: usize, height: usize) -> Option<Vec<DominoPlacement>> {
+    // Each domino covers two cells, so a grid with an odd number of cells can't be tiled.
+    // Otherwise, one of the dimensions is even, and the grid can be tiled
+    // with dominoes lined up along that dimension.
+    if !width.is_multiple_of(2) && !height.is_multiple_of(2) {
+        return None;
+    }
+    let mut result = Vec::with_capacity(width * height / 2);
+    if height.is_multiple_of(2) {
+        for x in 0..width {
+            for y in (0..height).step_by(2) {
+                result.push(DominoPlacement {
+                    cell_a: (x, y),
+                    cell_b: (x, y + 1),
+                });
+            }
+        }
+    } else {
+        for y in 0..height {
+            for x in (0..width).step_by(2) {
+                result.push(DominoPlacement {
+                    cell_a: (x, y),
+                    cell_b: (x + 1, y),
+                });
+            }
+        }
+    }
+    Some(result)
+    /*
+        Time complexity analysis:
+        Each domino is placed in `O(1)` time,
+        so this function completes in `O(width * height)` time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domino_tiling::*;
+    use crate::grid::Grid;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Checks that the given dominoes tile a grid with the given dimensions.
+    fn assert_valid(width: usize, height: usize, tiling: &[DominoPlacement]) {
+        let mut covered = Grid::filled(false, (width, height));
+        for &DominoPlacement { cell_a, cell_b } in tiling {
+            let ((xa, ya), (xb, yb)) = (cell_a, cell_b);
+            assert_eq!(
+                xa.abs_diff(xb) + ya.abs_diff(yb),
+                1,
+                "{cell_a:?} and {cell_b:?} aren't adjacent"
+            );
+            for cell in [cell_a, cell_b] {
+                let covered = covered
+                    .get_mut(cell)
+                    .unwrap_or_else(|| panic!("{cell:?} is outside the grid"));
+                assert!(!*covered, "{cell:?} is covered twice");
+                *covered = true;
+            }
+        }
+        assert_eq!(covered, Grid::filled(true, (width, height)));
+    }
+
+    #[rstest]
+    #[case(2, 2, 2)]
+    #[case(2, 3, 3)]
+    #[case(3, 2, 3)]
+    #[case(3, 3, 0)]
+    #[case(4, 4, 36)]
+    #[case(1, 6, 1)]
+    #[case(1, 7, 0)]
+    #[case(2, 10, 89)]
+    #[case(3, 4, 11)]
+    #[case(6, 6, 6728)]
+    #[case(8, 8, 12988816)]
+    #[case(0, 0, 1)]
+    #[case(0, 5, 1)]
+    fn test_count_tilings(#[case] width: usize, #[case] height: usize, #[case] expected: u64) {
+        assert_eq!(count_tilings(width, height), expected);
+    }
+
+    #[test]
+    fn test_long_grid() {
+        // 2 by n grids have a Fibonacci number of tilings.
+        assert_eq!(count_tilings(90, 2), 4660046610375530309);
+        assert_eq!(count_tilings(1, 1000), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't fit in a u64")]
+    fn test_too_many_tilings() {
+        count_tilings(2, 93);
+    }
+
+    #[test]
+    #[should_panic(expected = "both dimensions of a 21 by 30 grid are greater than 20")]
+    fn test_too_large() {
+        count_tilings(21, 30);
+    }
+
+    #[rstest]
+    #[case(2, 2)]
+    #[case(2, 3)]
+    #[case(3, 2)]
+    #[case(5, 4)]
+    #[case(7, 6)]
+    #[case(1, 2)]
+    #[case(0, 3)]
+    fn test_find_tiling(#[case] width: usize, #[case] height: usize) {
+        let tiling = find_tiling(width, height).unwrap();
+        assert_valid(width, height, &tiling);
+    }
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(3, 3)]
+    #[case(5, 7)]
+    fn test_no_tiling(#[case] width: usize, #[case] height: usize) {
+        assert_eq!(find_tiling(width, height), None);
+    }
+}
@@ -0,0 +1,369 @@
+//! Dots and Boxes is a two-player game played on a rectangular grid of dots.
+//! Players take turns drawing a horizontal or vertical line between two adjacent dots.
+//! A player who draws the fourth side of a box claims that box and has to move again.
+//! Once every line has been drawn, the player who claimed the most boxes wins.
+//!
+//! Problem: model the game, keeping track of each player's score.
+//!
+//! For example, on this 2 by 1 grid of boxes (`A` for a box claimed by the first player),
+//! ```text
+//! +---+---+
+//! | A |
+//! +---+---+
+//! ```
+//! drawing the right side of the second box claims it for the player who drew it,
+//! who would then have to move again, except that the game is over since every box is claimed.
+
+use crate::grid::Grid;
+
+/// One of the two players.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Player {
+    /// The player who moves first.
+    First,
+    Second,
+}
+
+impl Player {
+    /// The other player.
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::First => Player::Second,
+            Player::Second => Player::First,
+        }
+    }
+}
+
+type CellIndex = (usize, usize);
+
+/// A line between two adjacent dots, where dot `(x, y)` is the top left corner of box `(x, y)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// The line from dot `(x, y)` to dot `(x + 1, y)`.
+    Horizontal(CellIndex),
+    /// The line from dot `(x, y)` to dot `(x, y + 1)`.
+    Vertical(CellIndex),
+}
+
+/// The reason an edge can't be played.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InvalidEdge {
+    /// The edge is already drawn.
+    AlreadyPlayed,
+    /// The edge isn't between two dots of the grid.
+    OutOfBounds,
+}
+
+/// The state of a game of Dots and Boxes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DotsBoxesState {
+    /// Whether each horizontal edge has been drawn, with one more row than there are boxes.
+    horizontal_edges: Grid<bool>,
+    /// Whether each vertical edge has been drawn, with one more column than there are boxes.
+    vertical_edges: Grid<bool>,
+    /// The player who claimed each box, if any.
+    boxes: Grid<Option<Player>>,
+    current_player: Player,
+    /// The number of boxes claimed by the first and second players.
+    scores: (usize, usize),
+}
+
+impl DotsBoxesState {
+    /// Creates the starting state for a game with the given number of boxes in each row and column.
+    pub fn new(width: usize, height: usize) -> DotsBoxesState {
+        DotsBoxesState {
+            horizontal_edges: Grid::filled(false, (width, height + 1)),
+            vertical_edges: Grid::filled(false, (width + 1, height)),
+            boxes: Grid::filled(None, (width, height)),
+            current_player: Player::First,
+            scores: (0, 0),
+        }
+    }
+
+    /// The player whose turn it is.
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+
+    /// The number of boxes claimed by the first and second players.
+    pub fn scores(&self) -> (usize, usize) {
+        self.scores
+    }
+
+    /// The player who claimed each box, if any.
+    pub fn boxes(&self) -> &Grid<Option<Player>> {
+        &self.boxes
+    }
+
+    /// Returns whether the given edge has been drawn, or None if it's out of bounds.
+    pub fn has_edge(&self, edge: Edge) -> Option<bool> {
+        match edge {
+            Edge::Horizontal(index) => self.horizontal_edges.get(index).copied(),
+            Edge::Vertical(index) => self.vertical_edges.get(index).copied(),
+        }
+    }
+
+    /// Returns whether all four sides of the given box have been drawn.
+    fn is_closed(&self, (x, y): CellIndex) -> bool {
+        self.horizontal_edges[(x, y)]
+            && self.horizontal_edges[(x, y + 1)]
+            && self.vertical_edges[(x, y)]
+            && self.vertical_edges[(x + 1, y)]
+    }
+}
+
+/// Returns the boxes on either side of the given (in-bounds) edge.
+fn adjacent_boxes(boxes: &Grid<Option<Player>>, edge: Edge) -> Vec<CellIndex> {
+    let candidates = match edge {
+        Edge::Horizontal((x, y)) => [y.checked_sub(1).map(|y| (x, y)), Some((x, y))],
+        Edge::Vertical((x, y)) => [x.checked_sub(1).map(|x| (x, y)), Some((x, y))],
+    };
+    candidates
+        .into_iter()
+        .flatten()
+        .filter(|&index| boxes.get(index).is_some())
+        .collect()
+}
+
+/// Returns the result of the current player drawing the given edge.
+///
+/// Any boxes that the edge closes are claimed by the current player, who then moves again;
+/// otherwise, it becomes the other player's turn.
+pub fn play_edge(state: &DotsBoxesState, edge: Edge) -> Result<DotsBoxesState, InvalidEdge> {
+    if state.has_edge(edge).ok_or(InvalidEdge::OutOfBounds)? {
+        return Err(InvalidEdge::AlreadyPlayed);
+    }
+    let mut state = state.clone();
+    match edge {
+        Edge::Horizontal(index) => state.horizontal_edges[index] = true,
+        Edge::Vertical(index) => state.vertical_edges[index] = true,
+    }
+    let player = state.current_player;
+    let mut completed = 0;
+    for index in adjacent_boxes(&state.boxes, edge) {
+        if state.is_closed(index) {
+            state.boxes[index] = Some(player);
+            completed += 1;
+        }
+    }
+    match player {
+        Player::First => state.scores.0 += completed,
+        Player::Second => state.scores.1 += completed,
+    }
+    if completed == 0 {
+        state.current_player = player.opponent();
+    }
+    Ok(state)
+    /*
+        Time complexity analysis:
+        Cloning the state takes `O(b)` time, where `b` is the number of boxes,
+        and everything else takes `O(1)` time, so this function completes in `O(b)` time.
+    */
+}
+
+/// Returns whether every box has been claimed.
+pub fn game_over(state: &DotsBoxesState) -> bool {
+    let (first, second) = state.scores;
+    first + second == state.boxes.width() * state.boxes.height()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dots_boxes::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Plays the given edges in order, panicking if any of them is invalid.
+    fn play_all(state: &DotsBoxesState, edges: &[Edge]) -> DotsBoxesState {
+        edges.iter().fold(state.clone(), |state, &edge| {
+            play_edge(&state, edge).unwrap()
+        })
+    }
+
+    /// Returns every edge of a grid of boxes with the given dimensions, row by row.
+    fn all_edges(width: usize, height: usize) -> Vec<Edge> {
+        let mut result = vec![];
+        for y in 0..=height {
+            result.extend((0..width).map(|x| Edge::Horizontal((x, y))));
+            if y < height {
+                result.extend((0..=width).map(|x| Edge::Vertical((x, y))));
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let state = play_all(
+            &DotsBoxesState::new(2, 1),
+            &[
+                Edge::Horizontal((1, 0)),
+                Edge::Horizontal((0, 0)),
+                Edge::Horizontal((0, 1)),
+                Edge::Vertical((0, 0)),
+                Edge::Vertical((1, 0)),
+                Edge::Horizontal((1, 1)),
+            ],
+        );
+        // The first player claimed the first box by drawing its right side, moved again,
+        // and then drew the bottom of the second box.
+        assert_eq!(
+            state.boxes(),
+            &Grid::from_2d_array([[Some(Player::First), None]])
+        );
+        assert_eq!(state.current_player(), Player::Second);
+        assert_eq!(state.scores(), (1, 0));
+        assert!(!game_over(&state));
+        let state = play_edge(&state, Edge::Vertical((2, 0))).unwrap();
+        assert_eq!(
+            state.boxes(),
+            &Grid::from_2d_array([[Some(Player::First), Some(Player::Second)]])
+        );
+        assert_eq!(state.scores(), (1, 1));
+        assert_eq!(state.current_player(), Player::Second);
+        assert!(game_over(&state));
+    }
+
+    #[test]
+    fn test_new() {
+        let state = DotsBoxesState::new(3, 2);
+        assert_eq!(state.current_player(), Player::First);
+        assert_eq!(state.scores(), (0, 0));
+        assert_eq!(state.boxes(), &Grid::filled(None, (3, 2)));
+        assert!(all_edges(3, 2)
+            .into_iter()
+            .all(|edge| state.has_edge(edge) == Some(false)));
+        assert!(!game_over(&state));
+    }
+
+    #[test]
+    fn test_turns_alternate() {
+        let state = DotsBoxesState::new(2, 2);
+        let state = play_edge(&state, Edge::Vertical((1, 1))).unwrap();
+        assert_eq!(state.current_player(), Player::Second);
+        assert_eq!(state.has_edge(Edge::Vertical((1, 1))), Some(true));
+        let state = play_edge(&state, Edge::Horizontal((0, 2))).unwrap();
+        assert_eq!(state.current_player(), Player::First);
+        assert_eq!(state.scores(), (0, 0));
+    }
+
+    #[test]
+    fn test_completing_box_keeps_turn() {
+        // Three sides of the box at (1, 0), and one unrelated edge so the first player is to move.
+        let state = play_all(
+            &DotsBoxesState::new(2, 2),
+            &[
+                Edge::Horizontal((1, 0)),
+                Edge::Vertical((2, 0)),
+                Edge::Horizontal((1, 1)),
+                Edge::Vertical((0, 1)),
+            ],
+        );
+        assert_eq!(state.current_player(), Player::First);
+        let state = play_edge(&state, Edge::Vertical((1, 0))).unwrap();
+        assert_eq!(state.current_player(), Player::First);
+        assert_eq!(state.scores(), (1, 0));
+        assert_eq!(state.boxes()[(1, 0)], Some(Player::First));
+        assert_eq!(state.boxes()[(0, 0)], None);
+        // The first player has to move again, and loses the turn after not completing a box.
+        let state = play_edge(&state, Edge::Horizontal((0, 2))).unwrap();
+        assert_eq!(state.current_player(), Player::Second);
+        assert_eq!(state.scores(), (1, 0));
+    }
+
+    #[rstest]
+    #[case::horizontal(
+        &[
+            Edge::Horizontal((0, 0)),
+            Edge::Vertical((0, 0)),
+            Edge::Vertical((1, 0)),
+            Edge::Vertical((0, 1)),
+            Edge::Vertical((1, 1)),
+            Edge::Horizontal((0, 2)),
+        ],
+        Edge::Horizontal((0, 1)),
+    )]
+    #[case::vertical(
+        &[
+            Edge::Horizontal((0, 0)),
+            Edge::Horizontal((1, 0)),
+            Edge::Horizontal((0, 1)),
+            Edge::Horizontal((1, 1)),
+            Edge::Vertical((0, 0)),
+            Edge::Vertical((2, 0)),
+        ],
+        Edge::Vertical((1, 0)),
+    )]
+    fn test_completing_two_boxes(#[case] setup: &[Edge], #[case] edge: Edge) {
+        // After an even number of edges without completing boxes, it's the first player's turn.
+        let state = play_all(&DotsBoxesState::new(2, 2), setup);
+        assert_eq!(state.scores(), (0, 0));
+        let state = play_edge(&state, edge).unwrap();
+        assert_eq!(state.scores(), (2, 0));
+        assert_eq!(state.current_player(), Player::First);
+    }
+
+    #[test]
+    fn test_already_played() {
+        let state = play_edge(&DotsBoxesState::new(2, 2), Edge::Horizontal((1, 2))).unwrap();
+        assert_eq!(
+            play_edge(&state, Edge::Horizontal((1, 2))),
+            Err(InvalidEdge::AlreadyPlayed)
+        );
+        // Horizontal and vertical edges with the same index are different edges.
+        assert!(play_edge(&state, Edge::Vertical((1, 1))).is_ok());
+    }
+
+    #[rstest]
+    #[case::horizontal_x(Edge::Horizontal((2, 0)))]
+    #[case::horizontal_y(Edge::Horizontal((0, 3)))]
+    #[case::vertical_x(Edge::Vertical((3, 0)))]
+    #[case::vertical_y(Edge::Vertical((0, 2)))]
+    fn test_out_of_bounds(#[case] edge: Edge) {
+        let state = DotsBoxesState::new(2, 2);
+        assert_eq!(state.has_edge(edge), None);
+        assert_eq!(play_edge(&state, edge), Err(InvalidEdge::OutOfBounds));
+    }
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(2, 2)]
+    #[case(3, 2)]
+    #[case(1, 4)]
+    fn test_game_over(#[case] width: usize, #[case] height: usize) {
+        let edges = all_edges(width, height);
+        let mut state = DotsBoxesState::new(width, height);
+        for (i, &edge) in edges.iter().enumerate() {
+            assert!(!game_over(&state), "game over after {i} edges");
+            state = play_edge(&state, edge).unwrap();
+        }
+        assert!(game_over(&state));
+        let (first, second) = state.scores();
+        assert_eq!(first + second, width * height);
+        assert!(state
+            .boxes()
+            .enumerate::<CellIndex>()
+            .all(|(_, owner)| owner.is_some()));
+        // Every edge has been drawn.
+        assert_eq!(play_edge(&state, edges[0]), Err(InvalidEdge::AlreadyPlayed));
+    }
+
+    #[test]
+    fn test_row_by_row_scores() {
+        // Drawing row by row, each box is completed by its bottom edge, one after the other,
+        // and the first player to complete a box keeps completing them.
+        let state = play_all(&DotsBoxesState::new(2, 1), &all_edges(2, 1));
+        // 5 edges before the first bottom edge, so the second player draws it.
+        assert_eq!(state.scores(), (0, 2));
+    }
+
+    #[test]
+    fn test_no_boxes() {
+        let state = DotsBoxesState::new(0, 3);
+        assert!(game_over(&state));
+        assert_eq!(
+            play_edge(&state, Edge::Vertical((0, 0))).map(|state| state.scores()),
+            Ok((0, 0))
+        );
+    }
+}
@@ -0,0 +1,622 @@
+//! The Zebra puzzle (often attributed to Einstein) is a logic puzzle about a row of houses,
+//! where each house has exactly one value for each of several attributes
+//! (such as nationality, color, or pet), and each value appears in exactly one house.
+//!
+//! Problem: given some constraints on where values are, find which house has each value.
+//!
+//! For example, with 3 houses, the attributes `color` (red, green, blue)
+//! and `pet` (cat, dog, fish), and the constraints
+//! ```text
+//! The red house is immediately to the left of the green house.
+//! The cat lives next to the red house.
+//! The dog lives in the blue house.
+//! The fish lives in the first house.
+//! ```
+//! the unique solution is:
+//! ```text
+//! red  green blue
+//! fish cat   dog
+//! ```
+//!
+//! The solver finds the house of each value, treating the houses as variables
+//! with the houses that each value could still be in as their domains.
+//! After each guess, it checks forward, removing houses from other domains
+//! that are ruled out by the guess, by the other values of the same attribute,
+//! or by the other constraints, until nothing more can be removed.
+//! A house that only one value of an attribute could be in is also assigned that value.
+
+use std::collections::HashMap;
+
+/// A constraint on where the values of a Zebra puzzle are.
+///
+/// Each value is identified by its attribute, then the value itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// The two values are in the same house.
+    SameHouse(String, String, String, String),
+    /// The two values are in neighboring houses.
+    NextTo(String, String, String, String),
+    /// The first value is in the house immediately to the left of the second value's house.
+    LeftOf(String, String, String, String),
+    /// The value is in the given house, counting from 0 for the leftmost house.
+    Position(String, String, usize),
+}
+
+/// The largest supported number of houses.
+pub const MAX_HOUSES: usize = 32;
+
+/// A Zebra puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZebraPuzzle {
+    house_count: usize,
+    attributes: Vec<String>,
+    /// The values of each attribute, in the same order as `attributes`.
+    values: Vec<Vec<String>>,
+    constraints: Vec<Constraint>,
+}
+
+impl ZebraPuzzle {
+    /// Creates a puzzle with the given number of houses, attributes (each with its values),
+    /// and constraints.
+    ///
+    /// Returns Err(i) if `constraints[i]` refers to an attribute or value that doesn't exist,
+    /// or to a house out of range. If there are multiple such constraints,
+    /// the first one is returned.
+    ///
+    /// Panics if there are more than [`MAX_HOUSES`] houses,
+    /// or some attribute doesn't have exactly one different value for each house.
+    pub fn new(
+        house_count: usize,
+        attributes: Vec<(String, Vec<String>)>,
+        constraints: Vec<Constraint>,
+    ) -> Result<ZebraPuzzle, usize> {
+        assert!(
+            house_count <= MAX_HOUSES,
+            "{house_count} houses is more than the maximum of {MAX_HOUSES}"
+        );
+        let (attributes, values): (Vec<String>, Vec<Vec<String>>) = attributes.into_iter().unzip();
+        for (attribute, values) in attributes.iter().zip(&values) {
+            let mut distinct = values.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert!(
+                values.len() == house_count && distinct.len() == house_count,
+                "attribute {attribute} doesn't have {house_count} different values"
+            );
+        }
+        let puzzle = ZebraPuzzle {
+            house_count,
+            attributes,
+            values,
+            constraints,
+        };
+        let invalid = puzzle
+            .constraints
+            .iter()
+            .position(|constraint| match constraint {
+                Constraint::SameHouse(attr_a, val_a, attr_b, val_b)
+                | Constraint::NextTo(attr_a, val_a, attr_b, val_b)
+                | Constraint::LeftOf(attr_a, val_a, attr_b, val_b) => {
+                    puzzle.variable(attr_a, val_a).is_none()
+                        || puzzle.variable(attr_b, val_b).is_none()
+                }
+                Constraint::Position(attr, val, house) => {
+                    puzzle.variable(attr, val).is_none() || *house >= house_count
+                }
+            });
+        match invalid {
+            Some(i) => Err(i),
+            None => Ok(puzzle),
+        }
+    }
+
+    /// The number of houses.
+    pub fn house_count(&self) -> usize {
+        self.house_count
+    }
+
+    /// The names of the attributes.
+    pub fn attributes(&self) -> &[String] {
+        &self.attributes
+    }
+
+    /// The values of each attribute, in the same order as [`ZebraPuzzle::attributes`].
+    pub fn values(&self) -> &[Vec<String>] {
+        &self.values
+    }
+
+    /// The constraints of the puzzle.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Returns the index of the variable for the given value, if it exists:
+    /// the variables for the first attribute come first, then those for the second, and so on.
+    fn variable(&self, attribute: &str, value: &str) -> Option<usize> {
+        let a = self.attributes.iter().position(|name| name == attribute)?;
+        let v = self.values[a].iter().position(|name| name == value)?;
+        Some(a * self.house_count + v)
+    }
+}
+
+/// A set of houses, where bit `h` is set if house `h` is in the set.
+type Houses = u32;
+
+/// A constraint between two variables, as a relation between their houses.
+#[derive(Clone, Copy, Debug)]
+enum Relation {
+    /// Both variables are in the same house.
+    Same,
+    /// The variables are in neighboring houses.
+    Neighbors,
+    /// The first variable's house is immediately to the left of the second's.
+    Left,
+    /// The first variable's house is immediately to the right of the second's.
+    Right,
+}
+
+impl Relation {
+    /// Returns the houses that the first variable could be in
+    /// for the second variable to be in one of the given houses.
+    fn supported(self, houses: Houses) -> Houses {
+        match self {
+            Relation::Same => houses,
+            Relation::Neighbors => (houses << 1) | (houses >> 1),
+            Relation::Left => houses >> 1,
+            Relation::Right => houses << 1,
+        }
+    }
+}
+
+/// Returns the house of each value in the given puzzle, as a map from attributes to values
+/// for each house, or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(puzzle: &ZebraPuzzle) -> Option<Vec<HashMap<String, String>>> {
+    solutions(puzzle, 1).pop()
+    /*
+        Time complexity analysis:
+        Finding a solution can take exponential time in the number of values in the worst case.
+        Let `n` be the number of houses, `a` be the number of attributes, and `c` be the number of constraints.
+        Each round of forward checking revises every constraint and every attribute,
+        taking `O(c + a * n^2)` time (with bitsets, for up to 32 houses),
+        and each round except the last removes one of the `O(a * n^2)` possible houses,
+        so each guess takes `O(a * n^2 * (c + a * n^2))` time to propagate.
+    */
+}
+
+/// Returns up to `limit` solutions to the given puzzle, in the same format as [`solve`].
+fn solutions(puzzle: &ZebraPuzzle, limit: usize) -> Vec<Vec<HashMap<String, String>>> {
+    let n = puzzle.house_count;
+    if n == 0 {
+        // Every attribute has no values, so there's nothing to place.
+        return if limit > 0 { vec![vec![]] } else { vec![] };
+    }
+    let all_houses: Houses = ((1u64 << n) - 1) as Houses;
+    let mut domains = vec![all_houses; puzzle.attributes.len() * n];
+    let mut arcs = vec![];
+    for constraint in &puzzle.constraints {
+        // `new` checks that every constraint refers to existing values.
+        let variable =
+            |attribute: &String, value: &String| puzzle.variable(attribute, value).unwrap();
+        let (a, b, relation, reverse) = match constraint {
+            Constraint::SameHouse(attr_a, val_a, attr_b, val_b) => (
+                variable(attr_a, val_a),
+                variable(attr_b, val_b),
+                Relation::Same,
+                Relation::Same,
+            ),
+            Constraint::NextTo(attr_a, val_a, attr_b, val_b) => (
+                variable(attr_a, val_a),
+                variable(attr_b, val_b),
+                Relation::Neighbors,
+                Relation::Neighbors,
+            ),
+            Constraint::LeftOf(attr_a, val_a, attr_b, val_b) => (
+                variable(attr_a, val_a),
+                variable(attr_b, val_b),
+                Relation::Left,
+                Relation::Right,
+            ),
+            Constraint::Position(attr, val, house) => {
+                domains[variable(attr, val)] &= 1 << house;
+                continue;
+            }
+        };
+        arcs.push((a, b, relation));
+        arcs.push((b, a, reverse));
+    }
+    let mut result = vec![];
+    if propagate(&mut domains, &arcs, n, all_houses) {
+        search(domains, &arcs, n, all_houses, limit, &mut result);
+    }
+    result
+        .into_iter()
+        .map(|domains| {
+            let mut houses = vec![HashMap::new(); n];
+            for (variable, &house) in domains.iter().enumerate() {
+                let (a, v) = (variable / n, variable % n);
+                let house = house.trailing_zeros() as usize;
+                houses[house].insert(puzzle.attributes[a].clone(), puzzle.values[a][v].clone());
+            }
+            houses
+        })
+        .collect()
+}
+
+/// Tries to narrow down the given domains (which must be consistent) to a single house each,
+/// adding each solution found to `result` until there are `limit` solutions.
+fn search(
+    domains: Vec<Houses>,
+    arcs: &[(usize, usize, Relation)],
+    n: usize,
+    all_houses: Houses,
+    limit: usize,
+    result: &mut Vec<Vec<Houses>>,
+) {
+    // Guess a house for the variable with the fewest remaining houses (besides 1).
+    let undecided = domains
+        .iter()
+        .enumerate()
+        .filter(|&(_, &houses)| houses.count_ones() > 1)
+        .min_by_key(|&(_, &houses)| houses.count_ones());
+    let Some((variable, &houses)) = undecided else {
+        result.push(domains);
+        return;
+    };
+    for house in 0..n {
+        if result.len() >= limit {
+            return;
+        }
+        if houses & (1 << house) == 0 {
+            continue;
+        }
+        let mut domains = domains.clone();
+        domains[variable] = 1 << house;
+        if propagate(&mut domains, arcs, n, all_houses) {
+            search(domains, arcs, n, all_houses, limit, result);
+        }
+    }
+}
+
+/// Removes houses from the given domains that are ruled out by the constraints
+/// or by other values of the same attribute, and assigns houses that only one value of
+/// an attribute could be in, until nothing more can be removed.
+///
+/// Returns false if some variable has no houses left, or some house can't have a value
+/// for some attribute.
+/// When every variable is narrowed down to a single house and this returns true,
+/// the domains form a solution.
+fn propagate(
+    domains: &mut [Houses],
+    arcs: &[(usize, usize, Relation)],
+    n: usize,
+    all_houses: Houses,
+) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let mut narrow = |domains: &mut [Houses], variable: usize, houses: Houses| {
+            if domains[variable] & houses != domains[variable] {
+                domains[variable] &= houses;
+                changed = true;
+            }
+            domains[variable] != 0
+        };
+        for &(a, b, relation) in arcs {
+            if !narrow(domains, a, relation.supported(domains[b]) & all_houses) {
+                return false;
+            }
+        }
+        for attribute in domains.chunks_mut(n) {
+            // A house assigned to one value can't be used by the others.
+            for v in 0..n {
+                let houses = attribute[v];
+                if houses.count_ones() != 1 {
+                    continue;
+                }
+                for other in (0..n).filter(|&other| other != v) {
+                    if !narrow(attribute, other, !houses) {
+                        return false;
+                    }
+                }
+            }
+            // Every house needs some value.
+            for house in 0..n {
+                let mut possible = (0..n).filter(|&v| attribute[v] & (1 << house) != 0);
+                match (possible.next(), possible.next()) {
+                    (None, _) => return false,
+                    (Some(v), None) => {
+                        narrow(attribute, v, 1 << house);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::einstein_puzzle::*;
+    use pretty_assertions::assert_eq;
+
+    /// Creates an attribute with the given name and values.
+    fn attribute(name: &str, values: &[&str]) -> (String, Vec<String>) {
+        (
+            name.to_string(),
+            values.iter().map(|value| value.to_string()).collect(),
+        )
+    }
+
+    /// Creates a constraint of the given kind between the given values.
+    fn between(
+        kind: fn(String, String, String, String) -> Constraint,
+        a: (&str, &str),
+        b: (&str, &str),
+    ) -> Constraint {
+        kind(
+            a.0.to_string(),
+            a.1.to_string(),
+            b.0.to_string(),
+            b.1.to_string(),
+        )
+    }
+
+    /// Creates a constraint putting the given value in the given house.
+    fn position(attr: &str, val: &str, house: usize) -> Constraint {
+        Constraint::Position(attr.to_string(), val.to_string(), house)
+    }
+
+    /// Returns the house that has the given value, in the given solution.
+    fn house_of(solution: &[HashMap<String, String>], attribute: &str, value: &str) -> usize {
+        solution
+            .iter()
+            .position(|house| house[attribute] == value)
+            .unwrap()
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(puzzle: &ZebraPuzzle, solution: &[HashMap<String, String>]) {
+        assert_eq!(solution.len(), puzzle.house_count());
+        for (attribute, values) in puzzle.attributes().iter().zip(puzzle.values()) {
+            let mut actual: Vec<&String> = solution.iter().map(|house| &house[attribute]).collect();
+            let mut expected: Vec<&String> = values.iter().collect();
+            actual.sort();
+            expected.sort();
+            assert_eq!(actual, expected, "{attribute} doesn't have each value once");
+        }
+        for constraint in puzzle.constraints() {
+            let satisfied = match constraint {
+                Constraint::SameHouse(attr_a, val_a, attr_b, val_b) => {
+                    house_of(solution, attr_a, val_a) == house_of(solution, attr_b, val_b)
+                }
+                Constraint::NextTo(attr_a, val_a, attr_b, val_b) => {
+                    house_of(solution, attr_a, val_a).abs_diff(house_of(solution, attr_b, val_b))
+                        == 1
+                }
+                Constraint::LeftOf(attr_a, val_a, attr_b, val_b) => {
+                    house_of(solution, attr_a, val_a) + 1 == house_of(solution, attr_b, val_b)
+                }
+                Constraint::Position(attr, val, house) => house_of(solution, attr, val) == *house,
+            };
+            assert!(satisfied, "{constraint:?} isn't satisfied");
+        }
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let attributes = vec![
+            attribute("color", &["red", "green", "blue"]),
+            attribute("pet", &["cat", "dog", "fish"]),
+        ];
+        let constraints = vec![
+            between(Constraint::LeftOf, ("color", "red"), ("color", "green")),
+            between(Constraint::NextTo, ("pet", "cat"), ("color", "red")),
+            between(Constraint::SameHouse, ("pet", "dog"), ("color", "blue")),
+            position("pet", "fish", 0),
+        ];
+        let puzzle = ZebraPuzzle::new(3, attributes, constraints).unwrap();
+        let solution = solve(&puzzle).unwrap();
+        assert_valid(&puzzle, &solution);
+        let colors: Vec<&str> = solution
+            .iter()
+            .map(|house| house["color"].as_str())
+            .collect();
+        let pets: Vec<&str> = solution.iter().map(|house| house["pet"].as_str()).collect();
+        assert_eq!(colors, ["red", "green", "blue"]);
+        assert_eq!(pets, ["fish", "cat", "dog"]);
+        assert_eq!(solutions(&puzzle, 2).len(), 1);
+    }
+
+    /// Einstein's version of the puzzle, where the question is usually who owns the fish;
+    /// the fish is a zebra here.
+    fn einstein() -> ZebraPuzzle {
+        use Constraint::*;
+        let attributes = vec![
+            attribute(
+                "nationality",
+                &["Brit", "Swede", "Dane", "Norwegian", "German"],
+            ),
+            attribute("color", &["red", "green", "white", "yellow", "blue"]),
+            attribute("drink", &["tea", "coffee", "milk", "beer", "water"]),
+            attribute(
+                "smoke",
+                &["Pall Mall", "Dunhill", "Blends", "BlueMaster", "Prince"],
+            ),
+            attribute("pet", &["dogs", "birds", "cats", "horses", "zebra"]),
+        ];
+        let constraints = vec![
+            between(SameHouse, ("nationality", "Brit"), ("color", "red")),
+            between(SameHouse, ("nationality", "Swede"), ("pet", "dogs")),
+            between(SameHouse, ("nationality", "Dane"), ("drink", "tea")),
+            between(LeftOf, ("color", "green"), ("color", "white")),
+            between(SameHouse, ("color", "green"), ("drink", "coffee")),
+            between(SameHouse, ("smoke", "Pall Mall"), ("pet", "birds")),
+            between(SameHouse, ("color", "yellow"), ("smoke", "Dunhill")),
+            position("drink", "milk", 2),
+            position("nationality", "Norwegian", 0),
+            between(NextTo, ("smoke", "Blends"), ("pet", "cats")),
+            between(NextTo, ("pet", "horses"), ("smoke", "Dunhill")),
+            between(SameHouse, ("smoke", "BlueMaster"), ("drink", "beer")),
+            between(SameHouse, ("nationality", "German"), ("smoke", "Prince")),
+            between(NextTo, ("nationality", "Norwegian"), ("color", "blue")),
+            between(NextTo, ("smoke", "Blends"), ("drink", "water")),
+        ];
+        ZebraPuzzle::new(5, attributes, constraints).unwrap()
+    }
+
+    #[test]
+    fn test_einstein() {
+        let puzzle = einstein();
+        let solution = solve(&puzzle).unwrap();
+        assert_valid(&puzzle, &solution);
+        let zebra = house_of(&solution, "pet", "zebra");
+        let water = house_of(&solution, "drink", "water");
+        assert_eq!(solution[zebra]["nationality"], "German");
+        assert_eq!(solution[water]["nationality"], "Norwegian");
+        let nationalities: Vec<&str> = solution
+            .iter()
+            .map(|house| house["nationality"].as_str())
+            .collect();
+        assert_eq!(
+            nationalities,
+            ["Norwegian", "Dane", "Brit", "German", "Swede"]
+        );
+        assert_eq!(solutions(&puzzle, 2).len(), 1);
+    }
+
+    #[test]
+    fn test_life_international() {
+        // The version first published in Life International in 1962.
+        use Constraint::*;
+        let attributes = vec![
+            attribute(
+                "nationality",
+                &[
+                    "Englishman",
+                    "Spaniard",
+                    "Ukrainian",
+                    "Norwegian",
+                    "Japanese",
+                ],
+            ),
+            attribute("color", &["red", "green", "ivory", "yellow", "blue"]),
+            attribute("drink", &["coffee", "tea", "milk", "orange juice", "water"]),
+            attribute(
+                "smoke",
+                &[
+                    "Old Gold",
+                    "Kools",
+                    "Chesterfields",
+                    "Lucky Strike",
+                    "Parliaments",
+                ],
+            ),
+            attribute("pet", &["dog", "snails", "fox", "horse", "zebra"]),
+        ];
+        let constraints = vec![
+            between(SameHouse, ("nationality", "Englishman"), ("color", "red")),
+            between(SameHouse, ("nationality", "Spaniard"), ("pet", "dog")),
+            between(SameHouse, ("drink", "coffee"), ("color", "green")),
+            between(SameHouse, ("nationality", "Ukrainian"), ("drink", "tea")),
+            between(LeftOf, ("color", "ivory"), ("color", "green")),
+            between(SameHouse, ("smoke", "Old Gold"), ("pet", "snails")),
+            between(SameHouse, ("smoke", "Kools"), ("color", "yellow")),
+            position("drink", "milk", 2),
+            position("nationality", "Norwegian", 0),
+            between(NextTo, ("smoke", "Chesterfields"), ("pet", "fox")),
+            between(NextTo, ("smoke", "Kools"), ("pet", "horse")),
+            between(
+                SameHouse,
+                ("smoke", "Lucky Strike"),
+                ("drink", "orange juice"),
+            ),
+            between(
+                SameHouse,
+                ("nationality", "Japanese"),
+                ("smoke", "Parliaments"),
+            ),
+            between(NextTo, ("nationality", "Norwegian"), ("color", "blue")),
+        ];
+        let puzzle = ZebraPuzzle::new(5, attributes, constraints).unwrap();
+        let solution = solve(&puzzle).unwrap();
+        assert_valid(&puzzle, &solution);
+        let zebra = house_of(&solution, "pet", "zebra");
+        let water = house_of(&solution, "drink", "water");
+        assert_eq!(solution[zebra]["nationality"], "Japanese");
+        assert_eq!(solution[water]["nationality"], "Norwegian");
+        assert_eq!(solutions(&puzzle, 2).len(), 1);
+    }
+
+    #[test]
+    fn test_multiple_solutions() {
+        // Without constraints, any arrangement works.
+        let attributes = vec![attribute("color", &["red", "green", "blue"])];
+        let puzzle = ZebraPuzzle::new(3, attributes, vec![]).unwrap();
+        let solution = solve(&puzzle).unwrap();
+        assert_valid(&puzzle, &solution);
+        assert_eq!(solutions(&puzzle, 10).len(), 6);
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let attributes = vec![
+            attribute("color", &["red", "green"]),
+            attribute("pet", &["cat", "dog"]),
+        ];
+        let constraints = vec![
+            between(Constraint::SameHouse, ("color", "red"), ("pet", "cat")),
+            between(Constraint::NextTo, ("color", "red"), ("pet", "cat")),
+        ];
+        let puzzle = ZebraPuzzle::new(2, attributes, constraints).unwrap();
+        assert_eq!(solve(&puzzle), None);
+    }
+
+    #[test]
+    fn test_contradiction_after_guessing() {
+        // Nothing is ruled out until a guess is made, but every guess fails:
+        // in a row of houses, three values can't all be next to each other.
+        let attributes = vec![attribute("color", &["red", "green", "blue"])];
+        let constraints = vec![
+            between(Constraint::NextTo, ("color", "red"), ("color", "green")),
+            between(Constraint::NextTo, ("color", "green"), ("color", "blue")),
+            between(Constraint::NextTo, ("color", "blue"), ("color", "red")),
+        ];
+        let puzzle = ZebraPuzzle::new(3, attributes, constraints).unwrap();
+        assert_eq!(solve(&puzzle), None);
+    }
+
+    #[test]
+    fn test_invalid_constraints() {
+        let attributes = || vec![attribute("color", &["red", "green"])];
+        assert_eq!(
+            ZebraPuzzle::new(2, attributes(), vec![position("color", "red", 2)]),
+            Err(0)
+        );
+        let constraints = vec![
+            position("color", "red", 1),
+            between(Constraint::SameHouse, ("color", "green"), ("pet", "cat")),
+            position("color", "blue", 0),
+        ];
+        assert_eq!(ZebraPuzzle::new(2, attributes(), constraints), Err(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "attribute color doesn't have 3 different values")]
+    fn test_wrong_number_of_values() {
+        let _ = ZebraPuzzle::new(
+            3,
+            vec![attribute("color", &["red", "green", "red"])],
+            vec![],
+        );
+    }
+
+    #[test]
+    fn test_no_houses() {
+        let puzzle = ZebraPuzzle::new(0, vec![attribute("color", &[])], vec![]).unwrap();
+        assert_eq!(solve(&puzzle), Some(vec![]));
+    }
+}
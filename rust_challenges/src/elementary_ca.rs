@@ -0,0 +1,229 @@
+//! An elementary cellular automaton is a row of cells that are each on or off,
+//! where at each step, every cell changes based on itself and its two neighbors.
+//! There are 8 possible states for a cell and its neighbors, so there are 256 possible rules,
+//! numbered by Wolfram's convention: reading the state of the left neighbor, the cell itself,
+//! and the right neighbor as a 3-bit binary number `n`, the cell is on after the step
+//! if and only if bit `n` of the rule number is set.
+//!
+//! Problem: simulate elementary cellular automata.
+//!
+//! For example, Rule 90 (`01011010` in binary) turns a cell on
+//! if and only if exactly one of its neighbors is on, and starting from a single cell,
+//! it draws a Sierpinski triangle (`#` for on, `.` for off):
+//! ```text
+//! ...#...
+//! ..#.#..
+//! .#...#.
+//! #.#.#.#
+//! ```
+//! The cells past the ends of the row are either always off,
+//! or the row wraps around, with its first and last cells next to each other.
+
+use crate::grid::Grid;
+
+/// A rule for an elementary cellular automaton, by its Wolfram code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WolframRule(pub u8);
+
+/// What's past the ends of the row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BoundaryCondition {
+    /// The row wraps around.
+    Periodic,
+    /// The cells past the ends of the row are always off.
+    Zero,
+}
+
+impl WolframRule {
+    /// Returns whether a cell is on after a step,
+    /// given whether its left neighbor, itself, and its right neighbor are on before it.
+    pub fn next_state(self, left: bool, center: bool, right: bool) -> bool {
+        let WolframRule(number) = self;
+        let neighborhood = (u8::from(left) << 2) | (u8::from(center) << 1) | u8::from(right);
+        number & (1 << neighborhood) != 0
+    }
+}
+
+/// Returns the result of a single step of the elementary cellular automaton
+/// with the given rule on the given row of cells.
+pub fn apply_rule(rule: WolframRule, cells: &[bool], boundary: BoundaryCondition) -> Vec<bool> {
+    let len = cells.len();
+    let cell = |i: Option<usize>| match (i, boundary) {
+        (Some(i), _) if i < len => cells[i],
+        (_, BoundaryCondition::Zero) => false,
+        // Only the neighbors one past either end are ever asked for.
+        (Some(_), BoundaryCondition::Periodic) => cells[0],
+        (None, BoundaryCondition::Periodic) => cells[len - 1],
+    };
+    (0..len)
+        .map(|i| rule.next_state(cell(i.checked_sub(1)), cells[i], cell(Some(i + 1))))
+        .collect()
+    /*
+        Time complexity analysis:
+        Each cell takes `O(1)` time,
+        so this function completes in `O(n)` time, where `n` is the number of cells.
+    */
+}
+
+/// Returns the result of running the elementary cellular automaton with the given rule
+/// for the given number of steps, starting from the given row of cells,
+/// as a grid where row `y` is the row of cells after `y` steps
+/// (so there are `generations + 1` rows, with the first row being `initial`).
+pub fn run(
+    rule: WolframRule,
+    initial: Vec<bool>,
+    generations: usize,
+    boundary: BoundaryCondition,
+) -> Grid<bool> {
+    let mut result = Grid::filled(false, (initial.len(), generations + 1));
+    let mut cells = initial;
+    for y in 0..=generations {
+        if y > 0 {
+            cells = apply_rule(rule, &cells, boundary);
+        }
+        for (x, &on) in cells.iter().enumerate() {
+            result[(x, y)] = on;
+        }
+    }
+    result
+    /*
+        Time complexity analysis:
+        This function completes in `O(g * n)` time,
+        where `g` is the number of generations and `n` is the number of cells.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::elementary_ca::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a row of cells from the given string, with `#` for cells that are on.
+    fn row(s: &str) -> Vec<bool> {
+        s.chars().map(|c| c == '#').collect()
+    }
+
+    /// Creates a grid of cells from the given rows, with `#` for cells that are on.
+    fn cells(rows: &[&str]) -> Grid<bool> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut result = Grid::filled(false, (width, rows.len()));
+        for (y, s) in rows.iter().enumerate() {
+            for (x, on) in row(s).into_iter().enumerate() {
+                result[(x, y)] = on;
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let grid = run(WolframRule(90), row("...#..."), 3, BoundaryCondition::Zero);
+        assert_eq!(grid, cells(&["...#...", "..#.#..", ".#...#.", "#.#.#.#"]));
+    }
+
+    #[rstest]
+    fn test_sierpinski_triangle(
+        #[values(BoundaryCondition::Zero, BoundaryCondition::Periodic)] boundary: BoundaryCondition,
+    ) {
+        // By Lucas's theorem, the cell `k` places from the center in row `y` is on if and only if
+        // `y + k` is even and `C(y, (y + k) / 2)` is odd, which happens when `(y + k) / 2`
+        // only has bits that `y` also has.
+        let n = 31;
+        let mut initial = vec![false; 2 * n + 1];
+        initial[n] = true;
+        let grid = run(WolframRule(90), initial, n, boundary);
+        for y in 0..=n {
+            for x in 0..=2 * n {
+                // `y + k`, which is negative for cells too far to the left.
+                let expected = (x + y).checked_sub(n).is_some_and(|y_plus_k| {
+                    let m = y_plus_k / 2;
+                    y_plus_k % 2 == 0 && m <= y && (m & !y) == 0
+                });
+                assert_eq!(grid[(x, y)], expected, "{:?}", (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rule_110() {
+        let grid = run(
+            WolframRule(110),
+            row("...............#"),
+            7,
+            BoundaryCondition::Zero,
+        );
+        let expected = cells(&[
+            "...............#",
+            "..............##",
+            ".............###",
+            "............##.#",
+            "...........#####",
+            "..........##...#",
+            ".........###..##",
+            "........##.#.###",
+        ]);
+        assert_eq!(grid, expected);
+    }
+
+    #[rstest]
+    fn test_rule_0(
+        #[values("#", "#.#", "########", "..#..##.#")] initial: &str,
+        #[values(BoundaryCondition::Zero, BoundaryCondition::Periodic)] boundary: BoundaryCondition,
+    ) {
+        let initial = row(initial);
+        let len = initial.len();
+        assert_eq!(
+            apply_rule(WolframRule(0), &initial, boundary),
+            vec![false; len]
+        );
+    }
+
+    #[rstest]
+    #[case::periodic(BoundaryCondition::Periodic, "#..##.#")]
+    #[case::zero(BoundaryCondition::Zero, "...##.#")]
+    fn test_boundary_conditions(#[case] boundary: BoundaryCondition, #[case] expected: &str) {
+        // Rule 240 copies each cell's left neighbor, shifting everything to the right.
+        assert_eq!(
+            apply_rule(WolframRule(240), &row("..##.##"), boundary),
+            row(expected)
+        );
+    }
+
+    #[test]
+    fn test_single_cell_periodic() {
+        // The cell is its own left and right neighbor.
+        assert_eq!(
+            apply_rule(
+                WolframRule(0b1000_0000),
+                &[true],
+                BoundaryCondition::Periodic
+            ),
+            [true]
+        );
+        assert_eq!(
+            apply_rule(WolframRule(0b1000_0000), &[true], BoundaryCondition::Zero),
+            [false]
+        );
+    }
+
+    #[test]
+    fn test_next_state() {
+        let rule = WolframRule(30);
+        let states: Vec<bool> = (0..8)
+            .map(|n| rule.next_state(n & 4 != 0, n & 2 != 0, n & 1 != 0))
+            .collect();
+        assert_eq!(states, [false, true, true, true, true, false, false, false]);
+    }
+
+    #[rstest]
+    fn test_empty(
+        #[values(BoundaryCondition::Zero, BoundaryCondition::Periodic)] boundary: BoundaryCondition,
+    ) {
+        assert_eq!(apply_rule(WolframRule(255), &[], boundary), []);
+        assert_eq!(
+            run(WolframRule(255), vec![], 3, boundary).dimensions(),
+            (0, 4)
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Fillomino is a puzzle played on a rectangular grid, where some cells contain numbers.
+//!
+//! Problem: write a number in every cell, keeping the given numbers, such that
+//! each region (an orthogonally connected group of cells with the same number)
+//! has as many cells as its number.
+//! In other words, the grid is divided into polyominoes, each cell is labeled with the size of its polyomino,
+//! and no two polyominoes of the same size are orthogonally adjacent
+//! (since they would form one region that's too large).
+//!
+//! For example, the following puzzle (`.` for cells without numbers):
+//! ```text
+//! . . 1 .
+//! 2 . . .
+//! 4 . . 1
+//! . 2 . .
+//! ```
+//! has the unique solution:
+//! ```text
+//! 2 4 1 6
+//! 2 4 6 6
+//! 4 4 6 1
+//! 2 2 6 6
+//! ```
+//!
+//! The solver alternates between deductions and backtracking.
+//! A region that's too large, or that can't reach enough cells to grow to its size,
+//! is a contradiction, and an incomplete region with only one cell it can grow into must grow into that cell.
+//! When no more cells can be deduced,
+//! the solver picks the incomplete region with the fewest cells it can grow into and tries each of them,
+//! or, if every region is complete, starts a new region from an empty cell.
+
+use crate::flood_fill::{flood_fill_collect, Connectivity};
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// A Fillomino puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FillominoBoard(pub Grid<Option<u32>>);
+
+/// A region of a partial solution.
+#[derive(Debug)]
+struct Region {
+    /// The number written in each cell of the region.
+    number: u32,
+    /// The cells of the region.
+    cells: Vec<CellIndex>,
+}
+
+/// Returns the regions of the given partial solution (ignoring cells without numbers),
+/// along with the index of the region containing each cell.
+fn regions(state: &Grid<Option<u32>>) -> (Vec<Region>, Grid<Option<usize>>) {
+    let mut region_ids = Grid::filled(None, state.dimensions());
+    let mut result = vec![];
+    for ((x, y), &number) in state.enumerate::<CellIndex>() {
+        let Some(number) = number else {
+            continue;
+        };
+        if region_ids[(x, y)].is_some() {
+            continue;
+        }
+        let start = (x as i32, y as i32);
+        let cells: Vec<CellIndex> = flood_fill_collect(
+            state,
+            start,
+            |&other| other == Some(number),
+            Connectivity::Four,
+        )
+        .into_iter()
+        .map(|(x, y)| (x as usize, y as usize))
+        .collect();
+        for &cell in &cells {
+            region_ids[cell] = Some(result.len());
+        }
+        result.push(Region { number, cells });
+    }
+    (result, region_ids)
+}
+
+/// Returns whether the given grid is a valid solution to the given puzzle.
+///
+/// Returns false if the dimensions of the grid don't match the puzzle.
+pub fn is_solution(board: &FillominoBoard, solution: &Grid<u32>) -> bool {
+    let FillominoBoard(given) = board;
+    if solution.dimensions() != given.dimensions() {
+        return false;
+    }
+    let keeps_given = given
+        .enumerate::<CellIndex>()
+        .all(|(cell, &number)| number.is_none_or(|number| solution[cell] == number));
+    let (regions, _) = regions(&solution.clone().map(Some));
+    keeps_given
+        && regions
+            .iter()
+            .all(|region| region.cells.len() == region.number as usize)
+}
+
+/// Returns a solution to the given puzzle, or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &FillominoBoard) -> Option<Grid<u32>> {
+    let FillominoBoard(given) = board;
+    search(given.clone())
+    /*
+        Time complexity analysis:
+        Finding a solution can take exponential time in the number of cells in the worst case,
+        since the search may need to try many ways of growing each region.
+        Let `c` be the number of cells.
+        Finding the regions, and checking whether each of them can grow large enough, takes `O(c)` time,
+        so propagation takes `O(c^2)` time per guess (as each deduction fills in a cell).
+    */
+}
+
+/// Tries to complete the given partial solution (where None is a cell without a number).
+///
+/// Returns None if there is no solution.
+fn search(mut state: Grid<Option<u32>>) -> Option<Grid<u32>> {
+    if !propagate(&mut state) {
+        return None;
+    }
+    let (regions, region_ids) = regions(&state);
+    let growth = regions
+        .iter()
+        .enumerate()
+        .filter(|(_, region)| region.cells.len() < region.number as usize)
+        .map(|(id, region)| {
+            (
+                region.number,
+                growth_cells(&state, &regions, &region_ids, id),
+            )
+        })
+        .min_by_key(|(_, cells)| cells.len());
+    if let Some((number, cells)) = growth {
+        return cells.into_iter().find_map(|cell| {
+            let mut state = state.clone();
+            state[cell] = Some(number);
+            search(state)
+        });
+    }
+    // Every region is complete, so any new region consists entirely of cells without numbers.
+    let Some(start) = state
+        .enumerate::<CellIndex>()
+        .find(|(_, number)| number.is_none())
+        .map(|(cell, _)| cell)
+    else {
+        return Some(state.map(Option::unwrap));
+    };
+    let start_i32 = (start.0 as i32, start.1 as i32);
+    let max_size = flood_fill_collect(&state, start_i32, Option::is_none, Connectivity::Four).len();
+    (1..=max_size as u32).find_map(|number| {
+        let mut state = state.clone();
+        state[start] = Some(number);
+        search(state)
+    })
+}
+
+/// Returns the cells without numbers next to the region with the given ID
+/// that the region can grow into without becoming too large,
+/// taking into account other regions that the region would merge with.
+fn growth_cells(
+    state: &Grid<Option<u32>>,
+    regions: &[Region],
+    region_ids: &Grid<Option<usize>>,
+    id: usize,
+) -> Vec<CellIndex> {
+    let Region { number, cells } = &regions[id];
+    let mut result: Vec<CellIndex> = cells
+        .iter()
+        .flat_map(|&cell| state.orthogonal_neighbors(cell))
+        .filter(|(_, neighbor)| neighbor.is_none())
+        .map(|(neighbor, _)| neighbor)
+        .collect();
+    result.sort();
+    result.dedup();
+    result.retain(|&cell| {
+        let mut merged_ids: Vec<usize> = region_ids
+            .orthogonal_neighbors(cell)
+            .filter_map(|(_, &other)| other)
+            .filter(|&other| regions[other].number == *number)
+            .collect();
+        merged_ids.sort();
+        merged_ids.dedup();
+        let merged_size: usize = merged_ids
+            .iter()
+            .map(|&other| regions[other].cells.len())
+            .sum();
+        merged_size < *number as usize
+    });
+    result
+}
+
+/// Returns whether the given region of the given partial solution can grow to its full size,
+/// through cells without numbers or with the same number as the region.
+fn can_grow(state: &Grid<Option<u32>>, region: &Region) -> bool {
+    let target = region.number as usize;
+    let mut reached = Grid::filled(false, state.dimensions());
+    let mut stack = region.cells.clone();
+    let mut num_reached = stack.len();
+    for &cell in &stack {
+        reached[cell] = true;
+    }
+    while let Some(cell) = stack.pop() {
+        if num_reached >= target {
+            return true;
+        }
+        for (neighbor, contents) in state.orthogonal_neighbors(cell) {
+            let passable = contents.is_none_or(|number| number == region.number);
+            if passable && !reached[neighbor] {
+                reached[neighbor] = true;
+                num_reached += 1;
+                stack.push(neighbor);
+            }
+        }
+    }
+    num_reached >= target
+}
+
+/// Fills in cells of the given partial solution until no more cells can be deduced.
+/// An incomplete region with only one cell it can grow into must grow into that cell.
+///
+/// Returns false if some region is too large, or can't grow large enough.
+fn propagate(state: &mut Grid<Option<u32>>) -> bool {
+    'deduce: loop {
+        let (regions, region_ids) = regions(state);
+        for (id, region) in regions.iter().enumerate() {
+            let size = region.cells.len();
+            let number = region.number as usize;
+            if size > number || !can_grow(state, region) {
+                return false;
+            }
+            if size == number {
+                continue;
+            }
+            match growth_cells(state, &regions, &region_ids, id)[..] {
+                [] => return false,
+                [cell] => {
+                    state[cell] = Some(region.number);
+                    // Regions may have merged, so they need to be found again.
+                    continue 'deduce;
+                }
+                _ => {}
+            }
+        }
+        return true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fillomino::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Fillomino puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// fillomino![
+    ///     [<`.` for no number, or a number> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! fillomino {
+        (@cell .) => {None};
+        (@cell $number:literal) => {Some($number)};
+        ($([$($cell:tt)*])*) => {
+            FillominoBoard(Grid::from_2d_array([$([$(fillomino!(@cell $cell)),*]),*]))
+        };
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &FillominoBoard, solution: &Grid<u32>) {
+        let FillominoBoard(given) = board;
+        assert_eq!(solution.dimensions(), given.dimensions());
+        for (cell, &number) in given.enumerate::<CellIndex>() {
+            if let Some(number) = number {
+                assert_eq!(solution[cell], number, "given number at {cell:?} changed");
+            }
+        }
+        let (regions, region_ids) = regions(&solution.clone().map(Some));
+        for region in &regions {
+            assert_eq!(
+                region.cells.len(),
+                region.number as usize,
+                "region containing {:?} has the wrong size",
+                region.cells[0]
+            );
+        }
+        // Cells with the same number next to each other should be in the same polyomino.
+        for (cell, &number) in solution.enumerate::<CellIndex>() {
+            for (neighbor, &other) in solution.orthogonal_neighbors(cell) {
+                if other == number {
+                    assert_eq!(region_ids[neighbor], region_ids[cell]);
+                }
+            }
+        }
+        assert!(is_solution(board, solution));
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = fillomino![
+            [. . 1 .]
+            [2 . . .]
+            [4 . . 1]
+            [. 2 . .]
+        ];
+        let expected =
+            Grid::from_2d_array([[2, 4, 1, 6], [2, 4, 6, 6], [4, 4, 6, 1], [2, 2, 6, 6]]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_6_by_6() {
+        let board = fillomino![
+            [3 . . 5 2 .]
+            [3 3 . . 4 .]
+            [. 5 . 3 4 4]
+            [2 6 . . . .]
+            [. . . . 5 .]
+            [. 5 . . . 5]
+        ];
+        let expected = Grid::from_2d_array([
+            [3, 1, 5, 5, 2, 2],
+            [3, 3, 5, 3, 4, 4],
+            [2, 5, 5, 3, 4, 4],
+            [2, 6, 6, 3, 5, 5],
+            [5, 5, 6, 6, 5, 5],
+            [5, 5, 5, 6, 6, 5],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::no_numbers(fillomino![
+        [. . .]
+        [. . .]
+        [. . .]
+    ])]
+    #[case::single_region(fillomino![
+        [. . .]
+        [. 6 .]
+    ])]
+    #[case::ones(fillomino![
+        [1 . 1]
+        [. . .]
+        [1 . 1]
+    ])]
+    fn test_solve(#[case] board: FillominoBoard) {
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    #[case::zero(fillomino![[0 .]])]
+    #[case::adjacent_ones(fillomino![[1 1 .]])]
+    #[case::too_large(fillomino![
+        [. 5]
+        [. .]
+    ])]
+    // Both 2s would need the middle cell, and then they'd form a region of 3.
+    #[case::shared_cell(fillomino![[2 . 2]])]
+    // The bottom right cell is boxed in by the 1s, so it can only be a 1 as well.
+    #[case::boxed_in(fillomino![
+        [3 . 1]
+        [. 1 .]
+    ])]
+    fn test_unsolvable(#[case] board: FillominoBoard) {
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case::wrong_size(Grid::from_2d_array([[4, 2, 2], [4, 4, 1]]))]
+    #[case::adjacent_same_size(Grid::from_2d_array([[1, 2, 2], [2, 2, 1]]))]
+    #[case::given_changed(Grid::from_2d_array([[1, 2, 2], [3, 3, 3]]))]
+    #[case::wrong_dimensions(Grid::from_2d_array([[2, 2, 1]]))]
+    fn test_invalid_solution(#[case] solution: Grid<u32>) {
+        let board = fillomino![
+            [. 2 .]
+            [. . 1]
+        ];
+        assert!(!is_solution(&board, &solution));
+    }
+
+    #[test]
+    fn test_valid_solution() {
+        let board = fillomino![
+            [. 2 .]
+            [. . 1]
+        ];
+        let solution = Grid::from_2d_array([[3, 2, 2], [3, 3, 1]]);
+        assert!(is_solution(&board, &solution));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = FillominoBoard(Grid::filled(None, (width, height)));
+        assert_eq!(solve(&board), Some(Grid::filled(0, (width, height))));
+    }
+}
@@ -0,0 +1,164 @@
+//! Helper module that provides flood fill over grids.
+//!
+//! Flood fill starts at a given cell and spreads to every cell connected to it
+//! through cells satisfying some condition --
+//! the "paint bucket" tool in image editors is the classic example.
+
+use crate::grid::{Grid, ALL_OFFSETS, ORTHOGONAL_OFFSETS};
+
+type CellIndex = (i32, i32);
+
+/// Which cells are considered to be adjacent to a given cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Adjacent to the 4 orthogonal neighbors (the von Neumann neighborhood).
+    Four,
+    /// Adjacent to the 4 orthogonal and 4 diagonal neighbors (the Moore neighborhood).
+    Eight,
+}
+
+impl Connectivity {
+    /// The displacements from a cell to each of its adjacent cells.
+    pub fn displacements(self) -> &'static [CellIndex] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL_OFFSETS,
+            Connectivity::Eight => &ALL_OFFSETS,
+        }
+    }
+}
+
+/// Replaces every element equal to `target` that is connected to the cell at `start`
+/// through other such elements with `replacement`.
+///
+/// Does nothing if `start` is out of bounds or doesn't contain `target`.
+pub fn flood_fill<T: PartialEq + Clone>(
+    grid: &mut Grid<T>,
+    start: CellIndex,
+    target: &T,
+    replacement: T,
+    connectivity: Connectivity,
+) {
+    // Filling with the same value wouldn't change anything.
+    if *target == replacement {
+        return;
+    }
+    for index in flood_fill_collect(grid, start, |element| element == target, connectivity) {
+        grid[index] = replacement.clone();
+    }
+}
+
+/// Returns the indices of every cell that would be affected by a flood fill from `start`:
+/// all cells satisfying `is_fillable` that are connected to `start`
+/// through other cells satisfying `is_fillable`.
+///
+/// Indices are returned in breadth-first order, starting with `start` itself.
+/// Returns an empty list if `start` is out of bounds or doesn't satisfy `is_fillable`.
+pub fn flood_fill_collect<T>(
+    grid: &Grid<T>,
+    start: CellIndex,
+    is_fillable: impl Fn(&T) -> bool,
+    connectivity: Connectivity,
+) -> Vec<CellIndex> {
+    let diagonal = connectivity == Connectivity::Eight;
+    grid.flood_fill_connected(start, is_fillable, diagonal)
+        .into_iter()
+        .map(|(index, _)| index)
+        .collect()
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid.
+        This function completes in `O(c)` time in the worst case,
+        assuming that `is_fillable` completes in `O(1)` time,
+        since the breadth-first search in `Grid::flood_fill_connected` visits each cell at most once
+        and checks at most 8 neighbors per cell.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flood_fill::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Example picture for tests, with `.` as the background.
+    #[rustfmt::skip]
+    fn picture() -> Grid<char> {
+        Grid::from_2d_array([
+            ['.', '.', '#', '.', '.'],
+            ['.', '#', '#', '.', '.'],
+            ['#', '.', '.', '#', '#'],
+            ['.', '.', '.', '#', '.'],
+        ])
+    }
+
+    #[test]
+    fn test_paint_bucket() {
+        let mut grid = picture();
+        flood_fill(&mut grid, (1, 3), &'.', '~', Connectivity::Four);
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            ['.', '.', '#', '.', '.'],
+            ['.', '#', '#', '.', '.'],
+            ['#', '~', '~', '#', '#'],
+            ['~', '~', '~', '#', '.'],
+        ]);
+        assert_eq!(grid, expected);
+    }
+
+    #[test]
+    fn test_paint_bucket_eight_connectivity() {
+        let mut grid = picture();
+        flood_fill(&mut grid, (1, 3), &'.', '~', Connectivity::Eight);
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            ['~', '~', '#', '~', '~'],
+            ['~', '#', '#', '~', '~'],
+            ['#', '~', '~', '#', '#'],
+            ['~', '~', '~', '#', '.'],
+        ]);
+        assert_eq!(grid, expected);
+    }
+
+    #[rstest]
+    #[case::out_of_bounds((5, 0), &'.', '~')]
+    #[case::negative((-1, 2), &'.', '~')]
+    #[case::not_target((2, 0), &'.', '~')]
+    #[case::same_value((0, 0), &'.', '.')]
+    fn test_no_op(#[case] start: CellIndex, #[case] target: &char, #[case] replacement: char) {
+        let mut grid = picture();
+        flood_fill(&mut grid, start, target, replacement, Connectivity::Eight);
+        assert_eq!(grid, picture());
+    }
+
+    #[rstest]
+    #[case::four(Connectivity::Four, vec![(0, 0)])]
+    #[case::eight(Connectivity::Eight, vec![(0, 0), (1, 1), (0, 2), (2, 2)])]
+    fn test_diagonal_pattern(#[case] connectivity: Connectivity, #[case] expected: Vec<CellIndex>) {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [true,  false, false],
+            [false, true,  false],
+            [true,  false, true],
+        ]);
+        let actual = flood_fill_collect(&grid, (0, 0), |&element| element, connectivity);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_collect_breadth_first_order() {
+        let grid = picture();
+        let actual =
+            flood_fill_collect(&grid, (3, 1), |&element| element == '.', Connectivity::Four);
+        assert_eq!(actual, [(3, 1), (3, 0), (4, 1), (4, 0)]);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_grids(#[case] width: usize, #[case] height: usize) {
+        let grid = Grid::filled('.', (width, height));
+        let actual = flood_fill_collect(&grid, (0, 0), |_| true, Connectivity::Eight);
+        assert_eq!(actual, []);
+    }
+}
@@ -0,0 +1,413 @@
+//! Flow Free is a puzzle played on a rectangular grid,
+//! where some cells contain colored endpoints -- exactly two for each color.
+//!
+//! Problem: connect each pair of endpoints with a path of their color,
+//! such that paths don't cross or overlap, and every cell of the grid is covered by a path.
+//! Paths move orthogonally between cells.
+//!
+//! For example, the following puzzle (letters for endpoints, `.` for empty cells):
+//! ```text
+//! R . . .
+//! B . . .
+//! G . . .
+//! G . B R
+//! ```
+//! can be solved like so:
+//! ```text
+//! R R R R
+//! B B B R
+//! G G B R
+//! G G B R
+//! ```
+//!
+//! The solver extends one path at a time from one of its endpoints,
+//! always choosing the path with the fewest options for its next step,
+//! and backtracks when it detects that the puzzle can no longer be solved:
+//! - Dead ends: an empty cell that can't be both entered and exited by any path.
+//! - Isolation: a path that can no longer reach its other endpoint,
+//!   or a region of empty cells that no remaining path can fill.
+
+use crate::grid::Grid;
+use std::collections::HashMap;
+
+/// A color of path, identified by a letter.
+pub type Color = char;
+
+type CellIndex = (usize, usize);
+
+/// A Flow Free puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowBoard {
+    grid: Grid<Option<Color>>,
+    endpoints: Vec<(Color, CellIndex, CellIndex)>,
+}
+
+impl FlowBoard {
+    /// Creates a puzzle from a grid, where `Some(color)` marks an endpoint of the given color.
+    ///
+    /// Returns Err(c) if `c` is a color that doesn't appear exactly twice in the grid.
+    /// If there are multiple such colors, it's left unspecified which one is returned.
+    pub fn new(grid: Grid<Option<Color>>) -> Result<FlowBoard, Color> {
+        let mut cells_by_color: HashMap<Color, Vec<CellIndex>> = HashMap::new();
+        for (index, cell) in grid.enumerate::<CellIndex>() {
+            if let &Some(color) = cell {
+                cells_by_color.entry(color).or_default().push(index);
+            }
+        }
+        let mut endpoints = vec![];
+        for (color, cells) in cells_by_color {
+            let &[start, end] = &cells[..] else {
+                return Err(color);
+            };
+            endpoints.push((color, start, end));
+        }
+        endpoints.sort();
+        Ok(FlowBoard { grid, endpoints })
+    }
+
+    /// The grid of the puzzle, where `Some(color)` marks an endpoint.
+    pub fn grid(&self) -> &Grid<Option<Color>> {
+        &self.grid
+    }
+
+    /// The endpoints of each color, sorted by color.
+    pub fn endpoints(&self) -> &[(Color, CellIndex, CellIndex)] {
+        &self.endpoints
+    }
+}
+
+/// The state of a partially-solved puzzle whose endpoints are marked with labels of type `L`.
+struct Search<L> {
+    grid: Grid<Option<L>>,
+    /// The path for each label.
+    paths: Vec<Path<L>>,
+}
+
+/// A path being extended from one endpoint towards the other.
+struct Path<L> {
+    label: L,
+    /// The cell that the path has been extended to so far.
+    head: CellIndex,
+    /// The endpoint that the path is being extended towards.
+    target: CellIndex,
+    is_complete: bool,
+}
+
+/// Returns a solution to the given puzzle, with every cell colored by the path covering it,
+/// or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &FlowBoard) -> Option<Grid<Option<Color>>> {
+    solve_paths(&board.grid, &board.endpoints)
+}
+
+/// Returns a solution to the puzzle with the given grid, where `Some(label)` marks an endpoint,
+/// and the given pair of endpoints for each label,
+/// with every cell labeled by the path covering it,
+/// or None if the puzzle has no solution.
+///
+/// This is the solver behind [`solve`], for puzzles that label their endpoints with something
+/// other than colors (such as Numberlink).
+pub(crate) fn solve_paths<L: Copy + Eq>(
+    grid: &Grid<Option<L>>,
+    endpoints: &[(L, CellIndex, CellIndex)],
+) -> Option<Grid<Option<L>>> {
+    let paths = endpoints
+        .iter()
+        .map(|&(label, start, end)| Path {
+            label,
+            head: start,
+            target: end,
+            is_complete: false,
+        })
+        .collect();
+    let mut search = Search {
+        grid: grid.clone(),
+        paths,
+    };
+    search.solve().then_some(search.grid)
+}
+
+impl<L: Copy + Eq> Search<L> {
+    /// Tries to complete the solution from the current state, returning whether it was successful.
+    ///
+    /// On success, `grid` contains the solution; on failure, the state is left unchanged.
+    fn solve(&mut self) -> bool {
+        if self.paths.iter().all(|path| path.is_complete) {
+            return self
+                .grid
+                .enumerate::<CellIndex>()
+                .all(|(_, cell)| cell.is_some());
+        }
+        if self.has_dead_end() || self.has_isolation() {
+            return false;
+        }
+        // Extend the incomplete path with the fewest options for its next step.
+        let (path_index, moves) = self
+            .paths
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| !path.is_complete)
+            .map(|(i, _)| (i, self.moves(i)))
+            .min_by_key(|(_, moves)| moves.len())
+            .unwrap();
+        let Path { label, head, .. } = self.paths[path_index];
+        for next in moves {
+            if next == self.paths[path_index].target {
+                self.paths[path_index].is_complete = true;
+            } else {
+                self.grid[next] = Some(label);
+                self.paths[path_index].head = next;
+            }
+            if self.solve() {
+                return true;
+            }
+            if next == self.paths[path_index].target {
+                self.paths[path_index].is_complete = false;
+            } else {
+                self.grid[next] = None;
+                self.paths[path_index].head = head;
+            }
+        }
+        false
+    }
+
+    /// Returns the cells that the path with the given index can be extended into:
+    /// empty cells next to its head, plus its target if the target is next to its head.
+    fn moves(&self, path_index: usize) -> Vec<CellIndex> {
+        let path = &self.paths[path_index];
+        self.grid
+            .orthogonal_neighbors(path.head)
+            .filter(|&(neighbor, cell)| cell.is_none() || neighbor == path.target)
+            .map(|(neighbor, _)| neighbor)
+            .collect()
+    }
+
+    /// Returns whether a cell can be the head or the target of an incomplete path.
+    fn is_open_end(&self, cell: CellIndex) -> bool {
+        self.paths
+            .iter()
+            .any(|path| !path.is_complete && (path.head == cell || path.target == cell))
+    }
+
+    /// Returns whether there is an empty cell with fewer than 2 neighbors
+    /// that a path could enter it from or exit it to.
+    fn has_dead_end(&self) -> bool {
+        self.grid
+            .enumerate::<CellIndex>()
+            .filter(|(_, cell)| cell.is_none())
+            .any(|(cell, _)| {
+                let num_open_neighbors = self
+                    .grid
+                    .orthogonal_neighbors(cell)
+                    .filter(|&(neighbor, contents)| {
+                        contents.is_none() || self.is_open_end(neighbor)
+                    })
+                    .count();
+                num_open_neighbors < 2
+            })
+    }
+
+    /// Returns whether an incomplete path can no longer reach its target,
+    /// or a region of empty cells can no longer be filled by any incomplete path.
+    fn has_isolation(&self) -> bool {
+        // Label each region of orthogonally connected empty cells.
+        let mut regions: Grid<Option<usize>> = Grid::filled(None, self.grid.dimensions());
+        let mut num_regions = 0;
+        for (start, cell) in self.grid.enumerate::<CellIndex>() {
+            if cell.is_some() || regions[start].is_some() {
+                continue;
+            }
+            regions[start] = Some(num_regions);
+            let mut stack = vec![start];
+            while let Some(curr) = stack.pop() {
+                for (neighbor, contents) in self.grid.orthogonal_neighbors(curr) {
+                    if contents.is_none() && regions[neighbor].is_none() {
+                        regions[neighbor] = Some(num_regions);
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            num_regions += 1;
+        }
+        let touched_regions = |cell: CellIndex| -> Vec<usize> {
+            regions
+                .orthogonal_neighbors(cell)
+                .filter_map(|(_, &region)| region)
+                .collect()
+        };
+        let mut is_fillable = vec![false; num_regions];
+        for path in self.paths.iter().filter(|path| !path.is_complete) {
+            let head_regions = touched_regions(path.head);
+            let shared_regions: Vec<usize> = touched_regions(path.target)
+                .into_iter()
+                .filter(|region| head_regions.contains(region))
+                .collect();
+            let is_adjacent = self
+                .grid
+                .orthogonal_neighbors(path.head)
+                .any(|(cell, _)| cell == path.target);
+            if shared_regions.is_empty() && !is_adjacent {
+                return true;
+            }
+            for region in shared_regions {
+                is_fillable[region] = true;
+            }
+        }
+        is_fillable.contains(&false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flood_fill::{flood_fill_collect, Connectivity};
+    use crate::flow_free::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Flow Free puzzle grid.
+    ///
+    /// Syntax:
+    /// ```text
+    /// flow![
+    ///     [<a letter for an endpoint of that color, `.` for an empty cell> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! flow {
+        (@cell .) => {None};
+        (@cell $color:ident) => {Some(stringify!($color).chars().next().unwrap())};
+        ($([$($cell:tt)*])*) => {
+            Grid::<Option<Color>>::from_2d_array([$([$(flow!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Asserts that the given grid is a valid solution to the given puzzle.
+    fn assert_valid_solution(board: &FlowBoard, solution: &Grid<Option<Color>>) {
+        assert_eq!(solution.dimensions(), board.grid().dimensions());
+        for (index, &cell) in board.grid().enumerate::<CellIndex>() {
+            assert!(solution[index].is_some(), "cell {index:?} not covered");
+            if cell.is_some() {
+                assert_eq!(solution[index], cell, "endpoint {index:?} replaced");
+            }
+        }
+        for &(color, (x, y), end) in board.endpoints() {
+            let path = flood_fill_collect(
+                solution,
+                (x as i32, y as i32),
+                |&cell| cell == Some(color),
+                Connectivity::Four,
+            );
+            let num_cells = solution
+                .enumerate::<CellIndex>()
+                .filter(|&(_, &cell)| cell == Some(color))
+                .count();
+            assert_eq!(path.len(), num_cells, "path for {color} not contiguous");
+            let (end_x, end_y) = end;
+            assert!(path.contains(&(end_x as i32, end_y as i32)));
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let board = FlowBoard::new(flow![
+            [R . B]
+            [. . R]
+            [B . .]
+        ])
+        .unwrap();
+        assert_eq!(
+            board.endpoints(),
+            [('B', (2, 0), (0, 2)), ('R', (0, 0), (2, 1))]
+        );
+    }
+
+    #[rstest]
+    #[case::single_endpoint(flow![
+        [R . B]
+        [. . B]
+    ], 'R')]
+    #[case::three_endpoints(flow![
+        [G . G]
+        [. G .]
+    ], 'G')]
+    fn test_new_invalid(#[case] grid: Grid<Option<Color>>, #[case] expected: Color) {
+        assert_eq!(FlowBoard::new(grid), Err(expected));
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = FlowBoard::new(flow![
+            [R . . .]
+            [B . . .]
+            [G . . .]
+            [G . B R]
+        ])
+        .unwrap();
+        let solution = solve(&board).unwrap();
+        assert_valid_solution(&board, &solution);
+    }
+
+    #[rstest]
+    #[case::five_by_five(flow![
+        [R . G . Y]
+        [. . B . O]
+        [. . . . .]
+        [. G . Y .]
+        [. R B O .]
+    ])]
+    #[case::five_by_five_two_colors(flow![
+        [R . . . .]
+        [. . . . .]
+        [. . B . .]
+        [. . . . B]
+        [. . . . R]
+    ])]
+    #[case::six_by_six(flow![
+        [R . . . . .]
+        [. . . . . .]
+        [. . . . . .]
+        [. . Y . . .]
+        [. . . G . .]
+        [B G . Y B R]
+    ])]
+    #[case::adjacent_endpoints(flow![[R R]])]
+    fn test_solve(#[case] grid: Grid<Option<Color>>) {
+        let board = FlowBoard::new(grid).unwrap();
+        let solution = solve(&board).unwrap();
+        assert_valid_solution(&board, &solution);
+    }
+
+    #[rstest]
+    #[case::crossing(flow![
+        [R B]
+        [B R]
+    ])]
+    #[case::uncoverable_cell(flow![
+        [R .]
+        [. R]
+    ])]
+    #[case::blocked(flow![
+        [R B .]
+        [B . .]
+        [. . R]
+    ])]
+    #[case::no_endpoints(flow![
+        [. .]
+        [. .]
+    ])]
+    fn test_unsolvable(#[case] grid: Grid<Option<Color>>) {
+        let board = FlowBoard::new(grid).unwrap();
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = FlowBoard::new(Grid::filled(None, (width, height))).unwrap();
+        assert_eq!(solve(&board), Some(Grid::filled(None, (width, height))));
+    }
+}
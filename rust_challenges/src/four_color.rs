@@ -0,0 +1,302 @@
+//! The four color theorem states that the regions of any map drawn on a plane
+//! can be colored with at most four colors,
+//! so that no two regions sharing a border have the same color.
+//!
+//! Problem: given a map as a grid of region IDs, where cells with the same ID are in the same region,
+//! color each region with one of the colors 0 to 3, so that no two orthogonally adjacent cells
+//! in different regions have the same color, or determine that this isn't possible.
+//!
+//! For example, on this map
+//! ```text
+//! 3 3 3 3 3
+//! 3 3 0 3 3
+//! 3 1 0 2 3
+//! 3 3 0 3 3
+//! 3 3 3 3 3
+//! ```
+//! regions 1 and 2 can have the same color, while regions 0 and 3 need two other colors.
+//!
+//! If there are multiple solutions, one of them will be returned;
+//! it's left unspecified which specific solution is returned.
+//!
+//! The regions of a map with disconnected regions (cells with the same ID that aren't connected)
+//! may not be four-colorable, so the solution builds the graph of which regions are adjacent,
+//! and then colors it with a backtracking search,
+//! preferring to color the regions with the most differently colored neighbors
+//! (and then the regions with the most neighbors) first.
+
+use crate::grid::Grid;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The number of colors allowed in a coloring.
+const COLORS: u8 = 4;
+
+/// Returns the other regions that each region is orthogonally adjacent to.
+fn adjacency_graph(regions: &Grid<usize>) -> BTreeMap<usize, BTreeSet<usize>> {
+    let mut result: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+    for ((x, y), &region) in regions.enumerate::<(usize, usize)>() {
+        result.entry(region).or_default();
+        for neighbor in [(x + 1, y), (x, y + 1)] {
+            if let Some(&other) = regions.get(neighbor).filter(|&&other| other != region) {
+                result.entry(region).or_default().insert(other);
+                result.entry(other).or_default().insert(region);
+            }
+        }
+    }
+    result
+}
+
+/// Returns the number of different colors that the given region's neighbors have so far.
+fn saturation(
+    graph: &BTreeMap<usize, BTreeSet<usize>>,
+    coloring: &HashMap<usize, u8>,
+    region: usize,
+) -> usize {
+    graph[&region]
+        .iter()
+        .filter_map(|neighbor| coloring.get(neighbor))
+        .collect::<BTreeSet<_>>()
+        .len()
+}
+
+/// Assigns colors to the uncolored regions, given the colors assigned so far,
+/// returning whether this was successful.
+///
+/// The next region to color is the one whose neighbors have the most different colors,
+/// with ties broken by the number of neighbors, since these are the regions most likely to fail.
+fn assign_colors(
+    graph: &BTreeMap<usize, BTreeSet<usize>>,
+    color_count: u8,
+    coloring: &mut HashMap<usize, u8>,
+) -> bool {
+    let Some(region) = graph
+        .keys()
+        .copied()
+        .filter(|region| !coloring.contains_key(region))
+        .max_by_key(|&region| (saturation(graph, coloring, region), graph[&region].len()))
+    else {
+        return true;
+    };
+    for color in 0..color_count {
+        if graph[&region]
+            .iter()
+            .any(|neighbor| coloring.get(neighbor) == Some(&color))
+        {
+            continue;
+        }
+        coloring.insert(region, color);
+        if assign_colors(graph, color_count, coloring) {
+            return true;
+        }
+    }
+    coloring.remove(&region);
+    false
+}
+
+/// Returns a coloring of the given map's regions with the colors `0..color_count`,
+/// or None if there isn't one.
+fn color_regions(regions: &Grid<usize>, color_count: u8) -> Option<HashMap<usize, u8>> {
+    let graph = adjacency_graph(regions);
+    let mut coloring = HashMap::new();
+    assign_colors(&graph, color_count, &mut coloring).then_some(coloring)
+}
+
+/// Returns a grid with the color (from 0 to 3) of each cell's region,
+/// such that orthogonally adjacent cells in different regions have different colors,
+/// or None if there isn't one.
+pub fn four_color(regions: &Grid<usize>) -> Option<Grid<u8>> {
+    let coloring = color_regions(regions, COLORS)?;
+    Some(regions.clone().map(|region| coloring[&region]))
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells and `r` be the number of regions.
+        Building the graph takes `O(c log r)` time.
+        Each step of the backtracking search takes `O(c log r)` time to choose a region,
+        since there are `O(c)` adjacencies between regions,
+        and the search can take up to `O(4^r)` steps in the worst case,
+        though planar maps are usually colored with little or no backtracking.
+    */
+}
+
+/// Returns whether the given coloring assigns a color from 0 to 3 to every region of the map,
+/// with no two orthogonally adjacent regions having the same color.
+pub fn is_valid_coloring(regions: &Grid<usize>, coloring: &HashMap<usize, u8>) -> bool {
+    adjacency_graph(regions).iter().all(|(region, neighbors)| {
+        coloring.get(region).is_some_and(|&color| {
+            color < COLORS
+                && neighbors
+                    .iter()
+                    .all(|neighbor| coloring.get(neighbor) != Some(&color))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::four_color::*;
+    use pretty_assertions::assert_eq;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+    use rstest::rstest;
+
+    /// Returns the color of each region in the given coloring grid,
+    /// panicking if a region has multiple colors.
+    fn region_colors(regions: &Grid<usize>, coloring: &Grid<u8>) -> HashMap<usize, u8> {
+        assert_eq!(regions.dimensions(), coloring.dimensions());
+        let mut result = HashMap::new();
+        for (index, &region) in regions.enumerate::<(usize, usize)>() {
+            let color = coloring[index];
+            assert_eq!(*result.entry(region).or_insert(color), color, "{index:?}");
+        }
+        result
+    }
+
+    /// Asserts that `four_color` finds a valid coloring of the given map.
+    fn assert_colorable(regions: &Grid<usize>) {
+        let coloring = four_color(regions).expect("no coloring found");
+        assert!(is_valid_coloring(
+            regions,
+            &region_colors(regions, &coloring)
+        ));
+    }
+
+    /// Returns a random map with connected regions, grown from randomly placed starting cells.
+    fn random_map(
+        dimensions: (usize, usize),
+        region_count: usize,
+        rng: &mut StdRng,
+    ) -> Grid<usize> {
+        let (width, height) = dimensions;
+        let mut cells: Vec<(usize, usize)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .collect();
+        cells.shuffle(rng);
+        let mut regions: Grid<Option<usize>> = Grid::filled(None, dimensions);
+        let mut frontier = vec![];
+        for (region, &cell) in cells.iter().take(region_count).enumerate() {
+            regions[cell] = Some(region);
+            frontier.push(cell);
+        }
+        while !frontier.is_empty() {
+            let (x, y) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+            for neighbor in [
+                (x + 1, y),
+                (x, y + 1),
+                (x.wrapping_sub(1), y),
+                (x, y.wrapping_sub(1)),
+            ] {
+                if regions.get(neighbor) == Some(&None) {
+                    regions[neighbor] = regions[(x, y)];
+                    frontier.push(neighbor);
+                }
+            }
+        }
+        regions.map(|region| region.unwrap())
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let regions = Grid::from_2d_array([
+            [3, 3, 3, 3, 3],
+            [3, 3, 0, 3, 3],
+            [3, 1, 0, 2, 3],
+            [3, 3, 0, 3, 3],
+            [3, 3, 3, 3, 3],
+        ]);
+        assert_colorable(&regions);
+        // Regions 0, 1, and 3 are all adjacent to each other, as are regions 0, 2, and 3.
+        assert!(color_regions(&regions, 2).is_none());
+        let three_coloring = color_regions(&regions, 3).unwrap();
+        assert!(is_valid_coloring(&regions, &three_coloring));
+        assert_eq!(three_coloring[&1], three_coloring[&2]);
+    }
+
+    #[rstest]
+    #[case::two_by_two(Grid::from_2d_array([[0, 1], [2, 3]]))]
+    #[case::four_by_three(Grid::from_2d_array([
+        [0, 1, 2, 3],
+        [4, 5, 6, 7],
+        [8, 9, 10, 11],
+    ]))]
+    fn test_checkerboard(#[case] regions: Grid<usize>) {
+        // Each cell is its own region, so the regions can be colored like a checkerboard.
+        let coloring = color_regions(&regions, 2).unwrap();
+        assert!(is_valid_coloring(&regions, &coloring));
+        for ((x, y), region) in regions.enumerate::<(usize, usize)>() {
+            assert_eq!(
+                coloring[region],
+                coloring[&regions[(0, 0)]] ^ ((x + y) % 2) as u8
+            );
+        }
+        assert_colorable(&regions);
+    }
+
+    #[test]
+    fn test_four_colors_needed() {
+        // Every region is adjacent to every other region.
+        let regions = Grid::from_2d_array([[1, 1, 2], [3, 0, 2], [3, 3, 2]]);
+        assert!(color_regions(&regions, 3).is_none());
+        assert_colorable(&regions);
+        let coloring = region_colors(&regions, &four_color(&regions).unwrap());
+        let mut colors: Vec<u8> = coloring.into_values().collect();
+        colors.sort();
+        assert_eq!(colors, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_complete_graph_on_five_regions() {
+        // With disconnected regions, every pair of the 5 regions is adjacent somewhere
+        // (following an Eulerian circuit of the complete graph),
+        // which isn't possible if the regions are connected.
+        let regions = Grid::from_2d_array([[0, 1, 2, 3, 4, 0, 2, 4, 1, 3, 0]]);
+        assert_eq!(four_color(&regions), None);
+        let coloring = color_regions(&regions, 5).unwrap();
+        // Color 4 isn't one of the four colors.
+        assert!(!is_valid_coloring(&regions, &coloring));
+        let mut colors: Vec<u8> = coloring.into_values().collect();
+        colors.sort();
+        assert_eq!(colors, [0, 1, 2, 3, 4]);
+    }
+
+    #[rstest]
+    #[case::single_region(Grid::from_2d_array([[7, 7], [7, 7]]))]
+    #[case::single_cell(Grid::from_2d_array([[0]]))]
+    #[case::empty(Grid::filled(0, (0, 0)))]
+    fn test_trivial(#[case] regions: Grid<usize>) {
+        assert_eq!(
+            four_color(&regions),
+            Some(Grid::filled(0, regions.dimensions()))
+        );
+    }
+
+    #[rstest]
+    #[case::uncolored(HashMap::from([(0, 0), (1, 1)]))]
+    #[case::same_color(HashMap::from([(0, 0), (1, 1), (2, 1)]))]
+    #[case::color_out_of_range(HashMap::from([(0, 0), (1, 1), (2, 4)]))]
+    fn test_invalid_coloring(#[case] coloring: HashMap<usize, u8>) {
+        let regions = Grid::from_2d_array([[0, 1, 2]]);
+        assert!(!is_valid_coloring(&regions, &coloring));
+    }
+
+    #[rstest]
+    #[case::extra_regions(HashMap::from([(0, 0), (1, 1), (2, 0), (5, 1)]))]
+    #[case::non_adjacent_same_color(HashMap::from([(0, 3), (1, 1), (2, 3)]))]
+    fn test_valid_coloring(#[case] coloring: HashMap<usize, u8>) {
+        let regions = Grid::from_2d_array([[0, 1, 2]]);
+        assert!(is_valid_coloring(&regions, &coloring));
+    }
+
+    #[rstest]
+    #[case((10, 10), 15)]
+    #[case((20, 15), 40)]
+    #[case((30, 30), 100)]
+    #[case((40, 3), 30)]
+    fn test_planar_maps(#[case] dimensions: (usize, usize), #[case] region_count: usize) {
+        // By the four color theorem, a map with connected regions can always be colored.
+        let mut rng = StdRng::seed_from_u64(1153);
+        for _ in 0..10 {
+            assert_colorable(&random_map(dimensions, region_count, &mut rng));
+        }
+    }
+}
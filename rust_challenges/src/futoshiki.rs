@@ -0,0 +1,389 @@
+//! Futoshiki is a number puzzle played on an `n`×`n` grid,
+//! with some cells given and some pairs of adjacent cells marked with an inequality.
+//!
+//! Problem: fill the grid with numbers from 1 to `n`,
+//! such that no number is repeated within a row or column,
+//! every given cell keeps its number, and every inequality holds.
+//!
+//! For example, in the following puzzle
+//! (`.` for an empty cell, `<` and `>` for inequalities between horizontally adjacent cells,
+//! and `^` and `v` for inequalities between vertically adjacent cells, pointing at the smaller one):
+//! ```text
+//! . < . < .
+//!
+//! .   .   .
+//! v
+//! .   .   .
+//! ```
+//! the top row must be 1, 2, 3, and the left cell of the middle row must be 3, not 2,
+//! which leads to the unique solution:
+//! ```text
+//! 1 < 2 < 3
+//!
+//! 3   1   2
+//! v
+//! 2   3   1
+//! ```
+//!
+//! The solver (from [`crate::candidate_numbers`], shared with KenKen)
+//! keeps track of the numbers that each cell could still contain,
+//! and makes every pair of constrained cells (in the same row or column, or with an inequality)
+//! _arc consistent_ using the AC-3 algorithm: every candidate for one cell of the pair
+//! must be compatible with some candidate for the other cell.
+//! Since each row and column must use every number,
+//! a number that only one cell of a row or column can contain is also placed in that cell.
+//! It then guesses a number for the most constrained cell,
+//! and backtracks if the guess leads to a contradiction.
+
+use crate::candidate_numbers::{self, Arc, ArcConstraint, Numbers};
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// An inequality between two cells: the number in `smaller` must be less than the one in `larger`.
+///
+/// In a standard puzzle, the two cells are orthogonally adjacent,
+/// but the solver doesn't rely on that.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Inequality {
+    pub smaller: CellIndex,
+    pub larger: CellIndex,
+}
+
+/// A Futoshiki puzzle: the size of the grid, the given cells, and the inequalities.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FutoshikiBoard {
+    pub size: usize,
+    pub given: Grid<Option<u8>>,
+    pub inequalities: Vec<Inequality>,
+}
+
+/// A constraint between the number in one cell and the number in another cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Relation {
+    NotEqual,
+    LessThan,
+    GreaterThan,
+}
+
+impl ArcConstraint for Relation {
+    /// Returns every number `k` for which some number in `numbers` satisfies `k <relation> number`.
+    fn supported(self, numbers: Numbers) -> Numbers {
+        if numbers == 0 {
+            return 0;
+        }
+        match self {
+            // Every number has support unless `numbers` contains only that number.
+            Relation::NotEqual if numbers.count_ones() == 1 => !numbers,
+            Relation::NotEqual => Numbers::MAX,
+            // Every number less than the largest one.
+            Relation::LessThan => {
+                let largest = Numbers::BITS - 1 - numbers.leading_zeros();
+                (1 << largest) - 1
+            }
+            // Every number greater than the smallest one.
+            Relation::GreaterThan => {
+                let smallest = numbers.trailing_zeros();
+                Numbers::MAX << smallest << 1
+            }
+        }
+    }
+
+    fn reversed(self) -> Relation {
+        match self {
+            Relation::NotEqual => Relation::NotEqual,
+            Relation::LessThan => Relation::GreaterThan,
+            Relation::GreaterThan => Relation::LessThan,
+        }
+    }
+}
+
+/// Returns every arc of the given puzzle, grouped by the cell that the arc goes from.
+///
+/// Each constraint between two cells gives an arc in each direction.
+fn arcs(board: &FutoshikiBoard) -> Grid<Vec<Arc<Relation>>> {
+    let mut arcs = candidate_numbers::line_arcs(board.size, Relation::NotEqual);
+    for &Inequality { smaller, larger } in &board.inequalities {
+        arcs[smaller].push((smaller, larger, Relation::LessThan));
+        arcs[larger].push((larger, smaller, Relation::GreaterThan));
+    }
+    arcs
+}
+
+/// Returns a solution to the given puzzle, or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+///
+/// Panics if the dimensions of `given` aren't `size`×`size`,
+/// if a cell of some inequality is out of bounds, or if `size` is more than 31.
+pub fn solve(board: &FutoshikiBoard) -> Option<Grid<u8>> {
+    let FutoshikiBoard {
+        size,
+        given,
+        inequalities,
+    } = board;
+    let size = *size;
+    let dimensions = (size, size);
+    assert!(size < Numbers::BITS as usize, "size {size} too large");
+    if given.dimensions() != dimensions {
+        panic!(
+            "dimensions of given grid {:?} don't match size {size}",
+            given.dimensions()
+        );
+    }
+    for cell in inequalities
+        .iter()
+        .flat_map(|ineq| [ineq.smaller, ineq.larger])
+    {
+        if given.get(cell).is_none() {
+            panic!("cell {cell:?} out of bounds for dimensions {dimensions:?}");
+        }
+    }
+    let all_numbers: Numbers = ((1 << size) - 1) << 1;
+    // Given numbers out of range leave a cell with no candidates from the start.
+    let candidates = given.clone().map(|number| match number {
+        Some(number) => (1 << number) & all_numbers,
+        None => all_numbers,
+    });
+    candidate_numbers::solve_candidates(
+        candidates,
+        &arcs(board),
+        &candidate_numbers::lines(size),
+        &[],
+    )
+    /*
+        Time complexity analysis:
+        The puzzle is NP-complete in general,
+        and the search can take exponential time in the size of the grid in the worst case.
+        Let `n` be the size of the grid, and `a` the number of arcs (`O(n^3)`).
+        By the analysis of `candidate_numbers::solve_candidates`
+        (with `n^2` cells, and the `2 * n` rows and columns as groups),
+        each guess takes `O(a * n + n^6)` time to propagate in the very worst case.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::futoshiki::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates an inequality between the two given cells.
+    fn less(smaller: CellIndex, larger: CellIndex) -> Inequality {
+        Inequality { smaller, larger }
+    }
+
+    /// Checks that the given grid is a valid solution to the given puzzle.
+    fn assert_valid(board: &FutoshikiBoard, solution: &Grid<u8>) {
+        let size = board.size;
+        assert_eq!(solution.dimensions(), (size, size));
+        // Each row and column must be a permutation of 1 to `n`, so the grid is a Latin square.
+        let expected: Vec<u8> = (1..=size as u8).collect();
+        for i in 0..size {
+            let mut row: Vec<u8> = (0..size).map(|x| solution[(x, i)]).collect();
+            row.sort();
+            assert_eq!(row, expected, "invalid row {i}");
+            let mut column: Vec<u8> = (0..size).map(|y| solution[(i, y)]).collect();
+            column.sort();
+            assert_eq!(column, expected, "invalid column {i}");
+        }
+        for (index, &given) in board.given.enumerate::<CellIndex>() {
+            if let Some(number) = given {
+                assert_eq!(solution[index], number, "changed given cell {index:?}");
+            }
+        }
+        for &inequality in &board.inequalities {
+            let Inequality { smaller, larger } = inequality;
+            assert!(
+                solution[smaller] < solution[larger],
+                "unsatisfied inequality {inequality:?}"
+            );
+        }
+    }
+
+    /// The puzzle from the problem description.
+    fn problem_description_example() -> FutoshikiBoard {
+        FutoshikiBoard {
+            size: 3,
+            given: Grid::filled(None, (3, 3)),
+            inequalities: vec![
+                less((0, 0), (1, 0)),
+                less((1, 0), (2, 0)),
+                less((0, 2), (0, 1)),
+            ],
+        }
+    }
+
+    #[rstest]
+    #[case::not_equal_single(0b1000, Relation::NotEqual, !0b1000)]
+    #[case::not_equal_multiple(0b1100, Relation::NotEqual, Numbers::MAX)]
+    #[case::less_than(0b1010, Relation::LessThan, 0b0111)]
+    #[case::greater_than(0b1010, Relation::GreaterThan, !0b0011)]
+    #[case::empty(0, Relation::LessThan, 0)]
+    fn test_supported(
+        #[case] numbers: Numbers,
+        #[case] relation: Relation,
+        #[case] expected: Numbers,
+    ) {
+        assert_eq!(relation.supported(numbers), expected);
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 2, 3],
+            [3, 1, 2],
+            [2, 3, 1],
+        ]);
+        assert_eq!(solve(&problem_description_example()), Some(expected));
+    }
+
+    #[test]
+    fn test_5_by_5() {
+        let mut given = Grid::filled(None, (5, 5));
+        given[(3, 4)] = Some(3);
+        let board = FutoshikiBoard {
+            size: 5,
+            given,
+            inequalities: vec![
+                less((0, 1), (0, 2)),
+                less((0, 2), (1, 2)),
+                less((1, 2), (1, 3)),
+                less((2, 0), (3, 0)),
+                less((2, 1), (1, 1)),
+                less((2, 2), (1, 2)),
+                less((3, 0), (3, 1)),
+                less((4, 3), (4, 4)),
+            ],
+        };
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [5, 2, 1, 4, 3],
+            [1, 3, 2, 5, 4],
+            [2, 4, 3, 1, 5],
+            [3, 5, 4, 2, 1],
+            [4, 1, 5, 3, 2],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::no_constraints(FutoshikiBoard {
+        size: 6,
+        given: Grid::filled(None, (6, 6)),
+        inequalities: vec![],
+    })]
+    #[case::chain(FutoshikiBoard {
+        size: 4,
+        given: Grid::filled(None, (4, 4)),
+        inequalities: vec![
+            less((0, 0), (0, 1)),
+            less((0, 1), (1, 1)),
+            less((1, 1), (1, 2)),
+            less((3, 3), (2, 3)),
+        ],
+    })]
+    #[case::given(FutoshikiBoard {
+        size: 4,
+        given: Grid::from_2d_array([
+            [None, Some(4), None, None],
+            [None, None, None, None],
+            [Some(1), None, None, None],
+            [None, None, None, Some(2)],
+        ]),
+        inequalities: vec![less((2, 1), (2, 2))],
+    })]
+    fn test_solve(#[case] board: FutoshikiBoard) {
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    // A cycle of inequalities can never hold.
+    #[case::cycle(FutoshikiBoard {
+        size: 3,
+        given: Grid::filled(None, (3, 3)),
+        inequalities: vec![
+            less((0, 0), (1, 0)),
+            less((1, 0), (1, 1)),
+            less((1, 1), (0, 1)),
+            less((0, 1), (0, 0)),
+        ],
+    })]
+    // No number can be less than 1.
+    #[case::less_than_given_1(FutoshikiBoard {
+        size: 3,
+        given: Grid::from_2d_array([
+            [Some(1), None, None],
+            [None, None, None],
+            [None, None, None],
+        ]),
+        inequalities: vec![less((1, 0), (0, 0))],
+    })]
+    // The chain fills the left column with 1 to 4, so the bottom left cell can't be less than its neighbor.
+    #[case::chain_too_long(FutoshikiBoard {
+        size: 4,
+        given: Grid::filled(None, (4, 4)),
+        inequalities: vec![
+            less((0, 0), (0, 1)),
+            less((0, 1), (0, 2)),
+            less((0, 2), (0, 3)),
+            less((0, 3), (1, 3)),
+        ],
+    })]
+    #[case::repeated_given(FutoshikiBoard {
+        size: 3,
+        given: Grid::from_2d_array([
+            [Some(2), None, Some(2)],
+            [None, None, None],
+            [None, None, None],
+        ]),
+        inequalities: vec![],
+    })]
+    #[case::given_out_of_range(FutoshikiBoard {
+        size: 3,
+        given: Grid::from_2d_array([
+            [Some(4), None, None],
+            [None, None, None],
+            [None, None, None],
+        ]),
+        inequalities: vec![],
+    })]
+    fn test_unsolvable(#[case] board: FutoshikiBoard) {
+        assert_eq!(solve(&board), None);
+    }
+
+    #[test]
+    fn test_empty() {
+        let board = FutoshikiBoard {
+            size: 0,
+            given: Grid::filled(None, (0, 0)),
+            inequalities: vec![],
+        };
+        assert_eq!(solve(&board), Some(Grid::filled(0, (0, 0))));
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (0, 3) out of bounds for dimensions (3, 3)")]
+    fn test_out_of_bounds() {
+        let board = FutoshikiBoard {
+            inequalities: vec![less((0, 2), (0, 3))],
+            ..problem_description_example()
+        };
+        solve(&board);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions of given grid (2, 3) don't match size 3")]
+    fn test_mismatched_dimensions() {
+        let board = FutoshikiBoard {
+            given: Grid::filled(None, (2, 3)),
+            ..problem_description_example()
+        };
+        solve(&board);
+    }
+}
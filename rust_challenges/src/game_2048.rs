@@ -0,0 +1,351 @@
+//! 2048 is a single-player game played on a grid of numbered tiles.
+//! Each move slides every tile as far as possible in one direction;
+//! two tiles with the same number that collide merge into one tile with their sum,
+//! and the player scores the value of the merged tile.
+//! After every move, a new tile (a 2 with probability 0.9, or a 4 otherwise)
+//! appears in a random empty cell.
+//!
+//! Problem: model the game, and choose good moves by searching ahead.
+//!
+//! For example, sliding the following board (`.` for empty cells) to the left:
+//! ```text
+//! 2 2 . .        4 . . .
+//! 4 . 4 8   =>   8 8 . .
+//! 2 2 2 2        4 4 . .
+//! ```
+//! scores `4 + 8 + 4 + 4 = 20` points. Note that each tile can only merge once per move.
+//!
+//! The search is an expectimax search: the player's moves are chosen to maximize
+//! the expected score over the next few moves,
+//! averaging over every way that the new tile could appear.
+
+use crate::grid::Grid;
+use rand::Rng;
+
+/// A direction in which the tiles can be slid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+pub const DIRECTIONS: [Direction; 4] = [
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+];
+
+/// A 2048 board, where 0 indicates an empty cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board2048(pub Grid<u32>);
+
+type CellIndex = (usize, usize);
+
+/// Returns the lines of cells that tiles slide along when sliding in the given direction,
+/// with each line ordered from the cell that tiles slide towards.
+fn lines(direction: Direction, dimensions: (usize, usize)) -> Vec<Vec<CellIndex>> {
+    let (width, height) = dimensions;
+    match direction {
+        Direction::Left => (0..height)
+            .map(|y| (0..width).map(|x| (x, y)).collect())
+            .collect(),
+        Direction::Right => (0..height)
+            .map(|y| (0..width).rev().map(|x| (x, y)).collect())
+            .collect(),
+        Direction::Up => (0..width)
+            .map(|x| (0..height).map(|y| (x, y)).collect())
+            .collect(),
+        Direction::Down => (0..width)
+            .map(|x| (0..height).rev().map(|y| (x, y)).collect())
+            .collect(),
+    }
+}
+
+/// Returns the result of sliding all tiles on the given board in the given direction,
+/// along with the score gained from merging tiles.
+///
+/// If no tiles can move, the resulting board is the same as the original board.
+pub fn slide(board: &Board2048, direction: Direction) -> (Board2048, u32) {
+    let Board2048(grid) = board;
+    let mut result = Grid::filled(0, grid.dimensions());
+    let mut score = 0;
+    for line in lines(direction, grid.dimensions()) {
+        let mut slid: Vec<u32> = vec![];
+        // Whether the last tile in `slid` is the result of a merge during this move.
+        let mut last_merged = false;
+        for value in line
+            .iter()
+            .map(|&index| grid[index])
+            .filter(|&value| value != 0)
+        {
+            match slid.last_mut() {
+                Some(last) if *last == value && !last_merged => {
+                    *last *= 2;
+                    score += *last;
+                    last_merged = true;
+                }
+                _ => {
+                    slid.push(value);
+                    last_merged = false;
+                }
+            }
+        }
+        for (&index, value) in line.iter().zip(slid) {
+            result[index] = value;
+        }
+    }
+    (Board2048(result), score)
+}
+
+/// Returns the indices of the empty cells on the given board, in row-major order.
+fn empty_cells(board: &Board2048) -> Vec<CellIndex> {
+    let Board2048(grid) = board;
+    grid.enumerate()
+        .filter(|&(_, &value)| value == 0)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// The probability that a new tile is a 2 (rather than a 4).
+const TWO_PROBABILITY: f64 = 0.9;
+
+/// Returns the result of adding a new tile to a uniformly random empty cell of the given board,
+/// or None if the board has no empty cells.
+///
+/// The new tile is a 2 with probability 0.9, and a 4 otherwise.
+pub fn add_random_tile(board: &Board2048, rng: &mut impl Rng) -> Option<Board2048> {
+    let empty_cells = empty_cells(board);
+    if empty_cells.is_empty() {
+        return None;
+    }
+    let index = empty_cells[rng.gen_range(0..empty_cells.len())];
+    let value = if rng.gen_bool(TWO_PROBABILITY) { 2 } else { 4 };
+    let Board2048(grid) = board;
+    let mut grid = grid.clone();
+    grid[index] = value;
+    Some(Board2048(grid))
+}
+
+/// Returns the moves that change the given board,
+/// along with the resulting boards (before a new tile is added) and the scores gained.
+fn valid_moves(board: &Board2048) -> impl Iterator<Item = (Direction, Board2048, u32)> + '_ {
+    DIRECTIONS.into_iter().filter_map(|direction| {
+        let (result, score) = slide(board, direction);
+        (result != *board).then_some((direction, result, score))
+    })
+}
+
+/// Returns the greatest expected score that can be gained
+/// over the next `depth` moves from the given board, with the player about to move.
+fn max_value(board: &Board2048, depth: usize) -> f64 {
+    if depth == 0 {
+        return 0.0;
+    }
+    valid_moves(board)
+        .map(|(_, result, score)| score as f64 + chance_value(&result, depth - 1))
+        .fold(0.0, f64::max)
+}
+
+/// Returns the greatest expected score that can be gained
+/// over the next `depth` moves from the given board, with a new tile about to be added.
+fn chance_value(board: &Board2048, depth: usize) -> f64 {
+    let empty_cells = empty_cells(board);
+    if depth == 0 || empty_cells.is_empty() {
+        return 0.0;
+    }
+    let Board2048(grid) = board;
+    let mut total = 0.0;
+    for index in &empty_cells {
+        for (value, probability) in [(2, TWO_PROBABILITY), (4, 1.0 - TWO_PROBABILITY)] {
+            let mut grid = grid.clone();
+            grid[*index] = value;
+            total += probability * max_value(&Board2048(grid), depth);
+        }
+    }
+    total / empty_cells.len() as f64
+}
+
+/// Returns the move that maximizes the expected score over the next `depth` moves,
+/// or None if no move changes the given board (i.e. the game is over).
+///
+/// A search depth of 0 is treated as a depth of 1
+/// (the moves available to the player are always considered).
+/// If multiple moves are equally good, the first of them in the order
+/// left, right, up, down is returned.
+pub fn best_move(board: &Board2048, depth: usize) -> Option<Direction> {
+    let depth = depth.max(1);
+    let mut best: Option<(Direction, f64)> = None;
+    for (direction, result, score) in valid_moves(board) {
+        let value = score as f64 + chance_value(&result, depth - 1);
+        if best.is_none_or(|(_, best_value)| value > best_value) {
+            best = Some((direction, value));
+        }
+    }
+    best.map(|(direction, _)| direction)
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells on the board.
+        This function completes in `O((8c)^d * c)` time in the worst case, where `d` is the depth.
+        - Each move has up to 4 options, and each new tile has up to `2c` options,
+          so the search visits `O((8c)^d)` boards.
+        - Sliding a board, or finding its empty cells, takes `O(c)` time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game_2048::*;
+    use pretty_assertions::assert_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rstest::rstest;
+
+    /// Creates a 2048 board from an array of rows.
+    fn board<const W: usize, const H: usize>(rows: [[u32; W]; H]) -> Board2048 {
+        Board2048(Grid::from_2d_array(rows))
+    }
+
+    #[rstest]
+    #[case::merge_pair([2, 2, 0, 0], [4, 0, 0, 0], 4)]
+    #[case::merge_two_pairs([2, 2, 2, 2], [4, 4, 0, 0], 8)]
+    #[case::merge_across_gap([4, 0, 4, 8], [8, 8, 0, 0], 8)]
+    #[case::no_chain_merge([2, 2, 4, 0], [4, 4, 0, 0], 4)]
+    #[case::merge_first_pair([2, 2, 2, 0], [4, 2, 0, 0], 4)]
+    #[case::slide_only([0, 2, 0, 4], [2, 4, 0, 0], 0)]
+    #[case::different_values([2, 4, 8, 16], [2, 4, 8, 16], 0)]
+    #[case::empty([0, 0, 0, 0], [0, 0, 0, 0], 0)]
+    fn test_slide_row_left(
+        #[case] row: [u32; 4],
+        #[case] expected: [u32; 4],
+        #[case] expected_score: u32,
+    ) {
+        assert_eq!(
+            slide(&board([row]), Direction::Left),
+            (board([expected]), expected_score)
+        );
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let actual = slide(
+            &board([[2, 2, 0, 0], [4, 0, 4, 8], [2, 2, 2, 2]]),
+            Direction::Left,
+        );
+        let expected = board([[4, 0, 0, 0], [8, 8, 0, 0], [4, 4, 0, 0]]);
+        assert_eq!(actual, (expected, 20));
+    }
+
+    #[rstest]
+    #[case::right(Direction::Right, [
+        [0, 0, 2, 4],
+        [0, 2, 4, 2],
+        [0, 8, 2, 4],
+    ], 12)]
+    #[case::up(Direction::Up, [
+        [4, 2, 4, 4],
+        [4, 4, 2, 4],
+        [0, 0, 0, 0],
+    ], 8)]
+    #[case::down(Direction::Down, [
+        [0, 0, 0, 0],
+        [4, 2, 4, 4],
+        [4, 4, 2, 4],
+    ], 8)]
+    fn test_slide_directions(
+        #[case] direction: Direction,
+        #[case] expected: [[u32; 4]; 3],
+        #[case] expected_score: u32,
+    ) {
+        let original = board([[2, 2, 0, 2], [2, 0, 4, 2], [4, 4, 2, 4]]);
+        assert_eq!(
+            slide(&original, direction),
+            (board(expected), expected_score)
+        );
+    }
+
+    #[test]
+    fn test_add_random_tile() {
+        let original = board([[2, 0, 4], [0, 8, 0]]);
+        let mut rng = StdRng::seed_from_u64(2048);
+        let mut seen_values = vec![];
+        for _ in 0..100 {
+            let Board2048(result) = add_random_tile(&original, &mut rng).unwrap();
+            let Board2048(original_grid) = &original;
+            let changed: Vec<(CellIndex, u32)> = result
+                .enumerate::<CellIndex>()
+                .filter(|&(index, &value)| value != original_grid[index])
+                .map(|(index, &value)| (index, value))
+                .collect();
+            let &[(index, value)] = &changed[..] else {
+                panic!("expected exactly one changed cell, found {changed:?}");
+            };
+            assert_eq!(original_grid[index], 0, "tile added to nonempty cell");
+            assert!(value == 2 || value == 4, "unexpected tile value {value}");
+            seen_values.push(value);
+        }
+        // Both values appear with overwhelming probability.
+        assert!(seen_values.contains(&2));
+        assert!(seen_values.contains(&4));
+    }
+
+    #[test]
+    fn test_add_random_tile_full_board() {
+        let mut rng = StdRng::seed_from_u64(2048);
+        assert_eq!(add_random_tile(&board([[2, 4], [8, 16]]), &mut rng), None);
+    }
+
+    #[rstest]
+    // Only the vertical moves merge the 8s.
+    #[case::merge(board([
+        [2, 8, 4],
+        [4, 8, 2],
+        [2, 4, 2],
+    ]), 1, Direction::Up)]
+    #[case::merge_deep(board([
+        [2, 8, 4],
+        [4, 8, 2],
+        [2, 4, 2],
+    ]), 3, Direction::Up)]
+    // Merging the 8s is worth more than merging the 2s.
+    #[case::larger_merge(board([
+        [2, 4, 2],
+        [2, 8, 8],
+    ]), 1, Direction::Left)]
+    fn test_best_move(#[case] board: Board2048, #[case] depth: usize, #[case] expected: Direction) {
+        assert_eq!(best_move(&board, depth), Some(expected));
+    }
+
+    #[rstest]
+    #[case::full_board(board([
+        [2, 4, 2, 4],
+        [4, 2, 4, 2],
+        [2, 4, 2, 4],
+        [4, 2, 4, 2],
+    ]))]
+    #[case::single(board([[2]]))]
+    #[case::empty(Board2048(Grid::filled(0, (0, 0))))]
+    fn test_no_best_move(#[case] board: Board2048) {
+        assert_eq!(best_move(&board, 2), None);
+    }
+
+    #[test]
+    fn test_play() {
+        // Playing with the AI should always make progress until the board fills up.
+        let mut rng = StdRng::seed_from_u64(2048);
+        let mut board = add_random_tile(&Board2048(Grid::filled(0, (4, 4))), &mut rng).unwrap();
+        let mut total_score = 0;
+        for _ in 0..50 {
+            let Some(direction) = best_move(&board, 1) else {
+                break;
+            };
+            let (result, score) = slide(&board, direction);
+            assert_ne!(result, board);
+            total_score += score;
+            board = add_random_tile(&result, &mut rng).unwrap();
+        }
+        assert!(total_score > 0);
+    }
+}
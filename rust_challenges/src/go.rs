@@ -0,0 +1,445 @@
+//! Go (also known as Baduk or Weiqi) is a two-player game where players take turns
+//! placing stones on the intersections of a board.
+//! A group of stones (orthogonally connected stones of the same color) is captured and removed
+//! when it has no _liberties_ -- no empty points orthogonally adjacent to any of its stones.
+//!
+//! Problem: model placing stones, including captures and illegal moves, and count territory.
+//! A move is illegal if the point is occupied, if it would leave the player's own group without liberties
+//! (suicide), or if it would recreate the board position from just before the opponent's last move (Ko).
+//!
+//! For example, on the following board (`B` and `W` for black and white stones, `.` for empty points):
+//! ```text
+//! . B . .
+//! B W . .
+//! . B . .
+//! ```
+//! Black can place a stone to the right of the white stone, capturing it:
+//! ```text
+//! . B . .
+//! B . B .
+//! . B . .
+//! ```
+//! White then can't place a stone where its captured stone was, since that would be suicide.
+//!
+//! Territory is counted with flood fill: each empty region that only touches stones of one color
+//! is that player's territory, and regions touching both colors (or neither) belong to nobody.
+
+use crate::flood_fill::{flood_fill_collect, Connectivity};
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// The color of a stone, or a player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stone {
+    Black,
+    White,
+}
+
+impl Stone {
+    /// The other color.
+    pub fn opponent(self) -> Stone {
+        match self {
+            Stone::Black => Stone::White,
+            Stone::White => Stone::Black,
+        }
+    }
+}
+
+/// A Go board, where `None` indicates an empty point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoBoard(pub Grid<Option<Stone>>);
+
+impl GoBoard {
+    /// Creates an empty board with the given width and height.
+    pub fn new(width: usize, height: usize) -> GoBoard {
+        GoBoard(Grid::filled(None, (width, height)))
+    }
+}
+
+/// The reason a stone can't be placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlacementError {
+    /// The point already has a stone on it.
+    Occupied,
+    /// The stone's group would have no liberties, even after removing captured stones.
+    Suicide,
+    /// The move would recreate the board position from before the opponent's last move.
+    Ko,
+}
+
+/// Returns the orthogonally connected points starting from `start`
+/// whose contents are the same as the contents of `start`.
+fn region(grid: &Grid<Option<Stone>>, start: CellIndex) -> Vec<CellIndex> {
+    let contents = grid[start];
+    flood_fill_collect(
+        grid,
+        (start.0 as i32, start.1 as i32),
+        |&other| other == contents,
+        Connectivity::Four,
+    )
+    .into_iter()
+    .map(|(x, y)| (x as usize, y as usize))
+    .collect()
+}
+
+/// Returns whether any stone in the given group has an empty neighbor.
+fn has_liberties(grid: &Grid<Option<Stone>>, group: &[CellIndex]) -> bool {
+    group
+        .iter()
+        .flat_map(|&point| grid.orthogonal_neighbors(point))
+        .any(|(_, neighbor)| neighbor.is_none())
+}
+
+/// Returns the result of placing the given stone at the given position, after removing captured groups.
+///
+/// Returns Err if the position is occupied, or if the move is suicide.
+/// Ko can't be detected without knowing the previous position; see [`place_stone_checking_ko`].
+///
+/// Panics if the position is out of bounds.
+pub fn place_stone(
+    board: &GoBoard,
+    position: CellIndex,
+    stone: Stone,
+) -> Result<GoBoard, PlacementError> {
+    let GoBoard(grid) = board;
+    let Some(&contents) = grid.get(position) else {
+        panic!(
+            "cell {position:?} out of bounds for dimensions {:?}",
+            grid.dimensions()
+        );
+    };
+    if contents.is_some() {
+        return Err(PlacementError::Occupied);
+    }
+    let mut grid = grid.clone();
+    grid[position] = Some(stone);
+    // Opponent groups are captured before checking whether the placed stone's group has liberties.
+    let neighbors: Vec<CellIndex> = grid
+        .orthogonal_neighbors(position)
+        .map(|(neighbor, _)| neighbor)
+        .collect();
+    for neighbor in neighbors {
+        if grid[neighbor] != Some(stone.opponent()) {
+            continue;
+        }
+        let group = region(&grid, neighbor);
+        if !has_liberties(&grid, &group) {
+            for point in group {
+                grid[point] = None;
+            }
+        }
+    }
+    if !has_liberties(&grid, &region(&grid, position)) {
+        return Err(PlacementError::Suicide);
+    }
+    Ok(GoBoard(grid))
+    /*
+        Time complexity analysis:
+        Let `p` be the number of points on the board.
+        Each flood fill, and each check for liberties, takes `O(p)` time,
+        and there are at most 5 of each (one for each neighbor, and one for the placed stone),
+        so placing a stone takes `O(p)` time.
+    */
+}
+
+/// Like [`place_stone`], but also returns Err if the move is Ko:
+/// that is, if the resulting board is the same as `previous`,
+/// the board from just before the opponent's last move.
+pub fn place_stone_checking_ko(
+    board: &GoBoard,
+    previous: &GoBoard,
+    position: CellIndex,
+    stone: Stone,
+) -> Result<GoBoard, PlacementError> {
+    let result = place_stone(board, position, stone)?;
+    if result == *previous {
+        return Err(PlacementError::Ko);
+    }
+    Ok(result)
+}
+
+/// Returns the number of empty points in black's and white's territory, in that order.
+///
+/// An empty region (an orthogonally connected group of empty points) is a player's territory
+/// if it only touches that player's stones.
+pub fn count_territory(board: &GoBoard) -> (usize, usize) {
+    let GoBoard(grid) = board;
+    let mut visited = Grid::filled(false, grid.dimensions());
+    let (mut black, mut white) = (0, 0);
+    for (point, &contents) in grid.enumerate::<CellIndex>() {
+        if visited[point] || contents.is_some() {
+            continue;
+        }
+        let empty_region = region(grid, point);
+        for &other in &empty_region {
+            visited[other] = true;
+        }
+        let touching = |stone| {
+            empty_region
+                .iter()
+                .flat_map(|&other| grid.orthogonal_neighbors(other))
+                .any(|(_, &neighbor)| neighbor == Some(stone))
+        };
+        match (touching(Stone::Black), touching(Stone::White)) {
+            (true, false) => black += empty_region.len(),
+            (false, true) => white += empty_region.len(),
+            _ => {}
+        }
+    }
+    (black, white)
+    /*
+        Time complexity analysis:
+        Let `p` be the number of points on the board.
+        There's one flood fill for each empty region, and each one takes `O(p)` time
+        (if only to set up the grid of visited points),
+        so counting territory takes `O(p^2)` time in the worst case,
+        where there are `O(p)` small empty regions.
+        The checks for touching stones look at each empty point a constant number of times,
+        so they take `O(p)` time in total.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::go::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Go board.
+    ///
+    /// Syntax:
+    /// ```text
+    /// go![
+    ///     [<`.` for empty, `B` for black, `W` for white> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! go {
+        (@cell .) => {None};
+        (@cell B) => {Some(Stone::Black)};
+        (@cell W) => {Some(Stone::White)};
+        ($([$($cell:tt)*])*) => {
+            GoBoard(Grid::from_2d_array([$([$(go!(@cell $cell)),*]),*]))
+        };
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = go![
+            [. B . .]
+            [B W . .]
+            [. B . .]
+        ];
+        let expected = go![
+            [. B . .]
+            [B . B .]
+            [. B . .]
+        ];
+        let board = place_stone(&board, (2, 1), Stone::Black).unwrap();
+        assert_eq!(board, expected);
+        assert_eq!(
+            place_stone(&board, (1, 1), Stone::White),
+            Err(PlacementError::Suicide)
+        );
+    }
+
+    #[test]
+    fn test_capture_sequence() {
+        let mut board = GoBoard::new(4, 4);
+        let moves = [
+            ((0, 0), Stone::White),
+            ((1, 0), Stone::Black),
+            ((0, 1), Stone::White),
+            ((1, 1), Stone::Black),
+            ((3, 3), Stone::White),
+            // Captures both white stones in the corner.
+            ((0, 2), Stone::Black),
+        ];
+        for (position, stone) in moves {
+            board = place_stone(&board, position, stone).unwrap();
+        }
+        let expected = go![
+            [. B . .]
+            [. B . .]
+            [B . . .]
+            [. . . W]
+        ];
+        assert_eq!(board, expected);
+    }
+
+    #[rstest]
+    #[case::single_stone(
+        go![
+            [. W B .]
+            [W B W .]
+            [. . . .]
+        ],
+        (3, 0),
+        Stone::White,
+        go![
+            [. W . W]
+            [W B W .]
+            [. . . .]
+        ],
+    )]
+    #[case::edge_group(
+        go![
+            [. B B W]
+            [. W W .]
+        ],
+        (0, 0),
+        Stone::White,
+        go![
+            [W . . W]
+            [. W W .]
+        ],
+    )]
+    #[case::group(
+        go![
+            [. W W .]
+            [W B B W]
+            [. W . .]
+        ],
+        (2, 2),
+        Stone::White,
+        go![
+            [. W W .]
+            [W . . W]
+            [. W W .]
+        ],
+    )]
+    #[case::two_groups(
+        go![
+            [B W . W B]
+            [. B . B .]
+        ],
+        (2, 0),
+        Stone::Black,
+        go![
+            [B . B . B]
+            [. B . B .]
+        ],
+    )]
+    // Filling in the corner would be suicide, except that it captures both white stones.
+    #[case::capture_instead_of_suicide(
+        go![
+            [. W B]
+            [W B .]
+        ],
+        (0, 0),
+        Stone::Black,
+        go![
+            [B . B]
+            [. B .]
+        ],
+    )]
+    fn test_place_stone(
+        #[case] board: GoBoard,
+        #[case] position: CellIndex,
+        #[case] stone: Stone,
+        #[case] expected: GoBoard,
+    ) {
+        assert_eq!(place_stone(&board, position, stone), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::occupied_own(go![[B . .]], (0, 0), Stone::Black, PlacementError::Occupied)]
+    #[case::occupied_opponent(go![[B . .]], (0, 0), Stone::White, PlacementError::Occupied)]
+    #[case::suicide_single(go![[. B] [B .]], (0, 0), Stone::White, PlacementError::Suicide)]
+    // The white stone would connect to a group with no other liberties.
+    #[case::suicide_group(
+        go![
+            [. W B]
+            [W B .]
+            [B . .]
+        ],
+        (0, 0),
+        Stone::White,
+        PlacementError::Suicide,
+    )]
+    #[case::full_board(go![[.]], (0, 0), Stone::Black, PlacementError::Suicide)]
+    fn test_illegal_placement(
+        #[case] board: GoBoard,
+        #[case] position: CellIndex,
+        #[case] stone: Stone,
+        #[case] expected: PlacementError,
+    ) {
+        assert_eq!(place_stone(&board, position, stone), Err(expected));
+    }
+
+    #[test]
+    fn test_ko() {
+        let before_capture = go![
+            [. B W .]
+            [B W . W]
+            [. B W .]
+        ];
+        let after_capture = place_stone(&before_capture, (2, 1), Stone::Black).unwrap();
+        assert_eq!(
+            after_capture,
+            go![
+                [. B W .]
+                [B . B W]
+                [. B W .]
+            ]
+        );
+        // Recapturing immediately would recreate the position from before black's capture.
+        assert_eq!(
+            place_stone_checking_ko(&after_capture, &before_capture, (1, 1), Stone::White),
+            Err(PlacementError::Ko)
+        );
+        // Without knowing the previous position, the recapture looks legal.
+        assert_eq!(
+            place_stone(&after_capture, (1, 1), Stone::White),
+            Ok(before_capture.clone())
+        );
+        // Playing elsewhere is fine, and so is recapturing after the position has changed.
+        let elsewhere =
+            place_stone_checking_ko(&after_capture, &before_capture, (3, 2), Stone::White).unwrap();
+        let reply =
+            place_stone_checking_ko(&elsewhere, &after_capture, (0, 0), Stone::Black).unwrap();
+        assert!(place_stone_checking_ko(&reply, &elsewhere, (1, 1), Stone::White).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (3, 0) out of bounds for dimensions (3, 2)")]
+    fn test_place_stone_out_of_bounds() {
+        let _ = place_stone(&GoBoard::new(3, 2), (3, 0), Stone::Black);
+    }
+
+    #[rstest]
+    #[case::endgame(
+        go![
+            [. B W . .]
+            [B B W . .]
+            [. B W W W]
+            [B B W . .]
+            [. B W . .]
+        ],
+        (3, 8),
+    )]
+    // The region in the middle touches both colors, so it belongs to nobody.
+    #[case::neutral(
+        go![
+            [. B . W .]
+            [B B . W W]
+            [. B . W .]
+        ],
+        (2, 2),
+    )]
+    #[case::empty_board(GoBoard::new(3, 3), (0, 0))]
+    #[case::single_stone(go![[. . B] [. . .]], (5, 0))]
+    #[case::no_empty_points(go![[B W] [W B]], (0, 0))]
+    fn test_count_territory(#[case] board: GoBoard, #[case] expected: (usize, usize)) {
+        assert_eq!(count_territory(&board), expected);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        assert_eq!(count_territory(&GoBoard::new(width, height)), (0, 0));
+    }
+}
@@ -1,16 +1,24 @@
 //! Helper module that provides a 2D list type.
 
-use itertools::Itertools;
-use std::fmt::Debug;
-use std::ops::{Index, IndexMut};
+use itertools::{Either, Itertools};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::iter;
+use std::ops::{Add, Index, IndexMut, Mul};
+use std::str::FromStr;
+
+/// A list of `(x, y)` indices into a grid.
+type IndexList = Vec<(usize, usize)>;
 
 /// A 2D list.
 ///
 /// Indices are `(x, y)` tuples, with `(0, 0)` signifying the top-left element.
 ///
 /// Data is stored in row-major order,
-/// and all iteration over the grid is in row-major order.
-#[derive(PartialEq, Eq)]
+/// and all iteration over the grid is in row-major order,
+/// except for the dedicated traversals ([`Grid::spiral_iter`] and [`Grid::zigzag_iter`]).
+#[derive(Clone, PartialEq, Eq)]
 pub struct Grid<T> {
     /// The elements of the grid, stored contiguously in a 1D `Vec`.
     data: Vec<T>,
@@ -20,6 +28,59 @@ pub struct Grid<T> {
     height: usize,
 }
 
+/// The `(dx, dy)` offsets from an element to its orthogonal neighbors, in row-major order.
+#[rustfmt::skip]
+pub(crate) const ORTHOGONAL_OFFSETS: [(i32, i32); 4] = [
+              (0, -1),
+    (-1,  0),          (1,  0),
+              (0,  1),
+];
+
+/// The `(dx, dy)` offsets from an element to its orthogonal and diagonal neighbors,
+/// in row-major order.
+#[rustfmt::skip]
+pub(crate) const ALL_OFFSETS: [(i32, i32); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1,  0),          (1,  0),
+    (-1,  1), (0,  1), (1,  1),
+];
+
+/// The reason a grid couldn't be constructed from a flat `Vec`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FlatVecError {
+    /// The number of elements isn't the product of the dimensions.
+    LengthMismatch {
+        /// The number of elements a grid with the given dimensions has.
+        expected: usize,
+        /// The number of elements given.
+        actual: usize,
+    },
+}
+
+/// The reason a grid couldn't be parsed,
+/// with positions given as grid coordinates (so empty lines aren't counted).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseGridError<E> {
+    /// The element at the given position couldn't be parsed.
+    InvalidElement {
+        /// The x-coordinate of the element.
+        x: usize,
+        /// The y-coordinate of the element.
+        y: usize,
+        /// The error from parsing the element.
+        error: E,
+    },
+    /// The row at the given position has a different number of elements than the first row.
+    RaggedRow {
+        /// The y-coordinate of the row.
+        y: usize,
+        /// The number of elements in the first row.
+        expected: usize,
+        /// The number of elements in the row.
+        actual: usize,
+    },
+}
+
 impl<T: Clone> Grid<T> {
     /// Constructs a grid filled with the given value and dimensions.
     pub fn filled(value: T, dimensions: (usize, usize)) -> Grid<T> {
@@ -30,6 +91,118 @@ impl<T: Clone> Grid<T> {
             height,
         }
     }
+
+    /// Surrounds the grid with a border of the given value and thickness,
+    /// so that the element at `(x, y)` ends up at `(x + thickness, y + thickness)`.
+    pub fn pad(self, value: T, thickness: usize) -> Grid<T> {
+        let width = self.width + 2 * thickness;
+        let height = self.height + 2 * thickness;
+        let mut data = Vec::with_capacity(width * height);
+        data.extend(iter::repeat_n(value.clone(), thickness * width));
+        let mut elements = self.data.into_iter();
+        for _ in 0..self.height {
+            data.extend(iter::repeat_n(value.clone(), thickness));
+            data.extend(elements.by_ref().take(self.width));
+            data.extend(iter::repeat_n(value.clone(), thickness));
+        }
+        data.extend(iter::repeat_n(value, thickness * width));
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the grid with its rows and columns swapped,
+    /// so that the element at `(x, y)` ends up at `(y, x)`.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid {
+            data: self.columns().flatten().cloned().collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// Returns the rectangular part of the grid with the given top-left corner and dimensions,
+    /// containing the elements with x-coordinates `x..x + width` and y-coordinates `y..y + height`.
+    ///
+    /// Panics if the rectangle doesn't fit within the grid.
+    pub fn subgrid(&self, x: usize, y: usize, width: usize, height: usize) -> Grid<T> {
+        let fits = |start: usize, len: usize, max: usize| {
+            start.checked_add(len).is_some_and(|end| end <= max)
+        };
+        assert!(
+            fits(x, width, self.width) && fits(y, height, self.height),
+            "subgrid at ({x}, {y}) with dimensions ({width}, {height}) out of bounds for dimensions ({}, {})",
+            self.width,
+            self.height
+        );
+        Grid {
+            data: self
+                .rows()
+                .skip(y)
+                .take(height)
+                .flat_map(|row| &row[x..x + width])
+                .cloned()
+                .collect(),
+            width,
+            height,
+        }
+    }
+
+    /// Returns an iterator over all the `w×h` rectangular parts of the grid, in row-major order
+    /// of their top-left corners, like `slice::windows` in 2D.
+    ///
+    /// There are no windows if the window is wider or taller than the grid.
+    pub fn windows(&self, w: usize, h: usize) -> impl Iterator<Item = Grid<T>> + '_ {
+        let x_count = (self.width + 1).saturating_sub(w);
+        let y_count = (self.height + 1).saturating_sub(h);
+        (0..y_count)
+            .cartesian_product(0..x_count)
+            .map(move |(y, x)| self.subgrid(x, y, w, h))
+    }
+
+    /// Returns the grid mirrored left to right,
+    /// so that the element at `(x, y)` ends up at `(width - 1 - x, y)`.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        Grid {
+            data: self
+                .rows()
+                .flat_map(|row| row.iter().rev())
+                .cloned()
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns the grid mirrored top to bottom,
+    /// so that the element at `(x, y)` ends up at `(x, height - 1 - y)`.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        Grid {
+            data: self.rows().rev().flatten().cloned().collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns the grid rotated 90 degrees clockwise,
+    /// so that the element at `(x, y)` ends up at `(height - 1 - y, x)`.
+    pub fn rotate_90_clockwise(&self) -> Grid<T> {
+        self.transpose().flip_horizontal()
+    }
+
+    /// Returns the grid rotated 90 degrees counterclockwise,
+    /// so that the element at `(x, y)` ends up at `(y, width - 1 - x)`.
+    pub fn rotate_90_counterclockwise(&self) -> Grid<T> {
+        self.transpose().flip_vertical()
+    }
+
+    /// Returns the grid rotated 180 degrees,
+    /// so that the element at `(x, y)` ends up at `(width - 1 - x, height - 1 - y)`.
+    pub fn rotate_180(&self) -> Grid<T> {
+        self.rotate_90_clockwise().rotate_90_clockwise()
+    }
 }
 
 impl<T> Grid<T> {
@@ -42,6 +215,58 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Constructs a grid with the given dimensions,
+    /// where the element at each `(x, y)` is `f(x, y)`, with `f` called in row-major order.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                data.push(f(x, y));
+            }
+        }
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Constructs a grid with the given dimensions from its elements in row-major order,
+    /// or returns an error if the number of elements doesn't match the dimensions.
+    pub fn from_flat_vec(
+        data: Vec<T>,
+        width: usize,
+        height: usize,
+    ) -> Result<Grid<T>, FlatVecError> {
+        let expected = width * height;
+        if data.len() != expected {
+            return Err(FlatVecError::LengthMismatch {
+                expected,
+                actual: data.len(),
+            });
+        }
+        Ok(Grid {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Consumes the grid, returning its elements in row-major order, its width, and its height.
+    pub fn into_flat_vec(self) -> (Vec<T>, usize, usize) {
+        (self.data, self.width, self.height)
+    }
+
+    /// Returns the grid's elements in row-major order.
+    pub fn as_flat_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Returns the grid's elements in row-major order, for modification in place.
+    pub fn as_flat_slice_mut(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
     /// The horizontal size of the grid.
     pub fn width(&self) -> usize {
         self.width
@@ -76,6 +301,25 @@ impl<T> Grid<T> {
         Some(&mut self.data[index])
     }
 
+    /// Exchanges the elements with the given indices.
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self, a: impl GridIndex, b: impl GridIndex) {
+        let a = self.convert_index_to_1d(a).unwrap();
+        let b = self.convert_index_to_1d(b).unwrap();
+        self.data.swap(a, b);
+    }
+
+    /// Returns an iterator over the elements of the grid.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    /// Returns an iterator over mutable references to the elements of the grid.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.data.iter_mut()
+    }
+
     /// Returns an `(index, element)` iterator over the grid.
     pub fn enumerate<I: GridIndex>(&self) -> impl Iterator<Item = (I, &T)> {
         self.data
@@ -84,6 +328,189 @@ impl<T> Grid<T> {
             .map(|(index, element)| (I::from_1d_index(index, self.width, self.height), element))
     }
 
+    /// Returns an `(index, element)` iterator over the (up to 4) elements
+    /// orthogonally adjacent to the element with the given index
+    /// (above, left of, right of, and below it), in row-major order,
+    /// skipping the positions that are out of bounds.
+    ///
+    /// Panics if the given index is out of bounds.
+    pub fn orthogonal_neighbors<I: GridIndex>(&self, index: I) -> impl Iterator<Item = (I, &T)> {
+        self.neighbors_at_offsets(index, &ORTHOGONAL_OFFSETS)
+    }
+
+    /// Returns an `(index, element)` iterator over the (up to 8) elements
+    /// orthogonally or diagonally adjacent to the element with the given index,
+    /// in row-major order, skipping the positions that are out of bounds.
+    ///
+    /// Panics if the given index is out of bounds.
+    pub fn all_neighbors<I: GridIndex>(&self, index: I) -> impl Iterator<Item = (I, &T)> {
+        self.neighbors_at_offsets(index, &ALL_OFFSETS)
+    }
+
+    /// Returns an `(index, element)` iterator over the elements at the given `(dx, dy)` offsets
+    /// from the element with the given index, skipping the positions that are out of bounds.
+    ///
+    /// Panics if the given index is out of bounds.
+    fn neighbors_at_offsets<I: GridIndex>(
+        &self,
+        index: I,
+        offsets: &'static [(i32, i32)],
+    ) -> impl Iterator<Item = (I, &T)> {
+        let index = self.convert_index_to_1d(index).unwrap();
+        let (width, height) = self.dimensions();
+        let (x, y) = (index % width, index / width);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx as isize).filter(|&x| x < width)?;
+            let y = y.checked_add_signed(dy as isize).filter(|&y| y < height)?;
+            let index = y * width + x;
+            Some((I::from_1d_index(index, width, height), &self.data[index]))
+        })
+    }
+
+    /// Returns the `(index, element)` pairs for the elements satisfying the given predicate
+    /// that can be reached from the element with the given index by moving orthogonally
+    /// through such elements, in breadth-first order starting from the given index.
+    ///
+    /// Returns an empty `Vec` if the given index is out of bounds,
+    /// or the element there doesn't satisfy the predicate.
+    pub fn flood_fill<I: GridIndex>(
+        &self,
+        start: I,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Vec<(I, &T)> {
+        self.flood_fill_connected(start, predicate, false)
+    }
+
+    /// Returns the `(index, element)` pairs reached by a flood fill from the given index,
+    /// as in [`Grid::flood_fill`], also moving diagonally if `diagonal` is true.
+    pub(crate) fn flood_fill_connected<I: GridIndex>(
+        &self,
+        start: I,
+        predicate: impl Fn(&T) -> bool,
+        diagonal: bool,
+    ) -> Vec<(I, &T)> {
+        let Some(start) = self
+            .convert_index_to_1d(start)
+            .ok()
+            .filter(|&start| predicate(&self.data[start]))
+        else {
+            return vec![];
+        };
+        let mut visited = vec![false; self.data.len()];
+        self.fill_region(start, &predicate, diagonal, &mut visited)
+            .into_iter()
+            .map(|index| self.enumerate_1d(index))
+            .collect()
+    }
+
+    /// Returns the components of the graph formed by the elements satisfying the given predicate,
+    /// with edges between orthogonally adjacent elements
+    /// (or also diagonally adjacent elements, if `diagonal` is true),
+    /// as lists of `(index, element)` pairs.
+    ///
+    /// The components are in the order of their first elements in row-major order,
+    /// and the elements of each component are in breadth-first order starting from its first element.
+    pub fn connected_components<I: GridIndex>(
+        &self,
+        predicate: impl Fn(&T) -> bool,
+        diagonal: bool,
+    ) -> Vec<Vec<(I, &T)>> {
+        let mut visited = vec![false; self.data.len()];
+        let mut result = vec![];
+        for (start, element) in self.data.iter().enumerate() {
+            if visited[start] || !predicate(element) {
+                continue;
+            }
+            let component = self.fill_region(start, &predicate, diagonal, &mut visited);
+            result.push(
+                component
+                    .into_iter()
+                    .map(|index| self.enumerate_1d(index))
+                    .collect(),
+            );
+        }
+        result
+    }
+
+    /// Visits the unvisited elements satisfying the given predicate that are connected
+    /// (as in [`Grid::connected_components`]) to the element with the given 1D index,
+    /// marking them as visited and returning their 1D indices in breadth-first order.
+    ///
+    /// The element at the given index should satisfy the predicate and not have been visited yet.
+    fn fill_region(
+        &self,
+        start: usize,
+        predicate: &impl Fn(&T) -> bool,
+        diagonal: bool,
+        visited: &mut [bool],
+    ) -> Vec<usize> {
+        let width = self.width;
+        visited[start] = true;
+        let mut queue = VecDeque::from([start]);
+        let mut result = vec![];
+        while let Some(index) = queue.pop_front() {
+            result.push(index);
+            let position = (index % width, index / width);
+            let offsets: &[_] = if diagonal {
+                &ALL_OFFSETS
+            } else {
+                &ORTHOGONAL_OFFSETS
+            };
+            for ((x, y), element) in self.neighbors_at_offsets::<(usize, usize)>(position, offsets)
+            {
+                let neighbor = y * width + x;
+                if !visited[neighbor] && predicate(element) {
+                    visited[neighbor] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns an `(index, element)` iterator over the elements on the edges of the grid
+    /// (in the top or bottom row, or in the leftmost or rightmost column), in row-major order.
+    pub fn border_cells<I: GridIndex>(&self) -> impl Iterator<Item = (I, &T)> {
+        let (width, height) = self.dimensions();
+        (0..height)
+            .flat_map(move |y| {
+                // Only the leftmost and rightmost elements of the rows in between.
+                let step = if y == 0 || y + 1 == height {
+                    1
+                } else {
+                    width.saturating_sub(1).max(1)
+                };
+                (0..width).step_by(step).map(move |x| y * width + x)
+            })
+            .map(move |index| (I::from_1d_index(index, width, height), &self.data[index]))
+    }
+
+    /// Returns an `(index, element)` iterator over the grid, with mutable references to the elements.
+    pub fn enumerate_mut<I: GridIndex>(&mut self) -> impl Iterator<Item = (I, &mut T)> {
+        let (width, height) = self.dimensions();
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(index, element)| (I::from_1d_index(index, width, height), element))
+    }
+
+    /// Returns an iterator over the rows of the grid as slices, from top to bottom.
+    ///
+    /// Grids without any elements have no rows, even if their height isn't zero.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of 0, but there's no data to chunk in that case anyway.
+        self.data.chunks(self.width.max(1))
+    }
+
+    /// Returns an iterator over the columns of the grid, from left to right,
+    /// where each column is an iterator over its elements from top to bottom.
+    ///
+    /// Grids without any elements have no columns, even if their width isn't zero.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let column_count = if self.height == 0 { 0 } else { self.width };
+        (0..column_count).map(move |x| self.data.iter().skip(x).step_by(self.width))
+    }
+
     /// Transforms the grid by applying `f` to each element.
     pub fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
         Grid {
@@ -92,136 +519,809 @@ impl<T> Grid<T> {
             height: self.height,
         }
     }
-}
 
-impl<T, I: GridIndex> Index<I> for Grid<T> {
-    type Output = T;
-    fn index(&self, index: I) -> &Self::Output {
-        let index = self.convert_index_to_1d(index).unwrap();
-        &self.data[index]
+    /// Transforms the grid by applying the fallible `f` to each element in row-major order,
+    /// stopping at and returning the first error.
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Grid<U>, E> {
+        Ok(Grid {
+            data: self.data.into_iter().map(f).collect::<Result<_, _>>()?,
+            width: self.width,
+            height: self.height,
+        })
     }
-}
 
-impl<T, I: GridIndex> IndexMut<I> for Grid<T> {
-    fn index_mut(&mut self, index: I) -> &mut Self::Output {
-        let index = self.convert_index_to_1d(index).unwrap();
-        &mut self.data[index]
+    /// Combines the grid with another grid of the same dimensions
+    /// by applying `f` to each pair of corresponding elements.
+    ///
+    /// Panics if the grids have different dimensions.
+    pub fn zip_with<U, V>(self, other: Grid<U>, mut f: impl FnMut(T, U) -> V) -> Grid<V> {
+        assert_eq!(
+            self.dimensions(),
+            other.dimensions(),
+            "cannot zip grids with different dimensions"
+        );
+        Grid {
+            data: self
+                .data
+                .into_iter()
+                .zip(other.data)
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
     }
-}
 
-// Debug formatting: a grid is formatted like a 2D array.
-// Each grid row is meant to take up exactly one line.
-// Grids with a width or height of 0 are special-cased to make their dimensions clear.
-impl<T: Debug> Debug for Grid<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.width == 0 || self.height == 0 {
-            return write!(f, "<empty grid: ({}, {})>", self.width, self.height);
+    /// Pairs up the corresponding elements of the grid and another grid of the same dimensions.
+    ///
+    /// Panics if the grids have different dimensions.
+    pub fn zip<U>(self, other: Grid<U>) -> Grid<(T, U)> {
+        self.zip_with(other, |a, b| (a, b))
+    }
+
+    /// Returns a grid with the same dimensions,
+    /// where each element is the result of applying `stencil` centered at the corresponding element:
+    /// `combine` is applied to each stencil weight and the element under it,
+    /// and the results are folded together with `reduce`, starting from `identity`.
+    ///
+    /// The center of the stencil is at `(stencil.width() / 2, stencil.height() / 2)`,
+    /// and stencil weights that fall outside the grid are skipped (as if the grid were padded
+    /// with elements contributing `identity`).
+    pub fn apply_stencil<W: Copy, U: Clone>(
+        &self,
+        stencil: &Grid<W>,
+        combine: impl Fn(W, &T) -> U,
+        reduce: impl Fn(U, U) -> U,
+        identity: U,
+    ) -> Grid<U> {
+        let (center_x, center_y) = (stencil.width / 2, stencil.height / 2);
+        let data = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let mut result = identity.clone();
+                for ((stencil_x, stencil_y), &weight) in stencil.enumerate::<(usize, usize)>() {
+                    let neighbor = (x + stencil_x)
+                        .checked_sub(center_x)
+                        .zip((y + stencil_y).checked_sub(center_y));
+                    if let Some(element) = neighbor.and_then(|neighbor| self.get(neighbor)) {
+                        result = reduce(result, combine(weight, element));
+                    }
+                }
+                result
+            })
+            .collect();
+        Grid {
+            data,
+            width: self.width,
+            height: self.height,
         }
-        write!(
-            f,
-            "[\n{}\n]",
-            (0..self.height)
-                .map(|y| format!(
-                    "    [{}],",
-                    (0..self.width)
-                        .map(|x| {
-                            let element = &self[(x, y)];
-                            format!("{element:?}")
-                        })
-                        .join(", ")
-                ))
-                .join("\n")
+    }
+
+    /// Returns the convolution of the grid with the given kernel, with zero padding.
+    ///
+    /// The kernel is rotated by 180 degrees and then applied as in [`Grid::apply_stencil`],
+    /// multiplying each element by the weight over it and adding up the results.
+    /// For kernels that are symmetric under this rotation, such as the discrete Laplacian,
+    /// the rotation makes no difference.
+    pub fn convolve<W: Copy>(&self, kernel: &Grid<W>) -> Grid<T::Output>
+    where
+        T: Clone + Mul<W>,
+        T::Output: Add<Output = T::Output> + Default + Clone,
+    {
+        let rotated = Grid {
+            data: kernel.data.iter().rev().copied().collect(),
+            width: kernel.width,
+            height: kernel.height,
+        };
+        self.apply_stencil(
+            &rotated,
+            |weight, element| element.clone() * weight,
+            |a, b| a + b,
+            T::Output::default(),
         )
     }
-}
 
-use index::GridIndex;
+    /// Returns the grid obtained by applying the given kernel centered at each element,
+    /// multiplying the elements under the kernel by the corresponding weights and adding up the results,
+    /// and then passing the sum to `f`.
+    ///
+    /// Unlike with [`Grid::convolve`], the kernel isn't rotated, so `kernel[y][x]` is the weight
+    /// for the element at offset `(x - KW / 2, y - KH / 2)` from the center.
+    /// Elements outside the grid count as zero.
+    pub fn apply_kernel<const KW: usize, const KH: usize, U>(
+        &self,
+        kernel: [[f64; KW]; KH],
+        f: impl Fn(f64) -> U,
+    ) -> Grid<U>
+    where
+        T: Into<f64> + Copy,
+    {
+        self.apply_stencil(
+            &Grid::from_2d_array(kernel),
+            |weight, &element| weight * element.into(),
+            |a, b| a + b,
+            0.0,
+        )
+        .map(f)
+    }
 
-/// Module defining a sealed trait for grid indices.
-mod index {
-    use std::fmt::{Debug, Display};
+    /// Returns the number of times each distinct element appears in the grid.
+    pub fn histogram(&self) -> HashMap<T, usize>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let mut result = HashMap::new();
+        for element in &self.data {
+            *result.entry(element.clone()).or_insert(0) += 1;
+        }
+        result
+    }
 
-    /// Trait for types that can be used as an index for a grid.
-    pub trait GridIndex {
-        /// Error type returned for unsuccessful index conversions.
-        type Err: Debug;
+    /// Returns the index of the first element (in row-major order) satisfying the given predicate,
+    /// or None if there isn't one.
+    pub fn position<I: GridIndex>(&self, predicate: impl Fn(&T) -> bool) -> Option<I> {
+        let index = self.data.iter().position(predicate)?;
+        Some(I::from_1d_index(index, self.width, self.height))
+    }
 
-        /// Converts a 2D index into a 1D index for the data `Vec` of a grid
-        /// based on the given dimensions, if possible.
-        ///
-        /// If successful, the returned 1D index must be in bounds.
-        fn to_1d_index(self, width: usize, height: usize) -> Result<usize, Self::Err>;
+    /// Returns the first element (in row-major order) satisfying the given predicate,
+    /// or None if there isn't one.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<&T> {
+        self.data.iter().find(|element| predicate(element))
+    }
 
-        /// Converts a 1D index for the data `Vec` of a grid
-        /// into a 2D index based on the given dimensions.
-        ///
-        /// Will only be called with 1D indices that are in bounds.
-        fn from_1d_index(index: usize, width: usize, height: usize) -> Self;
+    /// The number of elements satisfying the given predicate.
+    pub fn count(&self, f: impl Fn(&T) -> bool) -> usize {
+        self.data.iter().filter(|element| f(element)).count()
     }
 
-    impl<T: Copy + Display + TryFrom<usize, Error = E> + TryInto<usize>, E: Debug> GridIndex
-        for (T, T)
-    {
-        type Err = IndexError<T>;
+    /// Returns whether any element satisfies the given predicate.
+    pub fn any(&self, f: impl Fn(&T) -> bool) -> bool {
+        self.data.iter().any(f)
+    }
 
-        fn to_1d_index(self, width: usize, height: usize) -> Result<usize, Self::Err> {
-            let (x, y) = self;
-            let index_error = || IndexError {
-                x,
-                y,
-                width,
-                height,
-            };
-            // If the coordinates cannot fit in a `usize`,
-            // then they are definitely out of bounds (either negative or too large).
-            let x: usize = x.try_into().map_err(|_| index_error())?;
-            let y: usize = y.try_into().map_err(|_| index_error())?;
-            if (0..width).contains(&x) && (0..height).contains(&y) {
-                Ok(y * width + x)
+    /// Returns whether every element satisfies the given predicate.
+    ///
+    /// Grids without elements always return true.
+    pub fn all(&self, f: impl Fn(&T) -> bool) -> bool {
+        self.data.iter().all(f)
+    }
+
+    /// Returns whether no element satisfies the given predicate.
+    pub fn none(&self, f: impl Fn(&T) -> bool) -> bool {
+        !self.any(f)
+    }
+
+    /// Returns the indices of the elements satisfying the given predicate
+    /// and the indices of the other elements, in row-major order.
+    pub fn partition(&self, f: impl Fn(&T) -> bool) -> (IndexList, IndexList) {
+        self.enumerate::<(usize, usize)>()
+            .partition_map(|(index, element)| {
+                if f(element) {
+                    Either::Left(index)
+                } else {
+                    Either::Right(index)
+                }
+            })
+    }
+
+    /// Splits the grid into the rows before row `y` and the rows from row `y` onwards,
+    /// or returns None if `y` isn't the index of a row.
+    pub fn split_rows(mut self, y: usize) -> Option<(Grid<T>, Grid<T>)> {
+        if y >= self.height {
+            return None;
+        }
+        let bottom = self.data.split_off(y * self.width);
+        let top = Grid {
+            data: self.data,
+            width: self.width,
+            height: y,
+        };
+        let bottom = Grid {
+            data: bottom,
+            width: self.width,
+            height: self.height - y,
+        };
+        Some((top, bottom))
+    }
+
+    /// Splits the grid into the columns before column `x` and the columns from column `x` onwards,
+    /// or returns None if `x` isn't the index of a column.
+    pub fn split_cols(self, x: usize) -> Option<(Grid<T>, Grid<T>)> {
+        if x >= self.width {
+            return None;
+        }
+        let mut left = Vec::with_capacity(x * self.height);
+        let mut right = Vec::with_capacity((self.width - x) * self.height);
+        for (index, element) in self.data.into_iter().enumerate() {
+            if index % self.width < x {
+                left.push(element);
             } else {
-                Err(index_error())
+                right.push(element);
             }
         }
+        let left = Grid {
+            data: left,
+            width: x,
+            height: self.height,
+        };
+        let right = Grid {
+            data: right,
+            width: self.width - x,
+            height: self.height,
+        };
+        Some((left, right))
+    }
 
-        fn from_1d_index(index: usize, width: usize, height: usize) -> Self {
-            debug_assert!(
-                index < width * height,
-                "index {index} out of bounds for dimensions {width} * {height}"
-            );
-            let (x, y) = (index % width, index / width);
-            (x.try_into().unwrap(), y.try_into().unwrap())
+    /// Joins the grid with another grid of the same height, placed to its right.
+    ///
+    /// Panics if the grids have different heights.
+    pub fn concat_horizontal(self, other: Grid<T>) -> Grid<T> {
+        assert_eq!(
+            self.height, other.height,
+            "cannot concatenate grids with different heights horizontally"
+        );
+        let width = self.width + other.width;
+        let mut data = Vec::with_capacity(width * self.height);
+        let mut left = self.data.into_iter();
+        let mut right = other.data.into_iter();
+        for _ in 0..self.height {
+            data.extend(left.by_ref().take(self.width));
+            data.extend(right.by_ref().take(other.width));
+        }
+        Grid {
+            data,
+            width,
+            height: self.height,
         }
     }
 
-    /// An error for out-of-bounds indices.
-    pub struct IndexError<T> {
-        /// x-coordinate of the out-of-bounds index.
-        x: T,
-        /// y-coordinate of the out-of-bounds index.
-        y: T,
-        /// Width of the grid that's being indexed.
-        width: usize,
-        /// Height of the grid that's being indexed.
-        height: usize,
+    /// Joins the grid with another grid of the same width, placed below it.
+    ///
+    /// Panics if the grids have different widths.
+    pub fn concat_vertical(self, other: Grid<T>) -> Grid<T> {
+        assert_eq!(
+            self.width, other.width,
+            "cannot concatenate grids with different widths vertically"
+        );
+        let mut data = self.data;
+        data.extend(other.data);
+        Grid {
+            data,
+            width: self.width,
+            height: self.height + other.height,
+        }
     }
 
-    impl<T: Display> Debug for IndexError<T> {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(
-                f,
-                "index ({}, {}) out of bounds for dimensions ({}, {})",
-                self.x, self.y, self.width, self.height
-            )
+    /// Returns views of the rows before row `y` and the rows from row `y` onwards,
+    /// or None if `y` isn't the index of a row.
+    pub fn split_rows_ref(&self, y: usize) -> Option<(GridView<'_, T>, GridView<'_, T>)> {
+        if y >= self.height {
+            return None;
         }
+        let (top, bottom) = self.data.split_at(y * self.width);
+        let top = GridView {
+            data: top,
+            width: self.width,
+            height: y,
+        };
+        let bottom = GridView {
+            data: bottom,
+            width: self.width,
+            height: self.height - y,
+        };
+        Some((top, bottom))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::grid::*;
-    use indoc::indoc;
-    use pretty_assertions::{assert_eq, assert_str_eq};
-    use rstest::rstest;
+    /// Returns an iterator over the top-left-to-bottom-right diagonals of the grid,
+    /// from the one starting at the bottom-left corner to the one starting at the top-right corner,
+    /// where each diagonal is an `(index, element)` iterator from its top-left end.
+    pub fn diagonals<I: GridIndex>(&self) -> impl Iterator<Item = impl Iterator<Item = (I, &T)>> {
+        let (width, height) = self.dimensions();
+        (0..self.diagonal_count()).map(move |i| {
+            let (start_x, start_y) = if i < height {
+                (0, height - 1 - i)
+            } else {
+                (i + 1 - height, 0)
+            };
+            let len = (width - start_x).min(height - start_y);
+            (0..len).map(move |k| self.enumerate_1d((start_y + k) * width + start_x + k))
+        })
+    }
+
+    /// Returns an iterator over the top-right-to-bottom-left diagonals of the grid,
+    /// from the one at the top-left corner to the one at the bottom-right corner,
+    /// where each diagonal is an `(index, element)` iterator from its top-right end.
+    pub fn anti_diagonals<I: GridIndex>(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = (I, &T)>> {
+        let (width, height) = self.dimensions();
+        (0..self.diagonal_count()).map(move |i| {
+            let start_y = i.saturating_sub(width - 1);
+            let start_x = i - start_y;
+            let len = (start_x + 1).min(height - start_y);
+            (0..len).map(move |k| self.enumerate_1d((start_y + k) * width + start_x - k))
+        })
+    }
+
+    /// The number of diagonals in either direction.
+    fn diagonal_count(&self) -> usize {
+        if self.width == 0 || self.height == 0 {
+            0
+        } else {
+            self.width + self.height - 1
+        }
+    }
+
+    /// Returns the `(index, element)` pair for the given 1D index for the grid's data `Vec`.
+    fn enumerate_1d<I: GridIndex>(&self, index: usize) -> (I, &T) {
+        (
+            I::from_1d_index(index, self.width, self.height),
+            &self.data[index],
+        )
+    }
+
+    /// Returns an `(x, y, element)` iterator over the grid in clockwise spiral order,
+    /// starting from the top-left corner and moving right.
+    pub fn spiral_iter(&self) -> SpiralIter<'_, T> {
+        SpiralIter {
+            grid: self,
+            left: 0,
+            top: 0,
+            right: self.width,
+            bottom: self.height,
+            x: 0,
+            y: 0,
+            direction: SpiralDirection::Right,
+        }
+    }
+
+    /// Returns an `(x, y, element)` iterator over the grid in zigzag order,
+    /// as used in JPEG encoding: diagonal by diagonal, starting from the top-left corner,
+    /// with the direction along the diagonals alternating between down-left and up-right.
+    pub fn zigzag_iter(&self) -> ZigzagIter<'_, T> {
+        ZigzagIter {
+            grid: self,
+            diagonal: 0,
+            x: 0,
+            num_diagonals: self.diagonal_count(),
+        }
+    }
+}
+
+impl<T> IntoIterator for Grid<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Returns an iterator over the elements of the grid, consuming the grid.
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for Grid<T> {
+    type Output = T;
+    fn index(&self, index: I) -> &Self::Output {
+        let index = self.convert_index_to_1d(index).unwrap();
+        &self.data[index]
+    }
+}
+
+impl<T, I: GridIndex> IndexMut<I> for Grid<T> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let index = self.convert_index_to_1d(index).unwrap();
+        &mut self.data[index]
+    }
+}
+
+/// A borrowed view of consecutive rows of a grid, returned by [`Grid::split_rows_ref`].
+///
+/// Indices are relative to the view, with `(0, 0)` signifying the view's top-left element.
+#[derive(PartialEq, Eq)]
+pub struct GridView<'a, T> {
+    /// The elements of the rows, stored contiguously in row-major order.
+    data: &'a [T],
+    /// The horizontal size of the view.
+    width: usize,
+    /// The vertical size of the view.
+    height: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// The horizontal size of the view.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The vertical size of the view.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// A `(width, height)` tuple describing the size of the view.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns a reference to the element with the given index,
+    /// or None if the index is out of bounds.
+    pub fn get(&self, index: impl GridIndex) -> Option<&'a T> {
+        let index = index.to_1d_index(self.width, self.height).ok()?;
+        Some(&self.data[index])
+    }
+
+    /// Returns an `(index, element)` iterator over the view.
+    pub fn enumerate<I: GridIndex>(&self) -> impl Iterator<Item = (I, &'a T)> {
+        let (width, height) = (self.width, self.height);
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(index, element)| (I::from_1d_index(index, width, height), element))
+    }
+
+    /// Returns a grid of references to the viewed elements.
+    fn to_grid_of_refs(&self) -> Grid<&'a T> {
+        Grid {
+            data: self.data.iter().collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copies the viewed elements into a new grid.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            data: self.data.to_vec(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: I) -> &T {
+        let index = index.to_1d_index(self.width, self.height).unwrap();
+        &self.data[index]
+    }
+}
+
+impl<T: Debug> Debug for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.to_grid_of_refs(), f)
+    }
+}
+
+/// A direction that a [`SpiralIter`] can be moving in.
+#[derive(Clone, Copy, Debug)]
+enum SpiralDirection {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+/// Iterator over a grid in clockwise spiral order, returned by [`Grid::spiral_iter`].
+#[derive(Clone, Debug)]
+pub struct SpiralIter<'a, T> {
+    /// The grid being iterated over.
+    grid: &'a Grid<T>,
+    /// The leftmost column not yet fully visited.
+    left: usize,
+    /// The topmost row not yet fully visited.
+    top: usize,
+    /// One past the rightmost column not yet fully visited.
+    right: usize,
+    /// One past the bottommost row not yet fully visited.
+    bottom: usize,
+    /// The x-coordinate of the next element.
+    x: usize,
+    /// The y-coordinate of the next element.
+    y: usize,
+    /// The direction to move in after the next element.
+    direction: SpiralDirection,
+}
+
+impl<'a, T> Iterator for SpiralIter<'a, T> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left >= self.right || self.top >= self.bottom {
+            return None;
+        }
+        let (x, y) = (self.x, self.y);
+        // At the end of each side, the row or column just finished is removed from the bounds,
+        // and the position moves onto the next side, if there's anything left.
+        match self.direction {
+            SpiralDirection::Right if self.x + 1 < self.right => self.x += 1,
+            SpiralDirection::Right => {
+                self.top += 1;
+                self.direction = SpiralDirection::Down;
+                if self.top < self.bottom {
+                    self.y += 1;
+                }
+            }
+            SpiralDirection::Down if self.y + 1 < self.bottom => self.y += 1,
+            SpiralDirection::Down => {
+                self.right -= 1;
+                self.direction = SpiralDirection::Left;
+                if self.left < self.right {
+                    self.x -= 1;
+                }
+            }
+            SpiralDirection::Left if self.x > self.left => self.x -= 1,
+            SpiralDirection::Left => {
+                self.bottom -= 1;
+                self.direction = SpiralDirection::Up;
+                if self.top < self.bottom {
+                    self.y -= 1;
+                }
+            }
+            SpiralDirection::Up if self.y > self.top => self.y -= 1,
+            SpiralDirection::Up => {
+                self.left += 1;
+                self.direction = SpiralDirection::Right;
+                if self.left < self.right {
+                    self.x += 1;
+                }
+            }
+        }
+        Some((x, y, &self.grid[(x, y)]))
+    }
+}
+
+/// Iterator over a grid in zigzag order, returned by [`Grid::zigzag_iter`].
+#[derive(Clone, Debug)]
+pub struct ZigzagIter<'a, T> {
+    /// The grid being iterated over.
+    grid: &'a Grid<T>,
+    /// The diagonal of the next element: the sum of its coordinates.
+    diagonal: usize,
+    /// The x-coordinate of the next element.
+    x: usize,
+    /// The number of diagonals in the grid.
+    num_diagonals: usize,
+}
+
+impl<T> ZigzagIter<'_, T> {
+    /// The smallest and largest x-coordinates of elements in the given diagonal.
+    fn x_range(&self, diagonal: usize) -> (usize, usize) {
+        let (width, height) = self.grid.dimensions();
+        (diagonal.saturating_sub(height - 1), diagonal.min(width - 1))
+    }
+}
+
+impl<'a, T> Iterator for ZigzagIter<'a, T> {
+    type Item = (usize, usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.diagonal >= self.num_diagonals {
+            return None;
+        }
+        let (x, y) = (self.x, self.diagonal - self.x);
+        // Even diagonals go up and to the right; odd diagonals go down and to the left.
+        let (min_x, max_x) = self.x_range(self.diagonal);
+        let going_right = self.diagonal.is_multiple_of(2);
+        if going_right && self.x < max_x {
+            self.x += 1;
+        } else if !going_right && self.x > min_x {
+            self.x -= 1;
+        } else {
+            self.diagonal += 1;
+            if self.diagonal < self.num_diagonals {
+                let (min_x, max_x) = self.x_range(self.diagonal);
+                self.x = if going_right { max_x } else { min_x };
+            }
+        }
+        Some((x, y, &self.grid[(x, y)]))
+    }
+}
+
+// Debug formatting: a grid is formatted like a 2D array.
+// Each grid row is meant to take up exactly one line.
+// Grids with a width or height of 0 are special-cased to make their dimensions clear.
+impl<T: Debug> Debug for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.width == 0 || self.height == 0 {
+            return write!(f, "<empty grid: ({}, {})>", self.width, self.height);
+        }
+        write!(
+            f,
+            "[\n{}\n]",
+            (0..self.height)
+                .map(|y| format!(
+                    "    [{}],",
+                    (0..self.width)
+                        .map(|x| {
+                            let element = &self[(x, y)];
+                            format!("{element:?}")
+                        })
+                        .join(", ")
+                ))
+                .join("\n")
+        )
+    }
+}
+
+/// Parses a grid with one line per row and elements separated by whitespace,
+/// skipping empty lines.
+///
+/// A string without any elements is parsed as a grid with dimensions `(0, 0)`.
+impl<T: FromStr> FromStr for Grid<T> {
+    type Err = ParseGridError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Grid<T>, Self::Err> {
+        let mut data = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for line in s.lines() {
+            let row_start = data.len();
+            for (x, element) in line.split_whitespace().enumerate() {
+                let element = element
+                    .parse()
+                    .map_err(|error| ParseGridError::InvalidElement {
+                        x,
+                        y: height,
+                        error,
+                    })?;
+                data.push(element);
+            }
+            let row_width = data.len() - row_start;
+            if row_width == 0 {
+                continue;
+            }
+            let expected = *width.get_or_insert(row_width);
+            if row_width != expected {
+                return Err(ParseGridError::RaggedRow {
+                    y: height,
+                    expected,
+                    actual: row_width,
+                });
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            data,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+}
+
+/// Formats the grid as a table, with one line per row and elements separated by spaces,
+/// where each column is as wide as its widest element.
+///
+/// Elements are right-aligned by default,
+/// and can be left-aligned or centered with the `<` and `^` format flags (as in `{:<}`).
+/// Grids without any elements are formatted as an empty string.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self
+            .data
+            .iter()
+            .map(|element| element.to_string())
+            .collect();
+        let column_widths: Vec<usize> = (0..self.width)
+            .map(|x| {
+                cells
+                    .iter()
+                    .skip(x)
+                    .step_by(self.width)
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        for (y, row) in cells.chunks(self.width.max(1)).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, (cell, &width)) in row.iter().zip(&column_widths).enumerate() {
+                if x > 0 {
+                    write!(f, " ")?;
+                }
+                match f.align() {
+                    Some(fmt::Alignment::Left) => write!(f, "{cell:<width$}")?,
+                    Some(fmt::Alignment::Center) => write!(f, "{cell:^width$}")?,
+                    Some(fmt::Alignment::Right) | None => write!(f, "{cell:>width$}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+use index::GridIndex;
+
+/// Module defining a sealed trait for grid indices.
+mod index {
+    use std::fmt::{Debug, Display};
+
+    /// Trait for types that can be used as an index for a grid.
+    pub trait GridIndex {
+        /// Error type returned for unsuccessful index conversions.
+        type Err: Debug;
+
+        /// Converts a 2D index into a 1D index for the data `Vec` of a grid
+        /// based on the given dimensions, if possible.
+        ///
+        /// If successful, the returned 1D index must be in bounds.
+        fn to_1d_index(self, width: usize, height: usize) -> Result<usize, Self::Err>;
+
+        /// Converts a 1D index for the data `Vec` of a grid
+        /// into a 2D index based on the given dimensions.
+        ///
+        /// Will only be called with 1D indices that are in bounds.
+        fn from_1d_index(index: usize, width: usize, height: usize) -> Self;
+    }
+
+    impl<T: Copy + Display + TryFrom<usize, Error = E> + TryInto<usize>, E: Debug> GridIndex
+        for (T, T)
+    {
+        type Err = IndexError<T>;
+
+        fn to_1d_index(self, width: usize, height: usize) -> Result<usize, Self::Err> {
+            let (x, y) = self;
+            let index_error = || IndexError {
+                x,
+                y,
+                width,
+                height,
+            };
+            // If the coordinates cannot fit in a `usize`,
+            // then they are definitely out of bounds (either negative or too large).
+            let x: usize = x.try_into().map_err(|_| index_error())?;
+            let y: usize = y.try_into().map_err(|_| index_error())?;
+            if (0..width).contains(&x) && (0..height).contains(&y) {
+                Ok(y * width + x)
+            } else {
+                Err(index_error())
+            }
+        }
+
+        fn from_1d_index(index: usize, width: usize, height: usize) -> Self {
+            debug_assert!(
+                index < width * height,
+                "index {index} out of bounds for dimensions {width} * {height}"
+            );
+            let (x, y) = (index % width, index / width);
+            (x.try_into().unwrap(), y.try_into().unwrap())
+        }
+    }
+
+    /// An error for out-of-bounds indices.
+    pub struct IndexError<T> {
+        /// x-coordinate of the out-of-bounds index.
+        x: T,
+        /// y-coordinate of the out-of-bounds index.
+        y: T,
+        /// Width of the grid that's being indexed.
+        width: usize,
+        /// Height of the grid that's being indexed.
+        height: usize,
+    }
+
+    impl<T: Display> Debug for IndexError<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "index ({}, {}) out of bounds for dimensions ({}, {})",
+                self.x, self.y, self.width, self.height
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::*;
+    use indoc::indoc;
+    use pretty_assertions::{assert_eq, assert_str_eq};
+    use rstest::rstest;
+    use std::collections::{HashMap, HashSet};
 
     /// Example grid for tests.
     #[rustfmt::skip]
@@ -233,121 +1333,1801 @@ mod tests {
     }
 
     #[test]
-    fn test_equality() {
-        assert_eq!(grid(), grid());
+    fn test_equality() {
+        assert_eq!(grid(), grid());
+    }
+
+    #[test]
+    fn test_inequality_different_shape() {
+        #[rustfmt::skip]
+        let grid_transposed = Grid::from_2d_array([
+            [3, 1],
+            [4, 1],
+            [5, 9],
+        ]);
+        assert_ne!(grid(), grid_transposed);
+    }
+
+    #[test]
+    fn test_inequality_different_values() {
+        #[rustfmt::skip]
+        let grid_doubled = Grid::from_2d_array([
+            [ 6,  2,  8],
+            [ 2, 10, 18],
+        ]);
+        assert_ne!(grid(), grid_doubled);
+    }
+
+    #[test]
+    fn test_filled() {
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 1, 1],
+            [1, 1, 1]
+        ]);
+        assert_eq!(Grid::filled(1, (3, 2)), expected);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [(0, 0), (1, 0), (2, 0)],
+            [(0, 1), (1, 1), (2, 1)],
+            [(0, 2), (1, 2), (2, 2)],
+        ]);
+        assert_eq!(Grid::from_fn(3, 3, |x, y| (x, y)), expected);
+        let altitudes = Grid::from_fn(4, 2, |x, y| x + y);
+        assert_eq!(altitudes, Grid::from_2d_array([[0, 1, 2, 3], [1, 2, 3, 4]]));
+    }
+
+    #[test]
+    fn test_from_fn_checkerboard() {
+        let checkerboard = Grid::from_fn(5, 3, |x, y| if (x + y) % 2 == 0 { '#' } else { '.' });
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            ['#', '.', '#', '.', '#'],
+            ['.', '#', '.', '#', '.'],
+            ['#', '.', '#', '.', '#'],
+        ]);
+        assert_eq!(checkerboard, expected);
+    }
+
+    #[test]
+    fn test_from_fn_order() {
+        let mut calls = vec![];
+        let grid = Grid::from_fn(3, 2, |x, y| {
+            calls.push((x, y));
+            calls.len()
+        });
+        assert_eq!(calls, [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]);
+        assert_eq!(grid, Grid::from_2d_array([[1, 2, 3], [4, 5, 6]]));
+    }
+
+    #[rstest]
+    #[case((0, 0))]
+    #[case((3, 0))]
+    #[case((0, 3))]
+    fn test_from_fn_empty(#[case] dimensions: (usize, usize)) {
+        let (width, height) = dimensions;
+        let grid: Grid<i32> = Grid::from_fn(width, height, |_, _| panic!("called f"));
+        assert_eq!(grid, Grid::filled(0, dimensions));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let grid = grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.dimensions(), (3, 2));
+    }
+
+    #[rstest]
+    #[case((0, 0), Some(3))]
+    #[case((1, 0), Some(1))]
+    #[case((2, 0), Some(4))]
+    #[case((0, 1), Some(1))]
+    #[case((1, 1), Some(5))]
+    #[case((2, 1), Some(9))]
+    #[case((3, 0), None)]
+    #[case((0, 2), None)]
+    #[case((6, 3), None)]
+    #[case((-1, 0), None)]
+    #[case((0, -1), None)]
+    #[case((-3, -3), None)]
+    fn test_get(#[case] index: (i32, i32), #[case] expected: Option<i32>) {
+        assert_eq!(grid().get(index), expected.as_ref());
+    }
+
+    #[rstest]
+    #[case((0, 0), 3)]
+    #[case((2, 0), 4)]
+    #[case((1, 1), 5)]
+    #[should_panic(expected = "index (3, 4) out of bounds for dimensions (3, 2)")]
+    #[case((3, 4), 0)]
+    #[should_panic(expected = "index (-1, -2) out of bounds for dimensions (3, 2)")]
+    #[case((-1, -2), 0)]
+    fn test_index(#[case] index: (i32, i32), #[case] expected: i32) {
+        assert_eq!(grid()[index], expected);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut grid = grid();
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [3, 1, 4],
+            [2, 5, 6],
+        ]);
+        *grid.get_mut((0, 1)).unwrap() = 2;
+        grid[(2, 1)] = 6;
+        assert_eq!(grid, expected);
+        assert_eq!(grid.get_mut((4, 1)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index (5, -6) out of bounds for dimensions (3, 2)")]
+    fn test_set_out_of_bounds() {
+        grid()[(5, -6)] = 0;
+    }
+
+    #[test]
+    fn test_iteration() {
+        let grid = grid();
+        let actual: Vec<((i32, i32), &i32)> = grid.enumerate().collect();
+        let expected = vec![
+            ((0, 0), &3),
+            ((1, 0), &1),
+            ((2, 0), &4),
+            ((0, 1), &1),
+            ((1, 1), &5),
+            ((2, 1), &9),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::top_left((0, 0), vec![((1, 0), 1), ((0, 1), 1)])]
+    #[case::top_right((2, 0), vec![((1, 0), 1), ((2, 1), 9)])]
+    #[case::bottom_left((0, 1), vec![((0, 0), 3), ((1, 1), 5)])]
+    #[case::bottom_right((2, 1), vec![((2, 0), 4), ((1, 1), 5)])]
+    #[case::top_edge((1, 0), vec![((0, 0), 3), ((2, 0), 4), ((1, 1), 5)])]
+    #[case::bottom_edge((1, 1), vec![((1, 0), 1), ((0, 1), 1), ((2, 1), 9)])]
+    fn test_orthogonal_neighbors(
+        #[case] index: (i32, i32),
+        #[case] expected: Vec<((i32, i32), i32)>,
+    ) {
+        let grid = grid();
+        let neighbors: Vec<_> = grid
+            .orthogonal_neighbors(index)
+            .map(|(index, &element)| (index, element))
+            .collect();
+        assert_eq!(neighbors, expected);
+    }
+
+    #[test]
+    fn test_orthogonal_neighbors_counts() {
+        // Corners have 2 neighbors, other edge cells have 3, and interior cells have 4.
+        let grid = indices((4, 3));
+        let counts = grid
+            .clone()
+            .map(|index| grid.orthogonal_neighbors(index).count());
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [2, 3, 3, 2],
+            [3, 4, 4, 3],
+            [2, 3, 3, 2],
+        ]);
+        assert_eq!(counts, expected);
+        for (index, _) in grid.enumerate::<(usize, usize)>() {
+            for (neighbor, &element) in grid.orthogonal_neighbors(index) {
+                assert_eq!(neighbor, element);
+                assert_eq!(
+                    neighbor.0.abs_diff(index.0) + neighbor.1.abs_diff(index.1),
+                    1
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::single(Grid::from_2d_array([[0]]), (0, 0), 0)]
+    #[case::single_row(Grid::from_2d_array([[0, 0, 0]]), (1, 0), 2)]
+    #[case::single_column(Grid::from_2d_array([[0], [0], [0]]), (0, 2), 1)]
+    fn test_orthogonal_neighbors_thin(
+        #[case] grid: Grid<i32>,
+        #[case] index: (usize, usize),
+        #[case] expected: usize,
+    ) {
+        assert_eq!(grid.orthogonal_neighbors(index).count(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "index (3, 0) out of bounds for dimensions (3, 2)")]
+    fn test_orthogonal_neighbors_out_of_bounds() {
+        grid().orthogonal_neighbors((3, 0)).for_each(drop);
+    }
+
+    #[test]
+    fn test_all_neighbors() {
+        let grid = grid();
+        let neighbors: Vec<((i32, i32), i32)> = grid
+            .all_neighbors((1, 0))
+            .map(|(index, &element)| (index, element))
+            .collect();
+        assert_eq!(
+            neighbors,
+            [
+                ((0, 0), 3),
+                ((2, 0), 4),
+                ((0, 1), 1),
+                ((1, 1), 5),
+                ((2, 1), 9)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_neighbors_counts() {
+        // Corners have 3 neighbors, other edge cells have 5, and interior cells have 8.
+        let grid = indices((5, 5));
+        let counts = grid.clone().map(|index| grid.all_neighbors(index).count());
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [3, 5, 5, 5, 3],
+            [5, 8, 8, 8, 5],
+            [5, 8, 8, 8, 5],
+            [5, 8, 8, 8, 5],
+            [3, 5, 5, 5, 3],
+        ]);
+        assert_eq!(counts, expected);
+        for (index, _) in grid.enumerate::<(usize, usize)>() {
+            let neighbors: Vec<_> = grid.all_neighbors(index).collect();
+            for &(neighbor, &element) in &neighbors {
+                assert_eq!(neighbor, element);
+                assert_eq!(
+                    neighbor
+                        .0
+                        .abs_diff(index.0)
+                        .max(neighbor.1.abs_diff(index.1)),
+                    1
+                );
+            }
+            // Row-major order.
+            assert!(neighbors.is_sorted_by_key(|&((x, y), _)| (y, x)));
+        }
+    }
+
+    #[rstest]
+    #[case::single(Grid::from_2d_array([[0]]), (0, 0), 0)]
+    #[case::single_row(Grid::from_2d_array([[0, 0, 0]]), (1, 0), 2)]
+    #[case::single_column(Grid::from_2d_array([[0], [0], [0]]), (0, 2), 1)]
+    #[case::two_by_two(Grid::from_2d_array([[0, 0], [0, 0]]), (1, 0), 3)]
+    fn test_all_neighbors_thin(
+        #[case] grid: Grid<i32>,
+        #[case] index: (usize, usize),
+        #[case] expected: usize,
+    ) {
+        assert_eq!(grid.all_neighbors(index).count(), expected);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = subgrid_example();
+        let filled: Vec<((usize, usize), i32)> = grid
+            .flood_fill((1, 1), |&element| element < 5)
+            .into_iter()
+            .map(|(index, &element)| (index, element))
+            .collect();
+        // The 2 at (1, 1) is connected to the 1 above it, and then to the rest of the top row
+        // up to the 5; the other elements below 5 aren't orthogonally connected to it.
+        let expected = vec![
+            ((1, 1), 2),
+            ((1, 0), 1),
+            ((0, 0), 3),
+            ((2, 0), 4),
+            ((3, 0), 1),
+        ];
+        assert_eq!(filled, expected);
+    }
+
+    #[test]
+    fn test_flood_fill_order() {
+        // In breadth-first order, the distance from the start never decreases.
+        let grid = Grid::filled(0, (5, 4));
+        let filled = grid.flood_fill::<(usize, usize)>((1, 2), |_| true);
+        assert_eq!(filled.len(), 20);
+        let distances: Vec<usize> = filled
+            .iter()
+            .map(|&((x, y), _)| x.abs_diff(1) + y.abs_diff(2))
+            .collect();
+        assert!(distances.is_sorted(), "{distances:?}");
+        let indices: HashSet<(usize, usize)> = filled.into_iter().map(|(index, _)| index).collect();
+        assert_eq!(indices.len(), 20);
+    }
+
+    #[test]
+    fn test_flood_fill_disconnected() {
+        // The two regions of zeroes are only connected diagonally.
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [0, 0, 1, 1],
+            [0, 0, 1, 1],
+            [1, 1, 0, 0],
+        ]);
+        let filled: HashSet<(i32, i32)> = grid
+            .flood_fill((0, 1), |&element| element == 0)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(filled, HashSet::from([(0, 0), (1, 0), (0, 1), (1, 1)]));
+        let filled = grid.flood_fill::<(i32, i32)>((3, 2), |&element| element == 0);
+        assert_eq!(filled.len(), 2);
+    }
+
+    #[rstest]
+    #[case::out_of_bounds((3, 0))]
+    #[case::negative((-1, 1))]
+    #[case::predicate_not_satisfied((2, 0))]
+    fn test_flood_fill_empty(#[case] start: (i32, i32)) {
+        let grid = grid();
+        assert_eq!(grid.flood_fill(start, |&element| element != 4), vec![]);
+    }
+
+    /// Returns the indices in each of the given components.
+    fn component_indices(components: Vec<Vec<((usize, usize), &i32)>>) -> Vec<Vec<(usize, usize)>> {
+        components
+            .into_iter()
+            .map(|component| component.into_iter().map(|(index, _)| index).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let grid = subgrid_example();
+        let components = grid.connected_components(|&element| element < 5, false);
+        let expected = vec![
+            vec![(0, 0), (1, 0), (2, 0), (1, 1), (3, 0)],
+            vec![(4, 1)],
+            vec![(0, 3), (1, 3), (2, 3)],
+            vec![(4, 3)],
+        ];
+        assert_eq!(component_indices(components), expected);
+    }
+
+    #[test]
+    fn test_connected_components_diagonal() {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [1, 0, 0, 1],
+            [0, 1, 0, 0],
+            [0, 0, 0, 1],
+            [1, 1, 0, 1],
+        ]);
+        let orthogonal =
+            grid.connected_components::<(usize, usize)>(|&element| element == 1, false);
+        let sizes: Vec<usize> = orthogonal.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [1, 1, 1, 2, 2]);
+        let diagonal = grid.connected_components(|&element| element == 1, true);
+        assert_eq!(
+            component_indices(diagonal),
+            vec![
+                vec![(0, 0), (1, 1)],
+                vec![(3, 0)],
+                vec![(3, 2), (3, 3)],
+                vec![(0, 3), (1, 3)]
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_connected_components_partition(#[values(false, true)] diagonal: bool) {
+        // Every element satisfying the predicate is in exactly one component,
+        // and the elements of each component are the ones reachable from its first element.
+        let grid = subgrid_example();
+        let predicate = |&element: &i32| element % 3 != 0;
+        let components = grid.connected_components::<(usize, usize)>(predicate, diagonal);
+        let mut indices: Vec<(usize, usize)> = components
+            .iter()
+            .flatten()
+            .map(|&(index, _)| index)
+            .collect();
+        indices.sort_by_key(|&(x, y)| (y, x));
+        let expected: Vec<(usize, usize)> = grid
+            .enumerate()
+            .filter(|(_, element)| predicate(element))
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(indices, expected);
+        let firsts: Vec<(usize, usize)> =
+            components.iter().map(|component| component[0].0).collect();
+        assert!(firsts.is_sorted_by_key(|&(x, y)| (y, x)), "{firsts:?}");
+        if !diagonal {
+            for component in components {
+                assert_eq!(grid.flood_fill(component[0].0, predicate), component);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::empty(Grid::filled(0, (0, 0)))]
+    #[case::none_satisfying(Grid::filled(0, (3, 2)))]
+    fn test_connected_components_none(#[case] grid: Grid<i32>) {
+        let components = grid.connected_components::<(usize, usize)>(|&element| element != 0, true);
+        assert_eq!(components, Vec::<Vec<_>>::new());
+    }
+
+    #[test]
+    fn test_border_cells() {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [3, 1, 4, 1],
+            [5, 9, 2, 6],
+            [5, 3, 5, 8],
+            [9, 7, 9, 3],
+        ]);
+        let border: Vec<i32> = grid
+            .border_cells::<(usize, usize)>()
+            .map(|(_, &element)| element)
+            .collect();
+        assert_eq!(border, [3, 1, 4, 1, 5, 6, 5, 8, 9, 7, 9, 3]);
+    }
+
+    #[rstest]
+    #[case((3, 3), 8)]
+    #[case((5, 4), 14)]
+    #[case((1, 1), 1)]
+    #[case((4, 1), 4)]
+    #[case((1, 4), 4)]
+    #[case((2, 2), 4)]
+    #[case((2, 5), 10)]
+    #[case((0, 0), 0)]
+    #[case((3, 0), 0)]
+    #[case((0, 3), 0)]
+    fn test_border_cells_count(#[case] dimensions: (usize, usize), #[case] expected: usize) {
+        let grid = indices(dimensions);
+        let (width, height) = dimensions;
+        let border: Vec<(usize, usize)> = grid
+            .border_cells()
+            .map(|(index, &element)| {
+                assert_eq!(index, element);
+                index
+            })
+            .collect();
+        assert_eq!(border.len(), expected);
+        let unique: HashSet<_> = border.iter().copied().collect();
+        assert_eq!(unique.len(), expected);
+        for (x, y) in grid.enumerate::<(usize, usize)>().map(|(index, _)| index) {
+            let on_border = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+            assert_eq!(unique.contains(&(x, y)), on_border, "{:?}", (x, y));
+        }
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut grid = grid();
+        grid.swap((0, 0), (2, 1));
+        grid.swap((1, 0), (1, 1));
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [9, 5, 4],
+            [1, 1, 3],
+        ]);
+        assert_eq!(grid, expected);
+        // Index types can be mixed.
+        grid.swap((2_usize, 0_usize), (0_i32, 1_i32));
+        assert_eq!(grid, Grid::from_2d_array([[9, 5, 1], [4, 1, 3]]));
+    }
+
+    #[rstest]
+    #[case((0, 0))]
+    #[case((1, 1))]
+    #[case((2, 0))]
+    fn test_swap_same_index(#[case] index: (usize, usize)) {
+        let mut grid = grid();
+        grid.swap(index, index);
+        assert_eq!(grid, self::grid());
+    }
+
+    #[test]
+    fn test_swap_single() {
+        let mut grid = Grid::from_2d_array([[7]]);
+        grid.swap((0, 0), (0, 0));
+        assert_eq!(grid, Grid::from_2d_array([[7]]));
+    }
+
+    #[rstest]
+    #[case((0, 0), (3, 0))]
+    #[case((0, 2), (1, 1))]
+    #[case((-1, 0), (1, 1))]
+    #[should_panic(expected = "out of bounds for dimensions (3, 2)")]
+    fn test_swap_out_of_bounds(#[case] a: (i32, i32), #[case] b: (i32, i32)) {
+        grid().swap(a, b);
+    }
+
+    #[test]
+    fn test_iter() {
+        let grid = grid();
+        let elements: Vec<&i32> = grid.iter().collect();
+        assert_eq!(elements, [&3, &1, &4, &1, &5, &9]);
+        assert_eq!(grid.iter().sum::<i32>(), 23);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut grid = grid();
+        for element in grid.iter_mut() {
+            *element *= 2;
+        }
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [6,  2,  8],
+            [2, 10, 18],
+        ]);
+        assert_eq!(grid, expected);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let elements: Vec<i32> = grid().into_iter().collect();
+        assert_eq!(elements, [3, 1, 4, 1, 5, 9]);
+        let mut count = 0;
+        for element in Grid::from_2d_array([[String::from("a")], [String::from("b")]]) {
+            assert_eq!(element.len(), 1);
+            count += 1;
+        }
+        assert_eq!(count, 2);
+    }
+
+    #[rstest]
+    fn test_iter_order(#[values((0, 0), (3, 0), (0, 2), (4, 3))] dimensions: (usize, usize)) {
+        let grid = indices(dimensions);
+        let (width, height) = dimensions;
+        let expected: Vec<(usize, usize)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .collect();
+        assert_eq!(grid.iter().count(), width * height);
+        assert_eq!(grid.iter().copied().collect::<Vec<_>>(), expected);
+        assert_eq!(grid.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_enumerate_mut() {
+        let mut grid = grid();
+        for ((x, y), element) in grid.enumerate_mut::<(usize, usize)>() {
+            *element += (10 * x + 100 * y) as i32;
+        }
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [  3,  11,  24],
+            [101, 115, 129],
+        ]);
+        assert_eq!(grid, expected);
+    }
+
+    #[rstest]
+    fn test_enumerate_mut_indices(
+        #[values((0, 0), (3, 0), (1, 1), (4, 3))] dimensions: (usize, usize),
+    ) {
+        let mut grid = Grid::filled((0, 0), dimensions);
+        for (index, element) in grid.enumerate_mut() {
+            *element = index;
+        }
+        assert_eq!(grid, indices(dimensions));
+        let signed: Vec<(i32, i32)> = grid
+            .enumerate_mut::<(i32, i32)>()
+            .map(|(index, _)| index)
+            .collect();
+        let expected: Vec<(i32, i32)> = indices(dimensions)
+            .enumerate::<(i32, i32)>()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(signed, expected);
+    }
+
+    #[test]
+    fn test_rows() {
+        let grid = grid();
+        let rows: Vec<&[i32]> = grid.rows().collect();
+        assert_eq!(rows, [[3, 1, 4], [1, 5, 9]]);
+    }
+
+    #[rstest]
+    #[case::single_row(Grid::from_2d_array([[2, 7, 1, 8]]), vec![vec![2, 7, 1, 8]])]
+    #[case::single_column(Grid::from_2d_array([[2], [7], [1]]), vec![vec![2], vec![7], vec![1]])]
+    #[case::single(Grid::from_2d_array([[2]]), vec![vec![2]])]
+    #[case::no_columns(Grid::filled(0, (0, 3)), vec![])]
+    #[case::no_rows(Grid::filled(0, (3, 0)), vec![])]
+    #[case::empty(Grid::filled(0, (0, 0)), vec![])]
+    fn test_rows_shapes(#[case] grid: Grid<i32>, #[case] expected: Vec<Vec<i32>>) {
+        let rows: Vec<&[i32]> = grid.rows().collect();
+        assert_eq!(rows, expected);
+    }
+
+    #[rstest]
+    fn test_rows_match_indexing(#[values((1, 1), (4, 3), (2, 5))] dimensions: (usize, usize)) {
+        let grid = indices(dimensions);
+        assert_eq!(grid.rows().count(), grid.height());
+        for (y, row) in grid.rows().enumerate() {
+            let expected: Vec<_> = (0..grid.width()).map(|x| grid[(x, y)]).collect();
+            assert_eq!(row, expected);
+        }
+    }
+
+    #[test]
+    fn test_columns() {
+        let grid = grid();
+        let columns: Vec<Vec<i32>> = grid
+            .columns()
+            .map(|column| column.copied().collect())
+            .collect();
+        assert_eq!(columns, [[3, 1], [1, 5], [4, 9]]);
+    }
+
+    #[rstest]
+    #[case::single_row(Grid::from_2d_array([[2, 7, 1]]), vec![vec![2], vec![7], vec![1]])]
+    #[case::single_column(Grid::from_2d_array([[2], [7], [1], [8]]), vec![vec![2, 7, 1, 8]])]
+    #[case::no_columns(Grid::filled(0, (0, 3)), vec![])]
+    #[case::no_rows(Grid::filled(0, (3, 0)), vec![])]
+    #[case::empty(Grid::filled(0, (0, 0)), vec![])]
+    fn test_columns_shapes(#[case] grid: Grid<i32>, #[case] expected: Vec<Vec<i32>>) {
+        let columns: Vec<Vec<i32>> = grid
+            .columns()
+            .map(|column| column.copied().collect())
+            .collect();
+        assert_eq!(columns, expected);
+    }
+
+    #[rstest]
+    fn test_columns_transpose(#[values((1, 1), (4, 3), (2, 5))] dimensions: (usize, usize)) {
+        // Flattening the columns gives the elements in column-major order.
+        let grid = indices(dimensions);
+        let (width, height) = dimensions;
+        let flattened: Vec<_> = grid.columns().flatten().copied().collect();
+        let expected: Vec<_> = (0..width)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .collect();
+        assert_eq!(flattened, expected);
+    }
+
+    #[test]
+    fn test_pad() {
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [0, 0, 0, 0, 0],
+            [0, 3, 1, 4, 0],
+            [0, 1, 5, 9, 0],
+            [0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(grid().pad(0, 1), expected);
+    }
+
+    #[rstest]
+    fn test_pad_offsets(
+        #[values((0, 0), (3, 0), (0, 2), (1, 1), (4, 3))] dimensions: (usize, usize),
+        #[values(0, 1, 3)] thickness: usize,
+    ) {
+        let grid = indices(dimensions).map(Some);
+        let padded = grid.clone().pad(None, thickness);
+        let (width, height) = dimensions;
+        assert_eq!(
+            padded.dimensions(),
+            (width + 2 * thickness, height + 2 * thickness)
+        );
+        for ((x, y), &element) in padded.enumerate::<(usize, usize)>() {
+            let inside = (thickness..thickness + width).contains(&x)
+                && (thickness..thickness + height).contains(&y);
+            let expected = inside.then(|| (x - thickness, y - thickness));
+            assert_eq!(element, expected, "{:?}", (x, y));
+        }
+        if thickness == 0 {
+            assert_eq!(padded, grid);
+        }
+    }
+
+    #[rstest]
+    #[case::square(
+        Grid::from_2d_array([[1, 2, 3], [4, 5, 6], [7, 8, 9]]),
+        Grid::from_2d_array([[1, 4, 7], [2, 5, 8], [3, 6, 9]]),
+    )]
+    #[case::rectangular(grid(), Grid::from_2d_array([[3, 1], [1, 5], [4, 9]]))]
+    #[case::rainfall_example(
+        Grid::from_2d_array([[3, 1, 4, 2, 5, 9], [2, 6, 5, 3, 5, 8], [9, 7, 9, 3, 1, 3]]),
+        Grid::from_2d_array([[3, 2, 9], [1, 6, 7], [4, 5, 9], [2, 3, 3], [5, 5, 1], [9, 8, 3]]),
+    )]
+    #[case::single_row(Grid::from_2d_array([[2, 7, 1]]), Grid::from_2d_array([[2], [7], [1]]))]
+    #[case::single(Grid::from_2d_array([[2]]), Grid::from_2d_array([[2]]))]
+    #[case::no_rows(Grid::filled(0, (3, 0)), Grid::filled(0, (0, 3)))]
+    #[case::no_columns(Grid::filled(0, (0, 3)), Grid::filled(0, (3, 0)))]
+    #[case::empty(Grid::filled(0, (0, 0)), Grid::filled(0, (0, 0)))]
+    fn test_transpose(#[case] grid: Grid<i32>, #[case] expected: Grid<i32>) {
+        let transposed = grid.transpose();
+        assert_eq!(transposed, expected);
+        assert_eq!(transposed.transpose(), grid);
+    }
+
+    #[rstest]
+    fn test_transpose_indices(#[values((1, 1), (4, 3), (2, 5))] dimensions: (usize, usize)) {
+        let grid = indices(dimensions);
+        let transposed = grid.transpose();
+        let (width, height) = dimensions;
+        assert_eq!(transposed.dimensions(), (height, width));
+        for (x, y) in (0..width).cartesian_product(0..height) {
+            assert_eq!(transposed[(y, x)], grid[(x, y)]);
+        }
+    }
+
+    #[rstest]
+    #[case::example(grid(), Grid::from_2d_array([[4, 1, 3], [9, 5, 1]]))]
+    #[case::single_row(Grid::from_2d_array([[2, 7, 1]]), Grid::from_2d_array([[1, 7, 2]]))]
+    #[case::single_column(Grid::from_2d_array([[2], [7]]), Grid::from_2d_array([[2], [7]]))]
+    fn test_flip_horizontal(#[case] grid: Grid<i32>, #[case] expected: Grid<i32>) {
+        assert_eq!(grid.flip_horizontal(), expected);
+    }
+
+    #[rstest]
+    #[case::example(grid(), Grid::from_2d_array([[1, 5, 9], [3, 1, 4]]))]
+    #[case::single_row(Grid::from_2d_array([[2, 7, 1]]), Grid::from_2d_array([[2, 7, 1]]))]
+    #[case::single_column(Grid::from_2d_array([[2], [7]]), Grid::from_2d_array([[7], [2]]))]
+    fn test_flip_vertical(#[case] grid: Grid<i32>, #[case] expected: Grid<i32>) {
+        assert_eq!(grid.flip_vertical(), expected);
+    }
+
+    #[rstest]
+    fn test_flip_indices(
+        #[values((1, 1), (4, 3), (2, 5), (5, 1), (1, 4))] dimensions: (usize, usize),
+    ) {
+        let grid = indices(dimensions);
+        let (width, height) = dimensions;
+        let horizontal = grid.flip_horizontal();
+        let vertical = grid.flip_vertical();
+        assert_eq!(horizontal.dimensions(), dimensions);
+        assert_eq!(vertical.dimensions(), dimensions);
+        for (x, y) in (0..width).cartesian_product(0..height) {
+            assert_eq!(horizontal[(width - 1 - x, y)], (x, y));
+            assert_eq!(vertical[(x, height - 1 - y)], (x, y));
+        }
+        assert_eq!(horizontal.flip_horizontal(), grid);
+        assert_eq!(vertical.flip_vertical(), grid);
+        assert_eq!(horizontal.flip_vertical(), grid.rotate_180());
+    }
+
+    #[rstest]
+    #[case((3, 0))]
+    #[case((0, 3))]
+    #[case((0, 0))]
+    fn test_flip_empty(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(0, dimensions);
+        assert_eq!(grid.flip_horizontal(), grid);
+        assert_eq!(grid.flip_vertical(), grid);
+    }
+
+    /// Example grid for subgrid tests.
+    fn subgrid_example() -> Grid<i32> {
+        Grid::from_2d_array([
+            [3, 1, 4, 1, 5],
+            [9, 2, 6, 5, 3],
+            [5, 8, 9, 7, 9],
+            [3, 2, 3, 8, 4],
+        ])
+    }
+
+    #[rstest]
+    #[case::top_left((0, 0, 2, 2), Grid::from_2d_array([[3, 1], [9, 2]]))]
+    #[case::top_right((3, 0, 2, 2), Grid::from_2d_array([[1, 5], [5, 3]]))]
+    #[case::bottom_left((0, 2, 2, 2), Grid::from_2d_array([[5, 8], [3, 2]]))]
+    #[case::bottom_right((3, 2, 2, 2), Grid::from_2d_array([[7, 9], [8, 4]]))]
+    #[case::interior((1, 1, 3, 2), Grid::from_2d_array([[2, 6, 5], [8, 9, 7]]))]
+    #[case::single_row((1, 2, 4, 1), Grid::from_2d_array([[8, 9, 7, 9]]))]
+    #[case::single_column((2, 0, 1, 4), Grid::from_2d_array([[4], [6], [9], [3]]))]
+    #[case::single((4, 3, 1, 1), Grid::from_2d_array([[4]]))]
+    #[case::whole((0, 0, 5, 4), subgrid_example())]
+    #[case::no_rows((1, 2, 3, 0), Grid::filled(0, (3, 0)))]
+    #[case::no_columns((5, 1, 0, 3), Grid::filled(0, (0, 3)))]
+    #[case::empty_at_corner((5, 4, 0, 0), Grid::filled(0, (0, 0)))]
+    fn test_subgrid(#[case] rectangle: (usize, usize, usize, usize), #[case] expected: Grid<i32>) {
+        let (x, y, width, height) = rectangle;
+        assert_eq!(subgrid_example().subgrid(x, y, width, height), expected);
+    }
+
+    #[rstest]
+    #[should_panic(
+        expected = "subgrid at (4, 0) with dimensions (2, 1) out of bounds for dimensions (5, 4)"
+    )]
+    #[case::too_wide((4, 0, 2, 1))]
+    #[should_panic(
+        expected = "subgrid at (0, 1) with dimensions (1, 4) out of bounds for dimensions (5, 4)"
+    )]
+    #[case::too_tall((0, 1, 1, 4))]
+    #[should_panic(
+        expected = "subgrid at (6, 0) with dimensions (0, 0) out of bounds for dimensions (5, 4)"
+    )]
+    #[case::empty_outside((6, 0, 0, 0))]
+    #[should_panic(expected = "out of bounds for dimensions (5, 4)")]
+    #[case::overflow((1, 0, usize::MAX, 1))]
+    fn test_subgrid_out_of_bounds(#[case] rectangle: (usize, usize, usize, usize)) {
+        let (x, y, width, height) = rectangle;
+        subgrid_example().subgrid(x, y, width, height);
+    }
+
+    #[rstest]
+    #[case((1, 1), 20)]
+    #[case((2, 2), 12)]
+    #[case((3, 2), 9)]
+    #[case((5, 1), 4)]
+    #[case((1, 4), 5)]
+    #[case((5, 4), 1)]
+    #[case((6, 1), 0)]
+    #[case((1, 5), 0)]
+    #[case((6, 5), 0)]
+    fn test_windows_count(#[case] size: (usize, usize), #[case] expected: usize) {
+        let (w, h) = size;
+        let windows: Vec<_> = subgrid_example().windows(w, h).collect();
+        assert_eq!(windows.len(), expected);
+        assert!(windows.iter().all(|window| window.dimensions() == size));
+    }
+
+    #[test]
+    fn test_windows_corners() {
+        let windows: Vec<_> = subgrid_example().windows(3, 2).collect();
+        assert_eq!(windows[0], Grid::from_2d_array([[3, 1, 4], [9, 2, 6]]));
+        assert_eq!(windows[2], Grid::from_2d_array([[4, 1, 5], [6, 5, 3]]));
+        assert_eq!(windows[6], Grid::from_2d_array([[5, 8, 9], [3, 2, 3]]));
+        assert_eq!(windows[8], Grid::from_2d_array([[9, 7, 9], [3, 8, 4]]));
+    }
+
+    #[rstest]
+    fn test_windows_order(#[values((1, 1), (2, 3), (4, 3))] size: (usize, usize)) {
+        // Each window's top-left element is the index of its top-left corner.
+        let grid = indices((4, 3));
+        let (w, h) = size;
+        let corners: Vec<_> = grid.windows(w, h).map(|window| window[(0, 0)]).collect();
+        let expected: Vec<_> = (0..=3 - h)
+            .flat_map(|y| (0..=4 - w).map(move |x| (x, y)))
+            .collect();
+        assert_eq!(corners, expected);
+    }
+
+    #[test]
+    fn test_windows_whole_grid() {
+        let windows: Vec<_> = subgrid_example().windows(5, 4).collect();
+        assert_eq!(windows, [subgrid_example()]);
+    }
+
+    #[test]
+    fn test_rotate_90_clockwise() {
+        let expected = Grid::from_2d_array([[1, 3], [5, 1], [9, 4]]);
+        assert_eq!(grid().rotate_90_clockwise(), expected);
+    }
+
+    #[test]
+    fn test_rotate_90_counterclockwise() {
+        let expected = Grid::from_2d_array([[4, 9], [1, 5], [3, 1]]);
+        assert_eq!(grid().rotate_90_counterclockwise(), expected);
+    }
+
+    #[test]
+    fn test_rotate_180() {
+        let expected = Grid::from_2d_array([[9, 5, 1], [4, 1, 3]]);
+        assert_eq!(grid().rotate_180(), expected);
+    }
+
+    #[rstest]
+    fn test_rotation_indices(#[values((1, 1), (4, 3), (2, 5))] dimensions: (usize, usize)) {
+        let grid = indices(dimensions);
+        let (width, height) = dimensions;
+        let clockwise = grid.rotate_90_clockwise();
+        let counterclockwise = grid.rotate_90_counterclockwise();
+        let half_turn = grid.rotate_180();
+        assert_eq!(clockwise.dimensions(), (height, width));
+        assert_eq!(counterclockwise.dimensions(), (height, width));
+        assert_eq!(half_turn.dimensions(), dimensions);
+        for (x, y) in (0..width).cartesian_product(0..height) {
+            assert_eq!(clockwise[(height - 1 - y, x)], (x, y));
+            assert_eq!(counterclockwise[(y, width - 1 - x)], (x, y));
+            assert_eq!(half_turn[(width - 1 - x, height - 1 - y)], (x, y));
+        }
+    }
+
+    #[rstest]
+    fn test_rotations_compose(#[values((1, 1), (4, 3), (2, 5))] dimensions: (usize, usize)) {
+        let grid = indices(dimensions);
+        assert_eq!(
+            grid.rotate_90_clockwise().rotate_90_counterclockwise(),
+            grid
+        );
+        assert_eq!(
+            grid.rotate_90_counterclockwise().rotate_90_clockwise(),
+            grid
+        );
+        assert_eq!(grid.rotate_180().rotate_180(), grid);
+        let four_turns = (0..4).fold(grid.clone(), |grid, _| grid.rotate_90_clockwise());
+        assert_eq!(four_turns, grid);
+    }
+
+    #[rstest]
+    #[case((3, 0))]
+    #[case((0, 3))]
+    #[case((0, 0))]
+    fn test_rotate_empty(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(0, dimensions);
+        let (width, height) = dimensions;
+        let rotated = Grid::filled(0, (height, width));
+        assert_eq!(grid.rotate_90_clockwise(), rotated);
+        assert_eq!(grid.rotate_90_counterclockwise(), rotated);
+        assert_eq!(grid.rotate_180(), grid);
+    }
+
+    #[test]
+    fn test_try_map() {
+        let strings = Grid::from_2d_array([["3", "1", "4"], ["1", "5", "9"]]);
+        assert_eq!(strings.try_map(|s| s.parse::<i32>()), Ok(grid()));
+    }
+
+    #[rstest]
+    #[case::first(Grid::from_2d_array([["x", "1", "y"], ["1", "5", "9"]]), "x")]
+    #[case::interior(Grid::from_2d_array([["3", "1", "4"], ["1", "z", "-"]]), "z")]
+    #[case::last(Grid::from_2d_array([["3", "1", "4"], ["1", "5", "?"]]), "?")]
+    fn test_try_map_error(#[case] strings: Grid<&str>, #[case] expected: &str) {
+        let mut visited = vec![];
+        let result = strings.try_map(|s| {
+            visited.push(s);
+            s.parse::<i32>().map_err(|_| s)
+        });
+        assert_eq!(result, Err(expected));
+        // Elements after the first error aren't transformed.
+        assert_eq!(visited.last(), Some(&expected));
+    }
+
+    #[rstest]
+    fn test_try_map_dimensions(
+        #[values((0, 0), (3, 0), (0, 2), (4, 3))] dimensions: (usize, usize),
+    ) {
+        let result: Result<_, ()> = indices(dimensions).try_map(|(x, y)| Ok(x + y));
+        let result = result.unwrap();
+        assert_eq!(result.dimensions(), dimensions);
+        assert_eq!(result, indices(dimensions).map(|(x, y)| x + y));
+    }
+
+    #[test]
+    fn test_zip_with() {
+        #[rustfmt::skip]
+        let other = Grid::from_2d_array([
+            [2, 7, 1],
+            [8, 2, 8],
+        ]);
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [ 5, 8,  5],
+            [ 9, 7, 17],
+        ]);
+        assert_eq!(grid().zip_with(other, |a, b| a + b), expected);
+    }
+
+    #[test]
+    fn test_zip() {
+        let labels = Grid::from_2d_array([["a", "b", "c"], ["d", "e", "f"]]);
+        let expected = Grid::from_2d_array([
+            [(3, "a"), (1, "b"), (4, "c")],
+            [(1, "d"), (5, "e"), (9, "f")],
+        ]);
+        assert_eq!(grid().zip(labels), expected);
+    }
+
+    #[rstest]
+    fn test_zip_indices(#[values((0, 0), (3, 0), (1, 1), (4, 3))] dimensions: (usize, usize)) {
+        let zipped = indices(dimensions).zip(indices(dimensions).map(|(x, y)| x * y));
+        assert_eq!(zipped.dimensions(), dimensions);
+        for ((x, y), &element) in zipped.enumerate::<(usize, usize)>() {
+            assert_eq!(element, ((x, y), x * y));
+        }
+    }
+
+    #[rstest]
+    #[case::transposed(Grid::filled(0, (2, 3)))]
+    #[case::wider(Grid::filled(0, (4, 2)))]
+    #[case::empty(Grid::filled(0, (0, 0)))]
+    #[should_panic(expected = "cannot zip grids with different dimensions")]
+    fn test_zip_different_dimensions(#[case] other: Grid<i32>) {
+        grid().zip_with(other, |a, b| a + b);
+    }
+
+    #[test]
+    fn test_map() {
+        #[rustfmt::skip]
+        let grid_doubled = Grid::from_2d_array([
+            [ 6,  2,  8],
+            [ 2, 10, 18],
+        ]);
+        assert_eq!(grid().map(|n| n * 2), grid_doubled);
+    }
+
+    /// Returns a grid of the given dimensions where each element is its own index.
+    #[rstest]
+    #[case::example(grid())]
+    #[case::indices(indices((4, 3)).map(|(x, y)| (10 * y + x) as i32))]
+    #[case::single_row(Grid::from_2d_array([[2, 7, 1, 8]]))]
+    #[case::empty(Grid::filled(0, (3, 0)))]
+    fn test_flat_vec_round_trip(#[case] grid: Grid<i32>) {
+        let (data, width, height) = grid.clone().into_flat_vec();
+        assert_eq!((width, height), grid.dimensions());
+        assert_eq!(data, grid.as_flat_slice());
+        assert_eq!(Grid::from_flat_vec(data, width, height), Ok(grid));
+    }
+
+    #[test]
+    fn test_from_flat_vec_example() {
+        assert_eq!(
+            Grid::from_flat_vec(vec![3, 1, 4, 1, 5, 9], 3, 2),
+            Ok(grid())
+        );
+    }
+
+    #[rstest]
+    #[case::too_short(vec![3, 1, 4, 1, 5], 3, 2, 6)]
+    #[case::too_long(vec![3, 1, 4, 1, 5, 9, 2], 3, 2, 6)]
+    #[case::empty_dimension(vec![3], 0, 5, 0)]
+    #[case::empty_data(vec![], 2, 2, 4)]
+    fn test_from_flat_vec_length_mismatch(
+        #[case] data: Vec<i32>,
+        #[case] width: usize,
+        #[case] height: usize,
+        #[case] expected: usize,
+    ) {
+        let actual = data.len();
+        assert_eq!(
+            Grid::from_flat_vec(data, width, height),
+            Err(FlatVecError::LengthMismatch { expected, actual })
+        );
+    }
+
+    #[rstest]
+    #[case::example(grid())]
+    #[case::empty(Grid::filled(0, (0, 2)))]
+    fn test_as_flat_slice(#[case] grid: Grid<i32>) {
+        let elements: Vec<&i32> = grid
+            .enumerate::<(usize, usize)>()
+            .map(|(_, element)| element)
+            .collect();
+        assert_eq!(grid.as_flat_slice().iter().collect::<Vec<_>>(), elements);
+    }
+
+    #[test]
+    fn test_as_flat_slice_mut() {
+        let mut grid = grid();
+        grid.as_flat_slice_mut().reverse();
+        assert_eq!(grid, Grid::from_2d_array([[9, 5, 1], [4, 1, 3]]));
+    }
+
+    fn indices(dimensions: (usize, usize)) -> Grid<(usize, usize)> {
+        let (width, height) = dimensions;
+        Grid::from_fn(width, height, |x, y| (x, y))
+    }
+
+    /// Checks that the given iterator output visits every element of the grid exactly once,
+    /// with each element matching its index.
+    fn assert_visits_all(visited: &[(usize, usize, &(usize, usize))], grid: &Grid<(usize, usize)>) {
+        let (width, height) = grid.dimensions();
+        assert_eq!(visited.len(), width * height);
+        let mut num_visits = Grid::filled(0, grid.dimensions());
+        for &(x, y, &element) in visited {
+            assert_eq!(element, (x, y));
+            num_visits[(x, y)] += 1;
+        }
+        assert!(num_visits
+            .enumerate::<(usize, usize)>()
+            .all(|(_, &n)| n == 1));
+    }
+
+    #[test]
+    fn test_convolve_laplacian() {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [1, 2, 0, 4],
+            [3, 5, 1, 0],
+            [0, 2, 6, 1],
+        ]);
+        #[rustfmt::skip]
+        let laplacian = Grid::from_2d_array([
+            [0,  1, 0],
+            [1, -4, 1],
+            [0,  1, 0],
+        ]);
+        // Each element is the sum of its orthogonal neighbors (zero outside the grid),
+        // minus four times the element itself.
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [ 1,  -2,   7, -16],
+            [-6, -12,   7,   6],
+            [ 5,   3, -20,   2],
+        ]);
+        assert_eq!(grid.convolve(&laplacian), expected);
+        // A constant grid has a Laplacian of zero away from the edges.
+        let constant = Grid::filled(7, (5, 5)).convolve(&laplacian);
+        assert_eq!(constant[(2, 2)], 0);
+        assert_eq!(constant[(0, 2)], -7);
+        assert_eq!(constant[(0, 0)], -14);
     }
 
     #[test]
-    fn test_inequality_different_shape() {
+    fn test_convolve_rotates_kernel() {
         #[rustfmt::skip]
-        let grid_transposed = Grid::from_2d_array([
-            [3, 1],
-            [4, 1],
-            [5, 9],
+        let grid = Grid::from_2d_array([
+            [1, 2, 0, 4],
+            [3, 5, 1, 0],
+            [0, 2, 6, 1],
         ]);
-        assert_ne!(grid(), grid_transposed);
+        let kernel = Grid::from_2d_array([[1, 2, 3]]);
+        #[rustfmt::skip]
+        let convolved = Grid::from_2d_array([
+            [4, 7, 10, 8],
+            [11, 20, 17, 3],
+            [2, 10, 19, 20],
+        ]);
+        assert_eq!(grid.convolve(&kernel), convolved);
+        #[rustfmt::skip]
+        let stenciled = Grid::from_2d_array([
+            [8, 5, 14, 8],
+            [21, 16, 7, 1],
+            [6, 22, 17, 8],
+        ]);
+        assert_eq!(
+            grid.apply_stencil(&kernel, |w, &x| w * x, |a, b| a + b, 0),
+            stenciled
+        );
     }
 
     #[test]
-    fn test_inequality_different_values() {
+    fn test_apply_kernel_neighborhood_sum() {
+        let sums = subgrid_example().apply_kernel([[1.0; 3]; 3], |sum| sum as i32);
+        let expected = Grid::from_2d_array([
+            [15, 25, 19, 24, 14],
+            [28, 47, 43, 49, 30],
+            [29, 47, 50, 54, 36],
+            [18, 30, 37, 40, 28],
+        ]);
+        assert_eq!(sums, expected);
+        // The neighborhood sums can also be computed from the neighbors of each element.
+        for ((x, y), &sum) in sums.enumerate::<(usize, usize)>() {
+            let grid = subgrid_example();
+            let neighbors: i32 = grid.all_neighbors((x, y)).map(|(_, &n)| n).sum();
+            assert_eq!(sum, neighbors + grid[(x, y)]);
+        }
+    }
+
+    #[test]
+    fn test_apply_kernel_border() {
+        // Only the weights over elements inside the grid contribute,
+        // so at a corner, only four of the nine weights are used.
+        let kernel = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        let result = grid().apply_kernel(kernel, |sum| sum);
+        assert_eq!(
+            result[(0, 0)],
+            5.0 * 3.0 + 6.0 * 1.0 + 8.0 * 1.0 + 9.0 * 5.0
+        );
+        assert_eq!(
+            result[(2, 1)],
+            1.0 * 1.0 + 2.0 * 4.0 + 4.0 * 5.0 + 5.0 * 9.0
+        );
+        assert_eq!(
+            result[(1, 0)],
+            4.0 * 3.0 + 5.0 * 1.0 + 6.0 * 4.0 + 7.0 * 1.0 + 8.0 * 5.0 + 9.0 * 9.0
+        );
+    }
+
+    #[test]
+    fn test_apply_kernel_not_rotated() {
+        // A kernel with a single weight to the right of its center shifts the grid to the left.
+        let shifted = grid().apply_kernel([[0.0, 0.0, 1.0]], |sum| sum as i32);
+        assert_eq!(shifted, Grid::from_2d_array([[1, 4, 0], [5, 9, 0]]));
+        let convolved = grid().convolve(&Grid::from_2d_array([[0, 0, 1]]));
+        assert_eq!(convolved, Grid::from_2d_array([[0, 3, 1], [0, 1, 5]]));
+    }
+
+    #[test]
+    fn test_apply_kernel_game_of_life() {
+        // Counting live neighbors, leaving out the cell itself.
         #[rustfmt::skip]
-        let grid_doubled = Grid::from_2d_array([
-            [ 6,  2,  8],
-            [ 2, 10, 18],
+        let glider = Grid::from_2d_array([
+            [0u8, 1, 0, 0],
+            [0, 0, 1, 0],
+            [1, 1, 1, 0],
+            [0, 0, 0, 0],
         ]);
-        assert_ne!(grid(), grid_doubled);
+        let kernel = [[1.0, 1.0, 1.0], [1.0, 0.0, 1.0], [1.0, 1.0, 1.0]];
+        let counts = glider.apply_kernel(kernel, |count| count as u8);
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 1, 2, 1],
+            [3, 5, 3, 2],
+            [1, 3, 2, 2],
+            [2, 3, 2, 1],
+        ]);
+        assert_eq!(counts, expected);
+        let next = glider.zip_with(counts, |alive, count| {
+            u8::from(count == 3 || (alive == 1 && count == 2))
+        });
+        #[rustfmt::skip]
+        assert_eq!(next, Grid::from_2d_array([
+            [0, 0, 0, 0],
+            [1, 0, 1, 0],
+            [0, 1, 1, 0],
+            [0, 1, 0, 0],
+        ]));
     }
 
     #[test]
-    fn test_filled() {
+    fn test_apply_kernel_empty() {
+        let empty: Grid<i32> = Grid::filled(0, (0, 3));
+        assert_eq!(
+            empty.apply_kernel([[1.0; 3]; 3], |sum| sum),
+            Grid::filled(0.0, (0, 3))
+        );
+        let zero_kernel = grid().apply_kernel([[0.0; 0]; 0], |sum| sum);
+        assert_eq!(zero_kernel, Grid::filled(0.0, (3, 2)));
+    }
+
+    #[test]
+    fn test_apply_stencil_even_dimensions() {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [1, 2, 0, 4],
+            [3, 5, 1, 0],
+            [0, 2, 6, 1],
+        ]);
+        // The center is the bottom right weight, so each element is combined with the ones
+        // above and to the left of it.
+        let stencil = Grid::from_2d_array([[1, 10], [100, 1000]]);
         #[rustfmt::skip]
         let expected = Grid::from_2d_array([
-            [1, 1, 1],
-            [1, 1, 1]
+            [1000, 2100, 200, 4000],
+            [3010, 5321, 1502, 140],
+            [30, 2053, 6215, 1601],
         ]);
-        assert_eq!(Grid::filled(1, (3, 2)), expected);
+        assert_eq!(
+            grid.apply_stencil(&stencil, |w, &x| w * x, |a, b| a + b, 0),
+            expected
+        );
     }
 
     #[test]
-    fn test_dimensions() {
+    fn test_apply_stencil_dilation() {
+        let grid = Grid::from_2d_array([
+            [false, false, false, false],
+            [false, true, false, false],
+            [false, false, false, true],
+        ]);
+        let cross = Grid::from_2d_array([
+            [false, true, false],
+            [true, true, true],
+            [false, true, false],
+        ]);
+        let dilated = grid.apply_stencil(&cross, |w, &x| w && x, |a, b| a || b, false);
+        let expected = Grid::from_2d_array([
+            [false, true, false, false],
+            [true, true, true, true],
+            [false, true, true, true],
+        ]);
+        assert_eq!(dilated, expected);
+    }
+
+    #[test]
+    fn test_apply_stencil_count_neighbors() {
+        // Counting the elements within the stencil shows the effect of the padding.
+        let ones = Grid::filled((), (3, 3));
+        let counts = ones.apply_stencil(&Grid::filled((), (3, 3)), |_, _| 1, |a, b| a + b, 0);
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [4, 6, 4],
+            [6, 9, 6],
+            [4, 6, 4],
+        ]);
+        assert_eq!(counts, expected);
+    }
+
+    #[rstest]
+    #[case::empty_grid((0, 0), (3, 3))]
+    #[case::empty_row_grid((4, 0), (3, 3))]
+    #[case::empty_stencil((2, 3), (0, 0))]
+    fn test_apply_stencil_empty(
+        #[case] dimensions: (usize, usize),
+        #[case] stencil_dimensions: (usize, usize),
+    ) {
+        let grid = Grid::filled(2, dimensions);
+        let stencil = Grid::filled(3, stencil_dimensions);
+        assert_eq!(
+            grid.apply_stencil(&stencil, |w, &x| w * x, |a, b| a + b, 0),
+            Grid::filled(0, dimensions)
+        );
+        assert_eq!(grid.convolve(&stencil), Grid::filled(0, dimensions));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let expected = HashMap::from([
+            (1, 2),
+            (2, 2),
+            (3, 4),
+            (4, 2),
+            (5, 3),
+            (6, 1),
+            (7, 1),
+            (8, 2),
+            (9, 3),
+        ]);
+        assert_eq!(subgrid_example().histogram(), expected);
+    }
+
+    #[rstest]
+    #[case((1, 1))]
+    #[case((4, 3))]
+    fn test_histogram_uniform(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled('x', dimensions);
+        assert_eq!(
+            grid.histogram(),
+            HashMap::from([('x', dimensions.0 * dimensions.1)])
+        );
+    }
+
+    #[rstest]
+    fn test_histogram_distinct(
+        #[values((0, 0), (1, 1), (3, 0), (4, 3))] dimensions: (usize, usize),
+    ) {
+        let histogram = indices(dimensions).histogram();
+        assert_eq!(histogram.len(), dimensions.0 * dimensions.1);
+        assert!(histogram.values().all(|&count| count == 1));
+    }
+
+    #[rstest]
+    #[case::first(|&x: &i32| x == 3, Some((0, 0)))]
+    #[case::first_of_several(|&x: &i32| x == 1, Some((1, 0)))]
+    #[case::later_row(|&x: &i32| x > 8, Some((0, 1)))]
+    #[case::later_column(|&x: &i32| x == 7, Some((3, 2)))]
+    #[case::none(|&x: &i32| x > 9, None)]
+    fn test_position(
+        #[case] predicate: fn(&i32) -> bool,
+        #[case] expected: Option<(usize, usize)>,
+    ) {
+        let grid = subgrid_example();
+        assert_eq!(grid.position(predicate), expected);
+        assert_eq!(grid.find(predicate), expected.map(|index| &grid[index]));
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = subgrid_example();
+        // The search is in row-major order, so the 4 in the top row comes before the 8
+        // further left in the third row.
+        assert_eq!(grid.find(|&x| x % 4 == 0), Some(&4));
+        assert_eq!(grid.position(|&x| x % 4 == 0), Some((2, 0)));
+        assert_eq!(grid.find(|&x| x > 7), Some(&9));
+        assert_eq!(grid.position(|&x| x > 7), Some((0, 1)));
+    }
+
+    #[rstest]
+    #[case((0, 0))]
+    #[case((0, 3))]
+    fn test_position_empty(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(0, dimensions);
+        assert_eq!(grid.position::<(usize, usize)>(|_| true), None);
+        assert_eq!(grid.find(|_| true), None);
+    }
+
+    #[test]
+    fn test_predicates_example() {
         let grid = grid();
-        assert_eq!(grid.width(), 3);
-        assert_eq!(grid.height(), 2);
-        assert_eq!(grid.dimensions(), (3, 2));
+        let is_odd = |&x: &i32| x % 2 != 0;
+        assert_eq!(grid.count(is_odd), 5);
+        assert!(grid.any(|&x| x == 4));
+        assert!(!grid.all(is_odd));
+        assert!(grid.all(|&x| x > 0));
+        assert!(grid.none(|&x| x > 9));
+        assert!(!grid.none(is_odd));
+        assert_eq!(
+            grid.partition(is_odd),
+            (vec![(0, 0), (1, 0), (0, 1), (1, 1), (2, 1)], vec![(2, 0)])
+        );
     }
 
     #[rstest]
-    #[case((0, 0), Some(3))]
-    #[case((1, 0), Some(1))]
-    #[case((2, 0), Some(4))]
-    #[case((0, 1), Some(1))]
-    #[case((1, 1), Some(5))]
-    #[case((2, 1), Some(9))]
-    #[case((3, 0), None)]
-    #[case((0, 2), None)]
-    #[case((6, 3), None)]
-    #[case((-1, 0), None)]
-    #[case((0, -1), None)]
-    #[case((-3, -3), None)]
-    fn test_get(#[case] index: (i32, i32), #[case] expected: Option<i32>) {
-        assert_eq!(grid().get(index), expected.as_ref());
+    #[case::empty_0_0((0, 0))]
+    #[case::empty_3_0((3, 0))]
+    #[case::empty_0_3((0, 3))]
+    fn test_predicates_empty(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(1, dimensions);
+        assert_eq!(grid.count(|_| true), 0);
+        assert!(!grid.any(|_| true));
+        assert!(grid.all(|_| false));
+        assert!(grid.none(|_| true));
+        assert_eq!(grid.partition(|_| true), (vec![], vec![]));
     }
 
     #[rstest]
-    #[case((0, 0), 3)]
-    #[case((2, 0), 4)]
-    #[case((1, 1), 5)]
-    #[should_panic(expected = "index (3, 4) out of bounds for dimensions (3, 2)")]
-    #[case((3, 4), 0)]
-    #[should_panic(expected = "index (-1, -2) out of bounds for dimensions (3, 2)")]
-    #[case((-1, -2), 0)]
-    fn test_index(#[case] index: (i32, i32), #[case] expected: i32) {
-        assert_eq!(grid()[index], expected);
+    #[case::all_match(|_: &(usize, usize)| true)]
+    #[case::none_match(|_: &(usize, usize)| false)]
+    #[case::diagonal(|&(x, y): &(usize, usize)| x == y)]
+    #[case::bottom_right(|&(x, y): &(usize, usize)| x >= 2 && y >= 1)]
+    fn test_predicates_manual_iteration(#[case] f: fn(&(usize, usize)) -> bool) {
+        let grid = indices((4, 3));
+        let elements = grid.data.clone();
+        let matching: Vec<(usize, usize)> = elements.iter().copied().filter(f).collect();
+        let non_matching: Vec<(usize, usize)> = elements
+            .iter()
+            .copied()
+            .filter(|element| !f(element))
+            .collect();
+        assert_eq!(grid.count(f), matching.len());
+        assert_eq!(grid.any(f), !matching.is_empty());
+        assert_eq!(grid.all(f), non_matching.is_empty());
+        assert_eq!(grid.none(f), matching.is_empty());
+        // Each element is its own index.
+        assert_eq!(grid.partition(f), (matching, non_matching));
+    }
+
+    #[rstest]
+    #[case::square(
+        Grid::from_2d_array([[1, 2], [3, 4]]),
+        Grid::from_2d_array([[5, 6], [7, 8]]),
+        Grid::from_2d_array([[1, 2, 5, 6], [3, 4, 7, 8]]),
+    )]
+    #[case::different_widths(
+        grid(),
+        Grid::from_2d_array([[2], [6]]),
+        Grid::from_2d_array([[3, 1, 4, 2], [1, 5, 9, 6]]),
+    )]
+    #[case::single_row(
+        Grid::from_2d_array([[2, 7]]),
+        Grid::from_2d_array([[1, 8, 2]]),
+        Grid::from_2d_array([[2, 7, 1, 8, 2]]),
+    )]
+    #[case::single_columns(
+        Grid::from_2d_array([[2], [7], [1]]),
+        Grid::from_2d_array([[8], [2], [8]]),
+        Grid::from_2d_array([[2, 8], [7, 2], [1, 8]]),
+    )]
+    #[case::empty_left(Grid::filled(0, (0, 2)), grid(), grid())]
+    #[case::empty_right(grid(), Grid::filled(0, (0, 2)), grid())]
+    #[case::no_rows(Grid::filled(0, (2, 0)), Grid::filled(0, (3, 0)), Grid::filled(0, (5, 0)))]
+    fn test_concat_horizontal(
+        #[case] left: Grid<i32>,
+        #[case] right: Grid<i32>,
+        #[case] expected: Grid<i32>,
+    ) {
+        assert_eq!(left.concat_horizontal(right), expected);
+    }
+
+    #[rstest]
+    #[case::square(
+        Grid::from_2d_array([[1, 2], [3, 4]]),
+        Grid::from_2d_array([[5, 6], [7, 8]]),
+        Grid::from_2d_array([[1, 2], [3, 4], [5, 6], [7, 8]]),
+    )]
+    #[case::different_heights(
+        grid(),
+        Grid::from_2d_array([[2, 6, 5]]),
+        Grid::from_2d_array([[3, 1, 4], [1, 5, 9], [2, 6, 5]]),
+    )]
+    #[case::single_rows(
+        Grid::from_2d_array([[2, 7, 1]]),
+        Grid::from_2d_array([[8, 2, 8]]),
+        Grid::from_2d_array([[2, 7, 1], [8, 2, 8]]),
+    )]
+    #[case::single_column(
+        Grid::from_2d_array([[2], [7]]),
+        Grid::from_2d_array([[1]]),
+        Grid::from_2d_array([[2], [7], [1]]),
+    )]
+    #[case::empty_top(Grid::filled(0, (3, 0)), grid(), grid())]
+    #[case::empty_bottom(grid(), Grid::filled(0, (3, 0)), grid())]
+    #[case::no_columns(Grid::filled(0, (0, 2)), Grid::filled(0, (0, 3)), Grid::filled(0, (0, 5)))]
+    fn test_concat_vertical(
+        #[case] top: Grid<i32>,
+        #[case] bottom: Grid<i32>,
+        #[case] expected: Grid<i32>,
+    ) {
+        assert_eq!(top.concat_vertical(bottom), expected);
+    }
+
+    #[rstest]
+    #[case(Grid::filled(0, (3, 3)))]
+    #[case(Grid::filled(0, (3, 0)))]
+    #[should_panic(expected = "cannot concatenate grids with different heights horizontally")]
+    fn test_concat_horizontal_different_heights(#[case] other: Grid<i32>) {
+        grid().concat_horizontal(other);
+    }
+
+    #[rstest]
+    #[case(Grid::filled(0, (2, 2)))]
+    #[case(Grid::filled(0, (0, 2)))]
+    #[should_panic(expected = "cannot concatenate grids with different widths vertically")]
+    fn test_concat_vertical_different_widths(#[case] other: Grid<i32>) {
+        grid().concat_vertical(other);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    fn test_split_rows(#[case] y: usize) {
+        let grid = indices((3, 4));
+        let (top, bottom) = grid.clone().split_rows(y).unwrap();
+        assert_eq!(top.dimensions(), (3, y));
+        assert_eq!(bottom.dimensions(), (3, 4 - y));
+        for (index, &element) in bottom.enumerate::<(usize, usize)>() {
+            assert_eq!(element, (index.0, index.1 + y));
+        }
+        assert_eq!(top.concat_vertical(bottom), grid);
     }
 
     #[test]
-    fn test_set() {
-        let mut grid = grid();
+    fn test_split_rows_example() {
+        let (top, bottom) = grid().split_rows(1).unwrap();
+        assert_eq!(top, Grid::from_2d_array([[3, 1, 4]]));
+        assert_eq!(bottom, Grid::from_2d_array([[1, 5, 9]]));
+        let (top, bottom) = grid().split_rows(0).unwrap();
+        assert_eq!(top, Grid::filled(0, (3, 0)));
+        assert_eq!(bottom, grid());
+    }
+
+    #[rstest]
+    #[case::at_height(grid(), 2)]
+    #[case::past_height(grid(), 5)]
+    #[case::empty(Grid::filled(0, (3, 0)), 0)]
+    fn test_split_rows_out_of_bounds(#[case] grid: Grid<i32>, #[case] y: usize) {
+        assert_eq!(grid.split_rows_ref(y), None);
+        assert_eq!(grid.split_rows(y), None);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    fn test_split_cols(#[case] x: usize) {
+        let grid = indices((3, 4));
+        let (left, right) = grid.clone().split_cols(x).unwrap();
+        assert_eq!(left.dimensions(), (x, 4));
+        assert_eq!(right.dimensions(), (3 - x, 4));
+        for (index, &element) in left.enumerate::<(usize, usize)>() {
+            assert_eq!(element, index);
+        }
+        for (index, &element) in right.enumerate::<(usize, usize)>() {
+            assert_eq!(element, (index.0 + x, index.1));
+        }
+        assert_eq!(left.concat_horizontal(right), grid);
+    }
+
+    #[test]
+    fn test_split_cols_example() {
+        let (left, right) = grid().split_cols(2).unwrap();
+        assert_eq!(left, Grid::from_2d_array([[3, 1], [1, 5]]));
+        assert_eq!(right, Grid::from_2d_array([[4], [9]]));
+        let (left, right) = grid().split_cols(0).unwrap();
+        assert_eq!(left, Grid::filled(0, (0, 2)));
+        assert_eq!(right, grid());
+    }
+
+    #[rstest]
+    #[case::at_width(grid(), 3)]
+    #[case::past_width(grid(), 4)]
+    #[case::empty(Grid::filled(0, (0, 3)), 0)]
+    fn test_split_cols_out_of_bounds(#[case] grid: Grid<i32>, #[case] x: usize) {
+        assert_eq!(grid.split_cols(x), None);
+    }
+
+    #[test]
+    fn test_split_rows_ref() {
+        let grid = indices((3, 4));
+        let (top, bottom) = grid.split_rows_ref(1).unwrap();
+        assert_eq!(top.dimensions(), (3, 1));
+        assert_eq!(bottom.dimensions(), (3, 3));
+        assert_eq!(top[(2, 0)], (2, 0));
+        assert_eq!(bottom[(2, 0)], (2, 1));
+        assert_eq!(bottom.get((1, 2)), Some(&(1, 3)));
+        assert_eq!(bottom.get((1, 3)), None);
+        let bottom_elements: Vec<_> = bottom.enumerate::<(usize, usize)>().collect();
+        assert_eq!(bottom_elements.len(), 9);
+        assert_eq!(bottom_elements[3], ((0, 1), &(0, 2)));
+        assert_eq!(top.to_grid().concat_vertical(bottom.to_grid()), grid);
+        assert_eq!(Some((top, bottom)), grid.split_rows_ref(1));
+    }
+
+    #[test]
+    fn test_grid_view_debug_formatting() {
+        let grid = grid();
+        let (_, bottom) = grid.split_rows_ref(1).unwrap();
+        assert_str_eq!(format!("{bottom:?}"), "[\n    [1, 5, 9],\n]");
+        let (top, _) = grid.split_rows_ref(0).unwrap();
+        assert_str_eq!(format!("{top:?}"), "<empty grid: (3, 0)>");
+    }
+
+    /// Collects the elements of each diagonal of a grid.
+    fn collect_diagonals<'a, D: Iterator<Item = ((usize, usize), &'a i32)>>(
+        diagonals: impl Iterator<Item = D>,
+    ) -> Vec<Vec<i32>> {
+        diagonals
+            .map(|diagonal| diagonal.map(|(_, &element)| element).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = grid();
+        let expected = vec![vec![1], vec![3, 5], vec![1, 9], vec![4]];
+        assert_eq!(collect_diagonals(grid.diagonals()), expected);
+    }
+
+    #[test]
+    fn test_anti_diagonals() {
+        let grid = grid();
+        let expected = vec![vec![3], vec![1, 1], vec![4, 5], vec![9]];
+        assert_eq!(collect_diagonals(grid.anti_diagonals()), expected);
+    }
+
+    #[test]
+    fn test_main_diagonals() {
         #[rustfmt::skip]
-        let expected = Grid::from_2d_array([
-            [3, 1, 4],
-            [2, 5, 6],
+        let grid = Grid::from_2d_array([
+            [1, 2, 3],
+            [4, 5, 6],
+            [7, 8, 9],
         ]);
-        *grid.get_mut((0, 1)).unwrap() = 2;
-        grid[(2, 1)] = 6;
-        assert_eq!(grid, expected);
-        assert_eq!(grid.get_mut((4, 1)), None);
+        let diagonals = collect_diagonals(grid.diagonals());
+        assert_eq!(
+            diagonals,
+            [vec![7], vec![4, 8], vec![1, 5, 9], vec![2, 6], vec![3]]
+        );
+        let anti_diagonals = collect_diagonals(grid.anti_diagonals());
+        assert_eq!(
+            anti_diagonals,
+            [vec![1], vec![2, 4], vec![3, 5, 7], vec![6, 8], vec![9]]
+        );
+    }
+
+    #[rstest]
+    fn test_diagonals_indices(
+        #[values((1, 1), (4, 3), (2, 5), (5, 1), (1, 4))] dimensions: (usize, usize),
+    ) {
+        let grid = indices(dimensions);
+        let (width, height) = dimensions;
+        let diagonals: Vec<Vec<_>> = grid
+            .diagonals::<(usize, usize)>()
+            .map(|diagonal| diagonal.collect())
+            .collect();
+        let anti_diagonals: Vec<Vec<_>> = grid
+            .anti_diagonals::<(usize, usize)>()
+            .map(|diagonal| diagonal.collect())
+            .collect();
+        assert_eq!(diagonals.len(), width + height - 1);
+        assert_eq!(anti_diagonals.len(), width + height - 1);
+        for (i, diagonal) in diagonals.iter().enumerate() {
+            for (k, &(index, &element)) in diagonal.iter().enumerate() {
+                assert_eq!(index, element);
+                // `x - y` is constant along a diagonal, and increases from one diagonal to the next.
+                assert_eq!(index.0 + height - 1, index.1 + i);
+                if k > 0 {
+                    assert_eq!(diagonal[k - 1].0, (index.0 - 1, index.1 - 1));
+                }
+            }
+        }
+        for (i, diagonal) in anti_diagonals.iter().enumerate() {
+            for (k, &(index, &element)) in diagonal.iter().enumerate() {
+                assert_eq!(index, element);
+                assert_eq!(index.0 + index.1, i);
+                if k > 0 {
+                    assert_eq!(diagonal[k - 1].0, (index.0 + 1, index.1 - 1));
+                }
+            }
+        }
+        // Every element is on exactly one diagonal in each direction.
+        for diagonals in [diagonals, anti_diagonals] {
+            let mut elements: Vec<_> = diagonals
+                .into_iter()
+                .flatten()
+                .map(|(index, _)| index)
+                .collect();
+            elements.sort_by_key(|&(x, y)| (y, x));
+            assert_eq!(elements, grid.iter().copied().collect::<Vec<_>>());
+        }
+    }
+
+    #[rstest]
+    #[case((0, 0))]
+    #[case((3, 0))]
+    #[case((0, 3))]
+    fn test_diagonals_empty(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(0, dimensions);
+        assert_eq!(grid.diagonals::<(usize, usize)>().count(), 0);
+        assert_eq!(grid.anti_diagonals::<(usize, usize)>().count(), 0);
     }
 
     #[test]
-    #[should_panic(expected = "index (5, -6) out of bounds for dimensions (3, 2)")]
-    fn test_set_out_of_bounds() {
-        grid()[(5, -6)] = 0;
+    fn test_spiral_iter() {
+        let grid = indices((3, 3));
+        let actual: Vec<_> = grid.spiral_iter().map(|(x, y, _)| (x, y)).collect();
+        let expected = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+            (1, 1),
+        ];
+        assert_eq!(actual, expected);
     }
 
     #[test]
-    fn test_iteration() {
-        let grid = grid();
-        let actual: Vec<((i32, i32), &i32)> = grid.enumerate().collect();
-        let expected = vec![
-            ((0, 0), &3),
-            ((1, 0), &1),
-            ((2, 0), &4),
-            ((0, 1), &1),
-            ((1, 1), &5),
-            ((2, 1), &9),
+    fn test_spiral_iter_non_square() {
+        let actual: Vec<_> = grid().spiral_iter().map(|(_, _, &n)| n).collect();
+        assert_eq!(actual, [3, 1, 4, 9, 5, 1]);
+        let grid = indices((2, 4));
+        let actual: Vec<_> = grid.spiral_iter().map(|(x, y, _)| (x, y)).collect();
+        let expected = [
+            (0, 0),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (0, 3),
+            (0, 2),
+            (0, 1),
         ];
         assert_eq!(actual, expected);
     }
 
     #[test]
-    fn test_map() {
-        #[rustfmt::skip]
-        let grid_doubled = Grid::from_2d_array([
-            [ 6,  2,  8],
-            [ 2, 10, 18],
-        ]);
-        assert_eq!(grid().map(|n| n * 2), grid_doubled);
+    fn test_zigzag_iter() {
+        let grid = indices((3, 3));
+        let actual: Vec<_> = grid.zigzag_iter().map(|(x, y, _)| (x, y)).collect();
+        let expected = [
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (0, 2),
+            (1, 1),
+            (2, 0),
+            (2, 1),
+            (1, 2),
+            (2, 2),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_zigzag_iter_jpeg() {
+        // The first row of the standard JPEG zigzag table for 8×8 blocks,
+        // giving the position in the zigzag order of each element.
+        let grid = indices((8, 8));
+        let order: Vec<_> = grid.zigzag_iter().map(|(x, y, _)| (x, y)).collect();
+        let first_row: Vec<_> = (0..8)
+            .map(|x| order.iter().position(|&index| index == (x, 0)).unwrap())
+            .collect();
+        assert_eq!(first_row, [0, 1, 5, 6, 14, 15, 27, 28]);
+        let last_row: Vec<_> = (0..8)
+            .map(|x| order.iter().position(|&index| index == (x, 7)).unwrap())
+            .collect();
+        assert_eq!(last_row, [35, 36, 48, 49, 57, 58, 62, 63]);
+    }
+
+    #[rstest]
+    #[case::empty_0_0((0, 0))]
+    #[case::empty_3_0((3, 0))]
+    #[case::empty_0_3((0, 3))]
+    #[case::single((1, 1))]
+    #[case::row((5, 1))]
+    #[case::column((1, 5))]
+    #[case::wide((4, 3))]
+    #[case::tall((3, 4))]
+    #[case::large((7, 10))]
+    fn test_traversals_visit_all(#[case] dimensions: (usize, usize)) {
+        let grid = indices(dimensions);
+        let spiral: Vec<_> = grid.spiral_iter().collect();
+        assert_visits_all(&spiral, &grid);
+        let zigzag: Vec<_> = grid.zigzag_iter().collect();
+        assert_visits_all(&zigzag, &grid);
     }
 
     #[test]
@@ -371,4 +3151,120 @@ mod tests {
         let expected = format!("<empty grid: ({width}, {height})>");
         assert_str_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_display_formatting() {
+        let actual = format!("{}\n", grid());
+        let expected = indoc! {"
+            3 1 4
+            1 5 9
+        "};
+        assert_str_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_display_alignment() {
+        let grid = Grid::from_2d_array([[1, -20, 300], [4000, 5, -6], [7, 80, 9]]);
+        let right = indoc! {"
+               1 -20 300
+            4000   5  -6
+               7  80   9"};
+        assert_str_eq!(format!("{grid}"), right);
+        assert_str_eq!(format!("{grid:>}"), right);
+        // The last column is padded too, so these lines have trailing spaces.
+        let left = ["1    -20 300", "4000 5   -6 ", "7    80  9  "].join("\n");
+        assert_str_eq!(format!("{grid:<}"), left);
+        let center = [" 1   -20 300", "4000  5  -6 ", " 7   80   9 "].join("\n");
+        assert_str_eq!(format!("{grid:^}"), center);
+    }
+
+    #[test]
+    fn test_display_strings() {
+        let grid = Grid::from_2d_array([["a", "bb"], ["ccc", "é"]]);
+        let expected = indoc! {"
+              a bb
+            ccc  é"};
+        assert_str_eq!(format!("{grid}"), expected);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_display_formatting_empty(#[case] width: usize, #[case] height: usize) {
+        assert_str_eq!(format!("{}", Grid::filled(0, (width, height))), "");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("3 1 4\n1 5 9".parse(), Ok(grid()));
+    }
+
+    #[test]
+    fn test_from_str_problem_description_example() {
+        // The region from the Rainfall problem description, with irregular whitespace.
+        let s = "\n3 1 4 2 5 9\n  2 6 5\t3 5 8\n\n9 7  9 3 1 3  \n";
+        let expected =
+            Grid::from_2d_array([[3, 1, 4, 2, 5, 9], [2, 6, 5, 3, 5, 8], [9, 7, 9, 3, 1, 3]]);
+        assert_eq!(s.parse(), Ok(expected));
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        let grid = Grid::from_2d_array([[1, -20, 300], [4000, 5, -6]]);
+        assert_eq!(grid.to_string().parse(), Ok(grid));
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::blank_lines("\n  \n\t\n")]
+    fn test_from_str_empty(#[case] s: &str) {
+        assert_eq!(s.parse(), Ok(Grid::<i32>::filled(0, (0, 0))));
+    }
+
+    #[rstest]
+    #[case::single("7", Grid::from_2d_array([[7]]))]
+    #[case::single_row("2 7 1 8\n", Grid::from_2d_array([[2, 7, 1, 8]]))]
+    #[case::single_column("2\n7\n1", Grid::from_2d_array([[2], [7], [1]]))]
+    fn test_from_str_shapes(#[case] s: &str, #[case] expected: Grid<i32>) {
+        assert_eq!(s.parse(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::first_element("x 1 4\n1 5 9", 0, 0)]
+    #[case::interior("3 1 4\n1 5.0 9", 1, 1)]
+    #[case::after_empty_line("3 1 4\n\n1 5 -", 2, 1)]
+    fn test_from_str_invalid_element(#[case] s: &str, #[case] x: usize, #[case] y: usize) {
+        let result: Result<Grid<i32>, _> = s.parse();
+        let Err(ParseGridError::InvalidElement {
+            x: actual_x,
+            y: actual_y,
+            ..
+        }) = result
+        else {
+            panic!("expected an invalid element, got {result:?}");
+        };
+        assert_eq!((actual_x, actual_y), (x, y));
+    }
+
+    #[rstest]
+    #[case::short_row("3 1 4\n1 5", 1, 3, 2)]
+    #[case::long_row("3 1 4\n1 5 9\n2 6 5 3", 2, 3, 4)]
+    #[case::after_empty_line("3 1\n\n\n4", 1, 2, 1)]
+    fn test_from_str_ragged(
+        #[case] s: &str,
+        #[case] y: usize,
+        #[case] expected: usize,
+        #[case] actual: usize,
+    ) {
+        let result: Result<Grid<i32>, _> = s.parse();
+        assert_eq!(
+            result,
+            Err(ParseGridError::RaggedRow {
+                y,
+                expected,
+                actual,
+            })
+        );
+    }
 }
@@ -0,0 +1,313 @@
+//! Helper module that provides a hexagonal grid type, as a companion to [`Grid`].
+//!
+//! Hexagons are addressed with _axial coordinates_ `(q, r)`:
+//! `q` increases to the right and `r` increases down and to the right,
+//! with the implicit third _cube coordinate_ `s = -q - r` increasing up and to the left.
+//! For pointy-topped hexagons, the neighborhood of `(0, 0)` looks like this:
+//! ```text
+//!      (0, -1) (1, -1)
+//! (-1, 0)  (0, 0)  (1, 0)
+//!      (-1, 1) (0, 1)
+//! ```
+//!
+//! Unlike [`Grid`], a hex grid is unbounded:
+//! every coordinate has a value, which is a default value unless it's been set to something else.
+//! To display or process part of a hex grid with rectangular-grid code,
+//! it can be converted to a [`Grid`] using "odd-r" offset coordinates,
+//! where each row is a constant `r`, and odd rows are shifted half a hexagon to the right:
+//! ```text
+//! (0, 0) (1, 0) (2, 0)
+//!    (0, 1) (1, 1) (2, 1)
+//! (0, 2) (1, 2) (2, 2)
+//! ```
+
+use crate::grid::Grid;
+use std::collections::HashMap;
+
+/// The axial coordinates of a hexagon in a hex grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HexCoord {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl HexCoord {
+    /// Constructs the coordinates `(q, r)`.
+    pub fn new(q: i32, r: i32) -> HexCoord {
+        HexCoord { q, r }
+    }
+
+    /// The implicit third cube coordinate, `-q - r`.
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+}
+
+/// The displacements from a hexagon to its six neighbors,
+/// starting from the right and going counterclockwise.
+const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+/// Returns the coordinates of the hexagon `steps` hexagons away from the given hexagon
+/// in the given direction.
+fn offset(coord: HexCoord, direction: (i32, i32), steps: i32) -> HexCoord {
+    let (dq, dr) = direction;
+    HexCoord::new(coord.q + dq * steps, coord.r + dr * steps)
+}
+
+/// Returns the six neighbors of the given hexagon,
+/// starting from the right and going counterclockwise.
+pub fn neighbors(coord: HexCoord) -> [HexCoord; 6] {
+    DIRECTIONS.map(|direction| offset(coord, direction, 1))
+}
+
+/// Returns the number of steps between neighboring hexagons needed to get from `a` to `b`.
+///
+/// With cube coordinates, this is the largest of the differences between corresponding coordinates.
+pub fn distance(a: HexCoord, b: HexCoord) -> u32 {
+    let (dq, dr, ds) = (a.q - b.q, a.r - b.r, a.s() - b.s());
+    dq.unsigned_abs()
+        .max(dr.unsigned_abs())
+        .max(ds.unsigned_abs())
+}
+
+/// Returns the hexagons at exactly the given distance from `center`,
+/// starting from the one at the bottom left and going counterclockwise.
+///
+/// There are `6 * radius` of them, or just `center` itself if `radius` is 0.
+pub fn ring(center: HexCoord, radius: u32) -> Vec<HexCoord> {
+    if radius == 0 {
+        return vec![center];
+    }
+    let radius = radius as i32;
+    let mut coord = offset(center, DIRECTIONS[4], radius);
+    let mut result = vec![];
+    for direction in DIRECTIONS {
+        for _ in 0..radius {
+            result.push(coord);
+            coord = offset(coord, direction, 1);
+        }
+    }
+    result
+}
+
+/// Returns the hexagons within the given distance of `center`,
+/// ordered by distance, with each ring ordered as in [`ring`].
+///
+/// There are `1 + 3 * radius * (radius + 1)` of them.
+pub fn spiral(center: HexCoord, radius: u32) -> Vec<HexCoord> {
+    (0..=radius)
+        .flat_map(|ring_radius| ring(center, ring_radius))
+        .collect()
+}
+
+/// An unbounded hexagonal grid, where every hexagon has a value.
+///
+/// Only values different from the default need to be stored,
+/// though values that have been set are stored even if they happen to equal the default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    /// The values that have been set.
+    data: HashMap<HexCoord, T>,
+    /// The value of every hexagon that hasn't been set.
+    default: T,
+}
+
+impl<T> HexGrid<T> {
+    /// Constructs a grid where every hexagon has the given value.
+    pub fn new(default: T) -> HexGrid<T> {
+        HexGrid {
+            data: HashMap::new(),
+            default,
+        }
+    }
+
+    /// The value of the given hexagon.
+    pub fn get(&self, coord: HexCoord) -> &T {
+        self.data.get(&coord).unwrap_or(&self.default)
+    }
+
+    /// Sets the value of the given hexagon.
+    pub fn set(&mut self, coord: HexCoord, value: T) {
+        self.data.insert(coord, value);
+    }
+}
+
+impl<T: Clone> HexGrid<T> {
+    /// Returns the values of the hexagons with offset coordinates
+    /// from `(0, 0)` to `(width - 1, height - 1)`, as a rectangular grid.
+    ///
+    /// Offset coordinates `(x, y)` correspond to axial coordinates `(x - (y - y % 2) / 2, y)`
+    /// (see the module documentation).
+    pub fn to_offset_grid(&self, width: usize, height: usize) -> Grid<T> {
+        let mut result = Grid::filled(self.default.clone(), (width, height));
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                let coord = HexCoord::new(x - (y - (y & 1)) / 2, y);
+                result[(x, y)] = self.get(coord).clone();
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hex_grid::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    const ORIGIN: HexCoord = HexCoord { q: 0, r: 0 };
+
+    #[rstest]
+    #[case::same(HexCoord::new(2, -1), HexCoord::new(2, -1), 0)]
+    #[case::neighbor(ORIGIN, HexCoord::new(1, -1), 1)]
+    #[case::along_q(ORIGIN, HexCoord::new(3, 0), 3)]
+    #[case::along_s(ORIGIN, HexCoord::new(-2, -2), 4)]
+    #[case::off_axis(HexCoord::new(-1, 2), HexCoord::new(2, -2), 4)]
+    #[case::far(HexCoord::new(-3, 5), HexCoord::new(4, 1), 7)]
+    fn test_distance(#[case] a: HexCoord, #[case] b: HexCoord, #[case] expected: u32) {
+        assert_eq!(distance(a, b), expected);
+        assert_eq!(distance(b, a), expected);
+    }
+
+    #[test]
+    fn test_s() {
+        assert_eq!(HexCoord::new(2, -5).s(), 3);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let center = HexCoord::new(3, -2);
+        let neighbors = neighbors(center);
+        assert_eq!(neighbors.len(), 6);
+        assert_eq!(neighbors.iter().collect::<HashSet<_>>().len(), 6);
+        for neighbor in neighbors {
+            assert_eq!(distance(center, neighbor), 1);
+        }
+        assert_eq!(neighbors[0], HexCoord::new(4, -2));
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    #[case(5)]
+    fn test_ring(#[case] radius: u32) {
+        let center = HexCoord::new(-1, 4);
+        let ring = ring(center, radius);
+        assert_eq!(ring.len(), (6 * radius).max(1) as usize);
+        assert_eq!(ring.iter().collect::<HashSet<_>>().len(), ring.len());
+        for &coord in &ring {
+            assert_eq!(distance(center, coord), radius);
+        }
+        // Consecutive hexagons in the ring are neighbors, including the last and first ones.
+        if radius > 0 {
+            for (i, &coord) in ring.iter().enumerate() {
+                let next = ring[(i + 1) % ring.len()];
+                assert_eq!(distance(coord, next), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ring_2() {
+        let ring = ring(ORIGIN, 2);
+        assert_eq!(ring.len(), 12);
+        assert_eq!(ring[0], HexCoord::new(-2, 2));
+        assert_eq!(ring[1], HexCoord::new(-1, 2));
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 7)]
+    #[case(2, 19)]
+    #[case(3, 37)]
+    fn test_spiral(#[case] radius: u32, #[case] expected_len: usize) {
+        let center = HexCoord::new(2, 2);
+        let spiral = spiral(center, radius);
+        assert_eq!(spiral.len(), expected_len);
+        assert_eq!(spiral[0], center);
+        let distances: Vec<u32> = spiral
+            .iter()
+            .map(|&coord| distance(center, coord))
+            .collect();
+        assert!(distances.is_sorted());
+        assert_eq!(spiral.iter().collect::<HashSet<_>>().len(), expected_len);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = HexGrid::new('.');
+        assert_eq!(grid.get(HexCoord::new(100, -7)), &'.');
+        grid.set(HexCoord::new(100, -7), '#');
+        grid.set(ORIGIN, '@');
+        assert_eq!(grid.get(HexCoord::new(100, -7)), &'#');
+        assert_eq!(grid.get(ORIGIN), &'@');
+        assert_eq!(grid.get(HexCoord::new(-7, 100)), &'.');
+        grid.set(ORIGIN, '.');
+        assert_eq!(grid.get(ORIGIN), &'.');
+    }
+
+    #[test]
+    fn test_to_offset_grid() {
+        let mut grid = HexGrid::new(0);
+        grid.set(HexCoord::new(0, 0), 1);
+        grid.set(HexCoord::new(2, 0), 2);
+        // Row 1 is shifted right, so its offset coordinates match its axial coordinates.
+        grid.set(HexCoord::new(1, 1), 3);
+        // Row 2 starts one hexagon further left in axial coordinates.
+        grid.set(HexCoord::new(-1, 2), 4);
+        grid.set(HexCoord::new(1, 2), 5);
+        grid.set(HexCoord::new(0, 3), 6);
+        // Outside the offset grid.
+        grid.set(HexCoord::new(0, -1), 7);
+        grid.set(HexCoord::new(3, 0), 8);
+        let expected = Grid::from_2d_array([[1, 0, 2], [0, 3, 0], [4, 0, 5], [0, 6, 0]]);
+        assert_eq!(grid.to_offset_grid(3, 4), expected);
+    }
+
+    #[test]
+    fn test_to_offset_grid_neighbors() {
+        // In an odd-r layout, a hexagon in an even row touches the two cells above it
+        // at the same x and one to the left, while one in an odd row touches the same x and one to the right.
+        for center in [HexCoord::new(1, 2), HexCoord::new(1, 1)] {
+            let mut grid = HexGrid::new(false);
+            for neighbor in neighbors(center) {
+                grid.set(neighbor, true);
+            }
+            let offset = grid.to_offset_grid(4, 4);
+            let (x, y) = (center.q + (center.r - (center.r & 1)) / 2, center.r);
+            let shift = if y % 2 == 0 { -1 } else { 1 };
+            let expected: HashSet<(i32, i32)> = [
+                (x - 1, y),
+                (x + 1, y),
+                (x, y - 1),
+                (x + shift, y - 1),
+                (x, y + 1),
+                (x + shift, y + 1),
+            ]
+            .into_iter()
+            .collect();
+            let actual: HashSet<(i32, i32)> = offset
+                .enumerate::<(i32, i32)>()
+                .filter(|&(_, &is_neighbor)| is_neighbor)
+                .map(|(index, _)| index)
+                .collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_to_offset_grid_empty(#[case] width: usize, #[case] height: usize) {
+        let mut grid = HexGrid::new(0);
+        grid.set(ORIGIN, 1);
+        assert_eq!(
+            grid.to_offset_grid(width, height),
+            Grid::filled(0, (width, height))
+        );
+    }
+}
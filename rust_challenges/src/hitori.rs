@@ -0,0 +1,282 @@
+//! Hitori is a logic puzzle played on a grid of numbers.
+//!
+//! Problem: blacken some of the cells of the grid, such that:
+//! - no number appears more than once in any row or column among the unblackened cells,
+//! - no two blackened cells are orthogonally adjacent, and
+//! - the unblackened cells form a single orthogonally connected region.
+//!
+//! For example, in the following puzzle:
+//! ```text
+//! 1 2 3
+//! 2 2 1
+//! 3 1 3
+//! ```
+//! one of the 2s in the middle row must be blackened, as must one of the 2s in the middle column,
+//! so blackening the center cell takes care of both.
+//! Similarly, blackening the bottom right cell takes care of the 3s in the bottom row
+//! and right column, giving the unique solution (`#` for blackened):
+//! ```text
+//! 1 2 3
+//! 2 # 1
+//! 3 1 #
+//! ```
+//!
+//! The solver marks each cell as blackened, unblackened, or undecided.
+//! Blackening a cell forces its neighbors to be unblackened,
+//! and leaving a cell unblackened forces every other cell with the same number
+//! in its row and column to be blackened.
+//! When no more cells can be decided this way, the solver guesses for an undecided cell,
+//! and backtracks when the guess leads to a contradiction.
+
+use crate::flood_fill::{flood_fill_collect, Connectivity};
+use crate::grid::Grid;
+
+/// A Hitori puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HitoriBoard(pub Grid<u8>);
+
+/// A solution to a Hitori puzzle, where `true` means that the cell is blackened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HitoriSolution(pub Grid<bool>);
+
+type CellIndex = (usize, usize);
+
+/// The state of the solver: for each cell, whether it's blackened, or None if undecided.
+type State = Grid<Option<bool>>;
+
+/// Returns the cells other than `cell` that are in the same row or column as `cell`
+/// and contain the same number.
+fn duplicates(grid: &Grid<u8>, cell: CellIndex) -> impl Iterator<Item = CellIndex> + '_ {
+    let (x, y) = cell;
+    let row = (0..grid.width()).map(move |i| (i, y));
+    let column = (0..grid.height()).map(move |j| (x, j));
+    row.chain(column)
+        .filter(move |&index| index != cell && grid[index] == grid[cell])
+}
+
+/// Decides as many cells as possible based on the cells that are already decided.
+///
+/// Returns None if a contradiction is found.
+fn propagate(grid: &Grid<u8>, state: &mut State) -> Option<()> {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        let mut decide = |state: &mut State, index: CellIndex, is_black: bool| {
+            match state[index] {
+                Some(existing) if existing != is_black => return None,
+                Some(_) => {}
+                None => {
+                    state[index] = Some(is_black);
+                    changed = true;
+                }
+            }
+            Some(())
+        };
+        for (index, _) in grid.enumerate::<CellIndex>() {
+            match state[index] {
+                Some(true) => {
+                    for (neighbor, _) in grid.orthogonal_neighbors(index) {
+                        decide(state, neighbor, false)?;
+                    }
+                }
+                Some(false) => {
+                    for duplicate in duplicates(grid, index) {
+                        decide(state, duplicate, true)?;
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+    // Every unblackened cell must still be able to reach every other one.
+    let mut unblackened = state
+        .enumerate::<(i32, i32)>()
+        .filter(|&(_, &cell)| cell == Some(false))
+        .map(|(index, _)| index);
+    if let Some(start) = unblackened.next() {
+        let reachable =
+            flood_fill_collect(state, start, |&cell| cell != Some(true), Connectivity::Four);
+        let num_reachable = reachable
+            .into_iter()
+            .filter(|&index| state[index] == Some(false))
+            .count();
+        if num_reachable != 1 + unblackened.count() {
+            return None;
+        }
+    }
+    Some(())
+}
+
+/// Returns a solution that extends the given state, or None if there is none.
+fn search(grid: &Grid<u8>, mut state: State) -> Option<State> {
+    propagate(grid, &mut state)?;
+    let Some((index, _)) = state
+        .enumerate::<CellIndex>()
+        .find(|(_, cell)| cell.is_none())
+    else {
+        return Some(state);
+    };
+    for is_black in [true, false] {
+        let mut guess = state.clone();
+        guess[index] = Some(is_black);
+        if let Some(solution) = search(grid, guess) {
+            return Some(solution);
+        }
+    }
+    None
+}
+
+/// Returns a solution to the given Hitori puzzle, or None if there is no solution.
+///
+/// Cells whose numbers are unique in their row and column are never blackened,
+/// since blackening them is never necessary.
+/// If there are multiple solutions otherwise, any one of them may be returned.
+pub fn solve(board: &HitoriBoard) -> Option<HitoriSolution> {
+    let HitoriBoard(grid) = board;
+    let mut state = Grid::filled(None, grid.dimensions());
+    for (index, _) in grid.enumerate::<CellIndex>() {
+        if duplicates(grid, index).next().is_none() {
+            state[index] = Some(false);
+        }
+    }
+    let state = search(grid, state)?;
+    Some(HitoriSolution(state.map(|cell| cell.unwrap())))
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid, and `n = max(width, height)`.
+        This function completes in `O(2^c * c^2 * n)` time in the worst case,
+        though propagation usually prunes the search far more than this suggests.
+        - The search tree has `O(2^c)` nodes.
+        - At each node, propagation makes at most `c + 1` passes over the grid,
+          since each pass that doesn't finish decides at least one cell.
+          Each pass takes `O(c * n)` time, looking at the `O(n)` duplicates or neighbors of each cell.
+        - The connectivity check takes `O(c)` time per node.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hitori::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Hitori solution, with `#` for blackened cells and `.` for unblackened ones.
+    macro_rules! solution {
+        (@cell #) => {true};
+        (@cell .) => {false};
+        ($([$($cell:tt)*])*) => {
+            HitoriSolution(Grid::from_2d_array([$([$(solution!(@cell $cell)),*]),*]))
+        };
+    }
+
+    /// Checks that the given solution satisfies all the rules of Hitori for the given board.
+    fn assert_valid(board: &HitoriBoard, solution: &HitoriSolution) {
+        let (HitoriBoard(grid), HitoriSolution(blackened)) = (board, solution);
+        assert_eq!(grid.dimensions(), blackened.dimensions());
+        for (index, &is_black) in blackened.enumerate::<CellIndex>() {
+            if is_black {
+                assert!(
+                    blackened
+                        .orthogonal_neighbors(index)
+                        .all(|(_, &neighbor)| !neighbor),
+                    "adjacent blackened cells at {index:?}"
+                );
+            } else {
+                assert!(
+                    duplicates(grid, index).all(|duplicate| blackened[duplicate]),
+                    "repeated number at {index:?}"
+                );
+            }
+        }
+        let unblackened: Vec<_> = blackened
+            .enumerate::<(i32, i32)>()
+            .filter(|(_, &is_black)| !is_black)
+            .map(|(index, _)| index)
+            .collect();
+        if let Some(&start) = unblackened.first() {
+            let region = flood_fill_collect(blackened, start, |&b| !b, Connectivity::Four);
+            assert_eq!(
+                region.len(),
+                unblackened.len(),
+                "unblackened cells not connected"
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::empty_0_0((0, 0))]
+    #[case::empty_3_0((3, 0))]
+    #[case::empty_0_3((0, 3))]
+    fn test_empty(#[case] dimensions: (usize, usize)) {
+        let board = HitoriBoard(Grid::filled(1, dimensions));
+        assert_eq!(
+            solve(&board),
+            Some(HitoriSolution(Grid::filled(false, dimensions)))
+        );
+    }
+
+    #[rstest]
+    #[case::single(
+        HitoriBoard(Grid::from_2d_array([[7]])),
+        solution![[.]],
+    )]
+    #[case::no_duplicates(
+        HitoriBoard(Grid::from_2d_array([[1, 2], [2, 1]])),
+        solution![[. .] [. .]],
+    )]
+    #[case::row(
+        HitoriBoard(Grid::from_2d_array([[1, 1, 1]])),
+        solution![[# . #]],
+    )]
+    #[case::example(
+        HitoriBoard(Grid::from_2d_array([
+            [1, 2, 3],
+            [2, 2, 1],
+            [3, 1, 3],
+        ])),
+        solution![
+            [. . .]
+            [. # .]
+            [. . #]
+        ],
+    )]
+    #[case::five_by_five(
+        HitoriBoard(Grid::from_2d_array([
+            [1, 2, 3, 3, 4],
+            [4, 3, 2, 5, 1],
+            [3, 4, 1, 5, 5],
+            [2, 2, 3, 4, 1],
+            [3, 3, 4, 1, 5],
+        ])),
+        solution![
+            [. . # . .]
+            [. # . . #]
+            [. . . # .]
+            [. # . . .]
+            [# . . . #]
+        ],
+    )]
+    fn test_solve(#[case] board: HitoriBoard, #[case] expected: HitoriSolution) {
+        let actual = solve(&board);
+        if let Some(solution) = &actual {
+            assert_valid(&board, solution);
+        }
+        assert_eq!(actual, Some(expected));
+    }
+
+    #[rstest]
+    // Blackening either diagonal leaves the unblackened cells disconnected.
+    #[case::disconnected(HitoriBoard(Grid::from_2d_array([[1, 1], [1, 1]])))]
+    // Three of the four cells must be blackened, so two of them must be adjacent.
+    #[case::adjacent(HitoriBoard(Grid::from_2d_array([[2, 2, 2, 2]])))]
+    // Every way of removing the repeated numbers either blackens adjacent cells
+    // or cuts off part of the grid.
+    #[case::larger(HitoriBoard(Grid::from_2d_array([
+        [1, 1, 2],
+        [1, 2, 2],
+        [2, 2, 1],
+    ])))]
+    fn test_no_solution(#[case] board: HitoriBoard) {
+        assert_eq!(solve(&board), None);
+    }
+}
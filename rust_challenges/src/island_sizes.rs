@@ -28,7 +28,7 @@
 //! with sizes 6, 6, 4, 6, and 6.
 
 use crate::grid::Grid;
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
 
 /// The possible square types.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -73,105 +73,80 @@ macro_rules! island_grid {
 
 /// Returns the sizes of the islands in the given grid (in no particular order).
 pub fn island_sizes(grid: &Grid<Square>) -> Vec<usize> {
-    let mut visited = Grid::filled(false, grid.dimensions());
-    grid.enumerate()
-        .filter_map(|(index, _)| visit_island(grid, index, &mut visited))
+    grid.connected_components::<SquareIndex>(|&square| square == Square::Land, true)
+        .iter()
+        .map(Vec::len)
         .collect()
     /*
         Time complexity analysis:
         Let `s` be the number of squares in the grid.
         This function completes in `O(s)` time in the worst case --
         for an `n×n` square grid, this translates to a time complexity of `O(n^2)`.
-        - Note that all operations of `VisitTracker` complete in `O(1)` time.
-        - Creating `visited`, iterating over `grid`, and collecting into a `Vec`
-          can all be done in `O(s)` time, disregarding work done in `visit_island`.
-        - The work done across all `visit_island` calls takes `O(s)` time:
-            - The code outside the while loop takes `O(1)` time,
-              and executes at most `s` times, taking `O(s)` time across all calls.
-            - The while loop condition takes `O(1)` time,
-              so the cost can be absorbed into the loop body / post-loop return statement.
-            - The loop body takes `O(s)` time across all calls:
-                - The loop body takes `O(1)` time to complete.
-                  Note that `NEIGHBOR_DISPLACEMENTS` has a fixed 8 elements.
-                - The loop body executes at most `s` times across all calls,
-                  since a square can only be visited (and thus, added into a tracker's queue) once.
+        - Finding the connected components of the land squares takes `O(s)` time:
+          each square is visited at most once, and has at most 8 neighbors to check.
+        - Collecting the sizes of the components takes `O(s)` time,
+          since there are at most `s` components.
     */
 }
 
-type SquareIndex = (i32, i32);
-
-#[rustfmt::skip]
-const NEIGHBOR_DISPLACEMENTS: [SquareIndex; 8] = [
-    (-1, -1), (0, -1), (1, -1),
-    (-1,  0),          (1,  0),
-    (-1,  1), (0,  1), (1,  1),
-];
-
-/// Visits every square in the island containing the square at the given index,
-/// and returns the number of squares visited.
-/// Returns None if the square at the given index is a water square or has already been visited.
-fn visit_island(
-    grid: &Grid<Square>,
-    index: SquareIndex,
-    visited: &mut Grid<bool>,
-) -> Option<usize> {
-    let mut tracker = VisitTracker::new(grid, visited);
-    if tracker.visit(index).is_err() {
-        return None;
-    }
-    while let Some((x, y)) = tracker.queue.pop_front() {
-        let neighbor_indices = NEIGHBOR_DISPLACEMENTS
-            .iter()
-            .map(|(dx, dy)| (x + dx, y + dy));
-        for neighbor_index in neighbor_indices {
-            _ = tracker.visit(neighbor_index);
-        }
-    }
-    Some(tracker.num_visited)
-}
-
-/// Data type for keeping track of visited squares.
-struct VisitTracker<'a> {
-    /// The grid whose squares are being visited.
-    grid: &'a Grid<Square>,
-    /// A grid indicating which squares have been visited by any tracker.
-    visited: &'a mut Grid<bool>,
-    /// The number of squares visited by this tracker.
-    num_visited: usize,
-    /// A queue of indices of visited squares whose neighbors still need visiting.
-    queue: VecDeque<SquareIndex>,
+/// Returns a grid with the index of the island that each land square belongs to
+/// (None for water squares).
+///
+/// Islands are numbered from 0, in the order of their first squares in row-major order.
+pub fn label_islands(grid: &Grid<Square>) -> Grid<Option<usize>> {
+    let (labels, _) = label_islands_with_sizes(grid);
+    labels
 }
 
-impl<'a> VisitTracker<'a> {
-    /// Creates a new tracker that has not yet visited any squares.
-    fn new(grid: &'a Grid<Square>, visited: &'a mut Grid<bool>) -> VisitTracker<'a> {
-        VisitTracker {
-            grid,
-            visited,
-            num_visited: 0,
-            queue: VecDeque::new(),
+/// Returns the indices of the islands that each island touches (by index, as in `label_islands`),
+/// where two islands touch if they're only separated by a single water square,
+/// with land squares from the two islands on opposite sides of it, either horizontally or vertically.
+///
+/// Every island has an entry, even if it doesn't touch any other islands.
+pub fn island_adjacency_graph(grid: &Grid<Square>) -> HashMap<usize, HashSet<usize>> {
+    let (labels, sizes) = label_islands_with_sizes(grid);
+    let mut result: HashMap<usize, HashSet<usize>> = (0..sizes.len())
+        .map(|island| (island, HashSet::new()))
+        .collect();
+    for ((x, y), &label) in labels.enumerate::<SquareIndex>() {
+        let Some(island) = label else {
+            continue;
+        };
+        for (between, beyond) in [((x + 1, y), (x + 2, y)), ((x, y + 1), (x, y + 2))] {
+            if grid.get(between) != Some(&Square::Water) {
+                continue;
+            }
+            if let Some(&Some(other)) = labels.get(beyond).filter(|&&other| other != label) {
+                result.get_mut(&island).unwrap().insert(other);
+                result.get_mut(&other).unwrap().insert(island);
+            }
         }
     }
+    result
+    /*
+        Time complexity analysis:
+        Let `s` be the number of squares in the grid.
+        Labelling the islands takes `O(s)` time, as in `island_sizes`,
+        and each square is checked against two other squares in `O(1)` expected time,
+        so this function completes in `O(s)` expected time.
+    */
+}
 
-    /// Visits the square at the given index, updating the tracker's info appropriately.
-    ///
-    /// Fails without updating the tracker if the index is out of bounds,
-    /// the square is a water square, or the square has already been visited.
-    fn visit(&mut self, index: SquareIndex) -> Result<(), ()> {
-        let Some(&square) = self.grid.get(index) else {
-            return Err(());
-        };
-        if square == Square::Land && !self.visited[index] {
-            self.visited[index] = true;
-            self.num_visited += 1;
-            self.queue.push_back(index);
-            Ok(())
-        } else {
-            Err(())
+/// Returns a grid with the index of the island that each land square belongs to,
+/// and the sizes of the islands, by index.
+fn label_islands_with_sizes(grid: &Grid<Square>) -> (Grid<Option<usize>>, Vec<usize>) {
+    let islands = grid.connected_components::<SquareIndex>(|&square| square == Square::Land, true);
+    let mut labels = Grid::filled(None, grid.dimensions());
+    for (island, squares) in islands.iter().enumerate() {
+        for &(index, _) in squares {
+            labels[index] = Some(island);
         }
     }
+    (labels, islands.iter().map(Vec::len).collect())
 }
 
+type SquareIndex = (i32, i32);
+
 #[cfg(test)]
 mod tests {
     use crate::island_sizes::*;
@@ -270,4 +245,70 @@ mod tests {
         actual.sort();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_label_islands() {
+        let grid = island_grid![
+            [# # . # .]
+            [. . . # .]
+            [# . # . .]
+        ];
+        let expected = Grid::from_2d_array([
+            [Some(0), Some(0), None, Some(1), None],
+            [None, None, None, Some(1), None],
+            [Some(2), None, Some(1), None, None],
+        ]);
+        assert_eq!(label_islands(&grid), expected);
+    }
+
+    #[rstest]
+    #[case::problem_description_example(island_grid![
+        [# # # . # # #]
+        [# # . . . # #]
+        [# . . # . . #]
+        [. . # . # . .]
+        [# . . # . . #]
+        [# # . . . # #]
+        [# # # . # # #]
+    ], vec![(0, vec![1, 3]), (1, vec![0, 4]), (2, vec![]), (3, vec![0, 4]), (4, vec![1, 3])])]
+    #[case::one_water_column(island_grid![
+        [# . #]
+        [# . #]
+    ], vec![(0, vec![1]), (1, vec![0])])]
+    #[case::one_water_row(island_grid![
+        [. # # .]
+        [. . . .]
+        [. . # #]
+    ], vec![(0, vec![1]), (1, vec![0])])]
+    #[case::two_water_columns(island_grid![
+        [# . . #]
+        [# . . #]
+    ], vec![(0, vec![]), (1, vec![])])]
+    #[case::water_square_between_diagonals(island_grid![
+        [# . .]
+        [. . #]
+    ], vec![(0, vec![]), (1, vec![])])]
+    #[case::same_island(island_grid![
+        [# . #]
+        [. # .]
+    ], vec![(0, vec![])])]
+    #[case::all_land(island_grid![
+        [# # # # #]
+        [# # # # #]
+        [# # # # #]
+    ], vec![(0, vec![])])]
+    #[case::all_water(island_grid![
+        [. . .]
+        [. . .]
+    ], vec![])]
+    fn test_island_adjacency_graph(
+        #[case] grid: Grid<Square>,
+        #[case] expected: Vec<(usize, Vec<usize>)>,
+    ) {
+        let expected: HashMap<usize, HashSet<usize>> = expected
+            .into_iter()
+            .map(|(island, neighbors)| (island, neighbors.into_iter().collect()))
+            .collect();
+        assert_eq!(island_adjacency_graph(&grid), expected);
+    }
 }
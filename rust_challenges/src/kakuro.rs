@@ -0,0 +1,438 @@
+//! Kakuro is a number puzzle played on a grid of blocks and entry cells.
+//! Each horizontal or vertical _run_ of consecutive entry cells has a clue
+//! in the block immediately before it (to the left of a horizontal run, or above a vertical run).
+//!
+//! Problem: fill every entry cell with a digit from 1 to 9,
+//! such that the digits in each run add up to the run's clue,
+//! and no digit is repeated within a run.
+//!
+//! For example, in the following puzzle
+//! (`\d` for a block with a clue for the run below it, `r\` for a block with a clue
+//! for the run to its right, `##` for a block without clues, and `__` for an entry cell):
+//! ```text
+//! ##  \4  \3
+//! 4\  __  __
+//! 3\  __  __
+//! ```
+//! the bottom row must contain 1 and 2, and the right column must also contain 1 and 2,
+//! while the top row must contain 1 and 3. So the top right cell must be 1,
+//! and the unique solution is:
+//! ```text
+//! ##  \4  \3
+//! 4\   3   1
+//! 3\   1   2
+//! ```
+//!
+//! The solver keeps track of the digits that each entry cell could still contain,
+//! and eliminates digits that can't be part of any valid combination for the cell's runs.
+//! When no more digits can be eliminated, it guesses a digit for the most constrained cell,
+//! and backtracks if the guess leads to a contradiction.
+
+use crate::grid::Grid;
+
+/// A cell of a Kakuro puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KakuroCell {
+    /// A block, with optional clues for the runs to its right and below it.
+    Block {
+        right_sum: Option<u32>,
+        down_sum: Option<u32>,
+    },
+    /// An entry cell, which may already have a digit filled in.
+    Entry(Option<u8>),
+}
+
+/// A Kakuro puzzle, or a (partial) solution to one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KakuroBoard(pub Grid<KakuroCell>);
+
+type CellIndex = (usize, usize);
+
+/// A run of consecutive entry cells, along with its clue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Run {
+    sum: u32,
+    cells: Vec<CellIndex>,
+}
+
+/// Returns every run with a clue on the given board.
+///
+/// A clue with no entry cells after it results in a run with no cells.
+fn runs(grid: &Grid<KakuroCell>) -> Vec<Run> {
+    let mut result = vec![];
+    for ((x, y), &cell) in grid.enumerate::<CellIndex>() {
+        let KakuroCell::Block {
+            right_sum,
+            down_sum,
+        } = cell
+        else {
+            continue;
+        };
+        let directions = [(right_sum, (1, 0)), (down_sum, (0, 1))];
+        for (sum, (dx, dy)) in directions {
+            let Some(sum) = sum else {
+                continue;
+            };
+            let cells = (1..)
+                .map(|i| (x + dx * i, y + dy * i))
+                .take_while(|&index| matches!(grid.get(index), Some(KakuroCell::Entry(_))))
+                .collect();
+            result.push(Run { sum, cells });
+        }
+    }
+    result
+}
+
+/// A set of digits from 1 to 9, where bit `d` is set if digit `d` is in the set.
+type Digits = u16;
+
+/// The set of all digits from 1 to 9.
+const ALL_DIGITS: Digits = 0b11_1111_1110;
+
+/// Returns the digits in the given set, in increasing order.
+fn digits_in(digits: Digits) -> impl Iterator<Item = u8> {
+    (1..=9).filter(move |&d| digits & (1 << d) != 0)
+}
+
+/// Returns every set of `len` distinct digits that add up to `sum`.
+fn combinations(len: usize, sum: u32) -> impl Iterator<Item = Digits> {
+    (0..=ALL_DIGITS)
+        .filter(|&digits| digits & !ALL_DIGITS == 0)
+        .filter(move |&digits| digits.count_ones() as usize == len)
+        .filter(move |&digits| digits_in(digits).map(u32::from).sum::<u32>() == sum)
+}
+
+/// Returns a solution to the given puzzle, with every entry cell filled in,
+/// or None if the puzzle has no solution.
+///
+/// Digits that are already filled in are kept. Entry cells that aren't part of any run
+/// with a clue may contain any digit.
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &KakuroBoard) -> Option<KakuroBoard> {
+    let KakuroBoard(grid) = board;
+    let runs = runs(grid);
+    if runs.iter().any(|run| run.cells.is_empty()) {
+        return None;
+    }
+    let candidates = grid.clone().map(|cell| match cell {
+        KakuroCell::Entry(Some(digit)) if (1..=9).contains(&digit) => 1 << digit,
+        KakuroCell::Entry(Some(_)) => 0,
+        KakuroCell::Entry(None) => ALL_DIGITS,
+        KakuroCell::Block { .. } => 0,
+    });
+    let candidates = search(candidates, &runs)?;
+    let mut grid = grid.clone();
+    for (index, &digits) in candidates.enumerate::<CellIndex>() {
+        if let KakuroCell::Entry(entry) = &mut grid[index] {
+            *entry = digits_in(digits).next();
+        }
+    }
+    Some(KakuroBoard(grid))
+}
+
+/// Tries to narrow down the given candidates to a single digit for each entry cell,
+/// such that every run is satisfied.
+///
+/// Returns the narrowed-down candidates, or None if that's impossible.
+fn search(mut candidates: Grid<Digits>, runs: &[Run]) -> Option<Grid<Digits>> {
+    if !propagate(&mut candidates, runs) {
+        return None;
+    }
+    // Guess a digit for the entry cell with the fewest remaining candidates (besides 1).
+    let undecided = candidates
+        .enumerate::<CellIndex>()
+        .filter(|&(_, &digits)| digits.count_ones() > 1)
+        .min_by_key(|&(_, &digits)| digits.count_ones());
+    let Some((index, &digits)) = undecided else {
+        return runs
+            .iter()
+            .all(|run| is_satisfied(&candidates, run))
+            .then_some(candidates);
+    };
+    digits_in(digits).find_map(|digit| {
+        let mut candidates = candidates.clone();
+        candidates[index] = 1 << digit;
+        search(candidates, runs)
+    })
+}
+
+/// Eliminates digits from the candidates for each entry cell
+/// until no more digits can be eliminated:
+/// a digit is eliminated from a cell if, for one of the cell's runs,
+/// no valid combination of digits for the run can contain the digit at that cell.
+///
+/// Returns false if some entry cell has no candidates left.
+fn propagate(candidates: &mut Grid<Digits>, runs: &[Run]) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for run in runs {
+            // A combination can only be used if every cell could contain one of its digits,
+            // and every digit could be placed in one of the cells.
+            let available = run
+                .cells
+                .iter()
+                .fold(0, |acc, &index| acc | candidates[index]);
+            // Digits that are already decided can't appear anywhere else in the run.
+            let decided: Vec<Digits> = run
+                .cells
+                .iter()
+                .map(|&index| candidates[index])
+                .filter(|digits| digits.count_ones() == 1)
+                .collect();
+            let usable = combinations(run.cells.len(), run.sum)
+                .filter(|&combination| combination & !available == 0)
+                .filter(|&combination| decided.iter().all(|&digit| combination & digit != 0))
+                .filter(|&combination| {
+                    run.cells
+                        .iter()
+                        .all(|&index| candidates[index] & combination != 0)
+                })
+                .fold(0, |acc, combination| acc | combination);
+            for &index in &run.cells {
+                let mut narrowed = candidates[index] & usable;
+                if narrowed.count_ones() != 1 {
+                    for &digit in &decided {
+                        narrowed &= !digit;
+                    }
+                }
+                if narrowed != candidates[index] {
+                    candidates[index] = narrowed;
+                    changed = true;
+                }
+                if narrowed == 0 {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+/// Returns whether every cell of the given run has been narrowed down to a single digit,
+/// with the digits being distinct and adding up to the run's clue.
+fn is_satisfied(candidates: &Grid<Digits>, run: &Run) -> bool {
+    let mut seen: Digits = 0;
+    let mut sum = 0;
+    for &index in &run.cells {
+        let digits = candidates[index];
+        if digits.count_ones() != 1 || seen & digits != 0 {
+            return false;
+        }
+        seen |= digits;
+        sum += digits.trailing_zeros();
+    }
+    sum == run.sum
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kakuro::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const X: KakuroCell = KakuroCell::Block {
+        right_sum: None,
+        down_sum: None,
+    };
+    const E: KakuroCell = KakuroCell::Entry(None);
+
+    /// A block with a clue for the run to its right.
+    fn right(sum: u32) -> KakuroCell {
+        KakuroCell::Block {
+            right_sum: Some(sum),
+            down_sum: None,
+        }
+    }
+
+    /// A block with a clue for the run below it.
+    fn down(sum: u32) -> KakuroCell {
+        KakuroCell::Block {
+            right_sum: None,
+            down_sum: Some(sum),
+        }
+    }
+
+    /// A block with clues for both the run to its right and the run below it.
+    fn both(right_sum: u32, down_sum: u32) -> KakuroCell {
+        KakuroCell::Block {
+            right_sum: Some(right_sum),
+            down_sum: Some(down_sum),
+        }
+    }
+
+    /// An entry cell with the given digit filled in.
+    fn digit(d: u8) -> KakuroCell {
+        KakuroCell::Entry(Some(d))
+    }
+
+    /// The puzzle from the problem description.
+    fn problem_description_example() -> KakuroBoard {
+        KakuroBoard(Grid::from_2d_array([
+            [X, down(4), down(3)],
+            [right(4), E, E],
+            [right(3), E, E],
+        ]))
+    }
+
+    /// Asserts that the given board is a valid solution to the given puzzle.
+    fn assert_valid_solution(puzzle: &KakuroBoard, solution: &KakuroBoard) {
+        let KakuroBoard(puzzle) = puzzle;
+        let KakuroBoard(solution) = solution;
+        assert_eq!(solution.dimensions(), puzzle.dimensions());
+        for (index, &cell) in puzzle.enumerate::<CellIndex>() {
+            match (cell, solution[index]) {
+                (KakuroCell::Entry(None), KakuroCell::Entry(Some(d))) => {
+                    assert!((1..=9).contains(&d), "invalid digit {d} at {index:?}");
+                }
+                (cell, solution_cell) => assert_eq!(cell, solution_cell, "changed at {index:?}"),
+            }
+        }
+        for run in runs(solution) {
+            let digits: Vec<u8> = run
+                .cells
+                .iter()
+                .map(|&index| match solution[index] {
+                    KakuroCell::Entry(Some(d)) => d,
+                    cell => panic!("unexpected cell {cell:?} at {index:?}"),
+                })
+                .collect();
+            let sum: u32 = digits.iter().map(|&d| u32::from(d)).sum();
+            assert_eq!(sum, run.sum, "wrong sum for {run:?}");
+            let mut distinct = digits.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), digits.len(), "repeated digit in {run:?}");
+        }
+    }
+
+    #[test]
+    fn test_runs() {
+        let grid = Grid::from_2d_array([
+            [X, down(4), down(3), X],
+            [right(4), E, E, down(7)],
+            [right(3), E, both(9, 1), E],
+            [X, X, X, E],
+        ]);
+        let expected = [
+            Run {
+                sum: 4,
+                cells: vec![(1, 1), (1, 2)],
+            },
+            Run {
+                sum: 3,
+                cells: vec![(2, 1)],
+            },
+            Run {
+                sum: 4,
+                cells: vec![(1, 1), (2, 1)],
+            },
+            Run {
+                sum: 7,
+                cells: vec![(3, 2), (3, 3)],
+            },
+            Run {
+                sum: 3,
+                cells: vec![(1, 2)],
+            },
+            Run {
+                sum: 9,
+                cells: vec![(3, 2)],
+            },
+            Run {
+                sum: 1,
+                cells: vec![],
+            },
+        ];
+        assert_eq!(runs(&grid), expected);
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let expected = KakuroBoard(Grid::from_2d_array([
+            [X, down(4), down(3)],
+            [right(4), digit(3), digit(1)],
+            [right(3), digit(1), digit(2)],
+        ]));
+        assert_eq!(solve(&problem_description_example()), Some(expected));
+    }
+
+    #[test]
+    fn test_unique_solution() {
+        let puzzle = KakuroBoard(Grid::from_2d_array([
+            [X, down(16), down(24), X],
+            [right(17), E, E, down(17)],
+            [right(24), E, E, E],
+            [X, right(16), E, E],
+        ]));
+        let expected = KakuroBoard(Grid::from_2d_array([
+            [X, down(16), down(24), X],
+            [right(17), digit(9), digit(8), down(17)],
+            [right(24), digit(7), digit(9), digit(8)],
+            [X, right(16), digit(7), digit(9)],
+        ]));
+        let solution = solve(&puzzle).unwrap();
+        assert_valid_solution(&puzzle, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    // Two solutions, so propagation alone can't decide between them.
+    #[case::two_solutions(KakuroBoard(Grid::from_2d_array([
+        [X, down(5), down(5)],
+        [right(4), E, E],
+        [right(6), E, E],
+    ])))]
+    // Every row and column must contain 1, 2, and 4, in some order.
+    #[case::latin_square(KakuroBoard(Grid::from_2d_array([
+        [X, down(7), down(7), down(7)],
+        [right(7), E, E, E],
+        [right(7), E, E, E],
+        [right(7), E, E, E],
+    ])))]
+    #[case::prefilled(KakuroBoard(Grid::from_2d_array([
+        [X, down(7), down(7), down(7)],
+        [right(7), E, digit(4), E],
+        [right(7), E, E, digit(4)],
+        [right(7), E, E, E],
+    ])))]
+    #[case::no_clues(KakuroBoard(Grid::from_2d_array([
+        [X, E],
+        [E, E],
+    ])))]
+    fn test_solve(#[case] puzzle: KakuroBoard) {
+        let solution = solve(&puzzle).unwrap();
+        assert_valid_solution(&puzzle, &solution);
+    }
+
+    #[rstest]
+    #[case::impossible_sum(KakuroBoard(Grid::from_2d_array([
+        [X, X, X],
+        [right(18), E, E],
+    ])))]
+    #[case::conflicting_runs(KakuroBoard(Grid::from_2d_array([
+        [X, down(3), down(5)],
+        [right(3), E, E],
+        [right(3), E, E],
+    ])))]
+    #[case::conflicting_prefilled(KakuroBoard(Grid::from_2d_array([
+        [X, down(4), down(3)],
+        [right(4), digit(1), E],
+        [right(3), E, E],
+    ])))]
+    #[case::invalid_prefilled(KakuroBoard(Grid::from_2d_array([[right(10), digit(10)]])))]
+    #[case::empty_run(KakuroBoard(Grid::from_2d_array([[right(10), X]])))]
+    fn test_unsolvable(#[case] puzzle: KakuroBoard) {
+        assert_eq!(solve(&puzzle), None);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let puzzle = KakuroBoard(Grid::filled(E, (width, height)));
+        assert_eq!(solve(&puzzle), Some(puzzle));
+    }
+}
@@ -0,0 +1,400 @@
+//! KenKen is a number puzzle played on an `n`×`n` grid divided into _cages_:
+//! groups of cells, each with a target number and an arithmetic operation.
+//!
+//! Problem: fill the grid with numbers from 1 to `n`,
+//! such that no number is repeated within a row or column,
+//! and the numbers in each cage produce the cage's target when combined with its operation:
+//! - for addition and multiplication, the sum or product of all the numbers in the cage;
+//! - for subtraction and division (only for cages of exactly two cells),
+//!   the larger number minus or divided by the smaller one (without remainder);
+//! - for a given number (only for cages of exactly one cell), the number itself.
+//!
+//! For example, in the following puzzle
+//! (each letter is a cage, with its target and operation listed below the grid):
+//! ```text
+//! A B B
+//! A C C
+//! D D E
+//! ```
+//! with `A: 2÷`, `B: 5+`, `C: 2-`, `D: 3×`, and `E: 2`,
+//! the cage `E` must be 2, while `C` and `D` must each contain 1 and 3.
+//! That leaves 2 for the left cell of the middle row, so the top left cell must be 1,
+//! and the rest of the unique solution follows:
+//! ```text
+//! 1 2 3
+//! 2 3 1
+//! 3 1 2
+//! ```
+//!
+//! The solver (from [`crate::candidate_numbers`], shared with Futoshiki)
+//! keeps track of the numbers that each cell could still contain,
+//! eliminating numbers that already appear elsewhere in the same row or column,
+//! or that can't be part of any combination of numbers satisfying one of the cell's cages.
+//! When no more numbers can be eliminated, it guesses a number for the most constrained cell,
+//! and backtracks if the guess leads to a contradiction.
+
+use crate::candidate_numbers::{self, lines, numbers_in, Numbers};
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// An arithmetic operation for a KenKen cage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KenKenOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Given,
+}
+
+impl KenKenOp {
+    /// Returns whether the given numbers produce the target with this operation.
+    fn is_satisfied(self, numbers: &[u32], target: u32) -> bool {
+        match (self, numbers) {
+            (KenKenOp::Add, _) => numbers.iter().sum::<u32>() == target,
+            (KenKenOp::Multiply, _) => numbers
+                .iter()
+                .try_fold(1_u32, |product, &number| product.checked_mul(number))
+                .is_some_and(|product| product == target),
+            (KenKenOp::Subtract, &[a, b]) => a.abs_diff(b) == target,
+            (KenKenOp::Divide, &[a, b]) => {
+                let (larger, smaller) = (a.max(b), a.min(b));
+                larger.is_multiple_of(smaller) && larger / smaller == target
+            }
+            (KenKenOp::Given, &[a]) => a == target,
+            _ => false,
+        }
+    }
+}
+
+/// A cage of a KenKen puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cage {
+    pub cells: Vec<CellIndex>,
+    pub operation: KenKenOp,
+    pub target: u32,
+}
+
+/// A KenKen puzzle: the size of the grid, and the cages.
+///
+/// Cells that aren't part of any cage aren't constrained beyond their row and column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KenKenBoard {
+    pub size: usize,
+    pub cages: Vec<Cage>,
+}
+
+/// Returns a solution to the given puzzle, or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+///
+/// Panics if a cell of some cage is out of bounds, or if the size of the grid is more than 31.
+pub fn solve(board: &KenKenBoard) -> Option<Grid<u8>> {
+    let KenKenBoard { size, cages } = board;
+    let size = *size;
+    assert!(size < Numbers::BITS as usize, "size {size} too large");
+    let dimensions = (size, size);
+    for &(x, y) in cages.iter().flat_map(|cage| &cage.cells) {
+        if x >= size || y >= size {
+            panic!(
+                "cell {:?} out of bounds for dimensions {dimensions:?}",
+                (x, y)
+            );
+        }
+    }
+    let all_numbers = ((1 << size) - 1) << 1;
+    let cage_rule = |candidates: &mut Grid<Numbers>| narrow_cages(candidates, cages);
+    candidate_numbers::solve_candidates(
+        Grid::filled(all_numbers, dimensions),
+        &candidate_numbers::line_arcs(size, ()),
+        &lines(size),
+        &[&cage_rule],
+    )
+    /*
+        Time complexity analysis:
+        The puzzle is NP-complete in general,
+        and the search can take exponential time in the size of the grid in the worst case.
+        Each round of propagation takes `O(n^4 + c * n^k)` time,
+        where `n` is the size of the grid, `c` is the number of cages,
+        and `k` is the number of cells in the largest cage,
+        since every combination of numbers for a cage may be checked.
+    */
+}
+
+/// Eliminates numbers from the candidates for each cell if no valid combination of numbers
+/// for one of the cell's cages contains the number at that cell.
+///
+/// Once every cell is decided (and no row or column contains a repeated number),
+/// every cage is satisfied unless some cell is left with no candidates.
+///
+/// Returns the cells that lost candidates, or None if some cell has no candidates left.
+fn narrow_cages(candidates: &mut Grid<Numbers>, cages: &[Cage]) -> Option<Vec<CellIndex>> {
+    let mut narrowed = vec![];
+    for cage in cages {
+        let supported = supported_numbers(cage, candidates);
+        for (&index, numbers) in cage.cells.iter().zip(supported) {
+            let numbers = candidates[index] & numbers;
+            if numbers == 0 {
+                return None;
+            }
+            if numbers != candidates[index] {
+                candidates[index] = numbers;
+                narrowed.push(index);
+            }
+        }
+    }
+    Some(narrowed)
+}
+
+/// Returns, for each cell of the given cage,
+/// the candidates for that cell that are part of some valid combination of numbers for the cage.
+///
+/// In a valid combination, every number is one of its cell's candidates,
+/// cells of the cage in the same row or column have different numbers,
+/// and the numbers produce the cage's target.
+fn supported_numbers(cage: &Cage, candidates: &Grid<Numbers>) -> Vec<Numbers> {
+    /// Tries every way to extend the given partial combination,
+    /// adding the numbers of each valid combination found to `supported`.
+    fn extend(
+        cage: &Cage,
+        candidates: &Grid<Numbers>,
+        combination: &mut Vec<u32>,
+        supported: &mut [Numbers],
+    ) {
+        let i = combination.len();
+        let Some(&(x, y)) = cage.cells.get(i) else {
+            if cage.operation.is_satisfied(combination, cage.target) {
+                for (supported, &number) in supported.iter_mut().zip(combination.iter()) {
+                    *supported |= 1 << number;
+                }
+            }
+            return;
+        };
+        for number in numbers_in(candidates[(x, y)]) {
+            let conflict = cage.cells[..i].iter().zip(combination.iter()).any(
+                |(&(other_x, other_y), &other)| other == number && (other_x == x || other_y == y),
+            );
+            if !conflict {
+                combination.push(number);
+                extend(cage, candidates, combination, supported);
+                combination.pop();
+            }
+        }
+    }
+
+    let mut supported = vec![0; cage.cells.len()];
+    extend(cage, candidates, &mut vec![], &mut supported);
+    supported
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kenken::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a cage with the given operation, target, and cells.
+    fn cage(operation: KenKenOp, target: u32, cells: &[CellIndex]) -> Cage {
+        Cage {
+            cells: cells.to_vec(),
+            operation,
+            target,
+        }
+    }
+
+    /// Checks that the given grid is a valid solution to the given puzzle.
+    fn assert_valid(board: &KenKenBoard, solution: &Grid<u8>) {
+        let size = board.size;
+        assert_eq!(solution.dimensions(), (size, size));
+        // Each row and column must be a permutation of 1 to `n`, so the grid is a Latin square.
+        let expected: Vec<u8> = (1..=size as u8).collect();
+        for line in lines(size) {
+            let mut numbers: Vec<u8> = line.iter().map(|&index| solution[index]).collect();
+            numbers.sort();
+            assert_eq!(numbers, expected, "invalid line {line:?}");
+        }
+        for cage in &board.cages {
+            let numbers: Vec<u32> = cage
+                .cells
+                .iter()
+                .map(|&index| u32::from(solution[index]))
+                .collect();
+            assert!(
+                cage.operation.is_satisfied(&numbers, cage.target),
+                "unsatisfied cage {cage:?}"
+            );
+        }
+    }
+
+    /// The puzzle from the problem description.
+    fn problem_description_example() -> KenKenBoard {
+        KenKenBoard {
+            size: 3,
+            cages: vec![
+                cage(KenKenOp::Divide, 2, &[(0, 0), (0, 1)]),
+                cage(KenKenOp::Add, 5, &[(1, 0), (2, 0)]),
+                cage(KenKenOp::Subtract, 2, &[(1, 1), (2, 1)]),
+                cage(KenKenOp::Multiply, 3, &[(0, 2), (1, 2)]),
+                cage(KenKenOp::Given, 2, &[(2, 2)]),
+            ],
+        }
+    }
+
+    #[rstest]
+    #[case::add(KenKenOp::Add, &[1, 4, 2], 7, true)]
+    #[case::add_wrong(KenKenOp::Add, &[1, 4, 2], 8, false)]
+    #[case::multiply(KenKenOp::Multiply, &[3, 4, 2], 24, true)]
+    #[case::multiply_empty(KenKenOp::Multiply, &[], 1, true)]
+    #[case::multiply_overflow(KenKenOp::Multiply, &[65536, 65536], 0, false)]
+    #[case::subtract(KenKenOp::Subtract, &[2, 5], 3, true)]
+    #[case::subtract_three(KenKenOp::Subtract, &[6, 2, 1], 3, false)]
+    #[case::divide(KenKenOp::Divide, &[6, 2], 3, true)]
+    #[case::divide_remainder(KenKenOp::Divide, &[5, 2], 2, false)]
+    #[case::divide_one(KenKenOp::Divide, &[4], 4, false)]
+    #[case::given(KenKenOp::Given, &[4], 4, true)]
+    #[case::given_two(KenKenOp::Given, &[4, 4], 4, false)]
+    fn test_is_satisfied(
+        #[case] operation: KenKenOp,
+        #[case] numbers: &[u32],
+        #[case] target: u32,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(operation.is_satisfied(numbers, target), expected);
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 2, 3],
+            [2, 3, 1],
+            [3, 1, 2],
+        ]);
+        assert_eq!(solve(&problem_description_example()), Some(expected));
+    }
+
+    #[test]
+    fn test_4_by_4() {
+        let board = KenKenBoard {
+            size: 4,
+            cages: vec![
+                cage(KenKenOp::Add, 7, &[(0, 0), (0, 1), (1, 1)]),
+                cage(KenKenOp::Add, 9, &[(1, 0), (2, 0), (3, 0)]),
+                cage(KenKenOp::Add, 7, &[(2, 1), (2, 2), (2, 3)]),
+                cage(KenKenOp::Subtract, 1, &[(3, 1), (3, 2)]),
+                cage(KenKenOp::Add, 4, &[(0, 2), (1, 2)]),
+                cage(KenKenOp::Multiply, 12, &[(0, 3), (1, 3)]),
+                cage(KenKenOp::Given, 1, &[(3, 3)]),
+            ],
+        };
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 2, 3, 4],
+            [2, 4, 1, 3],
+            [3, 1, 4, 2],
+            [4, 3, 2, 1],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_6_by_6() {
+        let board = KenKenBoard {
+            size: 6,
+            cages: vec![
+                cage(KenKenOp::Add, 12, &[(0, 0), (0, 1), (0, 2)]),
+                cage(KenKenOp::Add, 10, &[(1, 0), (2, 0), (3, 0)]),
+                cage(KenKenOp::Given, 6, &[(4, 0)]),
+                cage(KenKenOp::Add, 11, &[(5, 0), (5, 1), (5, 2)]),
+                cage(KenKenOp::Divide, 3, &[(1, 1), (2, 1)]),
+                cage(KenKenOp::Multiply, 24, &[(3, 1), (3, 2)]),
+                cage(KenKenOp::Add, 5, &[(4, 1), (4, 2)]),
+                cage(KenKenOp::Add, 15, &[(1, 2), (1, 3), (1, 4)]),
+                cage(KenKenOp::Multiply, 40, &[(2, 2), (2, 3), (3, 3), (3, 4)]),
+                cage(KenKenOp::Given, 2, &[(0, 3)]),
+                cage(KenKenOp::Given, 1, &[(4, 3)]),
+                cage(KenKenOp::Multiply, 12, &[(5, 3), (4, 4), (5, 4)]),
+                cage(KenKenOp::Add, 10, &[(0, 4), (0, 5), (1, 5)]),
+                cage(KenKenOp::Subtract, 4, &[(2, 4), (2, 5)]),
+                cage(KenKenOp::Multiply, 30, &[(3, 5), (4, 5), (5, 5)]),
+            ],
+        };
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 2, 5, 3, 6, 4],
+            [6, 1, 3, 4, 2, 5],
+            [5, 4, 1, 6, 3, 2],
+            [2, 6, 4, 5, 1, 3],
+            [3, 5, 6, 2, 4, 1],
+            [4, 3, 2, 1, 5, 6],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::no_cages(5)]
+    #[case::single(1)]
+    fn test_no_cages(#[case] size: usize) {
+        let board = KenKenBoard {
+            size,
+            cages: vec![],
+        };
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[test]
+    fn test_empty() {
+        let board = KenKenBoard {
+            size: 0,
+            cages: vec![],
+        };
+        assert_eq!(solve(&board), Some(Grid::filled(0, (0, 0))));
+    }
+
+    #[rstest]
+    // The two cells of a row can't both be 2.
+    #[case::repeated_in_row(cage(KenKenOp::Multiply, 4, &[(0, 0), (1, 0)]))]
+    #[case::sum_too_large(cage(KenKenOp::Add, 7, &[(0, 0), (1, 0)]))]
+    #[case::given_out_of_range(cage(KenKenOp::Given, 3, &[(0, 0)]))]
+    #[case::given_two_cells(cage(KenKenOp::Given, 1, &[(0, 0), (1, 1)]))]
+    #[case::subtract_three_cells(cage(KenKenOp::Subtract, 0, &[(0, 0), (1, 0), (1, 1)]))]
+    #[case::divide_one_cell(cage(KenKenOp::Divide, 1, &[(0, 0)]))]
+    fn test_invalid_cage(#[case] cage: Cage) {
+        let board = KenKenBoard {
+            size: 2,
+            cages: vec![cage],
+        };
+        assert_eq!(solve(&board), None);
+    }
+
+    #[test]
+    fn test_conflicting_cages() {
+        // The left two cells of the top two rows must be 1 and 2,
+        // which leaves 3 for both of the left two cells of the bottom row.
+        let board = KenKenBoard {
+            size: 3,
+            cages: vec![
+                cage(KenKenOp::Add, 3, &[(0, 0), (1, 0)]),
+                cage(KenKenOp::Add, 3, &[(0, 1), (1, 1)]),
+            ],
+        };
+        assert_eq!(solve(&board), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (3, 0) out of bounds for dimensions (3, 3)")]
+    fn test_out_of_bounds() {
+        let board = KenKenBoard {
+            size: 3,
+            cages: vec![cage(KenKenOp::Add, 4, &[(2, 0), (3, 0)])],
+        };
+        solve(&board);
+    }
+}
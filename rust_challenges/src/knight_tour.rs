@@ -0,0 +1,169 @@
+//! Problem: find a knight's tour of a rectangular board --
+//! a sequence of knight moves that visits every square of the board exactly once.
+//!
+//! A knight moves two squares in one orthogonal direction and one square in a perpendicular one.
+//! The tour does not need to end a knight's move away from its starting square
+//! (i.e. the tour is allowed to be open rather than closed).
+//!
+//! For example, a tour on a 4×3 board starting at the top left square
+//! (numbers indicate the order in which squares are visited):
+//! ```text
+//!  1  4  7 10
+//! 12  9  2  5
+//!  3  6 11  8
+//! ```
+//!
+//! The search is guided by Warnsdorff's rule: always move to the square
+//! with the fewest onward moves. On its own, the rule can run into dead ends,
+//! so the search backtracks when it does, trying squares in Warnsdorff order.
+
+use crate::grid::Grid;
+
+type Square = (usize, usize);
+
+#[rustfmt::skip]
+const KNIGHT_DISPLACEMENTS: [(i32, i32); 8] = [
+    (-1, -2), (1, -2),
+    (-2, -1), (2, -1),
+    (-2,  1), (2,  1),
+    (-1,  2), (1,  2),
+];
+
+/// Returns the squares that a knight can reach in one move from the given square
+/// on a board with the given dimensions.
+fn knight_moves(square: Square, width: usize, height: usize) -> impl Iterator<Item = Square> {
+    let (x, y) = square;
+    KNIGHT_DISPLACEMENTS.iter().filter_map(move |&(dx, dy)| {
+        let x = x.checked_add_signed(dx as isize)?;
+        let y = y.checked_add_signed(dy as isize)?;
+        (x < width && y < height).then_some((x, y))
+    })
+}
+
+/// Returns a knight's tour of a `width`×`height` board starting at `start`,
+/// as the list of squares in the order that they are visited,
+/// or None if no tour exists from `start` (including if `start` is out of bounds).
+pub fn knight_tour(width: usize, height: usize, start: Square) -> Option<Vec<Square>> {
+    let (x, y) = start;
+    if x >= width || y >= height {
+        return None;
+    }
+    let mut visited = Grid::filled(false, (width, height));
+    visited[start] = true;
+    let mut path = vec![start];
+    extend_tour(&mut visited, &mut path).then_some(path)
+}
+
+/// Tries to extend the given partial tour into a full tour,
+/// returning whether it was successful.
+///
+/// On failure, `path` and `visited` are restored to their original states.
+fn extend_tour(visited: &mut Grid<bool>, path: &mut Vec<Square>) -> bool {
+    let (width, height) = visited.dimensions();
+    if path.len() == width * height {
+        return true;
+    }
+    let curr = *path.last().unwrap();
+    let mut candidates: Vec<Square> = unvisited_moves(curr, visited).collect();
+    // Warnsdorff's rule: prefer squares with fewer onward moves.
+    candidates.sort_by_key(|&next| unvisited_moves(next, visited).count());
+    for next in candidates {
+        visited[next] = true;
+        path.push(next);
+        if extend_tour(visited, path) {
+            return true;
+        }
+        path.pop();
+        visited[next] = false;
+    }
+    false
+}
+
+/// Returns the unvisited squares that a knight can reach in one move from the given square.
+fn unvisited_moves(square: Square, visited: &Grid<bool>) -> impl Iterator<Item = Square> + '_ {
+    let (width, height) = visited.dimensions();
+    knight_moves(square, width, height).filter(|&next| !visited[next])
+}
+
+/// Returns whether the given path is a knight's tour of a `width`×`height` board:
+/// it visits every square exactly once, and consecutive squares are a knight's move apart.
+pub fn is_valid_knight_tour(width: usize, height: usize, path: &[Square]) -> bool {
+    if path.len() != width * height {
+        return false;
+    }
+    let mut visited = Grid::filled(false, (width, height));
+    for &square in path {
+        match visited.get_mut(square) {
+            Some(visited_square) if !*visited_square => *visited_square = true,
+            _ => return false,
+        }
+    }
+    path.windows(2)
+        .all(|pair| knight_moves(pair[0], width, height).any(|next| next == pair[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::knight_tour::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::standard(8, 8, (0, 0))]
+    #[case::standard_center(8, 8, (3, 4))]
+    #[case::single(1, 1, (0, 0))]
+    #[case::wide(4, 3, (0, 0))]
+    #[case::tall(3, 4, (2, 3))]
+    #[case::five_by_five(5, 5, (0, 0))]
+    #[case::five_by_five_center(5, 5, (2, 2))]
+    #[case::rectangle(10, 6, (9, 0))]
+    #[case::large(20, 20, (7, 13))]
+    fn test_tour_exists(#[case] width: usize, #[case] height: usize, #[case] start: Square) {
+        let tour = knight_tour(width, height, start).unwrap();
+        assert_eq!(tour[0], start);
+        assert!(
+            is_valid_knight_tour(width, height, &tour),
+            "invalid tour: {tour:?}"
+        );
+    }
+
+    #[rstest]
+    #[case::two_by_two(2, 2, (0, 0))]
+    #[case::three_by_three_corner(3, 3, (0, 0))]
+    #[case::three_by_three_center(3, 3, (1, 1))]
+    #[case::four_by_four(4, 4, (1, 2))]
+    #[case::single_row(3, 1, (0, 0))]
+    #[case::out_of_bounds(5, 5, (5, 0))]
+    #[case::empty(0, 0, (0, 0))]
+    fn test_no_tour(#[case] width: usize, #[case] height: usize, #[case] start: Square) {
+        assert_eq!(knight_tour(width, height, start), None);
+    }
+
+    #[rstest]
+    #[case::problem_description_example(4, 3, &[
+        (0, 0), (2, 1), (0, 2), (1, 0), (3, 1), (1, 2),
+        (2, 0), (3, 2), (1, 1), (3, 0), (2, 2), (0, 1),
+    ], true)]
+    #[case::single(1, 1, &[(0, 0)], true)]
+    #[case::empty(0, 0, &[], true)]
+    #[case::too_short(4, 3, &[
+        (0, 0), (2, 1), (0, 2), (1, 0), (3, 1), (1, 2),
+        (2, 0), (3, 2), (1, 1), (3, 0), (2, 2),
+    ], false)]
+    #[case::repeated_square(4, 3, &[
+        (0, 0), (2, 1), (0, 2), (1, 0), (3, 1), (1, 2),
+        (2, 0), (3, 2), (1, 1), (3, 0), (2, 2), (3, 0),
+    ], false)]
+    #[case::not_knight_move(4, 3, &[
+        (0, 0), (2, 1), (0, 2), (1, 0), (3, 1), (1, 2),
+        (2, 0), (3, 2), (1, 1), (3, 0), (0, 1), (2, 2),
+    ], false)]
+    #[case::out_of_bounds(1, 1, &[(1, 0)], false)]
+    fn test_is_valid_knight_tour(
+        #[case] width: usize,
+        #[case] height: usize,
+        #[case] path: &[Square],
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_valid_knight_tour(width, height, path), expected);
+    }
+}
@@ -0,0 +1,593 @@
+//! Langton's ant is a cellular automaton: an "ant" walks around an infinite grid
+//! of white and black cells, following two simple rules at each step:
+//! - on a white cell, turn right, flip the cell to black, and move forward one cell;
+//! - on a black cell, turn left, flip the cell to white, and move forward one cell.
+//!
+//! Problem: simulate Langton's ant, starting from an all-white grid.
+//!
+//! Only a finite part of the grid can be stored,
+//! so the stored grid starts out as a single white cell and expands as needed,
+//! whenever the ant would walk off its edge.
+//!
+//! For example, starting out facing up, the ant turns right and moves right,
+//! then turns right again and moves down, and so on,
+//! coming back to the top left cell after 4 steps, facing up again,
+//! with every cell it visited flipped to black (`#` for black):
+//! ```text
+//! # #
+//! # #
+//! ```
+//! After around 10 000 steps of seemingly chaotic behavior,
+//! the ant starts building a "highway" leading off diagonally forever.
+//!
+//! _Turmites_ generalize the ant to more colors, and to an internal state:
+//! at each step, the current color and state determine the new color of the cell,
+//! the direction to turn, and the new state.
+//! Langton's ant is the turmite with two colors and one state.
+//! Some other turmites also build highways, like the `RRRL` turmite
+//! (four colors, turning right on the first three and left on the last,
+//! and cycling through the colors), which starts one within the first 100 steps.
+
+use crate::grid::Grid;
+
+/// A direction that the ant can face.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AntDirection {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl AntDirection {
+    /// The direction after turning 90 degrees clockwise.
+    pub fn turn_right(self) -> AntDirection {
+        match self {
+            AntDirection::Up => AntDirection::Right,
+            AntDirection::Right => AntDirection::Down,
+            AntDirection::Down => AntDirection::Left,
+            AntDirection::Left => AntDirection::Up,
+        }
+    }
+
+    /// The direction after turning 90 degrees counterclockwise.
+    pub fn turn_left(self) -> AntDirection {
+        self.turn_right().turn_right().turn_right()
+    }
+
+    /// The direction after making the given turn.
+    pub fn turn(self, turn: TurnDirection) -> AntDirection {
+        match turn {
+            TurnDirection::NoTurn => self,
+            TurnDirection::Right => self.turn_right(),
+            TurnDirection::UTurn => self.turn_right().turn_right(),
+            TurnDirection::Left => self.turn_left(),
+        }
+    }
+
+    /// The displacement from a cell to the next cell in this direction.
+    fn displacement(self) -> (i32, i32) {
+        match self {
+            AntDirection::Up => (0, -1),
+            AntDirection::Right => (1, 0),
+            AntDirection::Down => (0, 1),
+            AntDirection::Left => (-1, 0),
+        }
+    }
+}
+
+/// A turn that a turmite can make.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TurnDirection {
+    NoTurn,
+    Right,
+    UTurn,
+    Left,
+}
+
+/// The state of the simulation: the stored part of the grid, where `true` indicates a black cell,
+/// along with the position of the ant (always within the bounds of the grid)
+/// and the direction it's facing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AntState {
+    pub grid: Grid<bool>,
+    pub position: (i32, i32),
+    pub direction: AntDirection,
+}
+
+impl Default for AntState {
+    /// A single white cell, with the ant on it, facing up.
+    fn default() -> AntState {
+        AntState {
+            grid: Grid::filled(false, (1, 1)),
+            position: (0, 0),
+            direction: AntDirection::Up,
+        }
+    }
+}
+
+/// Returns a copy of the grid with a row or column of white (default) cells added,
+/// so that the given position (at most one cell outside the grid) is within bounds,
+/// along with the position in the new grid.
+fn expand<T: Clone + Default>(grid: &Grid<T>, position: (i32, i32)) -> (Grid<T>, (i32, i32)) {
+    let (x, y) = position;
+    let (width, height) = (grid.width() as i32, grid.height() as i32);
+    let (shift_x, shift_y) = (i32::from(x < 0), i32::from(y < 0));
+    let new_width = width + i32::from(x < 0 || x >= width);
+    let new_height = height + i32::from(y < 0 || y >= height);
+    let mut expanded = Grid::filled(T::default(), (new_width as usize, new_height as usize));
+    for ((old_x, old_y), cell) in grid.enumerate::<(i32, i32)>() {
+        expanded[(old_x + shift_x, old_y + shift_y)] = cell.clone();
+    }
+    (expanded, (x + shift_x, y + shift_y))
+}
+
+/// Returns the state after one step of the simulation,
+/// expanding the grid if the ant walks off its edge.
+///
+/// Panics if the ant's position is out of bounds.
+pub fn step(state: &AntState) -> AntState {
+    let AntState {
+        grid,
+        position,
+        direction,
+    } = state;
+    let mut grid = grid.clone();
+    let Some(cell) = grid.get_mut(*position) else {
+        panic!(
+            "cell {position:?} out of bounds for dimensions {:?}",
+            grid.dimensions()
+        );
+    };
+    let direction = if *cell {
+        direction.turn_left()
+    } else {
+        direction.turn_right()
+    };
+    *cell = !*cell;
+    let (dx, dy) = direction.displacement();
+    let mut position = (position.0 + dx, position.1 + dy);
+    if grid.get(position).is_none() {
+        (grid, position) = expand(&grid, position);
+    }
+    AntState {
+        grid,
+        position,
+        direction,
+    }
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid.
+        This function completes in `O(c)` time in the worst case, since it copies the grid.
+        (Expanding the grid also takes `O(c)` time.)
+    */
+}
+
+/// Returns the state after the given number of steps of the simulation,
+/// starting from the default state (a single white cell, with the ant facing up).
+pub fn run(steps: usize) -> AntState {
+    let mut state = AntState::default();
+    for _ in 0..steps {
+        state = step(&state);
+    }
+    state
+    /*
+        Time complexity analysis:
+        This function completes in `O(s^3)` time in the worst case, where `s` is the number of steps.
+        - The grid gains at most one row or column per step,
+          so it has `O(s^2)` cells at any point.
+        - Each step takes time linear in the number of cells.
+        In practice, the ant stays within a much smaller area for the first 10 000 steps or so.
+    */
+}
+
+/// The rule for a turmite with `color_count` colors, numbered from 0.
+///
+/// For each combination of the current cell's color and the turmite's current state,
+/// `transitions[current_state * color_count + current_color]` is `(new_color, turn, new_state)`:
+/// the color to paint the cell, the direction to turn, and the state to switch to.
+/// So there are `transitions.len() / color_count` states, numbered from 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TurmiteRule {
+    pub color_count: usize,
+    pub transitions: Vec<(usize, TurnDirection, usize)>,
+}
+
+impl TurmiteRule {
+    /// The rule for a single-state turmite that makes the `i`th turn on a cell of color `i`,
+    /// and changes the cell to the next color (wrapping around to 0 after the last one).
+    ///
+    /// Langton's ant is `from_turns(&[TurnDirection::Right, TurnDirection::Left])`.
+    pub fn from_turns(turns: &[TurnDirection]) -> TurmiteRule {
+        let color_count = turns.len();
+        let transitions = turns
+            .iter()
+            .enumerate()
+            .map(|(color, &turn)| ((color + 1) % color_count, turn, 0))
+            .collect();
+        TurmiteRule {
+            color_count,
+            transitions,
+        }
+    }
+
+    /// The number of states in the rule.
+    pub fn num_states(&self) -> usize {
+        self.transitions
+            .len()
+            .checked_div(self.color_count)
+            .unwrap_or(0)
+    }
+
+    /// Returns whether the rule is well-formed: there's at least one color,
+    /// there's exactly one transition for each combination of color and state (and at least one state),
+    /// and every transition leads to a valid color and state.
+    pub fn is_valid(&self) -> bool {
+        let num_states = self.num_states();
+        num_states > 0
+            && self.transitions.len() == num_states * self.color_count
+            && self
+                .transitions
+                .iter()
+                .all(|&(color, _, state)| color < self.color_count && state < num_states)
+    }
+}
+
+/// The state of a turmite simulation: the stored part of the grid, with the color of each cell,
+/// along with the position of the turmite (always within the bounds of the grid),
+/// the direction it's facing, and its internal state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TurmiteState {
+    grid: Grid<usize>,
+    position: (i32, i32),
+    direction: AntDirection,
+    state: usize,
+}
+
+impl Default for TurmiteState {
+    /// A single cell of color 0, with the turmite on it, facing up, in state 0.
+    fn default() -> TurmiteState {
+        TurmiteState {
+            grid: Grid::filled(0, (1, 1)),
+            position: (0, 0),
+            direction: AntDirection::Up,
+            state: 0,
+        }
+    }
+}
+
+impl TurmiteState {
+    /// The stored part of the grid, with the color of each cell.
+    pub fn grid(&self) -> &Grid<usize> {
+        &self.grid
+    }
+
+    /// The position of the turmite, which is always within the bounds of the grid.
+    pub fn position(&self) -> (i32, i32) {
+        self.position
+    }
+
+    /// The direction the turmite is facing.
+    pub fn direction(&self) -> AntDirection {
+        self.direction
+    }
+
+    /// The internal state of the turmite.
+    pub fn state(&self) -> usize {
+        self.state
+    }
+}
+
+/// Returns the state after one step of the turmite simulation with the given rule,
+/// expanding the grid if the turmite walks off its edge.
+///
+/// Panics if the rule isn't valid (see [`TurmiteRule::is_valid`]),
+/// or if the current color or state isn't covered by the rule.
+pub fn step_turmite(state: &TurmiteState, rule: &TurmiteRule) -> TurmiteState {
+    assert!(rule.is_valid(), "invalid turmite rule: {rule:?}");
+    let TurmiteState {
+        grid,
+        position,
+        direction,
+        state,
+    } = state;
+    let mut grid = grid.clone();
+    let cell = &mut grid[*position];
+    assert!(
+        *cell < rule.color_count && *state < rule.num_states(),
+        "color {cell} and state {state} not covered by rule: {rule:?}"
+    );
+    let (new_color, turn, new_state) = rule.transitions[state * rule.color_count + *cell];
+    *cell = new_color;
+    let direction = direction.turn(turn);
+    let (dx, dy) = direction.displacement();
+    let mut position = (position.0 + dx, position.1 + dy);
+    if grid.get(position).is_none() {
+        (grid, position) = expand(&grid, position);
+    }
+    TurmiteState {
+        grid,
+        position,
+        direction,
+        state: new_state,
+    }
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid, and `t` be the number of transitions in the rule.
+        This function completes in `O(c + t)` time in the worst case,
+        since it copies the grid and checks that the rule is valid.
+    */
+}
+
+/// Returns the state after the given number of steps of the turmite simulation with the given rule,
+/// starting from the default state (a single cell of color 0, with the turmite facing up in state 0).
+pub fn run_turmite(rule: &TurmiteRule, steps: usize) -> TurmiteState {
+    let mut state = TurmiteState::default();
+    for _ in 0..steps {
+        state = step_turmite(&state, rule);
+    }
+    state
+    /*
+        Time complexity analysis:
+        As with `run`, this function completes in `O(s^3 + s * t)` time in the worst case,
+        where `s` is the number of steps and `t` is the number of transitions in the rule.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::langtons_ant::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Returns the number of black cells in the given state.
+    fn num_black(state: &AntState) -> usize {
+        state
+            .grid
+            .enumerate::<(i32, i32)>()
+            .filter(|&(_, &cell)| cell)
+            .count()
+    }
+
+    #[test]
+    fn test_turns() {
+        let directions = [
+            AntDirection::Up,
+            AntDirection::Right,
+            AntDirection::Down,
+            AntDirection::Left,
+        ];
+        for (i, direction) in directions.into_iter().enumerate() {
+            assert_eq!(direction.turn_right(), directions[(i + 1) % 4]);
+            assert_eq!(direction.turn_left(), directions[(i + 3) % 4]);
+        }
+    }
+
+    #[test]
+    fn test_step_1() {
+        let expected = AntState {
+            grid: Grid::from_2d_array([[true, false]]),
+            position: (1, 0),
+            direction: AntDirection::Right,
+        };
+        assert_eq!(step(&AntState::default()), expected);
+    }
+
+    #[test]
+    fn test_step_black() {
+        let state = AntState {
+            grid: Grid::from_2d_array([[false, false], [true, false]]),
+            position: (0, 1),
+            direction: AntDirection::Up,
+        };
+        // Turning left from the bottom left corner would walk off the left edge.
+        let expected = AntState {
+            grid: Grid::from_2d_array([[false, false, false], [false, false, false]]),
+            position: (0, 1),
+            direction: AntDirection::Left,
+        };
+        assert_eq!(step(&state), expected);
+    }
+
+    #[test]
+    fn test_run_4() {
+        let expected = AntState {
+            grid: Grid::from_2d_array([[true, true], [true, true]]),
+            position: (0, 0),
+            direction: AntDirection::Up,
+        };
+        assert_eq!(run(4), expected);
+    }
+
+    #[rstest]
+    #[case::expand_left((-1, 0), (3, 2), (0, 0))]
+    #[case::expand_right((2, 1), (3, 2), (2, 1))]
+    #[case::expand_up((1, -1), (2, 3), (1, 0))]
+    #[case::expand_down((0, 2), (2, 3), (0, 2))]
+    fn test_expand(
+        #[case] position: (i32, i32),
+        #[case] dimensions: (usize, usize),
+        #[case] expected_position: (i32, i32),
+    ) {
+        let grid = Grid::from_2d_array([[true, false], [false, true]]);
+        let (expanded, new_position) = expand(&grid, position);
+        assert_eq!(expanded.dimensions(), dimensions);
+        assert_eq!(new_position, expected_position);
+        let black_cells: Vec<_> = expanded
+            .enumerate::<(i32, i32)>()
+            .filter(|&(_, &cell)| cell)
+            .map(|(index, _)| index)
+            .collect();
+        let (dx, dy) = (
+            expected_position.0 - position.0,
+            expected_position.1 - position.1,
+        );
+        assert_eq!(black_cells, [(dx, dy), (1 + dx, 1 + dy)]);
+    }
+
+    #[test]
+    fn test_run_0() {
+        assert_eq!(run(0), AntState::default());
+    }
+
+    #[test]
+    fn test_run_10000() {
+        assert_eq!(num_black(&run(10_000)), 720);
+    }
+
+    /// Creates a single-state turmite rule from a string of turns
+    /// (`R` for right, `L` for left, `N` for no turn, and `U` for a U-turn).
+    fn turns(letters: &str) -> TurmiteRule {
+        let turns: Vec<TurnDirection> = letters
+            .chars()
+            .map(|letter| match letter {
+                'R' => TurnDirection::Right,
+                'L' => TurnDirection::Left,
+                'N' => TurnDirection::NoTurn,
+                'U' => TurnDirection::UTurn,
+                _ => panic!("unknown turn {letter:?}"),
+            })
+            .collect();
+        TurmiteRule::from_turns(&turns)
+    }
+
+    #[rstest]
+    #[case::no_turn(TurnDirection::NoTurn, AntDirection::Right)]
+    #[case::right(TurnDirection::Right, AntDirection::Down)]
+    #[case::u_turn(TurnDirection::UTurn, AntDirection::Left)]
+    #[case::left(TurnDirection::Left, AntDirection::Up)]
+    fn test_turn(#[case] turn: TurnDirection, #[case] expected: AntDirection) {
+        assert_eq!(AntDirection::Right.turn(turn), expected);
+    }
+
+    #[test]
+    fn test_from_turns() {
+        let expected = TurmiteRule {
+            color_count: 3,
+            transitions: vec![
+                (1, TurnDirection::Right, 0),
+                (2, TurnDirection::NoTurn, 0),
+                (0, TurnDirection::Left, 0),
+            ],
+        };
+        assert_eq!(turns("RNL"), expected);
+        assert_eq!(expected.num_states(), 1);
+    }
+
+    #[test]
+    fn test_langtons_ant_turmite() {
+        let rule = turns("RL");
+        let mut ant = AntState::default();
+        let mut turmite = TurmiteState::default();
+        for _ in 0..2000 {
+            ant = step(&ant);
+            turmite = step_turmite(&turmite, &rule);
+            assert_eq!(turmite.grid().clone().map(|color| color == 1), ant.grid);
+            assert_eq!(turmite.position(), ant.position);
+            assert_eq!(turmite.direction(), ant.direction);
+            assert_eq!(turmite.state(), 0);
+        }
+    }
+
+    #[test]
+    fn test_langtons_ant_turmite_10000() {
+        let state = run_turmite(&turns("RL"), 10_000);
+        let num_black = state
+            .grid()
+            .enumerate::<(i32, i32)>()
+            .filter(|&(_, &color)| color == 1)
+            .count();
+        assert_eq!(num_black, 720);
+    }
+
+    #[test]
+    fn test_highway() {
+        let rule = turns("RRRL");
+        let mut state = run_turmite(&rule, 1000);
+        let mut history = vec![];
+        for _ in 0..3 * 52 {
+            history.push((state.position(), state.direction()));
+            state = step_turmite(&state, &rule);
+        }
+        // The highway repeats every 52 steps, moving 2 cells right and 2 cells down each time.
+        // (It never expands the grid to the left or top, so positions stay comparable.)
+        for (&(start, direction), &(end, end_direction)) in history.iter().zip(&history[52..]) {
+            assert_eq!((end.0 - start.0, end.1 - start.1), (2, 2));
+            assert_eq!(end_direction, direction);
+        }
+    }
+
+    #[test]
+    fn test_two_states() {
+        let rule = TurmiteRule {
+            color_count: 2,
+            transitions: vec![
+                (1, TurnDirection::Right, 1),
+                (0, TurnDirection::Left, 0),
+                (1, TurnDirection::Left, 0),
+                (0, TurnDirection::Right, 1),
+            ],
+        };
+        let state = step_turmite(&TurmiteState::default(), &rule);
+        let expected = TurmiteState {
+            grid: Grid::from_2d_array([[1, 0]]),
+            position: (1, 0),
+            direction: AntDirection::Right,
+            state: 1,
+        };
+        assert_eq!(state, expected);
+        // In state 1, color 0 leads to a left turn, back to state 0, expanding the grid upwards.
+        let expected = TurmiteState {
+            grid: Grid::from_2d_array([[0, 0], [1, 1]]),
+            position: (1, 0),
+            direction: AntDirection::Up,
+            state: 0,
+        };
+        assert_eq!(step_turmite(&state, &rule), expected);
+    }
+
+    #[test]
+    fn test_run_turmite_0() {
+        assert_eq!(run_turmite(&turns("RLLR"), 0), TurmiteState::default());
+    }
+
+    #[rstest]
+    #[case::no_colors(0, vec![])]
+    #[case::no_transitions(2, vec![])]
+    #[case::too_few_transitions(2, vec![(1, TurnDirection::Right, 0)])]
+    #[case::partial_state(2, vec![
+        (1, TurnDirection::Right, 0),
+        (0, TurnDirection::Left, 0),
+        (1, TurnDirection::Left, 0),
+    ])]
+    #[case::invalid_color(2, vec![(1, TurnDirection::Right, 0), (2, TurnDirection::Left, 0)])]
+    #[case::invalid_state(2, vec![(1, TurnDirection::Right, 1), (0, TurnDirection::Left, 0)])]
+    fn test_invalid_rule(
+        #[case] color_count: usize,
+        #[case] transitions: Vec<(usize, TurnDirection, usize)>,
+    ) {
+        let rule = TurmiteRule {
+            color_count,
+            transitions,
+        };
+        assert!(!rule.is_valid());
+    }
+
+    #[rstest]
+    #[case::langtons_ant(turns("RL"))]
+    #[case::four_colors(turns("RLLR"))]
+    #[case::one_color(turns("N"))]
+    fn test_valid_rule(#[case] rule: TurmiteRule) {
+        assert!(rule.is_valid());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid turmite rule")]
+    fn test_step_invalid_rule() {
+        let rule = TurmiteRule {
+            color_count: 2,
+            transitions: vec![(1, TurnDirection::Right, 0)],
+        };
+        step_turmite(&TurmiteState::default(), &rule);
+    }
+}
@@ -0,0 +1,194 @@
+//! A Latin square of order `n` is an `n` by `n` grid filled with `n` different values,
+//! such that every row and every column contains each value exactly once.
+//!
+//! Problem: check whether a grid is a Latin square, construct a Latin square of any order,
+//! and count the _reduced_ Latin squares of a given order,
+//! where the values are 0 to `n - 1` and the first row and column are in increasing order.
+//!
+//! For example, this is a reduced Latin square of order 4:
+//! ```text
+//! 0 1 2 3
+//! 1 0 3 2
+//! 2 3 0 1
+//! 3 2 1 0
+//! ```
+//! and there are 4 reduced Latin squares of order 4 in total.
+//!
+//! The cyclic construction shifts each row one place to the left of the row above it.
+//! Reduced Latin squares are counted by filling in the cells one at a time,
+//! keeping track of the values already used in each row and column,
+//! and backtracking whenever a cell has no values left.
+
+use crate::grid::Grid;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// Returns whether the given grid is a Latin square.
+///
+/// Grids that aren't square are never Latin squares, while the empty grid is one (of order 0).
+pub fn is_latin_square<T: Eq + Hash>(grid: &Grid<T>) -> bool {
+    let n = grid.width();
+    if grid.height() != n {
+        return false;
+    }
+    let first_row: HashSet<&T> = (0..n).map(|x| &grid[(x, 0)]).collect();
+    if first_row.len() != n {
+        return false;
+    }
+    // A line of `n` cells contains each of the `n` values exactly once
+    // if and only if it contains `n` different values, all of them from the first row.
+    let is_permutation = |line: HashSet<&T>| line.len() == n && line.is_subset(&first_row);
+    (0..n).all(|y| is_permutation((0..n).map(|x| &grid[(x, y)]).collect()))
+        && (0..n).all(|x| is_permutation((0..n).map(|y| &grid[(x, y)]).collect()))
+    /*
+        Time complexity analysis:
+        Each of the `2 * n` lines is checked in `O(n)` expected time (with hash sets),
+        so this function completes in `O(n^2)` expected time.
+    */
+}
+
+/// Returns a Latin square of order `n` with the values 0 to `n - 1`,
+/// where the value at `(x, y)` is `(x + y) % n`.
+///
+/// The result is a reduced Latin square.
+pub fn generate_latin_square(n: usize) -> Grid<usize> {
+    let mut result = Grid::filled(0, (n, n));
+    for y in 0..n {
+        for x in 0..n {
+            result[(x, y)] = (x + y) % n;
+        }
+    }
+    result
+}
+
+/// Returns the number of reduced Latin squares of order `n`.
+///
+/// The number grows extremely quickly, so this is only practical for `n` up to about 6
+/// (with 9408 reduced Latin squares).
+///
+/// Panics if `n` is greater than 64.
+pub fn count_reduced_latin_squares(n: usize) -> usize {
+    assert!(n <= 64, "order {n} is too large");
+    // Bit `v` of `rows[y]` (or `cols[x]`) is set if value `v` is already used in that row (or column).
+    let mut rows = vec![0u64; n];
+    let mut cols = vec![0u64; n];
+    // The first row and column are fixed.
+    for i in 0..n {
+        rows[0] |= 1 << i;
+        cols[i] |= 1 << i;
+        rows[i] |= 1 << i;
+        cols[0] |= 1 << i;
+    }
+    count_completions(n, (1, 1), &mut rows, &mut cols)
+    /*
+        Time complexity analysis:
+        The number of reduced Latin squares grows faster than exponentially in `n`,
+        and every one of them is reached separately,
+        so this function takes super-exponential time in `n`.
+    */
+}
+
+/// Returns the number of ways to fill in the cells from `cell` onwards (in row-major order,
+/// skipping the first row and column) without repeating a value in any row or column,
+/// given the values already used in each row and column.
+fn count_completions(n: usize, cell: (usize, usize), rows: &mut [u64], cols: &mut [u64]) -> usize {
+    let (x, y) = cell;
+    if y >= n {
+        return 1;
+    }
+    let next = if x + 1 < n { (x + 1, y) } else { (1, y + 1) };
+    let mut count = 0;
+    for value in 0..n {
+        let bit = 1 << value;
+        if (rows[y] | cols[x]) & bit != 0 {
+            continue;
+        }
+        rows[y] |= bit;
+        cols[x] |= bit;
+        count += count_completions(n, next, rows, cols);
+        rows[y] &= !bit;
+        cols[x] &= !bit;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::latin_square::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_problem_description_example() {
+        let grid = Grid::from_2d_array([[0, 1, 2, 3], [1, 0, 3, 2], [2, 3, 0, 1], [3, 2, 1, 0]]);
+        assert!(is_latin_square(&grid));
+        assert_eq!(count_reduced_latin_squares(4), 4);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    #[case(5)]
+    #[case(12)]
+    fn test_generate_latin_square(#[case] n: usize) {
+        let square = generate_latin_square(n);
+        assert_eq!(square.dimensions(), (n, n));
+        assert!(is_latin_square(&square));
+        // The first row and column are in increasing order.
+        for i in 0..n {
+            assert_eq!(square[(i, 0)], i);
+            assert_eq!(square[(0, i)], i);
+        }
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 1)]
+    #[case(2, 1)]
+    #[case(3, 1)]
+    #[case(4, 4)]
+    #[case(5, 56)]
+    #[case(6, 9408)]
+    fn test_count_reduced_latin_squares(#[case] n: usize, #[case] expected: usize) {
+        assert_eq!(count_reduced_latin_squares(n), expected);
+    }
+
+    #[test]
+    fn test_sudoku_solution() {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [5, 3, 4, 6, 7, 8, 9, 1, 2],
+            [6, 7, 2, 1, 9, 5, 3, 4, 8],
+            [1, 9, 8, 3, 4, 2, 5, 6, 7],
+            [8, 5, 9, 7, 6, 1, 4, 2, 3],
+            [4, 2, 6, 8, 5, 3, 7, 9, 1],
+            [7, 1, 3, 9, 2, 4, 8, 5, 6],
+            [9, 6, 1, 5, 3, 7, 2, 8, 4],
+            [2, 8, 7, 4, 1, 9, 6, 3, 5],
+            [3, 4, 5, 2, 8, 6, 1, 7, 9],
+        ]);
+        assert!(is_latin_square(&grid));
+    }
+
+    #[rstest]
+    #[case::all_equal(Grid::filled('x', (3, 3)))]
+    #[case::not_square(Grid::from_2d_array([['a', 'b', 'c'], ['b', 'c', 'a']]))]
+    // Every row is fine, but the columns repeat values.
+    #[case::repeated_in_column(Grid::from_2d_array([['a', 'b'], ['a', 'b']]))]
+    // Every row and column has 3 different values, but there are 4 values in total.
+    #[case::too_many_values(Grid::from_2d_array([
+        ['a', 'b', 'c'],
+        ['b', 'c', 'd'],
+        ['c', 'a', 'b'],
+    ]))]
+    fn test_not_latin_square(#[case] grid: Grid<char>) {
+        assert!(!is_latin_square(&grid));
+    }
+
+    #[test]
+    fn test_non_numeric_values() {
+        let grid = Grid::from_2d_array([["red", "blue"], ["blue", "red"]]);
+        assert!(is_latin_square(&grid));
+    }
+}
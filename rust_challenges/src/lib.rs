@@ -1,6 +1,61 @@
+pub mod abc_puzzle;
+pub mod akari;
+pub mod binairo;
 pub mod bloxorz_model;
 pub mod bloxorz_solver;
+pub mod boggle;
+pub mod bridges;
+pub mod candidate_numbers;
+pub mod checkers;
+pub mod chess_piece;
+pub mod connect_four;
+pub mod cryptarithmetic;
+pub mod domino_tiling;
+pub mod dots_boxes;
+pub mod einstein_puzzle;
+pub mod elementary_ca;
+pub mod fillomino;
+pub mod flood_fill;
+pub mod flow_free;
+pub mod four_color;
+pub mod futoshiki;
+pub mod game_2048;
+pub mod go;
 pub mod grid;
+pub mod hex_grid;
+pub mod hitori;
 pub mod island_sizes;
+pub mod kakuro;
+pub mod kenken;
+pub mod knight_tour;
+pub mod langtons_ant;
+pub mod latin_square;
+pub mod life;
+pub mod lights_out;
+pub mod magic_square;
+pub mod mastermind;
+pub mod maze;
+pub mod maze_solver;
+pub mod minesweeper;
 pub mod mutable_aliasing;
+pub mod nonogram;
+pub mod numberlink;
+pub mod nurikabe;
+pub mod othello;
+pub mod pipe_puzzle;
+pub mod polyomino;
 pub mod rainfall;
+pub mod reaction_diffusion;
+pub mod ripple_effect;
+pub mod sandpile;
+pub mod slitherlink;
+pub mod sokoban;
+pub mod spanning_tree;
+pub mod suguru;
+pub mod tenner_grid;
+pub mod tic_tac_toe;
+pub mod tromino_tiling;
+pub mod union_find;
+pub mod voronoi;
+pub mod wire_world;
+pub mod word_ladder;
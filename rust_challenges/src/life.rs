@@ -0,0 +1,352 @@
+//! Conway's Game of Life is a cellular automaton where each cell is either alive or dead.
+//! At each step, every cell changes according to how many of its 8 neighbors are alive:
+//! - a dead cell with exactly 3 live neighbors becomes alive (is born), and
+//! - a live cell with 2 or 3 live neighbors stays alive (survives), while other live cells die.
+//!
+//! Other _Life-like_ cellular automata work the same way, but with different numbers of neighbors
+//! for birth and survival. They're written in `B/S` notation, with the numbers for birth
+//! after the `B` and the numbers for survival after the `S`, so Conway's Game of Life is `B3/S23`.
+//!
+//! Problem: simulate the Game of Life and other Life-like cellular automata.
+//!
+//! For example (`.` for dead, `#` for alive), a glider moves one cell diagonally every 4 steps:
+//! ```text
+//! . # . .    . . . .    . . . .    . . . .    . . . .
+//! . . # .    # . # .    . . # .    . # . .    . . # .
+//! # # # .    . # # .    # . # .    . . # #    . . . #
+//! . . . .    . # . .    . # # .    . # # .    . # # #
+//! ```
+//! Cells outside the grid are always dead.
+
+use crate::flood_fill::Connectivity;
+use crate::grid::Grid;
+use std::collections::HashSet;
+
+/// The notation for Conway's Game of Life.
+pub const CONWAY: &str = "B3/S23";
+/// The notation for HighLife, which is like the Game of Life,
+/// but with a small pattern that makes copies of itself.
+pub const HIGH_LIFE: &str = "B36/S23";
+/// The notation for Day & Night, where dead cells in a sea of live cells
+/// behave the same way as live cells in a sea of dead cells.
+pub const DAY_AND_NIGHT: &str = "B3678/S34678";
+/// The notation for Replicator, where every pattern eventually makes copies of itself.
+pub const REPLICATOR: &str = "B1357/S1357";
+
+/// The numbers of live neighbors that make a cell alive after a step in a Life-like automaton.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LifeRule {
+    /// The numbers of live neighbors for which a dead cell becomes alive.
+    pub birth: HashSet<u8>,
+    /// The numbers of live neighbors for which a live cell stays alive.
+    pub survival: HashSet<u8>,
+}
+
+/// The reason notation for a rule couldn't be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// The notation isn't of the form `B<numbers>/S<numbers>`.
+    Malformed,
+    /// A character in one of the lists of numbers isn't a number of neighbors (0 to 8).
+    InvalidCount(char),
+}
+
+impl LifeRule {
+    /// The rule for Conway's Game of Life, `B3/S23`.
+    pub fn conway() -> LifeRule {
+        LifeRule::from_notation(CONWAY).unwrap()
+    }
+
+    /// Parses a rule in `B/S` notation, such as `B3/S23`.
+    ///
+    /// Each number of neighbors is a single digit from 0 to 8, and either list can be empty.
+    pub fn from_notation(s: &str) -> Result<LifeRule, ParseError> {
+        let (birth, survival) = s.split_once('/').ok_or(ParseError::Malformed)?;
+        let birth = birth.strip_prefix('B').ok_or(ParseError::Malformed)?;
+        let survival = survival.strip_prefix('S').ok_or(ParseError::Malformed)?;
+        let parse_counts = |counts: &str| {
+            counts
+                .chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(count @ 0..=8) => Ok(count as u8),
+                    _ => Err(ParseError::InvalidCount(c)),
+                })
+                .collect::<Result<HashSet<u8>, ParseError>>()
+        };
+        Ok(LifeRule {
+            birth: parse_counts(birth)?,
+            survival: parse_counts(survival)?,
+        })
+    }
+
+    /// Returns the rule in `B/S` notation, with the numbers of neighbors in increasing order.
+    pub fn to_notation(&self) -> String {
+        let format_counts = |counts: &HashSet<u8>| {
+            let mut counts: Vec<u8> = counts.iter().copied().collect();
+            counts.sort_unstable();
+            counts.iter().map(u8::to_string).collect::<String>()
+        };
+        format!(
+            "B{}/S{}",
+            format_counts(&self.birth),
+            format_counts(&self.survival)
+        )
+    }
+}
+
+/// Returns the number of live neighbors of the given cell.
+fn live_neighbors(grid: &Grid<bool>, (x, y): (i32, i32)) -> usize {
+    Connectivity::Eight
+        .displacements()
+        .iter()
+        .filter(|(dx, dy)| grid.get((x + dx, y + dy)) == Some(&true))
+        .count()
+}
+
+/// Returns the result of a single step of Conway's Game of Life on the given grid.
+pub fn step(grid: &Grid<bool>) -> Grid<bool> {
+    let mut result = grid.clone();
+    for (index, &alive) in grid.enumerate::<(i32, i32)>() {
+        result[index] = matches!((alive, live_neighbors(grid, index)), (true, 2) | (_, 3));
+    }
+    result
+    /*
+        Time complexity analysis:
+        This function completes in `O(c)` time in the worst case,
+        where `c` is the number of cells in the grid,
+        since each cell has at most 8 neighbors to check.
+    */
+}
+
+/// Returns the result of a single step of the Life-like automaton with the given rule
+/// on the given grid.
+pub fn step_with_rule(grid: &Grid<bool>, rule: &LifeRule) -> Grid<bool> {
+    let mut result = grid.clone();
+    for (index, &alive) in grid.enumerate::<(i32, i32)>() {
+        let count = live_neighbors(grid, index) as u8;
+        let counts = if alive { &rule.survival } else { &rule.birth };
+        result[index] = counts.contains(&count);
+    }
+    result
+    /*
+        Time complexity analysis:
+        This function completes in `O(c)` expected time in the worst case,
+        where `c` is the number of cells in the grid,
+        since each cell has at most 8 neighbors to check,
+        and looking up the count in the rule takes `O(1)` expected time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::life::*;
+    use pretty_assertions::assert_eq;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rstest::rstest;
+
+    /// Creates a grid of cells from the given rows, with `#` for live cells.
+    fn cells(rows: &[&str]) -> Grid<bool> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut result = Grid::filled(false, (width, rows.len()));
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                result[(x, y)] = c == '#';
+            }
+        }
+        result
+    }
+
+    /// Returns the given pattern placed at the given position in an otherwise dead grid.
+    fn place(
+        pattern: &Grid<bool>,
+        (x, y): (usize, usize),
+        dimensions: (usize, usize),
+    ) -> Grid<bool> {
+        let mut result = Grid::filled(false, dimensions);
+        for ((dx, dy), &alive) in pattern.enumerate::<(usize, usize)>() {
+            result[(x + dx, y + dy)] = alive;
+        }
+        result
+    }
+
+    /// Returns the union of the live cells of the given grids.
+    fn union(a: &Grid<bool>, b: &Grid<bool>) -> Grid<bool> {
+        let mut result = a.clone();
+        for (index, &alive) in b.enumerate::<(usize, usize)>() {
+            result[index] |= alive;
+        }
+        result
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let mut grid = cells(&[".#..", "..#.", "###.", "...."]);
+        let expected = [
+            cells(&["....", "#.#.", ".##.", ".#.."]),
+            cells(&["....", "..#.", "#.#.", ".##."]),
+            cells(&["....", ".#..", "..##", ".##."]),
+            cells(&["....", "..#.", "...#", ".###"]),
+        ];
+        for expected in expected {
+            grid = step(&grid);
+            assert_eq!(grid, expected);
+        }
+    }
+
+    #[rstest]
+    #[case::block(&["....", ".##.", ".##.", "...."])]
+    #[case::beehive(&["......", "..##..", ".#..#.", "..##..", "......"])]
+    fn test_still_life(#[case] rows: &[&str]) {
+        let grid = cells(rows);
+        assert_eq!(step(&grid), grid);
+    }
+
+    #[test]
+    fn test_blinker() {
+        let horizontal = cells(&[".....", ".....", ".###.", ".....", "....."]);
+        let vertical = cells(&[".....", "..#..", "..#..", "..#..", "....."]);
+        assert_eq!(step(&horizontal), vertical);
+        assert_eq!(step(&vertical), horizontal);
+    }
+
+    #[test]
+    fn test_edges() {
+        // The cells that would be born outside the grid aren't there to support the others.
+        let grid = cells(&["###"]);
+        assert_eq!(step(&grid), cells(&[".#."]));
+    }
+
+    #[rstest]
+    #[case((0, 0))]
+    #[case((3, 0))]
+    #[case((0, 3))]
+    fn test_empty(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(false, dimensions);
+        assert_eq!(step(&grid), grid);
+        assert_eq!(step_with_rule(&grid, &LifeRule::conway()), grid);
+    }
+
+    #[test]
+    fn test_conway_rule() {
+        let rule = LifeRule::conway();
+        assert_eq!(rule.birth, HashSet::from([3]));
+        assert_eq!(rule.survival, HashSet::from([2, 3]));
+        let mut rng = StdRng::seed_from_u64(1149);
+        for _ in 0..20 {
+            let mut grid = Grid::filled(false, (12, 10));
+            for y in 0..10 {
+                for x in 0..12 {
+                    grid[(x, y)] = rng.gen_bool(0.4);
+                }
+            }
+            assert_eq!(step_with_rule(&grid, &rule), step(&grid));
+        }
+    }
+
+    /// The replicator in HighLife, which makes a copy of itself every 12 steps.
+    fn replicator() -> Grid<bool> {
+        cells(&["..###", ".#..#", "#...#", "#..#.", "###.."])
+    }
+
+    #[test]
+    fn test_high_life_replicator() {
+        let rule = LifeRule::from_notation(HIGH_LIFE).unwrap();
+        let mut grid = place(&replicator(), (8, 8), (24, 24));
+        for _ in 0..12 {
+            grid = step_with_rule(&grid, &rule);
+        }
+        // The original is replaced by two copies, one up and to the left, and one down and to the right.
+        let expected = union(
+            &place(&replicator(), (6, 6), (24, 24)),
+            &place(&replicator(), (10, 10), (24, 24)),
+        );
+        assert_eq!(grid, expected);
+        // In Conway's Game of Life, the same pattern doesn't replicate.
+        let mut grid = place(&replicator(), (8, 8), (24, 24));
+        for _ in 0..12 {
+            grid = step(&grid);
+        }
+        assert_ne!(grid, expected);
+    }
+
+    #[test]
+    fn test_replicator_rule() {
+        // After 4 steps, there are 8 copies of the pattern around where it used to be.
+        let rule = LifeRule::from_notation(REPLICATOR).unwrap();
+        let pattern = cells(&["##", "#."]);
+        let mut grid = place(&pattern, (5, 5), (16, 16));
+        for _ in 0..4 {
+            grid = step_with_rule(&grid, &rule);
+        }
+        let mut expected = Grid::filled(false, (16, 16));
+        for (x, y) in [
+            (1, 1),
+            (5, 1),
+            (9, 1),
+            (1, 5),
+            (9, 5),
+            (1, 9),
+            (5, 9),
+            (9, 9),
+        ] {
+            expected = union(&expected, &place(&pattern, (x, y), (16, 16)));
+        }
+        assert_eq!(grid, expected);
+    }
+
+    #[test]
+    fn test_day_and_night_symmetry() {
+        // Inverting every cell, stepping, and inverting back is the same as just stepping,
+        // away from the edges (where the cells outside the grid are always dead).
+        let rule = LifeRule::from_notation(DAY_AND_NIGHT).unwrap();
+        let invert = |grid: &Grid<bool>| grid.clone().map(|alive| !alive);
+        let mut rng = StdRng::seed_from_u64(1149);
+        let mut grid = Grid::filled(false, (12, 12));
+        for y in 3..9 {
+            for x in 3..9 {
+                grid[(x, y)] = rng.gen_bool(0.5);
+            }
+        }
+        let direct = step_with_rule(&grid, &rule);
+        let inverted = invert(&step_with_rule(&invert(&grid), &rule));
+        for y in 1..11 {
+            for x in 1..11 {
+                assert_eq!(direct[(x, y)], inverted[(x, y)], "{:?}", (x, y));
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(CONWAY)]
+    #[case(HIGH_LIFE)]
+    #[case(DAY_AND_NIGHT)]
+    #[case(REPLICATOR)]
+    #[case("B/S")]
+    #[case("B012345678/S012345678")]
+    fn test_round_trip(#[case] notation: &str) {
+        assert_eq!(
+            LifeRule::from_notation(notation).unwrap().to_notation(),
+            notation
+        );
+    }
+
+    #[test]
+    fn test_notation_order() {
+        let rule = LifeRule::from_notation("B63/S32").unwrap();
+        assert_eq!(rule, LifeRule::from_notation(HIGH_LIFE).unwrap());
+        assert_eq!(rule.to_notation(), HIGH_LIFE);
+    }
+
+    #[rstest]
+    #[case::no_slash("B3S23", ParseError::Malformed)]
+    #[case::no_b("3/S23", ParseError::Malformed)]
+    #[case::no_s("B3/23", ParseError::Malformed)]
+    #[case::swapped("S23/B3", ParseError::Malformed)]
+    #[case::too_many_neighbors("B39/S23", ParseError::InvalidCount('9'))]
+    #[case::not_a_number("B3/S2x", ParseError::InvalidCount('x'))]
+    #[case::two_slashes("B3/S2/3", ParseError::InvalidCount('/'))]
+    fn test_invalid_notation(#[case] notation: &str, #[case] expected: ParseError) {
+        assert_eq!(LifeRule::from_notation(notation), Err(expected));
+    }
+}
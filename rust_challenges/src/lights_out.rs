@@ -0,0 +1,317 @@
+//! Lights Out is a puzzle played on a rectangular grid of lights, each of which is on or off.
+//! Pressing a light toggles it, along with its orthogonal neighbors.
+//! The goal is to turn all of the lights off.
+//!
+//! Problem: given a Lights Out board, find a set of lights to press that turns all lights off.
+//!
+//! Since pressing a light twice has no effect, and the order of presses doesn't matter,
+//! a solution is just a set of lights to press once each.
+//! Treating "pressed" and "lit" as elements of GF(2) (the integers mod 2),
+//! the problem becomes a system of linear equations:
+//! for each light, the number of presses affecting the light must equal whether it is lit.
+//! This system can then be solved with Gaussian elimination.
+//!
+//! For example, on the following board (`#` lit, `.` off):
+//! ```text
+//! . # .
+//! # # #
+//! . # .
+//! ```
+//! pressing the center light solves the puzzle.
+
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// A Lights Out board, where `true` indicates a light that is on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LightsOutBoard(pub Grid<bool>);
+
+impl LightsOutBoard {
+    /// Returns whether all lights on the board are off.
+    pub fn is_solved(&self) -> bool {
+        let LightsOutBoard(grid) = self;
+        grid.enumerate::<CellIndex>().all(|(_, &lit)| !lit)
+    }
+}
+
+/// Returns the indices of the given cell and its orthogonal neighbors
+/// on a board with the given dimensions, leaving out indices that are out of bounds.
+fn neighborhood_indices(
+    cell: CellIndex,
+    dimensions: (usize, usize),
+) -> impl Iterator<Item = CellIndex> {
+    let (x, y) = cell;
+    let (width, height) = dimensions;
+    // Subtracting from a coordinate of 0 wraps around to a (very) out-of-bounds coordinate.
+    [
+        (x, y),
+        (x.wrapping_sub(1), y),
+        (x + 1, y),
+        (x, y.wrapping_sub(1)),
+        (x, y + 1),
+    ]
+    .into_iter()
+    .filter(move |&(x, y)| x < width && y < height)
+}
+
+/// Presses the light at the given cell, toggling it and its orthogonal neighbors.
+///
+/// Panics if the cell is out of bounds.
+pub fn apply_move(board: &mut LightsOutBoard, cell: CellIndex) {
+    let LightsOutBoard(grid) = board;
+    assert!(
+        grid.get(cell).is_some(),
+        "cell {cell:?} out of bounds for dimensions {:?}",
+        grid.dimensions()
+    );
+    for index in neighborhood_indices(cell, grid.dimensions()) {
+        grid[index] = !grid[index];
+    }
+}
+
+/// Returns a list of cells that, when pressed, turns off all lights on the given board,
+/// or None if no such list exists.
+///
+/// The cells are listed in row-major order, and no cell is listed more than once.
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &LightsOutBoard) -> Option<Vec<CellIndex>> {
+    let LightsOutBoard(grid) = board;
+    let (width, height) = grid.dimensions();
+    let num_cells = width * height;
+    // Augmented matrix for the system of equations:
+    // `equations[i][j]` (for `j < num_cells`) indicates whether pressing cell `j` toggles cell `i`,
+    // and `equations[i][num_cells]` indicates whether cell `i` is lit.
+    let mut equations: Vec<Vec<bool>> = grid
+        .enumerate::<CellIndex>()
+        .map(|(cell, &lit)| {
+            let mut equation = vec![false; num_cells + 1];
+            for (x, y) in neighborhood_indices(cell, (width, height)) {
+                equation[y * width + x] = true;
+            }
+            equation[num_cells] = lit;
+            equation
+        })
+        .collect();
+    // Gauss-Jordan elimination, where addition is XOR.
+    let mut pivot_columns = vec![];
+    for column in 0..num_cells {
+        let pivot_row = pivot_columns.len();
+        let Some(row) = (pivot_row..num_cells).find(|&row| equations[row][column]) else {
+            continue;
+        };
+        equations.swap(pivot_row, row);
+        let pivot_equation = equations[pivot_row].clone();
+        for (row, equation) in equations.iter_mut().enumerate() {
+            if row != pivot_row && equation[column] {
+                for (coefficient, &pivot_coefficient) in equation.iter_mut().zip(&pivot_equation) {
+                    *coefficient ^= pivot_coefficient;
+                }
+            }
+        }
+        pivot_columns.push(column);
+    }
+    // Equations without a pivot have been reduced to `0 = <constant>`,
+    // so the system is inconsistent if any such constant is 1.
+    if equations[pivot_columns.len()..]
+        .iter()
+        .any(|equation| equation[num_cells])
+    {
+        return None;
+    }
+    // Setting all free variables to 0, each pivot variable equals its equation's constant.
+    let mut presses: Vec<usize> = pivot_columns
+        .iter()
+        .zip(&equations)
+        .filter_map(|(&column, equation)| equation[num_cells].then_some(column))
+        .collect();
+    presses.sort();
+    Some(
+        presses
+            .into_iter()
+            .map(|index| (index % width, index / width))
+            .collect(),
+    )
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells on the board.
+        This function completes in `O(c^3)` time in the worst case --
+        for an `n×n` board, this translates to a time complexity of `O(n^6)`.
+        - Setting up the `c` equations, each with `c + 1` coefficients, takes `O(c^2)` time.
+        - Elimination processes `c` columns; for each, up to `c` equations are updated,
+          at a cost of `O(c)` per equation.
+        - Checking consistency and reading off the solution takes `O(c log c)` time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lights_out::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Lights Out board.
+    ///
+    /// Syntax:
+    /// ```text
+    /// lights![
+    ///     [<`#` for on, `.` for off> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! lights {
+        (@light #) => {true};
+        (@light .) => {false};
+        ($([$($light:tt)*])*) => {
+            LightsOutBoard(Grid::from_2d_array([$([$(lights!(@light $light)),*]),*]))
+        };
+    }
+
+    #[rstest]
+    #[case::center(lights![
+        [. . .]
+        [. . .]
+        [. . .]
+    ], (1, 1), lights![
+        [. # .]
+        [# # #]
+        [. # .]
+    ])]
+    #[case::corner(lights![
+        [. . .]
+        [. # .]
+        [. . .]
+    ], (0, 0), lights![
+        [# # .]
+        [# # .]
+        [. . .]
+    ])]
+    #[case::edge_toggles_off(lights![
+        [# # #]
+        [. # .]
+        [. . .]
+    ], (1, 0), lights![
+        [. . .]
+        [. . .]
+        [. . .]
+    ])]
+    #[case::single(lights![[.]], (0, 0), lights![[#]])]
+    fn test_apply_move(
+        #[case] mut board: LightsOutBoard,
+        #[case] cell: CellIndex,
+        #[case] expected: LightsOutBoard,
+    ) {
+        apply_move(&mut board, cell);
+        assert_eq!(board, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (3, 0) out of bounds for dimensions (3, 2)")]
+    fn test_apply_move_out_of_bounds() {
+        let mut board = LightsOutBoard(Grid::filled(false, (3, 2)));
+        apply_move(&mut board, (3, 0));
+    }
+
+    #[rstest]
+    #[case::already_solved(lights![
+        [. . .]
+        [. . .]
+        [. . .]
+    ], vec![])]
+    #[case::one_press(lights![
+        [. # .]
+        [# # #]
+        [. # .]
+    ], vec![(1, 1)])]
+    #[case::corner_press(lights![
+        [# # .]
+        [# . .]
+        [. . .]
+    ], vec![(0, 0)])]
+    #[case::two_presses(lights![
+        [# # .]
+        [# . #]
+        [. # #]
+    ], vec![(0, 0), (2, 2)])]
+    fn test_solve_exact(#[case] board: LightsOutBoard, #[case] expected: Vec<CellIndex>) {
+        // The boards for these cases have a unique solution.
+        assert_eq!(solve(&board), Some(expected));
+    }
+
+    #[rstest]
+    #[case::already_solved(lights![
+        [. . . . .]
+        [. . . . .]
+        [. . . . .]
+        [. . . . .]
+        [. . . . .]
+    ])]
+    #[case::all_on(lights![
+        [# # # #]
+        [# # # #]
+        [# # # #]
+    ])]
+    #[case::all_on_square(lights![
+        [# # # # # #]
+        [# # # # # #]
+        [# # # # # #]
+        [# # # # # #]
+        [# # # # # #]
+        [# # # # # #]
+    ])]
+    #[case::single_row(lights![[# # . # # . # . . # .]])]
+    #[case::single(lights![[#]])]
+    fn test_solve(#[case] board: LightsOutBoard) {
+        let solution = solve(&board).unwrap();
+        let mut result = board.clone();
+        for &cell in &solution {
+            apply_move(&mut result, cell);
+        }
+        assert!(result.is_solved(), "not solved by {solution:?}: {result:?}");
+    }
+
+    #[test]
+    fn test_solve_generated() {
+        // Any board reachable from a solved board by pressing lights is solvable,
+        // even on a 5×5 board.
+        let mut board = LightsOutBoard(Grid::filled(false, (5, 5)));
+        for cell in [(0, 0), (2, 1), (4, 4), (1, 3), (3, 2)] {
+            apply_move(&mut board, cell);
+        }
+        let solution = solve(&board).unwrap();
+        for &cell in &solution {
+            apply_move(&mut board, cell);
+        }
+        assert!(board.is_solved(), "not solved by {solution:?}: {board:?}");
+    }
+
+    #[rstest]
+    // The classic 5×5 board can't solve every configuration,
+    // such as a single light in the corner.
+    #[case::corner(lights![
+        [# . . . .]
+        [. . . . .]
+        [. . . . .]
+        [. . . . .]
+        [. . . . .]
+    ])]
+    // Every press toggles both lights.
+    #[case::pair(lights![[# .]])]
+    #[case::pair_vertical(lights![
+        [.]
+        [#]
+    ])]
+    fn test_unsolvable(#[case] board: LightsOutBoard) {
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = LightsOutBoard(Grid::filled(false, (width, height)));
+        assert_eq!(solve(&board), Some(vec![]));
+    }
+}
@@ -0,0 +1,220 @@
+//! A magic square of size `n` is an `n`×`n` grid of distinct positive integers,
+//! where every row, every column, and both main diagonals have the same sum.
+//! When the integers are 1 to `n^2`, that sum is the _magic constant_ `n * (n^2 + 1) / 2`.
+//!
+//! Problem: check whether a grid is a magic square,
+//! and construct a magic square of size `n` containing the integers 1 to `n^2`,
+//! for odd `n` and for `n` divisible by 4.
+//!
+//! For odd `n`, the Siamese method places 1 in the middle of the top row,
+//! then places each next number diagonally up and to the right of the last one,
+//! wrapping around the edges of the grid --
+//! or directly below the last one, if the cell up and to the right is already filled.
+//! For example, with `n = 3`:
+//! ```text
+//! 8 1 6
+//! 3 5 7
+//! 4 9 2
+//! ```
+//!
+//! For `n` divisible by 4, the numbers are written in order, row by row,
+//! and then every number on a diagonal of one of the 4×4 blocks making up the grid
+//! is replaced with its "complement" `n^2 + 1 - k`. For example, with `n = 4`:
+//! ```text
+//! 16  2  3 13
+//!  5 11 10  8
+//!  9  7  6 12
+//!  4 14 15  1
+//! ```
+
+use crate::grid::Grid;
+use std::collections::HashSet;
+
+type CellIndex = (usize, usize);
+
+/// Returns the magic constant for magic squares of size `n` containing the integers 1 to `n^2`.
+pub fn magic_constant(n: usize) -> i64 {
+    let n = n as i64;
+    n * (n * n + 1) / 2
+}
+
+/// Returns whether the given grid is a magic square:
+/// it is square, its entries are distinct positive integers,
+/// and every row, every column, and both main diagonals have the same sum.
+///
+/// The entries don't need to be the integers 1 to `n^2`.
+/// An empty (0×0) grid is considered to be a magic square.
+pub fn is_magic_square(grid: &Grid<i64>) -> bool {
+    let n = grid.width();
+    if grid.height() != n {
+        return false;
+    }
+    let mut seen = HashSet::new();
+    if !grid
+        .enumerate::<CellIndex>()
+        .all(|(_, &entry)| entry > 0 && seen.insert(entry))
+    {
+        return false;
+    }
+    let rows = (0..n).map(|y| (0..n).map(|x| (x, y)).collect());
+    let columns = (0..n).map(|x| (0..n).map(|y| (x, y)).collect());
+    let diagonals = [
+        (0..n).map(|i| (i, i)).collect(),
+        (0..n).map(|i| (n - 1 - i, i)).collect(),
+    ];
+    let mut sums = rows
+        .chain(columns)
+        .chain(diagonals)
+        .map(|line: Vec<CellIndex>| line.into_iter().map(|index| grid[index]).sum::<i64>());
+    match sums.next() {
+        Some(first) => sums.all(|sum| sum == first),
+        None => true,
+    }
+    /*
+        Time complexity analysis:
+        This function completes in `O(n^2)` time in the worst case, where the grid is `n`×`n`.
+        - Checking the entries takes `O(n^2)` time,
+          assuming that `HashSet` operations take `O(1)` time.
+        - There are `2n + 2` lines to sum, each of length `n`.
+    */
+}
+
+/// Returns a magic square of size `n` containing the integers 1 to `n^2`,
+/// constructed using the Siamese method.
+///
+/// Panics if `n` is even.
+pub fn generate_odd(n: usize) -> Grid<i64> {
+    assert!(n % 2 == 1, "expected odd size, got {n}");
+    let mut grid = Grid::filled(0, (n, n));
+    let (mut x, mut y) = (n / 2, 0);
+    for k in 1..=(n * n) as i64 {
+        grid[(x, y)] = k;
+        let up_right = ((x + 1) % n, (y + n - 1) % n);
+        if grid[up_right] == 0 {
+            (x, y) = up_right;
+        } else {
+            y = (y + 1) % n;
+        }
+    }
+    grid
+    /*
+        Time complexity analysis:
+        This function completes in `O(n^2)` time in the worst case,
+        placing each of the `n^2` numbers in `O(1)` time.
+    */
+}
+
+/// Returns a magic square of size `n` containing the integers 1 to `n^2`,
+/// constructed by complementing the numbers on the diagonals of each 4×4 block.
+///
+/// Panics if `n` is not divisible by 4.
+pub fn generate_doubly_even(n: usize) -> Grid<i64> {
+    assert!(n.is_multiple_of(4), "expected size divisible by 4, got {n}");
+    let mut grid = Grid::filled(0, (n, n));
+    let max = (n * n) as i64;
+    for y in 0..n {
+        for x in 0..n {
+            let k = (y * n + x + 1) as i64;
+            let is_on_block_diagonal = x % 4 == y % 4 || x % 4 + y % 4 == 3;
+            grid[(x, y)] = if is_on_block_diagonal { max + 1 - k } else { k };
+        }
+    }
+    grid
+    /*
+        Time complexity analysis:
+        This function completes in `O(n^2)` time in the worst case,
+        placing each of the `n^2` numbers in `O(1)` time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::magic_square::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::empty(0, 0)]
+    #[case::one(1, 1)]
+    #[case::three(3, 15)]
+    #[case::four(4, 34)]
+    #[case::five(5, 65)]
+    #[case::eight(8, 260)]
+    fn test_magic_constant(#[case] n: usize, #[case] expected: i64) {
+        assert_eq!(magic_constant(n), expected);
+    }
+
+    #[test]
+    fn test_generate_odd_3() {
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [8, 1, 6],
+            [3, 5, 7],
+            [4, 9, 2],
+        ]);
+        assert_eq!(generate_odd(3), expected);
+    }
+
+    #[test]
+    fn test_generate_doubly_even_4() {
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [16,  2,  3, 13],
+            [ 5, 11, 10,  8],
+            [ 9,  7,  6, 12],
+            [ 4, 14, 15,  1],
+        ]);
+        assert_eq!(generate_doubly_even(4), expected);
+    }
+
+    /// Checks that the given grid is a magic square of size `n`
+    /// containing exactly the integers 1 to `n^2`, with the expected magic constant.
+    fn assert_normal_magic_square(grid: &Grid<i64>, n: usize) {
+        assert_eq!(grid.dimensions(), (n, n));
+        assert!(is_magic_square(grid), "not a magic square: {grid:?}");
+        let mut entries: Vec<_> = grid.enumerate::<CellIndex>().map(|(_, &k)| k).collect();
+        entries.sort();
+        assert_eq!(entries, (1..=(n * n) as i64).collect::<Vec<_>>());
+        let first_row_sum: i64 = (0..n).map(|x| grid[(x, 0)]).sum();
+        assert_eq!(first_row_sum, magic_constant(n));
+    }
+
+    #[rstest]
+    fn test_generate_odd(#[values(1, 3, 5, 7, 9, 15)] n: usize) {
+        assert_normal_magic_square(&generate_odd(n), n);
+    }
+
+    #[rstest]
+    fn test_generate_doubly_even(#[values(0, 4, 8, 12, 16)] n: usize) {
+        assert_normal_magic_square(&generate_doubly_even(n), n);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected odd size, got 4")]
+    fn test_generate_odd_even_size() {
+        generate_odd(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected size divisible by 4, got 6")]
+    fn test_generate_doubly_even_wrong_size() {
+        generate_doubly_even(6);
+    }
+
+    #[rstest]
+    #[case::empty(Grid::filled(0, (0, 0)), true)]
+    #[case::single(Grid::from_2d_array([[7]]), true)]
+    #[case::single_zero(Grid::from_2d_array([[0]]), false)]
+    // Entries don't need to be consecutive.
+    #[case::scaled(generate_odd(3).map(|k| 10 * k + 3), true)]
+    #[case::swapped(Grid::from_2d_array([[1, 8, 6], [3, 5, 7], [4, 9, 2]]), false)]
+    // Rows and columns all have the same sum, but the diagonals don't.
+    #[case::semi_magic(Grid::from_2d_array([[1, 8, 6], [5, 3, 7], [9, 4, 2]]), false)]
+    #[case::all_equal(Grid::filled(5, (3, 3)), false)]
+    #[case::non_positive(generate_odd(3).map(|k| k - 5), false)]
+    #[case::not_square(Grid::from_2d_array([[1, 2]]), false)]
+    #[case::not_square_empty(Grid::filled(0, (3, 0)), false)]
+    fn test_is_magic_square(#[case] grid: Grid<i64>, #[case] expected: bool) {
+        assert_eq!(is_magic_square(&grid), expected);
+    }
+}
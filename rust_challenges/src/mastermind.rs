@@ -0,0 +1,295 @@
+//! Mastermind is a code-breaking game: one player picks a secret code of `length` pegs,
+//! each one of `colors` colors, and the other player tries to guess it.
+//! After each guess, the guesser is told how many pegs are the right color in the right position
+//! (black pegs), and how many other pegs are the right color in the wrong position (white pegs).
+//!
+//! Problem: score guesses, and find the secret code in as few guesses as possible.
+//!
+//! For example, with the secret `0 1 2 3`, the guess `0 2 1 5` scores 1 black peg (for the `0`)
+//! and 2 white pegs (for the `1` and the `2`).
+//!
+//! The solver uses Knuth's minimax algorithm:
+//! it keeps track of the codes that are still possible secrets given the scores so far,
+//! and guesses the code that minimizes the worst-case number of possibilities left after the next score,
+//! preferring codes that could be the secret, and then the first code in lexicographic order.
+//! For the standard game with 6 colors and 4 pegs, this always finds the secret in at most 5 guesses.
+
+/// A code: the color of each peg, from 0 up to (but not including) the number of colors.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Code(pub Vec<u8>);
+
+/// Returns every code with the given number of colors and length, in lexicographic order.
+pub fn all_codes(colors: u8, length: usize) -> Vec<Code> {
+    let mut result = vec![Code(vec![])];
+    for _ in 0..length {
+        result = result
+            .into_iter()
+            .flat_map(|Code(pegs)| {
+                (0..colors).map(move |color| {
+                    let mut pegs = pegs.clone();
+                    pegs.push(color);
+                    Code(pegs)
+                })
+            })
+            .collect();
+    }
+    result
+}
+
+/// Returns the score for the given guess against the given secret:
+/// the number of pegs with the right color in the right position (black pegs),
+/// and the number of other pegs with the right color in the wrong position (white pegs).
+///
+/// Panics if the codes have different lengths.
+pub fn score(guess: &Code, secret: &Code) -> (u8, u8) {
+    let (Code(guess), Code(secret)) = (guess, secret);
+    assert_eq!(
+        guess.len(),
+        secret.len(),
+        "guess and secret have different lengths"
+    );
+    let mut black = 0;
+    // The number of pegs with matching colors regardless of position, counting each color once.
+    let mut matching = 0;
+    for (i, &color) in guess.iter().enumerate() {
+        if secret[i] == color {
+            black += 1;
+        }
+        if guess[..i].contains(&color) {
+            continue;
+        }
+        let (mut in_guess, mut in_secret) = (0, 0);
+        for (&guess_color, &secret_color) in guess.iter().zip(secret) {
+            in_guess += usize::from(guess_color == color);
+            in_secret += usize::from(secret_color == color);
+        }
+        matching += in_guess.min(in_secret);
+    }
+    (black as u8, (matching - black) as u8)
+}
+
+/// Returns the guess that minimizes the worst-case number of possible secrets left
+/// after it's scored, out of the codes in `all_codes`.
+///
+/// Ties are broken in favor of codes in `possible_secrets` (which could win immediately),
+/// then in favor of codes that come earlier in `all_codes`.
+///
+/// Panics if `possible_secrets` or `all_codes` is empty.
+pub fn minimax_guess(possible_secrets: &[Code], all_codes: &[Code]) -> Code {
+    assert!(!possible_secrets.is_empty(), "no possible secrets");
+    let length = possible_secrets[0].0.len();
+    // The number of possible secrets with each score, indexed by `black * (length + 1) + white`.
+    let mut counts = vec![0; (length + 1) * (length + 1)];
+    let mut best: Option<(&Code, usize, bool)> = None;
+    for guess in all_codes {
+        counts.fill(0);
+        for secret in possible_secrets {
+            let (black, white) = score(guess, secret);
+            counts[usize::from(black) * (length + 1) + usize::from(white)] += 1;
+        }
+        let worst_case = counts.iter().copied().max().unwrap_or(0);
+        let is_better = match best {
+            None => true,
+            Some((_, best_worst_case, best_is_possible)) => {
+                worst_case < best_worst_case
+                    || (worst_case == best_worst_case
+                        && !best_is_possible
+                        && possible_secrets.contains(guess))
+            }
+        };
+        if is_better {
+            best = Some((guess, worst_case, possible_secrets.contains(guess)));
+        }
+    }
+    let (best, _, _) = best.expect("no codes to guess from");
+    best.clone()
+    /*
+        Time complexity analysis:
+        Let `p` be the number of possible secrets, `a` be the number of codes to guess from,
+        and `l` be the length of the codes.
+        Scoring takes `O(l^2)` time, and each guess is scored against every possible secret,
+        so this function completes in `O(a * p * l^2)` time.
+    */
+}
+
+/// Returns the guesses made by Knuth's algorithm (see [`minimax_guess`])
+/// to find the given secret, ending with the secret itself.
+///
+/// Panics if the secret doesn't have the given length, or has a color out of range.
+pub fn solve(secret: &Code, colors: u8, length: usize) -> Vec<Code> {
+    let Code(pegs) = secret;
+    assert_eq!(pegs.len(), length, "secret doesn't have length {length}");
+    assert!(
+        pegs.iter().all(|&color| color < colors),
+        "secret has a color out of range for {colors} colors"
+    );
+    let all_codes = all_codes(colors, length);
+    let mut possible_secrets = all_codes.clone();
+    let mut guesses = vec![];
+    loop {
+        let guess = minimax_guess(&possible_secrets, &all_codes);
+        let result = score(&guess, secret);
+        guesses.push(guess.clone());
+        if result == (length as u8, 0) {
+            return guesses;
+        }
+        possible_secrets.retain(|code| score(&guess, code) == result);
+    }
+    /*
+        Time complexity analysis:
+        Let `n = colors^length` be the number of codes, and `g` be the number of guesses.
+        Each guess takes `O(n^2 * length^2)` time to choose (see `minimax_guess`),
+        so this function completes in `O(g * n^2 * length^2)` time.
+        The number of possible secrets usually shrinks quickly, so later guesses are much faster.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mastermind::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    /// Creates a code from the given colors.
+    fn code<const N: usize>(pegs: [u8; N]) -> Code {
+        Code(pegs.to_vec())
+    }
+
+    #[rstest]
+    #[case::problem_description_example(code([0, 2, 1, 5]), code([0, 1, 2, 3]), (1, 2))]
+    #[case::no_matches(code([0, 0, 1, 1]), code([2, 3, 4, 5]), (0, 0))]
+    #[case::all_white(code([3, 2, 1, 0]), code([0, 1, 2, 3]), (0, 4))]
+    // Only one of the guess's 1s can match the secret's single 1.
+    #[case::repeated_in_guess(code([1, 1, 2, 2]), code([1, 3, 3, 3]), (1, 0))]
+    #[case::repeated_in_secret(code([0, 1, 2, 3]), code([1, 1, 1, 0]), (1, 1))]
+    #[case::repeated_in_both(code([1, 1, 2, 2]), code([2, 1, 1, 3]), (1, 2))]
+    #[case::empty(code([]), code([]), (0, 0))]
+    fn test_score(#[case] guess: Code, #[case] secret: Code, #[case] expected: (u8, u8)) {
+        assert_eq!(score(&guess, &secret), expected);
+    }
+
+    #[test]
+    fn test_score_symmetric() {
+        let codes = all_codes(3, 4);
+        for guess in &codes {
+            for secret in &codes {
+                assert_eq!(score(guess, secret), score(secret, guess));
+            }
+        }
+    }
+
+    #[test]
+    fn test_score_exact_match() {
+        for secret in all_codes(4, 3) {
+            assert_eq!(score(&secret, &secret), (3, 0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "guess and secret have different lengths")]
+    fn test_score_different_lengths() {
+        score(&code([0, 1]), &code([0, 1, 2]));
+    }
+
+    #[test]
+    fn test_all_codes() {
+        let codes = all_codes(3, 2);
+        let expected: Vec<Code> = [
+            [0, 0],
+            [0, 1],
+            [0, 2],
+            [1, 0],
+            [1, 1],
+            [1, 2],
+            [2, 0],
+            [2, 1],
+            [2, 2],
+        ]
+        .map(code)
+        .to_vec();
+        assert_eq!(codes, expected);
+        assert_eq!(all_codes(6, 4).len(), 1296);
+        assert_eq!(all_codes(5, 0), [code([])]);
+        assert_eq!(all_codes(0, 3), []);
+    }
+
+    #[test]
+    fn test_minimax_prefers_possible_secrets() {
+        let codes = all_codes(2, 2);
+        // Every guess leaves at most one possibility, so the earliest possible secret wins the tie.
+        let possible_secrets = [code([1, 0]), code([1, 1])];
+        assert_eq!(minimax_guess(&possible_secrets, &codes), code([1, 0]));
+        assert_eq!(minimax_guess(&[code([1, 1])], &codes), code([1, 1]));
+    }
+
+    /// Returns the largest number of guesses that Knuth's algorithm needs
+    /// to find any of the given possible secrets, given the previous guesses.
+    ///
+    /// This explores the whole tree of guesses that `solve` makes, so it covers every secret
+    /// without recomputing the guesses that they have in common.
+    fn max_guesses(possible_secrets: &[Code], all_codes: &[Code]) -> usize {
+        let guess = minimax_guess(possible_secrets, all_codes);
+        let mut groups: HashMap<(u8, u8), Vec<Code>> = HashMap::new();
+        for secret in possible_secrets {
+            groups
+                .entry(score(&guess, secret))
+                .or_default()
+                .push(secret.clone());
+        }
+        let length = guess.0.len() as u8;
+        let max_remaining = groups
+            .into_iter()
+            .filter(|&(result, _)| result != (length, 0))
+            .map(|(_, group)| max_guesses(&group, all_codes))
+            .max()
+            .unwrap_or(0);
+        1 + max_remaining
+    }
+
+    #[test]
+    fn test_standard_game_at_most_5_guesses() {
+        let codes = all_codes(6, 4);
+        assert_eq!(max_guesses(&codes, &codes), 5);
+    }
+
+    #[rstest]
+    #[case::all_same(code([5, 5, 5, 5]))]
+    #[case::mixed(code([4, 1, 4, 2]))]
+    fn test_solve_standard_game(#[case] secret: Code) {
+        let guesses = solve(&secret, 6, 4);
+        // This is Knuth's first guess of 1122, with colors numbered from 0.
+        assert_eq!(guesses[0], code([0, 0, 1, 1]));
+        assert!(
+            guesses.len() <= 5,
+            "{} guesses for {secret:?}",
+            guesses.len()
+        );
+        assert_eq!(guesses.last(), Some(&secret));
+        // A repeated guess would give no new information.
+        for (i, guess) in guesses.iter().enumerate() {
+            assert!(!guesses[..i].contains(guess));
+        }
+    }
+
+    #[test]
+    fn test_solve_every_secret() {
+        let codes = all_codes(3, 3);
+        for secret in &codes {
+            let guesses = solve(secret, 3, 3);
+            assert_eq!(guesses.last(), Some(secret));
+            assert!(guesses.len() <= max_guesses(&codes, &codes));
+        }
+    }
+
+    #[test]
+    fn test_solve_empty_code() {
+        assert_eq!(solve(&code([]), 6, 0), [code([])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "secret has a color out of range for 6 colors")]
+    fn test_solve_invalid_color() {
+        solve(&code([0, 6, 0, 0]), 6, 4);
+    }
+}
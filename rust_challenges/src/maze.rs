@@ -0,0 +1,26 @@
+//! A maze here is a rectangular grid of cells with walls between orthogonally adjacent cells,
+//! some of which have been removed to make passages.
+//! A _perfect_ maze has exactly one path between any two cells,
+//! which makes its passages a spanning tree of the grid of cells.
+//!
+//! Problem: generate random perfect mazes.
+//!
+//! For example, this is a perfect 4 by 3 maze, drawn with `#` for walls:
+//! ```text
+//! #########
+//! #.....#.#
+//! #.###.#.#
+//! #...#...#
+//! ###.#####
+//! #.......#
+//! #########
+//! ```
+
+// Dependencies (later modules depend on earlier ones): layout -> kruskal_maze, wilson_maze
+mod kruskal_maze;
+mod layout;
+mod wilson_maze;
+
+pub use kruskal_maze::generate_kruskal;
+pub use layout::{CellIndex, Maze};
+pub use wilson_maze::generate_wilson;
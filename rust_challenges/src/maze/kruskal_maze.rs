@@ -0,0 +1,109 @@
+//! Module for generating mazes with a randomized version of Kruskal's algorithm.
+//!
+//! Every wall between two cells is considered once, in a random order,
+//! and removed if the cells on either side of it aren't connected yet,
+//! which is tracked with a union-find forest of the cells.
+
+use crate::maze::layout::{CellIndex, Maze};
+use crate::union_find::UnionFind;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Returns a random perfect maze with the given dimensions.
+pub fn generate_kruskal(width: usize, height: usize, rng: &mut impl Rng) -> Maze {
+    let mut maze = Maze::new(width, height);
+    let cell_count = width * height;
+    let mut walls: Vec<(CellIndex, CellIndex)> = vec![];
+    for y in 0..height {
+        for x in 0..width {
+            if x + 1 < width {
+                walls.push(((x, y), (x + 1, y)));
+            }
+            if y + 1 < height {
+                walls.push(((x, y), (x, y + 1)));
+            }
+        }
+    }
+    walls.shuffle(rng);
+    let mut forest = UnionFind::new(cell_count);
+    let mut passage_count = 0;
+    for (a, b) in walls {
+        // Once every cell is connected, every remaining wall would form a cycle.
+        if passage_count + 1 >= cell_count {
+            break;
+        }
+        if forest.union(a.1 * width + a.0, b.1 * width + b.0) {
+            maze.add_passage(a, b);
+            passage_count += 1;
+        }
+    }
+    maze
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the maze.
+        There are `O(c)` walls between cells, which take `O(c)` time to shuffle,
+        and going through them takes `O(c log c)` time in the worst case
+        (with path compression in the union-find forest, but no union by rank),
+        so this function completes in `O(c log c)` time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::kruskal_maze::*;
+    use pretty_assertions::assert_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(4, 3)]
+    #[case(10, 10)]
+    #[case(1, 7)]
+    #[case(7, 1)]
+    #[case(1, 1)]
+    #[case(0, 0)]
+    #[case(5, 0)]
+    fn test_perfect_maze(#[case] width: usize, #[case] height: usize) {
+        let mut rng = StdRng::seed_from_u64(1147);
+        for _ in 0..10 {
+            let maze = generate_kruskal(width, height, &mut rng);
+            assert_eq!(maze.dimensions(), (width, height));
+            assert_eq!(maze.passage_count(), (width * height).saturating_sub(1));
+            assert!(maze.is_perfect());
+        }
+    }
+
+    #[test]
+    fn test_different_seeds() {
+        let mazes: Vec<Maze> = (0..5)
+            .map(|seed| generate_kruskal(8, 8, &mut StdRng::seed_from_u64(seed)))
+            .collect();
+        for (i, a) in mazes.iter().enumerate() {
+            for b in &mazes[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed() {
+        let a = generate_kruskal(8, 8, &mut StdRng::seed_from_u64(0));
+        let b = generate_kruskal(8, 8, &mut StdRng::seed_from_u64(0));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_every_maze_possible() {
+        // A 2 by 2 maze is a cycle of 4 cells with one passage missing, so there are 4 of them.
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut seen = vec![];
+        for _ in 0..100 {
+            let maze = generate_kruskal(2, 2, &mut rng);
+            if !seen.contains(&maze) {
+                seen.push(maze);
+            }
+        }
+        assert_eq!(seen.len(), 4);
+    }
+}
@@ -0,0 +1,256 @@
+//! Module for representing the passages of a maze.
+
+use crate::grid::Grid;
+use std::collections::VecDeque;
+
+pub type CellIndex = (usize, usize);
+
+/// A rectangular maze, as the passages between its cells.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Maze {
+    /// Whether each cell has a passage to the cell to its right.
+    right: Grid<bool>,
+    /// Whether each cell has a passage to the cell below it.
+    down: Grid<bool>,
+}
+
+impl Maze {
+    /// Creates a maze with the given dimensions and no passages.
+    pub fn new(width: usize, height: usize) -> Maze {
+        Maze {
+            right: Grid::filled(false, (width, height)),
+            down: Grid::filled(false, (width, height)),
+        }
+    }
+
+    /// The number of cells in each row.
+    pub fn width(&self) -> usize {
+        self.right.width()
+    }
+
+    /// The number of cells in each column.
+    pub fn height(&self) -> usize {
+        self.right.height()
+    }
+
+    /// The width and height of the maze.
+    pub fn dimensions(&self) -> (usize, usize) {
+        self.right.dimensions()
+    }
+
+    /// Returns the flag for the passage between the given cells,
+    /// or None if they aren't orthogonally adjacent cells of the maze.
+    fn passage(&self, a: CellIndex, b: CellIndex) -> Option<&bool> {
+        let (first, second) = (a.min(b), a.max(b));
+        if (first.0 + 1, first.1) == second && second.0 < self.width() {
+            self.right.get(first)
+        } else if (first.0, first.1 + 1) == second && second.1 < self.height() {
+            self.down.get(first)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether there's a passage between the given cells.
+    ///
+    /// Cells that aren't orthogonally adjacent never have a passage between them.
+    pub fn has_passage(&self, a: CellIndex, b: CellIndex) -> bool {
+        self.passage(a, b).is_some_and(|&passage| passage)
+    }
+
+    /// Removes the wall between the given cells.
+    ///
+    /// Panics if the cells aren't orthogonally adjacent cells of the maze.
+    pub fn add_passage(&mut self, a: CellIndex, b: CellIndex) {
+        assert!(
+            self.passage(a, b).is_some(),
+            "{a:?} and {b:?} aren't adjacent cells of the maze"
+        );
+        let (first, second) = (a.min(b), a.max(b));
+        if first.1 == second.1 {
+            self.right[first] = true;
+        } else {
+            self.down[first] = true;
+        }
+    }
+
+    /// Returns the cells that the given cell has passages to.
+    pub fn neighbors(&self, (x, y): CellIndex) -> Vec<CellIndex> {
+        let mut candidates = vec![(x + 1, y), (x, y + 1)];
+        if x > 0 {
+            candidates.push((x - 1, y));
+        }
+        if y > 0 {
+            candidates.push((x, y - 1));
+        }
+        candidates
+            .into_iter()
+            .filter(|&other| self.has_passage((x, y), other))
+            .collect()
+    }
+
+    /// The total number of passages in the maze.
+    pub fn passage_count(&self) -> usize {
+        let count = |passages: &Grid<bool>| {
+            passages
+                .enumerate::<CellIndex>()
+                .filter(|(_, &p)| p)
+                .count()
+        };
+        count(&self.right) + count(&self.down)
+    }
+
+    /// Returns whether there's exactly one path between any two cells of the maze.
+    ///
+    /// A maze with no cells is perfect.
+    pub fn is_perfect(&self) -> bool {
+        let (width, height) = self.dimensions();
+        if width == 0 || height == 0 {
+            return true;
+        }
+        // A connected graph is a tree if and only if it has one edge fewer than it has vertices.
+        if self.passage_count() != width * height - 1 {
+            return false;
+        }
+        let mut reached = Grid::filled(false, (width, height));
+        reached[(0, 0)] = true;
+        let mut queue = VecDeque::from([(0, 0)]);
+        let mut reached_count = 1;
+        while let Some(cell) = queue.pop_front() {
+            for next in self.neighbors(cell) {
+                if !reached[next] {
+                    reached[next] = true;
+                    reached_count += 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached_count == width * height
+    }
+
+    /// Draws the maze as a grid where walls are `false` and everything else is `true`.
+    ///
+    /// Cell `(x, y)` is drawn at `(2 * x + 1, 2 * y + 1)`, with walls all around the maze,
+    /// so the grid's dimensions are `(2 * width + 1, 2 * height + 1)`.
+    pub fn to_grid(&self) -> Grid<bool> {
+        let (width, height) = self.dimensions();
+        let mut result = Grid::filled(false, (2 * width + 1, 2 * height + 1));
+        for y in 0..height {
+            for x in 0..width {
+                let (drawn_x, drawn_y) = (2 * x + 1, 2 * y + 1);
+                result[(drawn_x, drawn_y)] = true;
+                result[(drawn_x + 1, drawn_y)] = self.right[(x, y)];
+                result[(drawn_x, drawn_y + 1)] = self.down[(x, y)];
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::layout::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// The example maze from the problem description.
+    fn example() -> Maze {
+        let mut maze = Maze::new(4, 3);
+        for (a, b) in [
+            ((0, 0), (1, 0)),
+            ((1, 0), (2, 0)),
+            ((0, 0), (0, 1)),
+            ((2, 0), (2, 1)),
+            ((3, 0), (3, 1)),
+            ((0, 1), (1, 1)),
+            ((3, 1), (2, 1)),
+            ((1, 1), (1, 2)),
+            ((0, 2), (1, 2)),
+            ((1, 2), (2, 2)),
+            ((2, 2), (3, 2)),
+        ] {
+            maze.add_passage(a, b);
+        }
+        maze
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let maze = example();
+        assert_eq!(maze.dimensions(), (4, 3));
+        assert_eq!(maze.passage_count(), 11);
+        assert!(maze.is_perfect());
+        let drawing = [
+            "#########",
+            "#.....#.#",
+            "#.###.#.#",
+            "#...#...#",
+            "###.#####",
+            "#.......#",
+            "#########",
+        ];
+        let mut expected = Grid::filled(false, (9, 7));
+        for (y, row) in drawing.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                expected[(x, y)] = c == '.';
+            }
+        }
+        assert_eq!(maze.to_grid(), expected);
+    }
+
+    #[test]
+    fn test_passages() {
+        let maze = example();
+        assert!(maze.has_passage((2, 1), (3, 1)));
+        assert!(maze.has_passage((3, 1), (2, 1)));
+        assert!(!maze.has_passage((1, 1), (2, 1)));
+        // Not adjacent.
+        assert!(!maze.has_passage((0, 0), (1, 1)));
+        assert!(!maze.has_passage((3, 0), (4, 0)));
+        let mut neighbors = maze.neighbors((1, 2));
+        neighbors.sort();
+        assert_eq!(neighbors, [(0, 2), (1, 1), (2, 2)]);
+        assert_eq!(maze.neighbors((3, 2)), [(2, 2)]);
+    }
+
+    #[rstest]
+    // There are enough passages, but they form a cycle around the top 2 by 2 cells,
+    // and the bottom right cell is cut off.
+    #[case::cycle(&[
+        ((0, 0), (1, 0)),
+        ((1, 0), (1, 1)),
+        ((1, 1), (0, 1)),
+        ((0, 1), (0, 0)),
+        ((0, 1), (0, 2)),
+    ])]
+    #[case::disconnected(&[((0, 0), (1, 0)), ((1, 0), (1, 1)), ((0, 1), (0, 2))])]
+    #[case::no_passages(&[])]
+    fn test_imperfect(#[case] passages: &[(CellIndex, CellIndex)]) {
+        let mut maze = Maze::new(2, 3);
+        for &(a, b) in passages {
+            maze.add_passage(a, b);
+        }
+        assert!(!maze.is_perfect());
+    }
+
+    #[rstest]
+    #[case(0, 0, true)]
+    #[case(3, 0, true)]
+    #[case(1, 1, true)]
+    #[case(2, 1, false)]
+    fn test_no_passages(#[case] width: usize, #[case] height: usize, #[case] expected: bool) {
+        let maze = Maze::new(width, height);
+        assert_eq!(maze.dimensions(), (width, height));
+        assert_eq!(maze.is_perfect(), expected);
+        assert_eq!(maze.to_grid().dimensions(), (2 * width + 1, 2 * height + 1));
+    }
+
+    #[rstest]
+    #[case::diagonal((0, 0), (1, 1))]
+    #[case::same_cell((1, 1), (1, 1))]
+    #[case::out_of_bounds((2, 0), (3, 0))]
+    #[should_panic(expected = "aren't adjacent cells of the maze")]
+    fn test_invalid_passage(#[case] a: CellIndex, #[case] b: CellIndex) {
+        Maze::new(3, 3).add_passage(a, b);
+    }
+}
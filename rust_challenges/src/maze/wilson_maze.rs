@@ -0,0 +1,160 @@
+//! Module for generating mazes with Wilson's algorithm.
+//!
+//! Starting with a single cell in the maze, each cell not yet in the maze
+//! starts a random walk that continues until it reaches the maze.
+//! Only the last direction the walk left each cell in is remembered,
+//! which erases any loops in the walk,
+//! and the loop-erased walk is then carved into the maze.
+//! Unlike most other maze generation algorithms
+//! (such as Kruskal's algorithm or a randomized depth-first search),
+//! every perfect maze with the given dimensions is equally likely to be generated.
+
+use crate::grid::Grid;
+use crate::maze::layout::{CellIndex, Maze};
+use rand::Rng;
+
+/// Returns a uniformly random perfect maze with the given dimensions.
+pub fn generate_wilson(width: usize, height: usize, rng: &mut impl Rng) -> Maze {
+    let mut maze = Maze::new(width, height);
+    if width == 0 || height == 0 {
+        return maze;
+    }
+    let mut in_maze = Grid::filled(false, (width, height));
+    in_maze[(rng.gen_range(0..width), rng.gen_range(0..height))] = true;
+    // The cell that the current walk last went to from each cell.
+    let mut next = Grid::filled((0, 0), (width, height));
+    for y in 0..height {
+        for x in 0..width {
+            let start = (x, y);
+            let mut cell = start;
+            while !in_maze[cell] {
+                let neighbors: Vec<CellIndex> = in_maze
+                    .orthogonal_neighbors(cell)
+                    .map(|(neighbor, _)| neighbor)
+                    .collect();
+                next[cell] = neighbors[rng.gen_range(0..neighbors.len())];
+                cell = next[cell];
+            }
+            let mut cell = start;
+            while !in_maze[cell] {
+                in_maze[cell] = true;
+                maze.add_passage(cell, next[cell]);
+                cell = next[cell];
+            }
+        }
+    }
+    maze
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the maze.
+        Carving the loop-erased walks takes `O(c)` time in total,
+        while the random walks take expected time proportional to the mean hitting time of the grid
+        (as shown by Wilson), which is `O(c log c)`,
+        so this function completes in `O(c log c)` expected time.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze::wilson_maze::*;
+    use pretty_assertions::assert_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    /// Returns a random perfect maze generated with a randomized depth-first search,
+    /// for comparison.
+    fn generate_depth_first(width: usize, height: usize, rng: &mut impl Rng) -> Maze {
+        let mut maze = Maze::new(width, height);
+        let mut visited = Grid::filled(false, (width, height));
+        visited[(0, 0)] = true;
+        let mut stack = vec![(0, 0)];
+        while let Some(&cell) = stack.last() {
+            let unvisited: Vec<CellIndex> = visited
+                .orthogonal_neighbors(cell)
+                .filter(|&(_, &is_visited)| !is_visited)
+                .map(|(neighbor, _)| neighbor)
+                .collect();
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+            let neighbor = unvisited[rng.gen_range(0..unvisited.len())];
+            visited[neighbor] = true;
+            maze.add_passage(cell, neighbor);
+            stack.push(neighbor);
+        }
+        maze
+    }
+
+    /// Returns the chi-squared statistic for how far the given counts are
+    /// from being equal across `categories` categories.
+    fn chi_squared(counts: &HashMap<Vec<bool>, usize>, categories: usize, samples: usize) -> f64 {
+        let expected = samples as f64 / categories as f64;
+        let missing = (categories - counts.len()) as f64 * expected;
+        let present: f64 = counts
+            .values()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        present + missing
+    }
+
+    /// Returns how many times each maze (as its drawing) was generated by the given generator.
+    fn sample(
+        generate: fn(usize, usize, &mut StdRng) -> Maze,
+        dimensions: (usize, usize),
+        samples: usize,
+    ) -> HashMap<Vec<bool>, usize> {
+        let mut rng = StdRng::seed_from_u64(1148);
+        let mut counts = HashMap::new();
+        for _ in 0..samples {
+            let maze = generate(dimensions.0, dimensions.1, &mut rng);
+            let drawing = maze
+                .to_grid()
+                .enumerate::<CellIndex>()
+                .map(|(_, &open)| open)
+                .collect();
+            *counts.entry(drawing).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[rstest]
+    #[case(2, 2)]
+    #[case(3, 3)]
+    #[case(4, 3)]
+    #[case(12, 9)]
+    #[case(1, 6)]
+    #[case(6, 1)]
+    #[case(1, 1)]
+    #[case(0, 0)]
+    #[case(0, 4)]
+    fn test_perfect_maze(#[case] width: usize, #[case] height: usize) {
+        let mut rng = StdRng::seed_from_u64(1148);
+        for _ in 0..10 {
+            let maze = generate_wilson(width, height, &mut rng);
+            assert_eq!(maze.dimensions(), (width, height));
+            assert!(maze.is_perfect());
+        }
+    }
+
+    #[test]
+    fn test_uniform() {
+        // A 3 by 2 maze is one of 15 spanning trees of the grid.
+        // With 14 degrees of freedom, the chi-squared statistic for a uniform distribution
+        // is less than 36.1 with a probability of 99.9%.
+        let samples = 3000;
+        let wilson = sample(generate_wilson, (3, 2), samples);
+        assert_eq!(wilson.len(), 15);
+        let wilson_statistic = chi_squared(&wilson, 15, samples);
+        assert!(wilson_statistic < 36.1, "{wilson_statistic}");
+        // A depth-first search favors long corridors.
+        let depth_first = sample(generate_depth_first, (3, 2), samples);
+        let depth_first_statistic = chi_squared(&depth_first, 15, samples);
+        assert!(
+            depth_first_statistic > 10.0 * wilson_statistic,
+            "{depth_first_statistic}"
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Finding a way through a maze, where each cell of a grid is either passable or a wall,
+//! and each step goes from a passable cell to an orthogonally adjacent passable cell.
+//!
+//! Problem: given a maze, a start cell, and an end cell,
+//! find a path from the start to the end with one of several classic algorithms.
+//!
+//! For example, in the maze
+//! ```text
+//! S.#...
+//! #...#.
+//! ..#.#E
+//! ```
+//! (where `#` is a wall), the shortest path from `S` to `E` takes 9 steps:
+//! ```text
+//! **#***
+//! #***#*
+//! ..#.#*
+//! ```
+//!
+//! Breadth-first search and depth-first search explore the maze outwards from the start,
+//! keeping track of where each cell was first reached from,
+//! with breadth-first search reaching cells in order of distance.
+//! A* search explores cells in order of their distance from the start
+//! plus their Manhattan distance to the end, which never overestimates the remaining distance.
+//! The wall followers walk straight until they hit a wall,
+//! then keep that wall on one side of them (turning towards that side whenever they can),
+//! which traces out the walls connected to it;
+//! whenever the walk comes back to a cell, the loop since the last visit is erased.
+
+use crate::grid::Grid;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+type CellIndex = (usize, usize);
+
+/// The displacements for each direction, in clockwise order starting from up.
+const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// An algorithm for finding a path through a maze.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MazeSolveAlgorithm {
+    /// Breadth-first search, which finds a shortest path.
+    BFS,
+    /// Depth-first search, which finds some path.
+    DFS,
+    /// A* search with the Manhattan distance as the heuristic, which finds a shortest path.
+    AStar,
+    /// Following the wall on the right.
+    WallFollowerRight,
+    /// Following the wall on the left.
+    WallFollowerLeft,
+}
+
+/// Returns a path from `start` to `end` through the cells of `grid` satisfying `is_passable`,
+/// moving orthogonally, as the list of cells along the way (including `start` and `end`),
+/// or None if no path is found.
+///
+/// [`MazeSolveAlgorithm::BFS`] and [`MazeSolveAlgorithm::AStar`] return a shortest path,
+/// while the other algorithms may return longer paths, though they never visit a cell twice.
+/// The wall followers can fail to find a path when there is one:
+/// if they end up following a wall that isn't connected to one next to the end,
+/// such as a wall standing by itself in the middle of the maze.
+/// For mazes with corridors one cell wide and no such _islands_, they always find a path.
+///
+/// Returns None if `start` or `end` is out of bounds or not passable.
+pub fn solve_maze<T>(
+    grid: &Grid<T>,
+    start: CellIndex,
+    end: CellIndex,
+    is_passable: impl Fn(&T) -> bool,
+    algo: MazeSolveAlgorithm,
+) -> Option<Vec<CellIndex>> {
+    let maze = Maze { grid, is_passable };
+    if !maze.is_passable(start) || !maze.is_passable(end) {
+        return None;
+    }
+    match algo {
+        MazeSolveAlgorithm::BFS => maze.breadth_first(start, end),
+        MazeSolveAlgorithm::DFS => maze.depth_first(start, end),
+        MazeSolveAlgorithm::AStar => maze.a_star(start, end),
+        MazeSolveAlgorithm::WallFollowerRight => maze.follow_wall(start, end, 1),
+        MazeSolveAlgorithm::WallFollowerLeft => maze.follow_wall(start, end, 3),
+    }
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid.
+        Assuming that `is_passable` completes in `O(1)` time:
+        - Breadth-first search and depth-first search visit each cell at most once,
+          so they complete in `O(c)` time.
+        - A* search pushes each cell onto the heap at most 4 times,
+          so it completes in `O(c log c)` time.
+        - The wall followers are in each of the `4 * c` combinations of a cell and a direction
+          at most once before giving up, and erasing loops takes `O(c)` time in total
+          (since each cell is erased at most once per time it's added), so they complete in `O(c)` time.
+    */
+}
+
+/// A maze, as a grid and a way to tell which of its cells are passable.
+struct Maze<'a, T, F> {
+    grid: &'a Grid<T>,
+    is_passable: F,
+}
+
+impl<T, F: Fn(&T) -> bool> Maze<'_, T, F> {
+    /// Returns whether the given cell is in bounds and passable.
+    fn is_passable(&self, cell: CellIndex) -> bool {
+        self.grid.get(cell).is_some_and(&self.is_passable)
+    }
+
+    /// Returns the cell one step away from the given cell in the given direction,
+    /// or None if it's not passable.
+    fn step(&self, (x, y): CellIndex, direction: usize) -> Option<CellIndex> {
+        let (dx, dy) = DIRECTIONS[direction];
+        let next = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.is_passable(next).then_some(next)
+    }
+
+    /// Returns the passable cells one step away from the given cell.
+    fn neighbors(&self, cell: CellIndex) -> impl Iterator<Item = CellIndex> + '_ {
+        (0..DIRECTIONS.len()).filter_map(move |direction| self.step(cell, direction))
+    }
+
+    fn breadth_first(&self, start: CellIndex, end: CellIndex) -> Option<Vec<CellIndex>> {
+        let mut parents = Grid::filled(None, self.grid.dimensions());
+        parents[start] = Some(start);
+        let mut queue = VecDeque::from([start]);
+        while let Some(cell) = queue.pop_front() {
+            if cell == end {
+                return Some(path_to(&parents, end));
+            }
+            for next in self.neighbors(cell) {
+                if parents[next].is_none() {
+                    parents[next] = Some(cell);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    fn depth_first(&self, start: CellIndex, end: CellIndex) -> Option<Vec<CellIndex>> {
+        let mut parents = Grid::filled(None, self.grid.dimensions());
+        // Each cell is visited from the cell it was most recently pushed by.
+        let mut stack = vec![(start, start)];
+        while let Some((cell, parent)) = stack.pop() {
+            if parents[cell].is_some() {
+                continue;
+            }
+            parents[cell] = Some(parent);
+            if cell == end {
+                return Some(path_to(&parents, end));
+            }
+            for next in self.neighbors(cell) {
+                if parents[next].is_none() {
+                    stack.push((next, cell));
+                }
+            }
+        }
+        None
+    }
+
+    fn a_star(&self, start: CellIndex, end: CellIndex) -> Option<Vec<CellIndex>> {
+        let heuristic = |(x, y): CellIndex| x.abs_diff(end.0) + y.abs_diff(end.1);
+        let mut parents = Grid::filled(None, self.grid.dimensions());
+        let mut distances = Grid::filled(usize::MAX, self.grid.dimensions());
+        parents[start] = Some(start);
+        distances[start] = 0;
+        let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+        while let Some(Reverse((_, distance, cell))) = heap.pop() {
+            // Skip outdated entries for cells that were later reached more quickly.
+            if distance > distances[cell] {
+                continue;
+            }
+            if cell == end {
+                return Some(path_to(&parents, end));
+            }
+            for next in self.neighbors(cell) {
+                if distance + 1 < distances[next] {
+                    distances[next] = distance + 1;
+                    parents[next] = Some(cell);
+                    heap.push(Reverse((
+                        distance + 1 + heuristic(next),
+                        distance + 1,
+                        next,
+                    )));
+                }
+            }
+        }
+        None
+    }
+
+    /// Follows the wall on one side, with `hand` being the number of clockwise quarter turns
+    /// from the direction being faced to that side (1 for right, 3 for left).
+    fn follow_wall(&self, start: CellIndex, end: CellIndex, hand: usize) -> Option<Vec<CellIndex>> {
+        let mut path = Path {
+            cells: vec![start],
+            positions: Grid::filled(None, self.grid.dimensions()),
+        };
+        path.positions[start] = Some(0);
+        let mut cell = start;
+        // Start by facing right and walking straight until there's a wall to follow.
+        let mut direction = 1;
+        while cell != end {
+            let Some(next) = self.step(cell, direction) else {
+                break;
+            };
+            cell = next;
+            path.visit(cell);
+        }
+        // Turn away from the wall in front, so that it's on the side being followed.
+        direction = (direction + 4 - hand) % 4;
+        let mut seen = Grid::filled([false; 4], self.grid.dimensions());
+        while cell != end {
+            // Coming back to the same cell facing the same way means going around in circles.
+            if seen[cell][direction] {
+                return None;
+            }
+            seen[cell][direction] = true;
+            // Turn towards the wall if possible, then go straight, then turn away, then go back.
+            let (next_direction, next) = [hand, 0, 4 - hand, 2]
+                .into_iter()
+                .map(|turn| (direction + turn) % 4)
+                .find_map(|direction| Some((direction, self.step(cell, direction)?)))?;
+            direction = next_direction;
+            cell = next;
+            path.visit(cell);
+        }
+        Some(path.cells)
+    }
+}
+
+/// A path without repeated cells, along with the position of each cell in the path.
+struct Path {
+    cells: Vec<CellIndex>,
+    positions: Grid<Option<usize>>,
+}
+
+impl Path {
+    /// Extends the path to the given cell, which must be adjacent to the last cell,
+    /// erasing the loop since the cell was last in the path if it's already there.
+    fn visit(&mut self, cell: CellIndex) {
+        if let Some(position) = self.positions[cell] {
+            for erased in self.cells.drain(position + 1..) {
+                self.positions[erased] = None;
+            }
+        } else {
+            self.positions[cell] = Some(self.cells.len());
+            self.cells.push(cell);
+        }
+    }
+}
+
+/// Returns the path from the start to `end`, given the cell each cell was reached from
+/// (with the start being reached from itself).
+fn path_to(parents: &Grid<Option<CellIndex>>, end: CellIndex) -> Vec<CellIndex> {
+    let mut result = vec![end];
+    let mut cell = end;
+    while let Some(parent) = parents[cell].filter(|&parent| parent != cell) {
+        result.push(parent);
+        cell = parent;
+    }
+    result.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maze_solver::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const ALGORITHMS: [MazeSolveAlgorithm; 5] = [
+        MazeSolveAlgorithm::BFS,
+        MazeSolveAlgorithm::DFS,
+        MazeSolveAlgorithm::AStar,
+        MazeSolveAlgorithm::WallFollowerRight,
+        MazeSolveAlgorithm::WallFollowerLeft,
+    ];
+
+    /// Creates a maze from the given rows, with `#` for walls.
+    fn maze(rows: &[&str]) -> Grid<bool> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut result = Grid::filled(false, (width, rows.len()));
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                result[(x, y)] = c != '#';
+            }
+        }
+        result
+    }
+
+    /// Checks that the given path goes from `start` to `end` through passable cells,
+    /// without visiting any cell twice.
+    fn assert_valid(grid: &Grid<bool>, start: CellIndex, end: CellIndex, path: &[CellIndex]) {
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        for &cell in path {
+            assert_eq!(grid.get(cell), Some(&true), "{cell:?} isn't passable");
+        }
+        for window in path.windows(2) {
+            let ((x1, y1), (x2, y2)) = (window[0], window[1]);
+            assert_eq!(
+                x1.abs_diff(x2) + y1.abs_diff(y2),
+                1,
+                "{window:?} isn't a step"
+            );
+        }
+        let mut cells = path.to_vec();
+        cells.sort();
+        cells.dedup();
+        assert_eq!(cells.len(), path.len(), "some cell is visited twice");
+    }
+
+    /// A maze where every pair of cells has exactly one path between them.
+    fn perfect_maze() -> Grid<bool> {
+        maze(&[
+            "###########",
+            "#.....#...#",
+            "#####.#.#.#",
+            "#...#.#.#.#",
+            "#.#.#.#.#.#",
+            "#.#...#.#.#",
+            "#.#####.#.#",
+            "#.......#.#",
+            "###########",
+        ])
+    }
+
+    #[rstest]
+    fn test_problem_description_example(
+        #[values(MazeSolveAlgorithm::BFS, MazeSolveAlgorithm::AStar)] algo: MazeSolveAlgorithm,
+    ) {
+        let grid = maze(&["S.#...", "#...#.", "..#.#E"]);
+        let path = solve_maze(&grid, (0, 0), (5, 2), |&passable| passable, algo).unwrap();
+        let expected = [
+            (0, 0),
+            (1, 0),
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (3, 0),
+            (4, 0),
+            (5, 0),
+            (5, 1),
+            (5, 2),
+        ];
+        assert_eq!(path, expected);
+    }
+
+    #[test]
+    fn test_perfect_maze() {
+        // With only one path, every algorithm has to find it.
+        let grid = perfect_maze();
+        let paths: Vec<_> = ALGORITHMS
+            .iter()
+            .map(|&algo| solve_maze(&grid, (1, 1), (9, 7), |&passable| passable, algo).unwrap())
+            .collect();
+        assert_valid(&grid, (1, 1), (9, 7), &paths[0]);
+        assert_eq!(paths[0].len(), 39);
+        for path in &paths {
+            assert_eq!(path, &paths[0]);
+        }
+    }
+
+    #[rstest]
+    #[case((9, 7), 27)]
+    #[case((9, 1), 33)]
+    #[case((3, 3), 13)]
+    fn test_shortest_paths(#[case] end: CellIndex, #[case] expected_len: usize) {
+        // Opening a wall creates a loop, with one way around it being much shorter.
+        let mut grid = perfect_maze();
+        grid[(8, 7)] = true;
+        for algo in [MazeSolveAlgorithm::BFS, MazeSolveAlgorithm::AStar] {
+            let path = solve_maze(&grid, (1, 1), end, |&p| p, algo).unwrap();
+            assert_valid(&grid, (1, 1), end, &path);
+            assert_eq!(path.len(), expected_len);
+        }
+        for algo in ALGORITHMS {
+            let path = solve_maze(&grid, (1, 1), end, |&p| p, algo).unwrap();
+            assert_valid(&grid, (1, 1), end, &path);
+            assert!(path.len() >= expected_len);
+        }
+    }
+
+    #[rstest]
+    fn test_any_path(
+        #[values(
+            MazeSolveAlgorithm::DFS,
+            MazeSolveAlgorithm::WallFollowerRight,
+            MazeSolveAlgorithm::WallFollowerLeft
+        )]
+        algo: MazeSolveAlgorithm,
+    ) {
+        let grid = maze(&[
+            "#########",
+            "#.......#",
+            "#.#.###.#",
+            "#.#...#.#",
+            "#.###.#.#",
+            "#.....#.#",
+            "#########",
+        ]);
+        let path = solve_maze(&grid, (1, 1), (5, 3), |&p| p, algo).unwrap();
+        assert_valid(&grid, (1, 1), (5, 3), &path);
+    }
+
+    #[rstest]
+    fn test_island(
+        #[values(
+            MazeSolveAlgorithm::WallFollowerRight,
+            MazeSolveAlgorithm::WallFollowerLeft
+        )]
+        algo: MazeSolveAlgorithm,
+    ) {
+        // The wall followers start next to the wall in the middle, and go around it forever.
+        let grid = maze(&[".......", "..#....", "......."]);
+        assert_eq!(solve_maze(&grid, (1, 1), (6, 1), |&p| p, algo), None);
+        let path = solve_maze(&grid, (1, 1), (6, 1), |&p| p, MazeSolveAlgorithm::BFS).unwrap();
+        assert_eq!(path.len(), 8);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let grid = maze(&["..#..", "..#..", "..#.."]);
+        for algo in ALGORITHMS {
+            assert_eq!(solve_maze(&grid, (0, 0), (4, 2), |&p| p, algo), None);
+        }
+    }
+
+    #[rstest]
+    #[case::start_is_wall((2, 0), (0, 0))]
+    #[case::end_is_wall((0, 0), (2, 1))]
+    #[case::start_out_of_bounds((5, 0), (0, 0))]
+    #[case::end_out_of_bounds((0, 0), (0, 3))]
+    fn test_invalid_cells(#[case] start: CellIndex, #[case] end: CellIndex) {
+        let grid = maze(&["..#..", "..#..", "....."]);
+        for algo in ALGORITHMS {
+            assert_eq!(solve_maze(&grid, start, end, |&p| p, algo), None);
+        }
+    }
+
+    #[test]
+    fn test_start_is_end() {
+        let grid = maze(&["#.#", "...", "#.#"]);
+        for algo in ALGORITHMS {
+            assert_eq!(
+                solve_maze(&grid, (1, 1), (1, 1), |&p| p, algo),
+                Some(vec![(1, 1)])
+            );
+        }
+    }
+
+    #[test]
+    fn test_other_element_types() {
+        let grid = Grid::from_2d_array([[0, 0, 9], [9, 0, 0]]);
+        let path = solve_maze(&grid, (0, 0), (2, 1), |&n| n < 5, MazeSolveAlgorithm::DFS);
+        assert_eq!(path, Some(vec![(0, 0), (1, 0), (1, 1), (2, 1)]));
+    }
+}
@@ -0,0 +1,443 @@
+//! Minesweeper is a single-player game played on a grid of hidden cells, some of which are mines.
+//! Revealing a safe cell shows how many of its 8 neighbors are mines;
+//! revealing a cell with no neighboring mines automatically reveals all of its neighbors too.
+//! Cells suspected to be mines can be flagged.
+//!
+//! Problem: model the game, and make progress on a board using simple deductions:
+//! - If a revealed cell already has as many flagged neighbors as neighboring mines,
+//!   its other hidden neighbors are safe, and can be revealed.
+//! - If a revealed cell has exactly as many hidden and flagged neighbors as neighboring mines,
+//!   its hidden neighbors must all be mines, and can be flagged.
+//!
+//! For example, on the following board
+//! (`#` for hidden cells, `F` for flagged cells, and numbers for revealed cells):
+//! ```text
+//! # 1 0
+//! # 2 0
+//! F 1 0
+//! ```
+//! the first rule, applied to the `1` at the bottom, reveals the hidden cell on the left edge.
+//! The `1` at the top then has only one hidden neighbor left,
+//! so the second rule flags the top left cell.
+
+use crate::grid::Grid;
+
+/// The state of a cell, as seen by the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MineCell {
+    Hidden,
+    /// A revealed safe cell, with the number of mines among its neighbors.
+    Revealed(u8),
+    Flagged,
+}
+
+/// The result of revealing a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevealResult {
+    /// The cell was safe, and has been revealed (along with any cascaded cells).
+    Safe,
+    /// The cell was a mine.
+    Mine,
+    /// The cell had already been revealed, so nothing happened.
+    AlreadyRevealed,
+}
+
+type CellIndex = (usize, usize);
+
+/// A Minesweeper board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinesweeperBoard {
+    grid: Grid<MineCell>,
+    mines: Grid<bool>,
+}
+
+impl MinesweeperBoard {
+    /// Creates a board with every cell hidden, where `true` in `mines` indicates a mine.
+    pub fn new(mines: Grid<bool>) -> MinesweeperBoard {
+        MinesweeperBoard {
+            grid: Grid::filled(MineCell::Hidden, mines.dimensions()),
+            mines,
+        }
+    }
+
+    /// The state of each cell, as seen by the player.
+    pub fn grid(&self) -> &Grid<MineCell> {
+        &self.grid
+    }
+
+    /// The location of each mine, where `true` indicates a mine.
+    pub fn mines(&self) -> &Grid<bool> {
+        &self.mines
+    }
+
+    /// Returns the indices of the neighbors of the given cell that are in bounds.
+    fn neighbors(&self, cell: CellIndex) -> impl Iterator<Item = CellIndex> + '_ {
+        self.grid.all_neighbors(cell).map(|(neighbor, _)| neighbor)
+    }
+
+    /// Returns the number of mines among the neighbors of the given cell.
+    fn neighboring_mines(&self, cell: CellIndex) -> u8 {
+        self.neighbors(cell)
+            .filter(|&neighbor| self.mines[neighbor])
+            .count() as u8
+    }
+
+    /// Panics if the given cell is out of bounds.
+    fn check_bounds(&self, cell: CellIndex) {
+        assert!(
+            self.grid.get(cell).is_some(),
+            "cell {cell:?} out of bounds for dimensions {:?}",
+            self.grid.dimensions()
+        );
+    }
+}
+
+/// Reveals the given cell (even if it has been flagged).
+/// If the cell has no neighboring mines, its neighbors are revealed too,
+/// cascading through every connected cell with no neighboring mines.
+///
+/// If the cell is a mine, the board is left unchanged.
+/// Panics if the cell is out of bounds.
+pub fn reveal(board: &mut MinesweeperBoard, cell: CellIndex) -> RevealResult {
+    board.check_bounds(cell);
+    if let MineCell::Revealed(_) = board.grid[cell] {
+        return RevealResult::AlreadyRevealed;
+    }
+    if board.mines[cell] {
+        return RevealResult::Mine;
+    }
+    board.grid[cell] = MineCell::Revealed(board.neighboring_mines(cell));
+    let mut stack = vec![cell];
+    while let Some(curr) = stack.pop() {
+        if board.grid[curr] != MineCell::Revealed(0) {
+            continue;
+        }
+        // A cell with no neighboring mines has no mines among its neighbors, so they're all safe.
+        let hidden_neighbors: Vec<CellIndex> = board
+            .neighbors(curr)
+            .filter(|&neighbor| board.grid[neighbor] == MineCell::Hidden)
+            .collect();
+        for neighbor in hidden_neighbors {
+            board.grid[neighbor] = MineCell::Revealed(board.neighboring_mines(neighbor));
+            stack.push(neighbor);
+        }
+    }
+    RevealResult::Safe
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells on the board.
+        This function completes in `O(c)` time in the worst case:
+        each cell is revealed (and pushed onto the stack) at most once,
+        and processing a cell takes `O(1)` time, since there are at most 8 neighbors.
+    */
+}
+
+/// Flags the given cell if it's hidden, or unflags it if it's flagged.
+/// Revealed cells are left unchanged.
+///
+/// Panics if the cell is out of bounds.
+pub fn toggle_flag(board: &mut MinesweeperBoard, cell: CellIndex) {
+    board.check_bounds(cell);
+    board.grid[cell] = match board.grid[cell] {
+        MineCell::Hidden => MineCell::Flagged,
+        MineCell::Flagged => MineCell::Hidden,
+        revealed => revealed,
+    };
+}
+
+/// Applies a single round of deductions to the given board, as described in the module docs:
+/// all deductions are made based on the state of the board before the round,
+/// and then applied together.
+///
+/// Returns whether any cells were revealed or flagged.
+pub fn auto_solve_step(board: &mut MinesweeperBoard) -> bool {
+    let mut to_reveal = vec![];
+    let mut to_flag = vec![];
+    for (cell, &state) in board.grid.enumerate::<CellIndex>() {
+        let MineCell::Revealed(count) = state else {
+            continue;
+        };
+        let hidden: Vec<CellIndex> = board
+            .neighbors(cell)
+            .filter(|&neighbor| board.grid[neighbor] == MineCell::Hidden)
+            .collect();
+        let num_flagged = board
+            .neighbors(cell)
+            .filter(|&neighbor| board.grid[neighbor] == MineCell::Flagged)
+            .count();
+        if num_flagged == count as usize {
+            to_reveal.extend(hidden);
+        } else if num_flagged + hidden.len() == count as usize {
+            to_flag.extend(hidden);
+        }
+    }
+    let mut made_progress = false;
+    for cell in to_flag {
+        if board.grid[cell] == MineCell::Hidden {
+            board.grid[cell] = MineCell::Flagged;
+            made_progress = true;
+        }
+    }
+    for cell in to_reveal {
+        // A cell may have been revealed by an earlier cascade in this round.
+        if board.grid[cell] == MineCell::Hidden {
+            reveal(board, cell);
+            made_progress = true;
+        }
+    }
+    made_progress
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::minesweeper::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a grid of mine locations.
+    ///
+    /// Syntax:
+    /// ```text
+    /// mines![
+    ///     [<`*` for a mine, `.` for a safe cell> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! mines {
+        (@cell *) => {true};
+        (@cell .) => {false};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(mines!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Creates a grid of cell states, as seen by the player.
+    ///
+    /// Syntax:
+    /// ```text
+    /// cells![
+    ///     [<`#` for hidden, `F` for flagged, or a number for revealed> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! cells {
+        (@cell #) => {MineCell::Hidden};
+        (@cell F) => {MineCell::Flagged};
+        (@cell $count:literal) => {MineCell::Revealed($count)};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(cells!(@cell $cell)),*]),*])
+        };
+    }
+
+    #[test]
+    fn test_reveal_number() {
+        let mut board = MinesweeperBoard::new(mines![
+            [* . .]
+            [. . *]
+        ]);
+        assert_eq!(reveal(&mut board, (1, 0)), RevealResult::Safe);
+        assert_eq!(
+            board.grid(),
+            &cells![
+                [# 2 #]
+                [# # #]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reveal_cascade() {
+        let mut board = MinesweeperBoard::new(mines![
+            [. . . . *]
+            [. . . . .]
+            [. . . . .]
+            [* . . . .]
+        ]);
+        assert_eq!(reveal(&mut board, (1, 1)), RevealResult::Safe);
+        assert_eq!(
+            board.grid(),
+            &cells![
+                [0 0 0 1 #]
+                [0 0 0 1 1]
+                [1 1 0 0 0]
+                [# 1 0 0 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reveal_cascade_stops_at_numbers() {
+        let mut board = MinesweeperBoard::new(mines![
+            [. . * . .]
+            [. . * . .]
+            [. . * . .]
+        ]);
+        reveal(&mut board, (0, 0));
+        assert_eq!(
+            board.grid(),
+            &cells![
+                [0 2 # # #]
+                [0 3 # # #]
+                [0 2 # # #]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reveal_mine() {
+        let mut board = MinesweeperBoard::new(mines![
+            [* . .]
+            [. . .]
+        ]);
+        assert_eq!(reveal(&mut board, (0, 0)), RevealResult::Mine);
+        assert_eq!(board.grid(), &Grid::filled(MineCell::Hidden, (3, 2)));
+    }
+
+    #[test]
+    fn test_reveal_already_revealed() {
+        let mut board = MinesweeperBoard::new(mines![
+            [* . .]
+            [. . .]
+        ]);
+        reveal(&mut board, (1, 1));
+        assert_eq!(reveal(&mut board, (1, 1)), RevealResult::AlreadyRevealed);
+        // Cascaded cells count as revealed too.
+        reveal(&mut board, (2, 0));
+        assert_eq!(reveal(&mut board, (2, 1)), RevealResult::AlreadyRevealed);
+    }
+
+    #[test]
+    fn test_reveal_flagged() {
+        let mut board = MinesweeperBoard::new(mines![[* . .]]);
+        toggle_flag(&mut board, (2, 0));
+        assert_eq!(reveal(&mut board, (2, 0)), RevealResult::Safe);
+        assert_eq!(board.grid(), &cells![[# 1 0]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (0, 2) out of bounds for dimensions (3, 2)")]
+    fn test_reveal_out_of_bounds() {
+        let mut board = MinesweeperBoard::new(Grid::filled(false, (3, 2)));
+        reveal(&mut board, (0, 2));
+    }
+
+    #[test]
+    fn test_toggle_flag() {
+        let mut board = MinesweeperBoard::new(mines![[* . .]]);
+        reveal(&mut board, (1, 0));
+        toggle_flag(&mut board, (0, 0));
+        toggle_flag(&mut board, (1, 0));
+        toggle_flag(&mut board, (2, 0));
+        assert_eq!(board.grid(), &cells![[F 1 F]]);
+        toggle_flag(&mut board, (2, 0));
+        assert_eq!(board.grid(), &cells![[F 1 #]]);
+    }
+
+    #[test]
+    fn test_auto_solve_problem_description_example() {
+        let mut board = MinesweeperBoard::new(mines![
+            [* . .]
+            [. . .]
+            [* . .]
+        ]);
+        reveal(&mut board, (2, 0));
+        toggle_flag(&mut board, (0, 2));
+        assert_eq!(
+            board.grid(),
+            &cells![
+                [# 1 0]
+                [# 2 0]
+                [F 1 0]
+            ]
+        );
+        assert!(auto_solve_step(&mut board));
+        assert_eq!(
+            board.grid(),
+            &cells![
+                [# 1 0]
+                [2 2 0]
+                [F 1 0]
+            ]
+        );
+        assert!(auto_solve_step(&mut board));
+        assert_eq!(
+            board.grid(),
+            &cells![
+                [F 1 0]
+                [2 2 0]
+                [F 1 0]
+            ]
+        );
+        assert!(!auto_solve_step(&mut board));
+    }
+
+    #[test]
+    fn test_auto_solve_flags() {
+        let mut board = MinesweeperBoard::new(mines![[* . .]]);
+        reveal(&mut board, (2, 0));
+        assert!(auto_solve_step(&mut board));
+        assert_eq!(board.grid(), &cells![[F 1 0]]);
+    }
+
+    #[test]
+    fn test_auto_solve_reveals() {
+        let mut board = MinesweeperBoard::new(mines![
+            [* . . .]
+            [. . . .]
+        ]);
+        reveal(&mut board, (1, 0));
+        toggle_flag(&mut board, (0, 0));
+        assert!(auto_solve_step(&mut board));
+        // Revealing (2, 0) cascades to the rest of the board.
+        assert_eq!(
+            board.grid(),
+            &cells![
+                [F 1 0 0]
+                [1 1 0 0]
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::all_hidden(MinesweeperBoard::new(mines![
+        [* . .]
+        [. . *]
+    ]))]
+    #[case::needs_more_reasoning({
+        let mut board = MinesweeperBoard::new(mines![
+            [. * . .]
+            [. . . .]
+        ]);
+        reveal(&mut board, (3, 0));
+        board
+    })]
+    #[case::empty(MinesweeperBoard::new(Grid::filled(false, (0, 0))))]
+    fn test_auto_solve_no_progress(#[case] mut board: MinesweeperBoard) {
+        let original = board.clone();
+        assert!(!auto_solve_step(&mut board));
+        assert_eq!(board, original);
+    }
+
+    #[test]
+    fn test_auto_solve_whole_board() {
+        let mut board = MinesweeperBoard::new(mines![
+            [* . . . .]
+            [. . . . .]
+            [. . . . .]
+            [. . . * .]
+            [. . . . *]
+        ]);
+        reveal(&mut board, (4, 0));
+        while auto_solve_step(&mut board) {}
+        for (cell, &state) in board.grid().enumerate::<CellIndex>() {
+            if board.mines()[cell] {
+                assert_eq!(state, MineCell::Flagged, "mine at {cell:?} not flagged");
+            } else {
+                assert!(
+                    matches!(state, MineCell::Revealed(_)),
+                    "safe cell {cell:?} not revealed"
+                );
+            }
+        }
+    }
+}
@@ -3,6 +3,16 @@
 //!
 //! (Posted with modifications to
 //! https://codegolf.stackexchange.com/questions/274829/is-there-mutable-aliasing-in-this-list-of-variable-references.)
+//!
+//! Extension: simulate the two-phase borrows of non-lexical lifetimes,
+//! which allow code like `v.push(v.len())`.
+//! A two-phase mutable borrow starts out _reserved_, and acts like a shared borrow
+//! until it's _activated_ by its first use as a mutable borrow,
+//! at which point it has to be the only borrow of its variable.
+//! Here, each reference in a list is used at the point given by its position in the list,
+//! and a two-phase borrow is live from its reservation up to and including its activation.
+//! For example, in `[&v, &v, &v]`, a two-phase borrow of `v` reserved at point 0
+//! and activated at point 2 conflicts only with the last reference.
 
 use std::{
     collections::{HashMap, HashSet},
@@ -23,6 +33,40 @@ pub struct Reference<T> {
     mutability: Mutability,
 }
 
+/// The phase that a two-phase mutable borrow is in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorrowPhase {
+    /// The borrow has been reserved, and acts like a shared borrow.
+    Reservation,
+    /// The borrow is being used as a mutable borrow.
+    Activation,
+}
+
+/// A two-phase mutable borrow of a variable,
+/// simulating the implicit `&mut` borrows of method calls like `v.push(v.len())`.
+pub struct TwoPhaseBorrow<T> {
+    /// The borrowed variable.
+    pub variable: T,
+    /// The point at which the borrow is reserved.
+    pub reservation_index: usize,
+    /// The point at which the borrow is activated, which can't be before its reservation.
+    pub activation_index: usize,
+}
+
+impl<T> TwoPhaseBorrow<T> {
+    /// Returns the phase that the borrow is in at the given point,
+    /// or None if the borrow isn't live at that point.
+    pub fn phase_at(&self, index: usize) -> Option<BorrowPhase> {
+        if index == self.activation_index {
+            Some(BorrowPhase::Activation)
+        } else if (self.reservation_index..self.activation_index).contains(&index) {
+            Some(BorrowPhase::Reservation)
+        } else {
+            None
+        }
+    }
+}
+
 /// A classification of a given variable's set of references,
 /// based on the number of immutable and mutable references.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -61,6 +105,58 @@ pub fn mutable_aliasing_violations<T: Copy + Eq + Hash>(references: &[Reference<
         .collect()
 }
 
+/// Returns the set of variables with a two-phase mutable borrow that conflicts with another borrow,
+/// where the reference at position `i` in `shared_refs` is used at point `i`.
+///
+/// Shared references can be used while a two-phase borrow is reserved, but not when it's activated,
+/// and mutable references can't be used while a two-phase borrow is live.
+/// Two two-phase borrows of the same variable conflict if they're live at the same point,
+/// since the first one to be activated would be used mutably while the other one is live.
+///
+/// Panics if a two-phase borrow is activated before it's reserved.
+pub fn two_phase_violations<T: Copy + Eq + Hash>(
+    shared_refs: &[Reference<T>],
+    two_phase_mut: &[TwoPhaseBorrow<T>],
+) -> HashSet<T> {
+    for borrow in two_phase_mut {
+        assert!(
+            borrow.reservation_index <= borrow.activation_index,
+            "a two-phase borrow can't be activated before it's reserved"
+        );
+    }
+    let conflicts_with_ref = |borrow: &TwoPhaseBorrow<T>| {
+        shared_refs.iter().enumerate().any(|(index, reference)| {
+            reference.variable == borrow.variable
+                && match (borrow.phase_at(index), reference.mutability) {
+                    (None, _) => false,
+                    (Some(BorrowPhase::Reservation), Mutability::Immutable) => false,
+                    (Some(BorrowPhase::Reservation), Mutability::Mutable) => true,
+                    (Some(BorrowPhase::Activation), _) => true,
+                }
+        })
+    };
+    let conflicts_with_other_borrow = |i: usize, borrow: &TwoPhaseBorrow<T>| {
+        two_phase_mut.iter().enumerate().any(|(j, other)| {
+            i != j
+                && other.variable == borrow.variable
+                && other.reservation_index <= borrow.activation_index
+                && borrow.reservation_index <= other.activation_index
+        })
+    };
+    two_phase_mut
+        .iter()
+        .enumerate()
+        .filter(|&(i, borrow)| conflicts_with_ref(borrow) || conflicts_with_other_borrow(i, borrow))
+        .map(|(_, borrow)| borrow.variable)
+        .collect()
+    /*
+        Time complexity analysis:
+        Let `r` be the number of references and `b` be the number of two-phase borrows.
+        Each borrow is checked against every reference and every other borrow,
+        so this function completes in `O(b * (r + b))` time.
+    */
+}
+
 #[cfg(test)]
 mod tests {
     use crate::mutable_aliasing::*;
@@ -124,4 +220,74 @@ mod tests {
             HashSet::from(expected)
         );
     }
+
+    /// Creates a two-phase borrow of the variable with the given name.
+    fn two_phase(
+        variable: &str,
+        reservation_index: usize,
+        activation_index: usize,
+    ) -> TwoPhaseBorrow<&str> {
+        TwoPhaseBorrow {
+            variable,
+            reservation_index,
+            activation_index,
+        }
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let borrows = [two_phase("v", 0, 2)];
+        assert_eq!(
+            two_phase_violations(&refs![&v, &v], &borrows),
+            HashSet::new()
+        );
+        assert_eq!(
+            two_phase_violations(&refs![&v, &v, &v], &borrows),
+            HashSet::from(["v"])
+        );
+    }
+
+    #[rstest]
+    #[case::vec_push_len(&refs![&x, &v], vec![two_phase("v", 0, 2)], [])]
+    #[case::shared_during_reservation(&refs![&a, &a, &b], vec![two_phase("a", 0, 2)], [])]
+    #[case::shared_at_activation(&refs![&b, &a, &a], vec![two_phase("a", 0, 2)], ["a"])]
+    #[case::shared_at_immediate_activation(&refs![&a], vec![two_phase("a", 0, 0)], ["a"])]
+    #[case::mutable_during_reservation(&refs![&b, &mut a], vec![two_phase("a", 0, 2)], ["a"])]
+    #[case::outside_borrow(&refs![&mut a, &b, &b, &a], vec![two_phase("a", 1, 2)], [])]
+    #[case::other_variable(&refs![&mut b, &b, &b], vec![two_phase("a", 0, 2)], [])]
+    #[case::simultaneous_activation(&refs![], vec![two_phase("a", 0, 2), two_phase("a", 1, 2)], ["a"])]
+    #[case::overlapping(&refs![], vec![two_phase("a", 0, 2), two_phase("a", 2, 3)], ["a"])]
+    #[case::nested(&refs![], vec![two_phase("a", 0, 3), two_phase("a", 1, 2)], ["a"])]
+    #[case::sequential(&refs![], vec![two_phase("a", 0, 1), two_phase("a", 2, 3)], [])]
+    #[case::different_variables(&refs![], vec![two_phase("a", 0, 2), two_phase("b", 1, 2)], [])]
+    #[case::mixed(
+        &refs![&a, &b, &c, &mut d, &c],
+        vec![two_phase("a", 0, 1), two_phase("b", 0, 1), two_phase("c", 2, 4), two_phase("d", 4, 5)],
+        ["b", "c"],
+    )]
+    fn test_two_phase_violations<const N: usize>(
+        #[case] references: &[Reference<&str>],
+        #[case] borrows: Vec<TwoPhaseBorrow<&str>>,
+        #[case] expected: [&str; N],
+    ) {
+        assert_eq!(
+            two_phase_violations(references, &borrows),
+            HashSet::from(expected)
+        );
+    }
+
+    #[rstest]
+    #[case(0, Some(BorrowPhase::Reservation))]
+    #[case(1, Some(BorrowPhase::Reservation))]
+    #[case(2, Some(BorrowPhase::Activation))]
+    #[case(3, None)]
+    fn test_phase_at(#[case] index: usize, #[case] expected: Option<BorrowPhase>) {
+        assert_eq!(two_phase("a", 0, 2).phase_at(index), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "a two-phase borrow can't be activated before it's reserved")]
+    fn test_activation_before_reservation() {
+        two_phase_violations(&refs![&a], &[two_phase("a", 2, 1)]);
+    }
 }
@@ -0,0 +1,407 @@
+//! Problem: solve nonograms (also known as Picross puzzles).
+//!
+//! A nonogram is a rectangular grid of cells, each of which is to be either filled or left empty.
+//! Every row and every column comes with a clue:
+//! the lengths of the runs of consecutive filled cells in that line, in order.
+//! Consecutive runs are separated by at least one empty cell.
+//!
+//! For example, the clues
+//! ```text
+//!           1   1
+//!           1   1
+//!         3 1 5 1 3
+//!     3   . . . . .
+//! 1 1 1   . . . . .
+//!     5   . . . . .
+//! 1 1 1   . . . . .
+//!     3   . . . . .
+//! ```
+//! have the following solution (`#` denotes a filled cell and `.` denotes an empty cell):
+//! ```text
+//! . # # # .
+//! # . # . #
+//! # # # # #
+//! # . # . #
+//! . # # # .
+//! ```
+//!
+//! The main building block for solving a nonogram is solving a single line:
+//! given a line's clue and what is currently known about the line,
+//! determine which cells must be filled and which cells must be empty.
+//! Repeatedly solving every row and column is often enough to solve an entire puzzle;
+//! when it isn't, we fall back to guessing a cell and backtracking on contradictions.
+
+use crate::grid::Grid;
+
+/// What is known about a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    /// Could still be either filled or empty.
+    Unknown,
+    Filled,
+    Empty,
+}
+
+/// Returns the most specific line that is consistent with the given line and its clues:
+/// every cell that is filled in all arrangements of runs fitting the clues becomes `Filled`,
+/// every cell that is empty in all such arrangements becomes `Empty`,
+/// and all other cells are `Unknown`.
+///
+/// Returns None if no arrangement of runs fits both the clues and the given line.
+///
+/// Clues of length 0 are ignored, so that `[0]` can be used to denote an empty line.
+pub fn solve_line(clues: &[usize], line: &[Cell]) -> Option<Vec<Cell>> {
+    let clues: Vec<usize> = clues.iter().copied().filter(|&clue| clue != 0).collect();
+    let mut solver = LineSolver {
+        clues: &clues,
+        line,
+        memo: Grid::filled(None, (line.len() + 1, clues.len() + 1)),
+    };
+    if !solver.fits(0, 0) {
+        return None;
+    }
+    // A cell's final value is determined by whether it can be filled, empty, or both.
+    let mut can_be_filled = vec![false; line.len()];
+    let mut can_be_empty = vec![false; line.len()];
+    // `reachable[(position, clue_index)]` is true if some arrangement of the first `clue_index`
+    // runs fits into the first `position` cells and can be extended into a full arrangement.
+    let mut reachable = Grid::filled(false, (line.len() + 1, clues.len() + 1));
+    reachable[(0, 0)] = true;
+    for position in 0..=line.len() {
+        for clue_index in 0..=clues.len() {
+            if !reachable[(position, clue_index)] {
+                continue;
+            }
+            if clue_index == clues.len() {
+                can_be_empty[position..].fill(true);
+                continue;
+            }
+            if solver.can_leave_empty(position) && solver.fits(clue_index, position + 1) {
+                can_be_empty[position] = true;
+                reachable[(position + 1, clue_index)] = true;
+            }
+            let after_run = solver.position_after_run(clue_index, position);
+            if solver.can_place_run(clue_index, position) && solver.fits(clue_index + 1, after_run)
+            {
+                let run_end = position + clues[clue_index];
+                can_be_filled[position..run_end].fill(true);
+                if run_end < line.len() {
+                    can_be_empty[run_end] = true;
+                }
+                reachable[(after_run, clue_index + 1)] = true;
+            }
+        }
+    }
+    Some(
+        can_be_filled
+            .into_iter()
+            .zip(can_be_empty)
+            .map(|possibilities| match possibilities {
+                (true, false) => Cell::Filled,
+                (false, true) => Cell::Empty,
+                _ => Cell::Unknown,
+            })
+            .collect(),
+    )
+    /*
+        Time complexity analysis:
+        Let `n` be the length of the line and `k` be the number of clues.
+        This function completes in `O(n^2 * k)` time in the worst case.
+        - There are `(n + 1) * (k + 1)` states `(position, clue_index)`.
+        - Thanks to memoization, `fits` does `O(n)` work per state
+          (the `O(n)` coming from checking the cells covered by a run),
+          disregarding work done in recursive calls.
+        - The main loop also does `O(n)` work per state, marking the cells covered by a run.
+    */
+}
+
+/// Helper for solving a single line.
+struct LineSolver<'a> {
+    /// The (nonzero) clues for the line.
+    clues: &'a [usize],
+    /// What is currently known about the line.
+    line: &'a [Cell],
+    /// Memoized results of `fits`, indexed by `(position, clue_index)`.
+    memo: Grid<Option<bool>>,
+}
+
+impl LineSolver<'_> {
+    /// Returns whether the runs described by `clues[clue_index..]`
+    /// can be arranged to fit the cells `line[position..]`.
+    fn fits(&mut self, clue_index: usize, position: usize) -> bool {
+        if let Some(fits) = self.memo[(position, clue_index)] {
+            return fits;
+        }
+        let fits = if clue_index == self.clues.len() {
+            self.line[position..]
+                .iter()
+                .all(|&cell| cell != Cell::Filled)
+        } else {
+            (self.can_leave_empty(position) && self.fits(clue_index, position + 1))
+                || (self.can_place_run(clue_index, position)
+                    && self.fits(
+                        clue_index + 1,
+                        self.position_after_run(clue_index, position),
+                    ))
+        };
+        self.memo[(position, clue_index)] = Some(fits);
+        fits
+    }
+
+    /// Returns whether the cell at the given position exists and can be empty.
+    fn can_leave_empty(&self, position: usize) -> bool {
+        self.line
+            .get(position)
+            .is_some_and(|&cell| cell != Cell::Filled)
+    }
+
+    /// Returns whether the run for the given clue can start at the given position,
+    /// including the empty cell separating it from the next run (if not at the end of the line).
+    fn can_place_run(&self, clue_index: usize, position: usize) -> bool {
+        let run_end = position + self.clues[clue_index];
+        run_end <= self.line.len()
+            && self.line[position..run_end]
+                .iter()
+                .all(|&cell| cell != Cell::Empty)
+            && self.line.get(run_end) != Some(&Cell::Filled)
+    }
+
+    /// The position right after the run for the given clue (and the separating empty cell)
+    /// if the run were to start at the given position.
+    fn position_after_run(&self, clue_index: usize, position: usize) -> usize {
+        (position + self.clues[clue_index] + 1).min(self.line.len())
+    }
+}
+
+/// Solves the nonogram with the given row clues (top to bottom)
+/// and column clues (left to right).
+///
+/// On success, returns a grid with no `Unknown` cells that satisfies all of the clues.
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+///
+/// Returns None if the nonogram has no solution.
+pub fn solve_nonogram(row_clues: &[Vec<usize>], col_clues: &[Vec<usize>]) -> Option<Grid<Cell>> {
+    let grid = Grid::filled(Cell::Unknown, (col_clues.len(), row_clues.len()));
+    solve_from(row_clues, col_clues, grid)
+}
+
+/// Solves the nonogram with the given clues, starting from the given partially solved grid.
+fn solve_from(
+    row_clues: &[Vec<usize>],
+    col_clues: &[Vec<usize>],
+    mut grid: Grid<Cell>,
+) -> Option<Grid<Cell>> {
+    propagate(row_clues, col_clues, &mut grid)?;
+    let unknown = grid
+        .enumerate::<(usize, usize)>()
+        .find(|&(_, &cell)| cell == Cell::Unknown);
+    let Some((index, _)) = unknown else {
+        return Some(grid);
+    };
+    [Cell::Filled, Cell::Empty].into_iter().find_map(|guess| {
+        let mut guessed_grid = grid.clone();
+        guessed_grid[index] = guess;
+        solve_from(row_clues, col_clues, guessed_grid)
+    })
+}
+
+/// Repeatedly solves every row and column of the grid until no more progress can be made.
+///
+/// Fails if a contradiction is found.
+fn propagate(
+    row_clues: &[Vec<usize>],
+    col_clues: &[Vec<usize>],
+    grid: &mut Grid<Cell>,
+) -> Option<()> {
+    let (width, height) = grid.dimensions();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (y, clues) in row_clues.iter().enumerate() {
+            let indices: Vec<_> = (0..width).map(|x| (x, y)).collect();
+            changed |= update_line(grid, clues, &indices)?;
+        }
+        for (x, clues) in col_clues.iter().enumerate() {
+            let indices: Vec<_> = (0..height).map(|y| (x, y)).collect();
+            changed |= update_line(grid, clues, &indices)?;
+        }
+    }
+    Some(())
+}
+
+/// Solves the line consisting of the cells at the given indices, updating the grid accordingly.
+///
+/// Returns whether any cells were changed, or None if a contradiction is found.
+fn update_line(grid: &mut Grid<Cell>, clues: &[usize], indices: &[(usize, usize)]) -> Option<bool> {
+    let line: Vec<Cell> = indices.iter().map(|&index| grid[index]).collect();
+    let solved_line = solve_line(clues, &line)?;
+    for (&index, cell) in indices.iter().zip(solved_line) {
+        grid[index] = cell;
+    }
+    Some(
+        line.iter()
+            .zip(indices)
+            .any(|(&cell, &index)| grid[index] != cell),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nonogram::*;
+    use itertools::Itertools;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates an array of cells.
+    ///
+    /// Syntax:
+    /// ```text
+    /// cells![<`?` for unknown, `#` for filled, `.` for empty> ...]
+    /// ```
+    macro_rules! cells {
+        (@cell ?) => {Cell::Unknown};
+        (@cell #) => {Cell::Filled};
+        (@cell .) => {Cell::Empty};
+        ($($cell:tt)*) => {[$(cells!(@cell $cell)),*]};
+    }
+
+    #[rstest]
+    #[case::no_clues(&[], &cells![? ? ?], &cells![. . .])]
+    #[case::zero_clue(&[0], &cells![? ? ?], &cells![. . .])]
+    #[case::full(&[3], &cells![? ? ?], &cells![# # #])]
+    #[case::overlap(&[3], &cells![? ? ? ? ?], &cells![? ? # ? ?])]
+    #[case::no_overlap(&[2], &cells![? ? ? ? ?], &cells![? ? ? ? ?])]
+    #[case::tight_fit(&[1, 1, 1], &cells![? ? ? ? ?], &cells![# . # . #])]
+    #[case::anchored(&[1], &cells![? # ? ? ?], &cells![. # . . .])]
+    #[case::split_by_empty(&[2], &cells![? ? . ? ?], &cells![? ? . ? ?])]
+    #[case::forced_by_filled(&[1, 2], &cells![? ? # ? ?], &cells![# . # # .])]
+    #[case::extend_to_edge(&[2, 1], &cells![? ? ? . ? #], &cells![? # ? . . #])]
+    #[case::already_solved(&[1, 1], &cells![# . . #], &cells![# . . #])]
+    #[case::empty_line(&[], &cells![], &cells![])]
+    fn test_solve_line(#[case] clues: &[usize], #[case] line: &[Cell], #[case] expected: &[Cell]) {
+        assert_eq!(solve_line(clues, line).as_deref(), Some(expected));
+    }
+
+    #[rstest]
+    #[case::too_long(&[4], &cells![? ? ?])]
+    #[case::broken_up(&[2], &cells![? . ? . ?])]
+    #[case::too_many_filled(&[1], &cells![# #])]
+    #[case::filled_without_clues(&[], &cells![? # ?])]
+    fn test_solve_line_inconsistent(#[case] clues: &[usize], #[case] line: &[Cell]) {
+        assert_eq!(solve_line(clues, line), None);
+    }
+
+    /// Returns the clue describing the given fully solved line.
+    fn clues_of(line: impl Iterator<Item = Cell>) -> Vec<usize> {
+        line.dedup_with_count()
+            .filter(|&(_, cell)| cell == Cell::Filled)
+            .map(|(count, _)| count)
+            .collect()
+    }
+
+    /// Asserts that the given grid is a solution to the nonogram with the given clues.
+    fn assert_satisfies_clues(
+        grid: &Grid<Cell>,
+        row_clues: &[Vec<usize>],
+        col_clues: &[Vec<usize>],
+    ) {
+        let (width, height) = grid.dimensions();
+        assert_eq!(grid.dimensions(), (col_clues.len(), row_clues.len()));
+        for (y, clues) in row_clues.iter().enumerate() {
+            assert_eq!(
+                &clues_of((0..width).map(|x| grid[(x, y)])),
+                clues,
+                "row {y}"
+            );
+        }
+        for (x, clues) in col_clues.iter().enumerate() {
+            assert_eq!(
+                &clues_of((0..height).map(|y| grid[(x, y)])),
+                clues,
+                "column {x}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fully_determined() {
+        let row_clues = [vec![3], vec![1, 1, 1], vec![5], vec![1, 1, 1], vec![3]];
+        let col_clues = row_clues.clone();
+        let solution = solve_nonogram(&row_clues, &col_clues).unwrap();
+        let expected = Grid::from_2d_array([
+            cells![. # # # .],
+            cells![# . # . #],
+            cells![# # # # #],
+            cells![# . # . #],
+            cells![. # # # .],
+        ]);
+        assert_eq!(solution, expected);
+        assert_satisfies_clues(&solution, &row_clues, &col_clues);
+    }
+
+    #[test]
+    fn test_rectangle() {
+        // Solution:
+        // # # . # # # #
+        // . # . # . . #
+        // . # # # . # #
+        let row_clues = [vec![2, 4], vec![1, 1, 1], vec![3, 2]];
+        let col_clues = [
+            vec![1],
+            vec![3],
+            vec![1],
+            vec![3],
+            vec![1],
+            vec![1, 1],
+            vec![3],
+        ];
+        let solution = solve_nonogram(&row_clues, &col_clues).unwrap();
+        assert_satisfies_clues(&solution, &row_clues, &col_clues);
+    }
+
+    #[rstest]
+    // Each of these has multiple solutions,
+    // so solving lines alone can't make any progress from the blank grid.
+    #[case::diagonal(vec![vec![1], vec![1]], vec![vec![1], vec![1]])]
+    #[case::permutation(
+        vec![vec![1], vec![1], vec![1]],
+        vec![vec![1], vec![1], vec![1]],
+    )]
+    #[case::pairs(
+        vec![vec![1, 1], vec![1, 1], vec![1, 1], vec![1, 1]],
+        vec![vec![1, 1], vec![1, 1], vec![1, 1], vec![1, 1]],
+    )]
+    fn test_backtracking(#[case] row_clues: Vec<Vec<usize>>, #[case] col_clues: Vec<Vec<usize>>) {
+        let blank_row = vec![Cell::Unknown; col_clues.len()];
+        for clues in &row_clues {
+            assert_eq!(solve_line(clues, &blank_row), Some(blank_row.clone()));
+        }
+        let solution = solve_nonogram(&row_clues, &col_clues).unwrap();
+        assert_satisfies_clues(&solution, &row_clues, &col_clues);
+    }
+
+    #[rstest]
+    #[case::mismatched_totals(vec![vec![1], vec![1]], vec![vec![2], vec![2]])]
+    #[case::clue_too_long(vec![vec![3]], vec![vec![1], vec![1]])]
+    #[case::no_consistent_guess(
+        vec![vec![1, 1], vec![0], vec![1, 1]],
+        vec![vec![1], vec![1], vec![1]],
+    )]
+    #[case::no_columns(vec![vec![1]], vec![])]
+    fn test_inconsistent(#[case] row_clues: Vec<Vec<usize>>, #[case] col_clues: Vec<Vec<usize>>) {
+        assert_eq!(solve_nonogram(&row_clues, &col_clues), None);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_grids(#[case] width: usize, #[case] height: usize) {
+        let row_clues = vec![vec![]; height];
+        let col_clues = vec![vec![]; width];
+        let solution = solve_nonogram(&row_clues, &col_clues);
+        let expected = Grid::filled(Cell::Empty, (width, height));
+        assert_eq!(solution, Some(expected));
+    }
+}
@@ -0,0 +1,261 @@
+//! Numberlink is a puzzle played on a rectangular grid,
+//! where some cells contain numbered endpoints -- exactly two for each number.
+//!
+//! Problem: connect each pair of endpoints with a path of their number,
+//! such that paths don't cross or overlap, and every cell of the grid is covered by a path.
+//! Paths move orthogonally between cells.
+//!
+//! For example, the following puzzle (`.` for empty cells):
+//! ```text
+//! 1 . . . .
+//! 2 . . . .
+//! . . 3 . .
+//! . 2 . . 1
+//! . . . . 3
+//! ```
+//! can be solved like so:
+//! ```text
+//! 1 1 1 1 1
+//! 2 2 2 2 1
+//! 3 3 3 2 1
+//! 3 2 2 2 1
+//! 3 3 3 3 3
+//! ```
+//!
+//! This is the same puzzle as Flow Free, with numbers instead of colors,
+//! so it's solved with the same solver (see [`crate::flow_free`]).
+
+use crate::flow_free;
+use crate::grid::Grid;
+use std::collections::HashMap;
+
+type CellIndex = (usize, usize);
+
+/// A Numberlink puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberlinkBoard {
+    grid: Grid<Option<u32>>,
+    size: (usize, usize),
+}
+
+impl NumberlinkBoard {
+    /// Creates a puzzle from a grid, where `Some(n)` marks an endpoint with the number `n`.
+    ///
+    /// Returns Err(n) if `n` is a number that doesn't appear exactly twice in the grid.
+    /// If there are multiple such numbers, the smallest one is returned.
+    pub fn new(grid: Grid<Option<u32>>) -> Result<NumberlinkBoard, u32> {
+        let mut counts: HashMap<u32, usize> = HashMap::new();
+        for (_, &cell) in grid.enumerate::<CellIndex>() {
+            if let Some(number) = cell {
+                *counts.entry(number).or_default() += 1;
+            }
+        }
+        if let Some(number) = counts
+            .into_iter()
+            .filter(|&(_, count)| count != 2)
+            .map(|(number, _)| number)
+            .min()
+        {
+            return Err(number);
+        }
+        let size = grid.dimensions();
+        Ok(NumberlinkBoard { grid, size })
+    }
+
+    /// The grid of the puzzle, where `Some(n)` marks an endpoint with the number `n`.
+    pub fn grid(&self) -> &Grid<Option<u32>> {
+        &self.grid
+    }
+
+    /// The `(width, height)` dimensions of the puzzle.
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+}
+
+/// Returns a solution to the given puzzle, with every cell numbered by the path covering it,
+/// or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &NumberlinkBoard) -> Option<Grid<Option<u32>>> {
+    let mut cells_by_number: HashMap<u32, Vec<CellIndex>> = HashMap::new();
+    for (index, &cell) in board.grid.enumerate::<CellIndex>() {
+        if let Some(number) = cell {
+            cells_by_number.entry(number).or_default().push(index);
+        }
+    }
+    let mut endpoints: Vec<(u32, CellIndex, CellIndex)> = cells_by_number
+        .into_iter()
+        .map(|(number, cells)| (number, cells[0], cells[1]))
+        .collect();
+    endpoints.sort();
+    flow_free::solve_paths(&board.grid, &endpoints)
+    /*
+        Time complexity analysis:
+        The puzzle is NP-complete in general,
+        and the search can take exponential time in the number of cells in the worst case.
+        Each check for dead ends and isolation takes `O(p * c)` time,
+        where `p` is the number of paths and `c` is the number of cells.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flood_fill::{flood_fill_collect, Connectivity};
+    use crate::numberlink::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Numberlink puzzle grid.
+    ///
+    /// Syntax:
+    /// ```text
+    /// numberlink![
+    ///     [<a number for an endpoint, `.` for an empty cell> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! numberlink {
+        (@cell .) => {None};
+        (@cell $number:literal) => {Some($number)};
+        ($([$($cell:tt)*])*) => {
+            Grid::<Option<u32>>::from_2d_array([$([$(numberlink!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Checks that the given grid is a valid solution to the given puzzle.
+    fn assert_valid(board: &NumberlinkBoard, solution: &Grid<Option<u32>>) {
+        assert_eq!(solution.dimensions(), board.size());
+        for (index, &cell) in board.grid().enumerate::<CellIndex>() {
+            assert!(solution[index].is_some(), "cell {index:?} not covered");
+            if cell.is_some() {
+                assert_eq!(solution[index], cell, "endpoint {index:?} replaced");
+            }
+        }
+        for (index, &cell) in board.grid().enumerate::<CellIndex>() {
+            let Some(number) = cell else {
+                continue;
+            };
+            // Every cell with the number must be connected to the endpoint,
+            // which means the other endpoint is connected to it too.
+            let (x, y) = index;
+            let region = flood_fill_collect(
+                solution,
+                (x as i32, y as i32),
+                |&cell| cell == Some(number),
+                Connectivity::Four,
+            );
+            let num_cells = solution
+                .enumerate::<CellIndex>()
+                .filter(|&(_, &cell)| cell == Some(number))
+                .count();
+            assert_eq!(region.len(), num_cells, "path for {number} not connected");
+        }
+    }
+
+    /// The puzzle from the problem description.
+    fn problem_description_example() -> NumberlinkBoard {
+        NumberlinkBoard::new(numberlink![
+            [1 . . . .]
+            [2 . . . .]
+            [. . 3 . .]
+            [. 2 . . 1]
+            [. . . . 3]
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let board = problem_description_example();
+        assert_eq!(board.size(), (5, 5));
+        assert_eq!(board.grid()[(4, 3)], Some(1));
+    }
+
+    #[rstest]
+    #[case::single_endpoint(numberlink![
+        [1 . 2]
+        [. . 2]
+    ], 1)]
+    #[case::three_endpoints(numberlink![
+        [3 . 3]
+        [. 3 .]
+    ], 3)]
+    #[case::smallest(numberlink![
+        [5 . 4]
+        [. 2 .]
+    ], 2)]
+    fn test_new_invalid(#[case] grid: Grid<Option<u32>>, #[case] expected: u32) {
+        assert_eq!(NumberlinkBoard::new(grid), Err(expected));
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = problem_description_example();
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    #[case::seven_by_seven(numberlink![
+        [1 . . . . . .]
+        [2 . . . . . .]
+        [3 . . . . . .]
+        [4 . . . . . .]
+        [. . 5 . . . .]
+        [. 4 . . 3 2 1]
+        [. . . . . . 5]
+    ])]
+    #[case::large_numbers(numberlink![
+        [100 . 7]
+        [100 . 7]
+    ])]
+    #[case::adjacent_endpoints(numberlink![[1 1]])]
+    fn test_solve(#[case] grid: Grid<Option<u32>>) {
+        let board = NumberlinkBoard::new(grid).unwrap();
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    #[case::crossing(numberlink![
+        [1 2]
+        [2 1]
+    ])]
+    #[case::uncoverable_cell(numberlink![
+        [1 .]
+        [. 1]
+    ])]
+    #[case::blocked(numberlink![
+        [1 2 .]
+        [2 . .]
+        [. . 1]
+    ])]
+    #[case::seven_by_seven(numberlink![
+        [. . . . . . .]
+        [. 1 . . . 2 .]
+        [. . . 3 . . .]
+        [. . 4 . 4 . .]
+        [. . . 3 . . .]
+        [. 2 . . . 1 .]
+        [. . . . . . .]
+    ])]
+    #[case::no_endpoints(numberlink![
+        [. .]
+        [. .]
+    ])]
+    fn test_unsolvable(#[case] grid: Grid<Option<u32>>) {
+        let board = NumberlinkBoard::new(grid).unwrap();
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = NumberlinkBoard::new(Grid::filled(None, (width, height))).unwrap();
+        assert_eq!(solve(&board), Some(Grid::filled(None, (width, height))));
+    }
+}
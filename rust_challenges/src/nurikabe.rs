@@ -0,0 +1,544 @@
+//! Nurikabe is a puzzle played on a rectangular grid, where some cells contain numbered clues.
+//!
+//! Problem: shade some cells of the grid black (the "sea"), leaving the rest white ("islands"),
+//! such that:
+//! - each island (an orthogonally connected group of white cells)
+//!   contains exactly one clue, and has as many cells as the clue says;
+//! - no 2×2 square of cells is entirely black; and
+//! - all black cells are orthogonally connected.
+//!
+//! For example, the following puzzle (`.` for cells without clues):
+//! ```text
+//! . 2 . . .
+//! . . 3 . 2
+//! 3 . . . .
+//! . . . . 1
+//! . . . . .
+//! ```
+//! has the unique solution (`#` for black cells):
+//! ```text
+//! . 2 # # .
+//! # # 3 # 2
+//! 3 # . # #
+//! . # . # 1
+//! . # # # #
+//! ```
+//!
+//! The solver alternates between rule-based deductions and backtracking.
+//! The deductions include:
+//! - a complete island is surrounded by black cells;
+//! - a cell next to two different islands with clues is black;
+//! - a cell that no island can reach without growing too large is black;
+//! - an incomplete island, or a group of black cells that needs to connect to the others,
+//!   with only one undecided neighbor must grow into that neighbor; and
+//! - the last undecided cell of a 2×2 square with three black cells is white.
+
+use crate::flood_fill::{flood_fill_collect, Connectivity};
+use crate::grid::Grid;
+use std::collections::VecDeque;
+
+type CellIndex = (usize, usize);
+
+/// A Nurikabe puzzle, where `Some(n)` marks a clue for an island of `n` cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NurikabeBoard(pub Grid<Option<u32>>);
+
+/// Returns the orthogonally connected groups of cells satisfying `predicate`,
+/// each listed in breadth-first order from its first cell in row-major order.
+fn regions<T>(grid: &Grid<T>, predicate: impl Fn(&T) -> bool) -> Vec<Vec<CellIndex>> {
+    let mut visited = Grid::filled(false, grid.dimensions());
+    let mut result = vec![];
+    for ((x, y), element) in grid.enumerate::<CellIndex>() {
+        if visited[(x, y)] || !predicate(element) {
+            continue;
+        }
+        let start = (x as i32, y as i32);
+        let region: Vec<CellIndex> =
+            flood_fill_collect(grid, start, &predicate, Connectivity::Four)
+                .into_iter()
+                .map(|(x, y)| (x as usize, y as usize))
+                .collect();
+        for &cell in &region {
+            visited[cell] = true;
+        }
+        result.push(region);
+    }
+    result
+}
+
+/// Returns whether the given grid (where `true` is black) is a valid solution to the given puzzle.
+///
+/// Returns false if the dimensions of the grid don't match the puzzle.
+pub fn is_solution(board: &NurikabeBoard, solution: &Grid<bool>) -> bool {
+    let NurikabeBoard(clues) = board;
+    if solution.dimensions() != clues.dimensions() {
+        return false;
+    }
+    let clues_white = clues
+        .enumerate::<CellIndex>()
+        .all(|(cell, clue)| clue.is_none() || !solution[cell]);
+    let islands_valid = regions(solution, |&black| !black).iter().all(|island| {
+        let island_clues: Vec<u32> = island.iter().filter_map(|&cell| clues[cell]).collect();
+        island_clues == [island.len() as u32]
+    });
+    let (width, height) = solution.dimensions();
+    let has_black_square = (1..height).any(|y| {
+        (1..width).any(|x| {
+            [(x - 1, y - 1), (x, y - 1), (x - 1, y), (x, y)]
+                .iter()
+                .all(|&cell| solution[cell])
+        })
+    });
+    let sea_connected = regions(solution, |&black| black).len() <= 1;
+    clues_white && islands_valid && !has_black_square && sea_connected
+}
+
+/// Returns a solution to the given puzzle, where `true` is black and `false` is white,
+/// or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &NurikabeBoard) -> Option<Grid<bool>> {
+    let NurikabeBoard(clues) = board;
+    let state = clues.clone().map(|clue| clue.map(|_| false));
+    let state = search(state, clues)?;
+    Some(state.map(|cell| cell.unwrap()))
+    /*
+        Time complexity analysis:
+        The puzzle is NP-complete in general,
+        and the search can take exponential time in the number of cells in the worst case.
+        Each round of deductions takes `O(k * c)` time,
+        where `k` is the number of clues and `c` is the number of cells,
+        dominated by the search for cells that each island can reach.
+    */
+}
+
+/// Tries to decide every cell of the given partial solution
+/// (where `Some(true)` is black, `Some(false)` is white, and None is undecided),
+/// such that every rule is satisfied.
+///
+/// Returns the completed solution, or None if that's impossible.
+fn search(mut state: Grid<Option<bool>>, clues: &Grid<Option<u32>>) -> Option<Grid<Option<bool>>> {
+    if !propagate(&mut state, clues) {
+        return None;
+    }
+    let Some((index, _)) = state
+        .enumerate::<CellIndex>()
+        .find(|(_, cell)| cell.is_none())
+    else {
+        return Some(state);
+    };
+    [true, false].into_iter().find_map(|black| {
+        let mut state = state.clone();
+        state[index] = Some(black);
+        search(state, clues)
+    })
+}
+
+/// Applies deductions to the given partial solution until no more cells can be decided.
+///
+/// Once every cell is decided, the partial solution satisfies every rule.
+///
+/// Returns false if the partial solution is found to break some rule.
+fn propagate(state: &mut Grid<Option<bool>>, clues: &Grid<Option<u32>>) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        // Every deduction in a round is based on the state at the start of the round,
+        // so that cells decided partway through don't mix with outdated islands.
+        let current = state.clone();
+        let mut decide = |index: CellIndex, black: bool| {
+            if state[index].is_none() {
+                state[index] = Some(black);
+                changed = true;
+            }
+        };
+        // 2×2 squares.
+        let (width, height) = current.dimensions();
+        for (x, y) in (1..height).flat_map(|y| (1..width).map(move |x| (x, y))) {
+            let square = [(x - 1, y - 1), (x, y - 1), (x - 1, y), (x, y)];
+            let num_black = square
+                .iter()
+                .filter(|&&cell| current[cell] == Some(true))
+                .count();
+            let undecided: Vec<_> = square
+                .iter()
+                .filter(|&&cell| current[cell].is_none())
+                .collect();
+            match (num_black, &undecided[..]) {
+                (4, _) => return false,
+                (3, &[&cell]) => decide(cell, false),
+                _ => {}
+            }
+        }
+        // Islands.
+        let islands = regions(&current, |&cell| cell == Some(false));
+        let mut island_ids: Grid<Option<usize>> = Grid::filled(None, current.dimensions());
+        let mut island_clues = vec![];
+        for (id, island) in islands.iter().enumerate() {
+            for &cell in island {
+                island_ids[cell] = Some(id);
+            }
+            let clues: Vec<u32> = island.iter().filter_map(|&cell| clues[cell]).collect();
+            let clue = match clues[..] {
+                [] => None,
+                [clue] if island.len() <= clue as usize => Some(clue),
+                _ => return false,
+            };
+            island_clues.push(clue);
+            let undecided = undecided_neighbors(&current, island);
+            if clue == Some(island.len() as u32) {
+                for cell in undecided {
+                    decide(cell, true);
+                }
+            } else {
+                match undecided[..] {
+                    [] => return false,
+                    [cell] => decide(cell, false),
+                    _ => {}
+                }
+            }
+        }
+        for (index, cell) in current.enumerate::<CellIndex>() {
+            if cell.is_some() {
+                continue;
+            }
+            let mut clued_islands: Vec<usize> = island_ids
+                .orthogonal_neighbors(index)
+                .filter_map(|(_, &id)| id)
+                .filter(|&id| island_clues[id].is_some())
+                .collect();
+            clued_islands.sort();
+            clued_islands.dedup();
+            if clued_islands.len() > 1 {
+                decide(index, true);
+            }
+        }
+        // Cells that no island with a clue can reach.
+        let mut is_reachable = Grid::filled(false, current.dimensions());
+        for (id, island) in islands.iter().enumerate() {
+            let Some(clue) = island_clues[id] else {
+                continue;
+            };
+            let remaining = clue as usize - island.len();
+            let reachable = reachable_cells(&current, &island_ids, &island_clues, id, remaining);
+            for cell in reachable {
+                is_reachable[cell] = true;
+            }
+        }
+        for (index, &cell) in current.enumerate::<CellIndex>() {
+            match cell {
+                None if !is_reachable[index] => decide(index, true),
+                Some(false) if !is_reachable[index] => return false,
+                _ => {}
+            }
+        }
+        // Black cells.
+        let seas = regions(&current, |&cell| cell == Some(true));
+        if seas.len() > 1 {
+            for sea in &seas {
+                match undecided_neighbors(&current, sea)[..] {
+                    [] => return false,
+                    [cell] => decide(cell, true),
+                    _ => {}
+                }
+            }
+        }
+        let num_connectable_seas = regions(&current, |&cell| cell != Some(false))
+            .iter()
+            .filter(|region| region.iter().any(|&cell| current[cell] == Some(true)))
+            .count();
+        if num_connectable_seas > 1 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns the undecided cells orthogonally adjacent to the given region, without duplicates.
+fn undecided_neighbors(state: &Grid<Option<bool>>, region: &[CellIndex]) -> Vec<CellIndex> {
+    let mut result: Vec<CellIndex> = region
+        .iter()
+        .flat_map(|&cell| state.orthogonal_neighbors(cell))
+        .filter(|(_, neighbor)| neighbor.is_none())
+        .map(|(neighbor, _)| neighbor)
+        .collect();
+    result.sort();
+    result.dedup();
+    result
+}
+
+/// Returns the cells that the island with the given ID (which has a clue) could grow to include,
+/// by adding at most `remaining` more cells, including the cells of the island itself.
+///
+/// The island can't grow through black cells, or through cells next to another island with a clue,
+/// since the two islands would merge.
+fn reachable_cells(
+    state: &Grid<Option<bool>>,
+    island_ids: &Grid<Option<usize>>,
+    island_clues: &[Option<u32>],
+    id: usize,
+    remaining: usize,
+) -> Vec<CellIndex> {
+    let can_enter = |cell: CellIndex| {
+        state[cell] != Some(true)
+            && island_ids[cell].is_none_or(|other| island_clues[other].is_none())
+            && island_ids.orthogonal_neighbors(cell).all(|(_, neighbor)| {
+                neighbor.is_none_or(|other| other == id || island_clues[other].is_none())
+            })
+    };
+    let mut distances: Grid<Option<usize>> = Grid::filled(None, state.dimensions());
+    let mut queue = VecDeque::new();
+    for (cell, &other) in island_ids.enumerate::<CellIndex>() {
+        if other == Some(id) {
+            distances[cell] = Some(0);
+            queue.push_back(cell);
+        }
+    }
+    while let Some(curr) = queue.pop_front() {
+        let distance = distances[curr].unwrap();
+        if distance == remaining {
+            continue;
+        }
+        for (neighbor, _) in state.orthogonal_neighbors(curr) {
+            if distances[neighbor].is_none() && can_enter(neighbor) {
+                distances[neighbor] = Some(distance + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    distances
+        .enumerate::<CellIndex>()
+        .filter(|(_, distance)| distance.is_some())
+        .map(|(cell, _)| cell)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nurikabe::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Nurikabe puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// nurikabe![
+    ///     [<a number for a clue, `.` for a cell without a clue> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! nurikabe {
+        (@cell .) => {None};
+        (@cell $clue:literal) => {Some($clue)};
+        ($([$($cell:tt)*])*) => {
+            NurikabeBoard(Grid::from_2d_array([$([$(nurikabe!(@cell $cell)),*]),*]))
+        };
+    }
+
+    /// Creates a grid of cells, where `true` is black.
+    ///
+    /// Syntax:
+    /// ```text
+    /// shading![
+    ///     [<`#` for black, `.` for white> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! shading {
+        (@cell .) => {false};
+        (@cell #) => {true};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(shading!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &NurikabeBoard, solution: &Grid<bool>) {
+        let NurikabeBoard(clues) = board;
+        assert_eq!(solution.dimensions(), clues.dimensions());
+        for (cell, clue) in clues.enumerate::<CellIndex>() {
+            assert!(clue.is_none() || !solution[cell], "clue at {cell:?} shaded");
+        }
+        for island in regions(solution, |&black| !black) {
+            let island_clues: Vec<u32> = island.iter().filter_map(|&cell| clues[cell]).collect();
+            assert_eq!(
+                island_clues.len(),
+                1,
+                "island {island:?} has wrong number of clues"
+            );
+            assert_eq!(
+                island.len() as u32,
+                island_clues[0],
+                "island {island:?} has wrong size"
+            );
+        }
+        for (x, y) in solution.enumerate::<CellIndex>().map(|(cell, _)| cell) {
+            let square = [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)];
+            let is_black_square = square.iter().all(|&cell| solution.get(cell) == Some(&true));
+            assert!(!is_black_square, "2×2 black square at {:?}", (x, y));
+        }
+        assert!(
+            regions(solution, |&black| black).len() <= 1,
+            "black cells not connected"
+        );
+        assert!(is_solution(board, solution));
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = nurikabe![
+            [. 2 . . .]
+            [. . 3 . 2]
+            [3 . . . .]
+            [. . . . 1]
+            [. . . . .]
+        ];
+        let expected = shading![
+            [. . # # .]
+            [# # . # .]
+            [. # . # #]
+            [. # . # .]
+            [. # # # #]
+        ];
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_7_by_7() {
+        let board = nurikabe![
+            [. . . 4 . 3 .]
+            [. . . . . . .]
+            [3 . . . . . .]
+            [. 3 . . . . 3]
+            [. . . . . . .]
+            [2 . . 3 . . .]
+            [. . . . . . .]
+        ];
+        let expected = shading![
+            [. # . . # . .]
+            [. # . . # . #]
+            [. # # # # # #]
+            [# . . . # . .]
+            [# # # # # # .]
+            [. . # . . . #]
+            [# # # # # # #]
+        ];
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::single_clue(nurikabe![
+        [. . .]
+        [. 3 .]
+        [. . .]
+    ])]
+    #[case::all_white(nurikabe![
+        [. 4]
+        [. .]
+    ])]
+    #[case::ones(nurikabe![
+        [1 . 1]
+        [. . .]
+        [1 . 1]
+    ])]
+    #[case::no_clues_row(nurikabe![[. . . .]])]
+    fn test_solve(#[case] board: NurikabeBoard) {
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    // Every cell would have to be black, making a 2×2 black square.
+    #[case::no_clues(nurikabe![
+        [. .]
+        [. .]
+    ])]
+    #[case::clue_too_large(nurikabe![
+        [. 5]
+        [. .]
+    ])]
+    #[case::zero(nurikabe![[0 . .]])]
+    // The islands can't both have 2 cells without touching.
+    #[case::islands_merge(nurikabe![[2 . 2]])]
+    // The black cells would be split into two groups.
+    #[case::disconnected_sea(nurikabe![[. 1 .]])]
+    #[case::adjacent_clues(nurikabe![[1 1]])]
+    fn test_unsolvable(#[case] board: NurikabeBoard) {
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case::all_black(shading![
+        [# # #]
+        [# # #]
+        [# # #]
+    ])]
+    #[case::wrong_island_size(shading![
+        [. . #]
+        [# # #]
+        [# . .]
+    ])]
+    #[case::black_square(shading![
+        [. # #]
+        [# # #]
+        [# # .]
+    ])]
+    #[case::stray_island(shading![
+        [. # .]
+        [# . #]
+        [. # .]
+    ])]
+    #[case::clue_shaded(shading![
+        [. # #]
+        [# # .]
+        [. # .]
+    ])]
+    #[case::wrong_dimensions(shading![
+        [. # .]
+        [# # #]
+    ])]
+    fn test_invalid_solution(#[case] solution: Grid<bool>) {
+        let board = nurikabe![
+            [1 . 1]
+            [. . .]
+            [1 . 1]
+        ];
+        assert!(!is_solution(&board, &solution));
+    }
+
+    #[test]
+    fn test_valid_solution() {
+        let board = nurikabe![
+            [1 . 1]
+            [. . .]
+            [1 . 1]
+        ];
+        let solution = shading![
+            [. # .]
+            [# # #]
+            [. # .]
+        ];
+        assert!(is_solution(&board, &solution));
+    }
+
+    #[test]
+    fn test_disconnected_sea_solution() {
+        let board = nurikabe![[. 1 .]];
+        assert!(!is_solution(&board, &shading![[# . #]]));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = NurikabeBoard(Grid::filled(None, (width, height)));
+        assert_eq!(solve(&board), Some(Grid::filled(false, (width, height))));
+    }
+}
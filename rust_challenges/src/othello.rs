@@ -0,0 +1,418 @@
+//! Othello (also known as Reversi) is a two-player game played on an 8×8 board.
+//! Players take turns placing discs of their color, with black moving first.
+//! A disc must be placed so that it _captures_ at least one line of the opponent's discs:
+//! a straight line (horizontal, vertical, or diagonal) of one or more opposing discs,
+//! bounded on the far end by a disc of the player's own color.
+//! All captured discs are flipped to the player's color.
+//! A player with no valid moves passes; the game ends when neither player can move,
+//! and the player with more discs on the board wins.
+//!
+//! Problem: model the game, and find a good move for a player by searching the game tree.
+//!
+//! For example, from the standard starting position
+//! (`B` for black, `W` for white, `.` for empty; only the middle of the board is shown):
+//! ```text
+//! . . . .
+//! . W B .
+//! . B W .
+//! . . . .
+//! ```
+//! black can play directly above the left `W`, which captures it:
+//! ```text
+//! . B . .
+//! . B B .
+//! . B W .
+//! . . . .
+//! ```
+//!
+//! The search uses negamax with alpha-beta pruning, limited to a given depth.
+//! Positions at the depth limit are scored by the difference in disc counts.
+
+use crate::flood_fill::Connectivity;
+use crate::grid::Grid;
+
+/// One of the two players.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Player {
+    Black,
+    White,
+}
+
+impl Player {
+    /// The other player.
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::Black => Player::White,
+            Player::White => Player::Black,
+        }
+    }
+}
+
+/// An Othello board, where `None` indicates an empty cell.
+///
+/// Boards created with `new` are 8×8, but the other functions in this module
+/// work with boards of any size.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OthelloBoard(pub Grid<Option<Player>>);
+
+/// The side length of a standard board.
+const SIZE: usize = 8;
+
+impl OthelloBoard {
+    /// Creates an 8×8 board with the standard starting position:
+    /// two white discs and two black discs in the middle, with white on the main diagonal.
+    pub fn new() -> OthelloBoard {
+        let mut grid = Grid::filled(None, (SIZE, SIZE));
+        let (low, high) = (SIZE / 2 - 1, SIZE / 2);
+        grid[(low, low)] = Some(Player::White);
+        grid[(high, high)] = Some(Player::White);
+        grid[(high, low)] = Some(Player::Black);
+        grid[(low, high)] = Some(Player::Black);
+        OthelloBoard(grid)
+    }
+}
+
+impl Default for OthelloBoard {
+    fn default() -> OthelloBoard {
+        OthelloBoard::new()
+    }
+}
+
+type CellIndex = (usize, usize);
+
+/// Returns the cells that would be flipped if `player` placed a disc at `cell`,
+/// ignoring whether `cell` itself is empty.
+fn captured_cells(board: &OthelloBoard, player: Player, cell: CellIndex) -> Vec<CellIndex> {
+    let OthelloBoard(grid) = board;
+    let (x, y) = (cell.0 as i32, cell.1 as i32);
+    let mut captured = vec![];
+    for (dx, dy) in Connectivity::Eight.displacements() {
+        let mut line = vec![];
+        let mut curr = (x + dx, y + dy);
+        while grid.get(curr) == Some(&Some(player.opponent())) {
+            line.push((curr.0 as usize, curr.1 as usize));
+            curr = (curr.0 + dx, curr.1 + dy);
+        }
+        // The line only counts if it's bounded by one of the player's own discs.
+        if grid.get(curr) == Some(&Some(player)) {
+            captured.extend(line);
+        }
+    }
+    captured
+}
+
+/// Returns the cells where `player` can place a disc on the given board, in row-major order.
+pub fn valid_moves(board: &OthelloBoard, player: Player) -> Vec<CellIndex> {
+    let OthelloBoard(grid) = board;
+    grid.enumerate::<CellIndex>()
+        .filter(|(_, cell)| cell.is_none())
+        .map(|(index, _)| index)
+        .filter(|&index| !captured_cells(board, player, index).is_empty())
+        .collect()
+}
+
+/// Returns the result of `player` placing a disc at the given cell and flipping captured discs,
+/// or None if the move is invalid
+/// (the cell is occupied or out of bounds, or the move doesn't capture anything).
+pub fn apply_move(board: &OthelloBoard, player: Player, cell: CellIndex) -> Option<OthelloBoard> {
+    let OthelloBoard(grid) = board;
+    if grid.get(cell)?.is_some() {
+        return None;
+    }
+    let captured = captured_cells(board, player, cell);
+    if captured.is_empty() {
+        return None;
+    }
+    let mut grid = grid.clone();
+    grid[cell] = Some(player);
+    for index in captured {
+        grid[index] = Some(player);
+    }
+    Some(OthelloBoard(grid))
+}
+
+/// Returns the number of black discs and the number of white discs on the given board.
+pub fn game_score(board: &OthelloBoard) -> (usize, usize) {
+    let OthelloBoard(grid) = board;
+    let count = |player| {
+        grid.enumerate::<CellIndex>()
+            .filter(|&(_, &cell)| cell == Some(player))
+            .count()
+    };
+    (count(Player::Black), count(Player::White))
+}
+
+/// Returns the number of discs that `player` has,
+/// minus the number of discs that the opponent has.
+fn disc_difference(board: &OthelloBoard, player: Player) -> i32 {
+    let (black, white) = game_score(board);
+    let difference = black as i32 - white as i32;
+    match player {
+        Player::Black => difference,
+        Player::White => -difference,
+    }
+}
+
+/// The score of a won game, before adding the disc difference.
+const WIN_SCORE: i32 = 1_000_000;
+
+/// Returns the negamax score of the given board from the perspective of `player`,
+/// who is about to move, searching up to `depth` moves ahead (not counting passes).
+///
+/// The result is exact if it lies strictly between `alpha` and `beta`;
+/// otherwise, it's only a bound in the corresponding direction.
+fn negamax(board: &OthelloBoard, player: Player, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+    let moves = valid_moves(board, player);
+    if moves.is_empty() {
+        if valid_moves(board, player.opponent()).is_empty() {
+            // The game is over.
+            let difference = disc_difference(board, player);
+            return difference.signum() * WIN_SCORE + difference;
+        }
+        return -negamax(board, player.opponent(), depth, -beta, -alpha);
+    }
+    if depth == 0 {
+        return disc_difference(board, player);
+    }
+    let mut best = i32::MIN + 1;
+    for cell in moves {
+        let child = apply_move(board, player, cell).unwrap();
+        let score = -negamax(&child, player.opponent(), depth - 1, -beta, -alpha);
+        best = best.max(score);
+        alpha = alpha.max(score);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// Returns the cell where `player` should place a disc on the given board,
+/// according to a search of the game tree `depth` moves deep,
+/// or None if `player` has no valid moves.
+///
+/// A search depth of 0 is treated as a depth of 1
+/// (the moves available to `player` are always considered).
+/// If multiple moves are equally good, the first of them in row-major order is returned.
+pub fn best_move(board: &OthelloBoard, player: Player, depth: usize) -> Option<CellIndex> {
+    let mut best: Option<(CellIndex, i32)> = None;
+    for cell in valid_moves(board, player) {
+        let child = apply_move(board, player, cell).unwrap();
+        let alpha = best.map_or(i32::MIN + 1, |(_, score)| score);
+        let score = -negamax(
+            &child,
+            player.opponent(),
+            depth.saturating_sub(1),
+            i32::MIN + 1,
+            -alpha,
+        );
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((cell, score));
+        }
+    }
+    best.map(|(cell, _)| cell)
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells on the board, and `m` the maximum number of valid moves
+        in any position (`m < c`).
+        This function completes in `O(m^d * c^2)` time in the worst case, where `d` is the depth.
+        - The search visits `O(m^d)` positions (passes don't increase this,
+          since a pass is always followed by a move or the end of the game).
+        - Finding the valid moves in a position takes `O(c^2)` time:
+          `O(c)` empty cells, each with 8 lines of length `O(sqrt(c))` to check.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::othello::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates an Othello board.
+    ///
+    /// Syntax:
+    /// ```text
+    /// othello![
+    ///     [<`B` for black, `W` for white, `.` for empty> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! othello {
+        (@cell B) => {Some(Player::Black)};
+        (@cell W) => {Some(Player::White)};
+        (@cell .) => {None};
+        ($([$($cell:tt)*])*) => {
+            OthelloBoard(Grid::from_2d_array([$([$(othello!(@cell $cell)),*]),*]))
+        };
+    }
+
+    #[test]
+    fn test_new() {
+        let expected = othello![
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . W B . . .]
+            [. . . B W . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+        ];
+        assert_eq!(OthelloBoard::new(), expected);
+    }
+
+    #[rstest]
+    #[case::black(Player::Black, vec![(3, 2), (2, 3), (5, 4), (4, 5)])]
+    #[case::white(Player::White, vec![(4, 2), (5, 3), (2, 4), (3, 5)])]
+    fn test_opening_moves(#[case] player: Player, #[case] expected: Vec<CellIndex>) {
+        assert_eq!(valid_moves(&OthelloBoard::new(), player), expected);
+    }
+
+    #[test]
+    fn test_apply_opening_move() {
+        let expected = othello![
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . B . . . .]
+            [. . . B B . . .]
+            [. . . B W . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+            [. . . . . . . .]
+        ];
+        let actual = apply_move(&OthelloBoard::new(), Player::Black, (3, 2));
+        assert_eq!(actual, Some(expected));
+    }
+
+    #[test]
+    fn test_apply_move_multiple_lines() {
+        let board = othello![
+            [B . B . B]
+            [. W W W .]
+            [B W . W B]
+            [. W W W .]
+            [B . W . B]
+        ];
+        // Every line is captured except the one towards the bottom, which isn't bounded.
+        let expected = othello![
+            [B . B . B]
+            [. B B B .]
+            [B B B B B]
+            [. B W B .]
+            [B . W . B]
+        ];
+        assert_eq!(apply_move(&board, Player::Black, (2, 2)), Some(expected));
+    }
+
+    #[test]
+    fn test_apply_move_long_line() {
+        let board = othello![[. W W W B]];
+        assert_eq!(
+            apply_move(&board, Player::Black, (0, 0)),
+            Some(othello![[B B B B B]])
+        );
+    }
+
+    #[rstest]
+    #[case::no_capture((2, 2))]
+    #[case::not_adjacent((0, 0))]
+    #[case::occupied((3, 3))]
+    #[case::occupied_by_self((4, 3))]
+    #[case::out_of_bounds((8, 3))]
+    fn test_apply_move_invalid(#[case] cell: CellIndex) {
+        assert_eq!(apply_move(&OthelloBoard::new(), Player::Black, cell), None);
+    }
+
+    #[test]
+    fn test_apply_move_unbounded_line() {
+        let board = othello![[. W W W .]];
+        assert_eq!(apply_move(&board, Player::Black, (0, 0)), None);
+        assert_eq!(valid_moves(&board, Player::Black), []);
+    }
+
+    #[test]
+    fn test_game_score() {
+        assert_eq!(game_score(&OthelloBoard::new()), (2, 2));
+        let board = othello![
+            [B . B . B]
+            [. W W W .]
+            [B W . W B]
+            [. W W W .]
+            [B . W . B]
+        ];
+        assert_eq!(game_score(&board), (7, 9));
+    }
+
+    #[test]
+    fn test_score_sums_to_occupied_cells() {
+        let mut board = OthelloBoard::new();
+        let mut player = Player::Black;
+        for _ in 0..20 {
+            let Some(&cell) = valid_moves(&board, player).first() else {
+                break;
+            };
+            board = apply_move(&board, player, cell).unwrap();
+            player = player.opponent();
+            let OthelloBoard(grid) = &board;
+            let num_occupied = grid
+                .enumerate::<CellIndex>()
+                .filter(|(_, cell)| cell.is_some())
+                .count();
+            let (black, white) = game_score(&board);
+            assert_eq!(black + white, num_occupied);
+        }
+    }
+
+    #[rstest]
+    // Capturing three discs is better than capturing one.
+    #[case::most_captures(othello![
+        [. W B . .]
+        [. . . . .]
+        [. W W W B]
+    ], Player::Black, 1, (0, 2))]
+    // Either move captures one disc, but only the second leaves black unable to recapture,
+    // letting white take the last black disc after black passes.
+    #[case::force_pass(othello![
+        [W B . . .]
+        [. . . . .]
+        [. . . . .]
+        [. . . . .]
+        [. B W . .]
+    ], Player::White, 2, (0, 4))]
+    fn test_best_move(
+        #[case] board: OthelloBoard,
+        #[case] player: Player,
+        #[case] depth: usize,
+        #[case] expected: CellIndex,
+    ) {
+        assert_eq!(best_move(&board, player, depth), Some(expected));
+    }
+
+    #[test]
+    fn test_best_move_no_moves() {
+        let board = othello![
+            [B B .]
+            [B B .]
+        ];
+        assert_eq!(best_move(&board, Player::White, 3), None);
+        assert_eq!(best_move(&board, Player::Black, 3), None);
+    }
+
+    #[test]
+    fn test_self_play() {
+        // Playing a full game should only ever make valid moves, and should fill most of the board.
+        let mut board = OthelloBoard::new();
+        let mut player = Player::Black;
+        loop {
+            match best_move(&board, player, 2) {
+                Some(cell) => board = apply_move(&board, player, cell).unwrap(),
+                None if valid_moves(&board, player.opponent()).is_empty() => break,
+                None => {}
+            }
+            player = player.opponent();
+        }
+        let (black, white) = game_score(&board);
+        assert!(black + white > 40, "game ended early: {board:?}");
+    }
+}
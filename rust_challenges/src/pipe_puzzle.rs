@@ -0,0 +1,431 @@
+//! Problem: model a pipe-connecting puzzle, and solve it by rotating tiles.
+//!
+//! The puzzle is played on a rectangular grid of tiles, each containing a section of pipe.
+//! Every tile can be rotated in quarter turns.
+//! The goal is to rotate the tiles so that the pipes form a single leak-free network:
+//! - Every pipe opening must lead into a matching opening on the neighboring tile
+//!   (openings can't lead off the edge of the grid, or into a closed side of a neighbor).
+//! - There is exactly one source, and at least one sink.
+//! - Every non-empty tile is connected to the source through the network.
+//!
+//! For example (drawn with box-drawing characters; `S` is the source and `K` is the sink,
+//! which each have a single opening):
+//! ```text
+//! ┌ ─ ┐
+//! │ . │
+//! S . K
+//! ```
+//! is connected -- the source opens upwards and the sink opens upwards.
+
+use crate::grid::Grid;
+use itertools::Itertools;
+use std::collections::VecDeque;
+
+/// A side of a tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// All sides, in clockwise order.
+const SIDES: [Side; 4] = [Side::Up, Side::Right, Side::Down, Side::Left];
+
+impl Side {
+    /// The side facing this one on a neighboring tile.
+    fn opposite(self) -> Side {
+        self.rotated(2)
+    }
+
+    /// The side reached by rotating this side clockwise by the given number of quarter turns.
+    fn rotated(self, quarter_turns: usize) -> Side {
+        SIDES[(self as usize + quarter_turns) % 4]
+    }
+
+    /// The displacement to the neighboring tile on this side.
+    fn displacement(self) -> (i32, i32) {
+        match self {
+            Side::Up => (0, -1),
+            Side::Right => (1, 0),
+            Side::Down => (0, 1),
+            Side::Left => (-1, 0),
+        }
+    }
+}
+
+/// The orientation of a straight pipe (before rotation).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// The pair of sides connected by an elbow pipe (before rotation).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Corner {
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
+}
+
+/// A type of tile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PipeTile {
+    /// No pipe at all.
+    Empty,
+    /// A pipe connecting opposite sides.
+    Straight(Axis),
+    /// A pipe connecting adjacent sides.
+    Elbow(Corner),
+    /// A pipe connecting every side except the given (closed) side.
+    Tee(Side),
+    /// A pipe connecting all four sides.
+    Cross,
+    /// Where the flow starts, open (before rotation) on the top side only.
+    Source,
+    /// Where the flow ends up, open (before rotation) on the top side only.
+    Sink,
+}
+
+/// How far a tile has been rotated clockwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    Zero,
+    Quarter,
+    Half,
+    ThreeQuarters,
+}
+
+const ROTATIONS: [Rotation; 4] = [
+    Rotation::Zero,
+    Rotation::Quarter,
+    Rotation::Half,
+    Rotation::ThreeQuarters,
+];
+
+impl PipeTile {
+    /// Returns whether the tile, before rotation, has an opening on the given side.
+    fn is_open_unrotated(self, side: Side) -> bool {
+        use Side::*;
+        match self {
+            PipeTile::Empty => false,
+            PipeTile::Straight(Axis::Horizontal) => matches!(side, Left | Right),
+            PipeTile::Straight(Axis::Vertical) => matches!(side, Up | Down),
+            PipeTile::Elbow(Corner::UpRight) => matches!(side, Up | Right),
+            PipeTile::Elbow(Corner::DownRight) => matches!(side, Down | Right),
+            PipeTile::Elbow(Corner::DownLeft) => matches!(side, Down | Left),
+            PipeTile::Elbow(Corner::UpLeft) => matches!(side, Up | Left),
+            PipeTile::Tee(closed) => side != closed,
+            PipeTile::Cross => true,
+            PipeTile::Source | PipeTile::Sink => side == Up,
+        }
+    }
+
+    /// Returns whether the tile, after the given rotation, has an opening on the given side.
+    pub fn is_open(self, rotation: Rotation, side: Side) -> bool {
+        // Undo the rotation to find the corresponding side of the unrotated tile.
+        self.is_open_unrotated(side.rotated(4 - rotation as usize))
+    }
+
+    /// Returns the rotations of the tile that result in distinct sets of openings.
+    fn distinct_rotations(self) -> Vec<Rotation> {
+        let mut result: Vec<Rotation> = vec![];
+        for rotation in ROTATIONS {
+            let is_new = result.iter().all(|&seen| {
+                SIDES
+                    .iter()
+                    .any(|&side| self.is_open(seen, side) != self.is_open(rotation, side))
+            });
+            if is_new {
+                result.push(rotation);
+            }
+        }
+        result
+    }
+}
+
+/// The state of a pipe puzzle: every tile, along with how it has been rotated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PipeBoard(pub Grid<(PipeTile, Rotation)>);
+
+type TileIndex = (i32, i32);
+
+/// Returns whether the opening (or lack thereof) on the given side of the tile at `index`
+/// is matched by the neighboring tile -- an out-of-bounds neighbor counts as being closed.
+fn is_matched(grid: &Grid<(PipeTile, Rotation)>, index: TileIndex, side: Side) -> bool {
+    let (x, y) = index;
+    let (tile, rotation) = grid[index];
+    let (dx, dy) = side.displacement();
+    let neighbor_is_open =
+        grid.get((x + dx, y + dy))
+            .is_some_and(|&(neighbor, neighbor_rotation)| {
+                neighbor.is_open(neighbor_rotation, side.opposite())
+            });
+    tile.is_open(rotation, side) == neighbor_is_open
+}
+
+/// Returns whether the pipes on the given board form a single leak-free network,
+/// with exactly one source and at least one sink.
+pub fn is_connected(board: &PipeBoard) -> bool {
+    let PipeBoard(grid) = board;
+    let no_leaks = grid
+        .enumerate()
+        .all(|(index, _)| SIDES.iter().all(|&side| is_matched(grid, index, side)));
+    if !no_leaks {
+        return false;
+    }
+    let tiles_of_type = |tile_type: PipeTile| {
+        grid.enumerate::<TileIndex>()
+            .filter(move |&(_, &(tile, _))| tile == tile_type)
+            .map(|(index, _)| index)
+    };
+    let Ok(source) = tiles_of_type(PipeTile::Source).exactly_one() else {
+        return false;
+    };
+    if tiles_of_type(PipeTile::Sink).next().is_none() {
+        return false;
+    }
+    // Breadth-first search through the network, starting from the source.
+    let mut visited = Grid::filled(false, grid.dimensions());
+    visited[source] = true;
+    let mut num_visited = 1;
+    let mut queue = VecDeque::from([source]);
+    while let Some((x, y)) = queue.pop_front() {
+        let (tile, rotation) = grid[(x, y)];
+        for side in SIDES {
+            if !tile.is_open(rotation, side) {
+                continue;
+            }
+            let (dx, dy) = side.displacement();
+            let neighbor = (x + dx, y + dy);
+            // There are no leaks, so the neighbor must exist and be connected.
+            if !visited[neighbor] {
+                visited[neighbor] = true;
+                num_visited += 1;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    let num_pipes = grid
+        .enumerate::<TileIndex>()
+        .filter(|&(_, &(tile, _))| tile != PipeTile::Empty)
+        .count();
+    num_visited == num_pipes
+}
+
+/// Returns a version of the given board with tiles rotated so that it is connected,
+/// or None if there is no such version.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &PipeBoard) -> Option<PipeBoard> {
+    let PipeBoard(grid) = board;
+    let mut grid = grid.clone();
+    solve_from(&mut grid, 0).then_some(PipeBoard(grid))
+}
+
+/// Tries to rotate the tiles from the given row-major position onwards so that the board
+/// becomes connected, assuming that the tiles before that position have already been rotated.
+///
+/// Returns whether it was successful; on success, `grid` contains the solution.
+fn solve_from(grid: &mut Grid<(PipeTile, Rotation)>, position: usize) -> bool {
+    let (width, height) = grid.dimensions();
+    if position == width * height {
+        return is_connected(&PipeBoard(grid.clone()));
+    }
+    let index = ((position % width) as i32, (position / width) as i32);
+    let (tile, _) = grid[index];
+    for rotation in tile.distinct_rotations() {
+        grid[index] = (tile, rotation);
+        // The tiles above and to the left have already been rotated, so they can be checked;
+        // the tiles below and to the right can only be checked if they are out of bounds.
+        let (x, y) = index;
+        let fits = is_matched(grid, index, Side::Up)
+            && is_matched(grid, index, Side::Left)
+            && (x + 1 < width as i32 || is_matched(grid, index, Side::Right))
+            && (y + 1 < height as i32 || is_matched(grid, index, Side::Down));
+        if fits && solve_from(grid, position + 1) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pipe_puzzle::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use Axis::*;
+    use Corner::*;
+    use PipeTile::*;
+    use Rotation::*;
+
+    /// The board from the problem description.
+    fn problem_description_example() -> PipeBoard {
+        PipeBoard(Grid::from_2d_array([
+            [
+                (Elbow(DownRight), Zero),
+                (Straight(Horizontal), Zero),
+                (Elbow(DownLeft), Zero),
+            ],
+            [
+                (Straight(Vertical), Zero),
+                (Empty, Zero),
+                (Straight(Vertical), Zero),
+            ],
+            [(Source, Zero), (Empty, Zero), (Sink, Zero)],
+        ]))
+    }
+
+    /// A board with a cross in the middle connecting a source to three sinks.
+    fn cross_board() -> PipeBoard {
+        PipeBoard(Grid::from_2d_array([
+            [(Empty, Zero), (Sink, Half), (Empty, Zero)],
+            [(Sink, Quarter), (Cross, Zero), (Sink, ThreeQuarters)],
+            [(Empty, Zero), (Source, Zero), (Empty, Zero)],
+        ]))
+    }
+
+    #[rstest]
+    #[case::straight_unrotated(Straight(Vertical), Zero, [true, false, true, false])]
+    #[case::straight_rotated(Straight(Vertical), Quarter, [false, true, false, true])]
+    #[case::elbow_unrotated(Elbow(UpRight), Zero, [true, true, false, false])]
+    #[case::elbow_half(Elbow(UpRight), Half, [false, false, true, true])]
+    #[case::elbow_three_quarters(Elbow(UpRight), ThreeQuarters, [true, false, false, true])]
+    #[case::tee_unrotated(Tee(Side::Left), Zero, [true, true, true, false])]
+    #[case::tee_rotated(Tee(Side::Left), Quarter, [false, true, true, true])]
+    #[case::source_rotated(Source, Quarter, [false, true, false, false])]
+    #[case::sink_three_quarters(Sink, ThreeQuarters, [false, false, false, true])]
+    #[case::cross(Cross, Half, [true, true, true, true])]
+    #[case::empty(Empty, Quarter, [false, false, false, false])]
+    fn test_is_open(
+        #[case] tile: PipeTile,
+        #[case] rotation: Rotation,
+        #[case] expected: [bool; 4],
+    ) {
+        assert_eq!(SIDES.map(|side| tile.is_open(rotation, side)), expected);
+    }
+
+    #[rstest]
+    #[case::empty(Empty, 1)]
+    #[case::cross(Cross, 1)]
+    #[case::straight(Straight(Horizontal), 2)]
+    #[case::elbow(Elbow(DownLeft), 4)]
+    #[case::tee(Tee(Side::Up), 4)]
+    #[case::source(Source, 4)]
+    fn test_distinct_rotations(#[case] tile: PipeTile, #[case] expected: usize) {
+        assert_eq!(tile.distinct_rotations().len(), expected);
+    }
+
+    #[rstest]
+    #[case::problem_description_example(problem_description_example(), true)]
+    #[case::cross(cross_board(), true)]
+    #[case::line(PipeBoard(Grid::from_2d_array([[
+        (Source, Quarter),
+        (Straight(Horizontal), Zero),
+        (Sink, ThreeQuarters),
+    ]])), true)]
+    #[case::leak_off_edge(PipeBoard(Grid::from_2d_array([[
+        (Source, Quarter),
+        (Straight(Horizontal), Zero),
+        (Straight(Horizontal), Zero),
+    ]])), false)]
+    #[case::leak_into_closed_side(PipeBoard(Grid::from_2d_array([[
+        (Source, Quarter),
+        (Straight(Vertical), Zero),
+        (Sink, ThreeQuarters),
+    ]])), false)]
+    #[case::no_source(PipeBoard(Grid::from_2d_array([[
+        (Sink, Quarter),
+        (Sink, ThreeQuarters),
+    ]])), false)]
+    #[case::no_sink(PipeBoard(Grid::from_2d_array([[
+        (Source, Quarter),
+        (Source, ThreeQuarters),
+    ]])), false)]
+    #[case::two_networks(PipeBoard(Grid::from_2d_array([
+        [(Source, Quarter), (Sink, ThreeQuarters)],
+        [(Elbow(DownRight), Zero), (Elbow(DownLeft), Zero)],
+        [(Elbow(UpRight), Zero), (Elbow(UpLeft), Zero)],
+    ])), false)]
+    #[case::all_empty(PipeBoard(Grid::filled((Empty, Zero), (3, 2))), false)]
+    fn test_is_connected(#[case] board: PipeBoard, #[case] expected: bool) {
+        assert_eq!(is_connected(&board), expected);
+    }
+
+    #[test]
+    fn test_solve_already_solved() {
+        let board = problem_description_example();
+        assert_eq!(solve(&board), Some(board));
+    }
+
+    #[test]
+    fn test_solve_one_rotation() {
+        let board = PipeBoard(Grid::from_2d_array([[
+            (Source, Quarter),
+            (Straight(Vertical), Zero),
+            (Sink, ThreeQuarters),
+        ]]));
+        let expected = PipeBoard(Grid::from_2d_array([[
+            (Source, Quarter),
+            (Straight(Vertical), Quarter),
+            (Sink, ThreeQuarters),
+        ]]));
+        assert_eq!(solve(&board), Some(expected));
+    }
+
+    #[rstest]
+    #[case::scrambled_problem_description_example(PipeBoard(Grid::from_2d_array([
+        [(Elbow(DownRight), Half), (Straight(Horizontal), Quarter), (Elbow(UpLeft), Zero)],
+        [(Straight(Vertical), Zero), (Empty, Zero), (Straight(Horizontal), Half)],
+        [(Source, Quarter), (Empty, Zero), (Sink, Half)],
+    ])))]
+    #[case::scrambled_cross(PipeBoard(Grid::from_2d_array([
+        [(Empty, Zero), (Sink, Zero), (Empty, Zero)],
+        [(Source, Zero), (Cross, Zero), (Sink, Zero)],
+        [(Empty, Zero), (Sink, Zero), (Empty, Zero)],
+    ])))]
+    #[case::branching(PipeBoard(Grid::from_2d_array([
+        [(Source, Zero), (Tee(Side::Left), Zero), (Sink, Zero)],
+        [(Elbow(UpLeft), Zero), (Cross, Zero), (Elbow(DownRight), Zero)],
+        [(Sink, Zero), (Sink, Zero), (Sink, Zero)],
+    ])))]
+    fn test_solve(#[case] board: PipeBoard) {
+        let solution = solve(&board).unwrap();
+        assert!(is_connected(&solution), "not connected: {solution:?}");
+        let PipeBoard(original_grid) = &board;
+        let PipeBoard(solution_grid) = &solution;
+        for ((index, &(tile, _)), (_, &(solution_tile, _))) in original_grid
+            .enumerate::<TileIndex>()
+            .zip(solution_grid.enumerate::<TileIndex>())
+        {
+            assert_eq!(tile, solution_tile, "tile at {index:?} was replaced");
+        }
+    }
+
+    #[rstest]
+    #[case::dead_end(PipeBoard(Grid::from_2d_array([[
+        (Source, Zero),
+        (Straight(Vertical), Zero),
+    ]])))]
+    #[case::elbow_in_line(PipeBoard(Grid::from_2d_array([[
+        (Source, Zero),
+        (Elbow(UpRight), Zero),
+        (Sink, Zero),
+    ]])))]
+    #[case::two_sources(PipeBoard(Grid::from_2d_array([[
+        (Source, Zero),
+        (Source, Zero),
+    ]])))]
+    #[case::cross_on_edge(PipeBoard(Grid::from_2d_array([
+        [(Source, Zero), (Cross, Zero), (Sink, Zero)],
+        [(Sink, Zero), (Sink, Zero), (Sink, Zero)],
+    ])))]
+    fn test_unsolvable(#[case] board: PipeBoard) {
+        assert_eq!(solve(&board), None);
+    }
+}
@@ -0,0 +1,248 @@
+//! A polyomino is a shape formed by joining squares edge to edge.
+//! Polyominoes with 4 squares (tetrominoes) are familiar from _Tetris_.
+//!
+//! Problem: enumerate all free polyominoes with a given number of squares --
+//! two polyominoes are considered the same if one can be rotated and/or reflected into the other.
+//! Also, find all the positions where a polyomino fits into the empty cells of a grid.
+//!
+//! For example, there are 5 free tetrominoes:
+//! ```text
+//! # # # #   # # #   # # #   # #     # #
+//!           #         #     # #   # #
+//! ```
+//! (known as the I, L, T, O, and S tetrominoes).
+//!
+//! Polyominoes are enumerated by growing each polyomino with `n - 1` squares by one square
+//! in every possible way, and discarding duplicates.
+
+use crate::grid::{Grid, ORTHOGONAL_OFFSETS};
+use std::collections::BTreeSet;
+
+type Offset = (i32, i32);
+
+/// A polyomino, represented by the offsets of its squares.
+///
+/// The offsets are normalized: they are sorted (by `x`, then `y`),
+/// and translated so that the smallest `x` and the smallest `y` coordinates are both 0.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Polyomino(Vec<Offset>);
+
+impl Polyomino {
+    /// Creates a polyomino from the given square offsets,
+    /// normalizing them and removing duplicates.
+    ///
+    /// Does not check that the squares are connected.
+    pub fn new(offsets: impl IntoIterator<Item = Offset>) -> Polyomino {
+        let offsets: BTreeSet<Offset> = offsets.into_iter().collect();
+        let min_x = offsets.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let min_y = offsets.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        // Translating every offset by the same amount preserves the sorted order.
+        Polyomino(
+            offsets
+                .into_iter()
+                .map(|(x, y)| (x - min_x, y - min_y))
+                .collect(),
+        )
+    }
+
+    /// The normalized offsets of the polyomino's squares.
+    pub fn offsets(&self) -> &[Offset] {
+        let Polyomino(offsets) = self;
+        offsets
+    }
+
+    /// The number of squares in the polyomino.
+    pub fn len(&self) -> usize {
+        self.offsets().len()
+    }
+
+    /// Returns whether the polyomino has no squares.
+    pub fn is_empty(&self) -> bool {
+        self.offsets().is_empty()
+    }
+
+    /// Returns a canonical representative of all rotations and reflections of this polyomino,
+    /// such that two polyominoes are the same free polyomino
+    /// if and only if they have the same canonical form.
+    fn canonical_form(&self) -> Polyomino {
+        // The 8 symmetries of the square.
+        let transformations: [fn(Offset) -> Offset; 8] = [
+            |(x, y)| (x, y),
+            |(x, y)| (-y, x),
+            |(x, y)| (-x, -y),
+            |(x, y)| (y, -x),
+            |(x, y)| (-x, y),
+            |(x, y)| (y, x),
+            |(x, y)| (x, -y),
+            |(x, y)| (-y, -x),
+        ];
+        transformations
+            .iter()
+            .map(|transform| Polyomino::new(self.offsets().iter().map(|&offset| transform(offset))))
+            .min()
+            .unwrap()
+    }
+}
+
+/// Returns all distinct free polyominoes with `n` squares, in canonical form.
+///
+/// The polyominoes are sorted by their offsets; returns an empty list if `n` is 0.
+pub fn enumerate_polyominoes(n: usize) -> Vec<Polyomino> {
+    if n == 0 {
+        return vec![];
+    }
+    let mut polyominoes = BTreeSet::from([Polyomino::new([(0, 0)])]);
+    for _ in 1..n {
+        let mut grown = BTreeSet::new();
+        for polyomino in &polyominoes {
+            for &(x, y) in polyomino.offsets() {
+                for (dx, dy) in ORTHOGONAL_OFFSETS {
+                    let square = (x + dx, y + dy);
+                    if polyomino.offsets().contains(&square) {
+                        continue;
+                    }
+                    let offsets = polyomino.offsets().iter().copied().chain([square]);
+                    grown.insert(Polyomino::new(offsets).canonical_form());
+                }
+            }
+        }
+        polyominoes = grown;
+    }
+    polyominoes.into_iter().collect()
+    /*
+        Time complexity analysis:
+        Let `p(k)` be the number of free polyominoes with `k` squares.
+        This function completes in `O(n^3 log(p(n)) * p(n - 1))` time in the worst case
+        (`p(k)` itself grows exponentially).
+        - Each polyomino with `k` squares can be grown in `O(k)` ways,
+          each of which takes `O(k log k)` time to normalize and canonicalize,
+          and `O(k log(p(k + 1)))` time to insert into the set.
+        - Summing over `k` from 1 to `n - 1`, the last step dominates.
+    */
+}
+
+/// Returns all positions in the given grid where the given polyomino (without rotation)
+/// fits entirely over empty (`false`) cells.
+///
+/// A position is the cell that the polyomino's `(0, 0)` offset would be placed on,
+/// even if the polyomino doesn't contain a square at that offset.
+/// Positions are returned in row-major order.
+pub fn pack_polyomino(grid: &Grid<bool>, piece: &Polyomino) -> Vec<(i32, i32)> {
+    grid.enumerate::<(i32, i32)>()
+        .map(|(position, _)| position)
+        .filter(|&(x, y)| {
+            piece
+                .offsets()
+                .iter()
+                .all(|&(dx, dy)| grid.get((x + dx, y + dy)) == Some(&false))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::polyomino::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 1)]
+    #[case(2, 1)]
+    #[case(3, 2)]
+    #[case(4, 5)]
+    #[case(5, 12)]
+    #[case(6, 35)]
+    #[case(7, 108)]
+    #[case(8, 369)]
+    fn test_enumerate_count(#[case] n: usize, #[case] expected: usize) {
+        let polyominoes = enumerate_polyominoes(n);
+        assert_eq!(polyominoes.len(), expected);
+        for polyomino in &polyominoes {
+            assert_eq!(polyomino.len(), n, "wrong size: {polyomino:?}");
+        }
+    }
+
+    #[test]
+    fn test_enumerate_tetrominoes() {
+        // Each tetromino from the problem description, in some orientation.
+        let tetrominoes = [
+            Polyomino::new([(0, 0), (1, 0), (2, 0), (3, 0)]),
+            Polyomino::new([(0, 0), (1, 0), (2, 0), (0, 1)]),
+            Polyomino::new([(0, 0), (1, 0), (2, 0), (1, 1)]),
+            Polyomino::new([(0, 0), (1, 0), (0, 1), (1, 1)]),
+            Polyomino::new([(1, 0), (2, 0), (0, 1), (1, 1)]),
+        ];
+        let mut expected: Vec<Polyomino> =
+            tetrominoes.iter().map(Polyomino::canonical_form).collect();
+        expected.sort();
+        assert_eq!(enumerate_polyominoes(4), expected);
+    }
+
+    #[rstest]
+    #[case::already_normalized([(0, 0), (1, 0), (0, 1)], &[(0, 0), (0, 1), (1, 0)])]
+    #[case::translated([(5, -2), (6, -2), (5, -1)], &[(0, 0), (0, 1), (1, 0)])]
+    #[case::duplicates([(2, 2), (2, 2), (3, 2)], &[(0, 0), (1, 0)])]
+    fn test_new<const N: usize>(#[case] offsets: [Offset; N], #[case] expected: &[Offset]) {
+        assert_eq!(Polyomino::new(offsets).offsets(), expected);
+    }
+
+    #[rstest]
+    #[case::rotation(
+        Polyomino::new([(0, 0), (1, 0), (2, 0), (0, 1)]),
+        Polyomino::new([(0, 0), (1, 0), (1, 1), (1, 2)]),
+    )]
+    #[case::reflection(
+        Polyomino::new([(1, 0), (2, 0), (0, 1), (1, 1)]),
+        Polyomino::new([(0, 0), (1, 0), (1, 1), (2, 1)]),
+    )]
+    fn test_canonical_form_same(#[case] a: Polyomino, #[case] b: Polyomino) {
+        assert_eq!(a.canonical_form(), b.canonical_form());
+    }
+
+    #[test]
+    fn test_canonical_form_different() {
+        let l = Polyomino::new([(0, 0), (1, 0), (2, 0), (0, 1)]);
+        let t = Polyomino::new([(0, 0), (1, 0), (2, 0), (1, 1)]);
+        assert_ne!(l.canonical_form(), t.canonical_form());
+    }
+
+    #[rstest]
+    #[case::horizontal(
+        Polyomino::new([(0, 0), (1, 0), (2, 0), (3, 0)]),
+        vec![(0, 0), (0, 1), (0, 2), (0, 3)],
+    )]
+    #[case::vertical(
+        Polyomino::new([(0, 0), (0, 1), (0, 2), (0, 3)]),
+        vec![(0, 0), (1, 0), (2, 0), (3, 0)],
+    )]
+    fn test_pack_straight_tetromino(#[case] piece: Polyomino, #[case] expected: Vec<(i32, i32)>) {
+        let grid = Grid::filled(false, (4, 4));
+        assert_eq!(pack_polyomino(&grid, &piece), expected);
+    }
+
+    #[test]
+    fn test_pack_around_filled_cells() {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [true,  false, false, true],
+            [false, false, false, false],
+            [true,  false, false, false],
+        ]);
+        let square = Polyomino::new([(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert_eq!(pack_polyomino(&grid, &square), [(1, 0), (1, 1), (2, 1)]);
+        // The `(0, 0)` offset isn't part of this piece, so it can be placed over a filled cell.
+        let s = Polyomino::new([(1, 0), (2, 0), (0, 1), (1, 1)]);
+        assert_eq!(pack_polyomino(&grid, &s), [(0, 0), (1, 1)]);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_pack_empty_grids(#[case] width: usize, #[case] height: usize) {
+        let grid = Grid::filled(false, (width, height));
+        let monomino = Polyomino::new([(0, 0)]);
+        assert_eq!(pack_polyomino(&grid, &monomino), []);
+    }
+}
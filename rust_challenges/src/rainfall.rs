@@ -60,12 +60,16 @@
 
 use crate::grid::Grid;
 use itertools::Itertools;
+use std::collections::HashMap;
 use std::fmt::{self, Debug};
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::thread;
 
 type CellCoordinates = (i32, i32);
 
 /// A basin, identified by the sink that the basin is associated with.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Basin {
     /// The coordinates of the sink that all cells in the basin drain into.
     sink: CellCoordinates,
@@ -89,17 +93,27 @@ type Region = Grid<Altitude>;
 /// Fails for invalid regions, returning the coordinates of the cell
 /// where the unique lowest altitude requirement is found to be violated.
 pub fn identify_basins(region: &Region) -> Result<Grid<Basin>, CellCoordinates> {
+    identify_basins_with_sinks(region).map(|(basins, _)| basins)
+}
+
+/// Identifies the basins in the given region, like `identify_basins`,
+/// additionally returning the coordinates of the sinks, in the order of the first cells
+/// (in row-major order) of their basins.
+pub fn identify_basins_with_sinks(
+    region: &Region,
+) -> Result<(Grid<Basin>, Vec<CellCoordinates>), CellCoordinates> {
     let mut basins = Grid::filled(None, region.dimensions());
+    let mut sinks = vec![];
     for (cell, _) in region.enumerate() {
-        identify_basin_at(region, cell, &mut basins)?;
+        identify_basin_at(region, cell, &mut basins, &mut sinks)?;
     }
-    Ok(basins.map(|basin| basin.unwrap()))
+    Ok((basins.map(|basin| basin.unwrap()), sinks))
     /*
         Time complexity analysis:
         Let `c` be the number of cells in the region.
         This function completes in `O(c)` time in the worst case --
         for an `n×n` square region, this translates to a time complexity of `O(n^2)`.
-        - Disregarding work done in `identify_basin_at`, `identify_basins` completes in
+        - Disregarding work done in `identify_basin_at`, `identify_basins_with_sinks` completes in
           `O(c)` time -- creating `basins`, executing the for loop, and mapping over `basins`
           can all be done in `O(c)` time.
         - `identify_basin_at` relies on memoization to achieve an efficient time complexity.
@@ -114,7 +128,8 @@ pub fn identify_basins(region: &Region) -> Result<Grid<Basin>, CellCoordinates>
 }
 
 /// Identifies the basin for the cell at the given coordinates in the given region,
-/// recording the basin in `basins` if not already recorded.
+/// recording the basin in `basins` if not already recorded,
+/// and adding the cell to `sinks` if it's a newly found sink.
 ///
 /// Fails if the region is discovered to be invalid, returning the coordinates of the cell
 /// where the unique lowest altitude requirement is found to be violated.
@@ -122,20 +137,243 @@ fn identify_basin_at(
     region: &Region,
     cell: CellCoordinates,
     basins: &mut Grid<Option<Basin>>,
+    sinks: &mut Vec<CellCoordinates>,
 ) -> Result<(), CellCoordinates> {
     if basins[cell].is_none() {
         let lowest = locally_lowest_cell(region, cell)?;
         let cell_is_sink = cell == lowest;
         if cell_is_sink {
             basins[cell] = Some(Basin { sink: cell });
+            sinks.push(cell);
         } else {
-            identify_basin_at(region, lowest, basins)?;
+            identify_basin_at(region, lowest, basins, sinks)?;
             basins[cell] = basins[lowest];
         }
     }
     Ok(())
 }
 
+/// A tiebreaker for `identify_basins_permissive` that picks the topmost of the tied cells,
+/// and then the leftmost of those.
+pub const TOPMOST_LEFTMOST_TIEBREAKER: fn(&[CellCoordinates]) -> CellCoordinates =
+    |cells| *cells.iter().min_by_key(|&&(x, y)| (y, x)).unwrap();
+
+/// Identifies the basins in the given region, like `identify_basins`,
+/// but instead of failing when a cell and its neighbors have multiple cells of lowest altitude,
+/// passes those cells (in row-major order) to `tiebreaker` to choose which one rain flows to.
+/// If the tiebreaker chooses the cell itself, then it's a sink.
+///
+/// Panics if the tiebreaker returns coordinates that aren't among the tied cells,
+/// or makes rain flow in a cycle. Tiebreakers that pick the first cell according to
+/// a fixed ordering of cells, like `TOPMOST_LEFTMOST_TIEBREAKER`, never cause cycles.
+pub fn identify_basins_permissive(
+    region: &Region,
+    tiebreaker: impl Fn(&[CellCoordinates]) -> CellCoordinates,
+) -> Grid<Basin> {
+    let mut basins = Grid::filled(None, region.dimensions());
+    let mut visited = Grid::filled(false, region.dimensions());
+    for (start, _) in region.enumerate::<CellCoordinates>() {
+        let mut path = vec![];
+        let mut cell = start;
+        let basin = loop {
+            if let Some(basin) = basins[cell] {
+                break basin;
+            }
+            assert!(!visited[cell], "the tiebreaker made rain flow in a cycle");
+            visited[cell] = true;
+            path.push(cell);
+            let lowest = locally_lowest_cell_with_tiebreaker(region, cell, &tiebreaker);
+            if lowest == cell {
+                break Basin { sink: cell };
+            }
+            cell = lowest;
+        };
+        for cell in path {
+            basins[cell] = Some(basin);
+        }
+    }
+    basins.map(|basin| basin.unwrap())
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the region.
+        Each cell is added to a path at most once, since it's given a basin right after,
+        and the work per cell is `O(1)` plus the time taken by the tiebreaker on at most 5 cells,
+        so this function completes in `O(c)` time, assuming the tiebreaker takes `O(1)` time.
+    */
+}
+
+/// Returns a grid with the coordinates of the neighbor that rain flows to from each cell,
+/// or None for sinks.
+///
+/// Fails for invalid regions, returning the coordinates of the first cell (in row-major order)
+/// where the unique lowest altitude requirement is violated.
+pub fn flow_direction_grid(
+    region: &Region,
+) -> Result<Grid<Option<CellCoordinates>>, CellCoordinates> {
+    let directions = region
+        .enumerate()
+        .map(|(cell, _)| {
+            let lowest = locally_lowest_cell(region, cell)?;
+            Ok((lowest != cell).then_some(lowest))
+        })
+        .collect::<Result<_, CellCoordinates>>()?;
+    Ok(Grid::from_flat_vec(directions, region.width(), region.height()).unwrap())
+}
+
+/// Returns the cells that rain falling on the cell at the given coordinates flows through,
+/// from that cell to the sink it drains into (inclusive).
+///
+/// Fails if the rain reaches a cell where the unique lowest altitude requirement is violated,
+/// returning the coordinates of that cell.
+///
+/// Panics if the given coordinates are out of bounds.
+pub fn drainage_path(
+    region: &Region,
+    start: CellCoordinates,
+) -> Result<Vec<CellCoordinates>, CellCoordinates> {
+    let mut path = vec![start];
+    loop {
+        let cell = path[path.len() - 1];
+        let lowest = locally_lowest_cell(region, cell)?;
+        if lowest == cell {
+            return Ok(path);
+        }
+        path.push(lowest);
+    }
+}
+
+/// Returns the number of cells in each basin of the given grid of basins
+/// (as returned by `identify_basins`).
+pub fn basin_sizes(basins: &Grid<Basin>) -> HashMap<Basin, usize> {
+    basins.histogram()
+}
+
+/// Identifies the basins in the given region, like `identify_basins`,
+/// but splits the region into horizontal strips (one per available thread)
+/// and follows the flow within each strip on its own thread.
+///
+/// Returns the same result as `identify_basins`, including the same failing cell
+/// for invalid regions.
+///
+/// The strips are run on scoped threads from the standard library, not with `rayon`,
+/// so this is always available, with no extra dependency or feature flag.
+/// No speedup over `identify_basins` is claimed or measured:
+/// the flows between strips are still merged sequentially,
+/// and with a single available thread the whole region is one strip.
+pub fn identify_basins_parallel(region: &Region) -> Result<Grid<Basin>, CellCoordinates> {
+    let strip_count = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    identify_basins_in_strips(region, strip_count)
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the region and `p` be the number of strips.
+        Each strip is processed in `O(c / p)` time, by the same argument as for `identify_basins`,
+        and merging the strips takes `O(c)` time, since each cell is resolved once
+        (disregarding memoized calls, which can be absorbed into the cost at the call site).
+        The total work is `O(c)`, as before.
+    */
+}
+
+/// Where rain that falls on a cell ends up, as far as can be told from within the cell's strip.
+#[derive(Clone, Copy)]
+enum StripFlow {
+    /// The rain collects in a sink within the strip.
+    Sink(Basin),
+    /// The rain leaves the strip, flowing into the cell with the given coordinates.
+    Exit(CellCoordinates),
+}
+
+/// Identifies the basins in the given region by splitting it into (at most) the given number
+/// of horizontal strips, following the flow within each strip on its own thread,
+/// and then following the flows between strips sequentially.
+fn identify_basins_in_strips(
+    region: &Region,
+    strip_count: usize,
+) -> Result<Grid<Basin>, CellCoordinates> {
+    let (width, height) = region.dimensions();
+    let strip_height = height.div_ceil(strip_count).max(1);
+    let flows = thread::scope(|scope| {
+        let strips: Vec<_> = (0..height)
+            .step_by(strip_height)
+            .map(|top| {
+                let rows = top as i32..(top + strip_height).min(height) as i32;
+                scope.spawn(move || trace_strip(region, rows))
+            })
+            .collect();
+        strips
+            .into_iter()
+            .flat_map(|strip| strip.join().unwrap())
+            .collect()
+    });
+    let flows = Grid::from_flat_vec(flows, width, height).unwrap();
+    let mut basins = Grid::filled(None, region.dimensions());
+    for (cell, _) in region.enumerate() {
+        merge_strip_flow(&flows, cell, &mut basins)?;
+    }
+    Ok(basins.map(|basin| basin.unwrap()))
+}
+
+/// Follows the flow of rain within the strip of the region consisting of the given rows,
+/// returning where rain that falls on each cell of the strip ends up, in row-major order.
+///
+/// The result for a cell is an error if a violation of the unique lowest altitude requirement
+/// is found before the rain leaves the strip, with the coordinates of the first such cell.
+fn trace_strip(region: &Region, rows: Range<i32>) -> Vec<Result<StripFlow, CellCoordinates>> {
+    let width = region.width();
+    let mut flows = Grid::filled(None, (width, rows.len()));
+    rows.clone()
+        .cartesian_product(0..width as i32)
+        .map(|(y, x)| trace_within_strip(region, &rows, (x, y), &mut flows))
+        .collect()
+}
+
+/// Follows the flow of rain from the cell at the given coordinates until it collects in a sink
+/// or leaves the strip consisting of the given rows,
+/// recording the result in `flows` (indexed relative to the top of the strip)
+/// if not already recorded.
+fn trace_within_strip(
+    region: &Region,
+    rows: &Range<i32>,
+    cell: CellCoordinates,
+    flows: &mut Grid<Option<Result<StripFlow, CellCoordinates>>>,
+) -> Result<StripFlow, CellCoordinates> {
+    let (x, y) = cell;
+    let strip_cell = (x, y - rows.start);
+    if let Some(flow) = flows[strip_cell] {
+        return flow;
+    }
+    let flow = match locally_lowest_cell(region, cell) {
+        Err(cell) => Err(cell),
+        Ok(lowest) if lowest == cell => Ok(StripFlow::Sink(Basin { sink: cell })),
+        Ok(lowest) if rows.contains(&lowest.1) => trace_within_strip(region, rows, lowest, flows),
+        Ok(lowest) => Ok(StripFlow::Exit(lowest)),
+    };
+    flows[strip_cell] = Some(flow);
+    flow
+}
+
+/// Identifies the basin for the cell at the given coordinates,
+/// given where rain ends up within each strip,
+/// following the rain from strip to strip and recording the basin in `basins`
+/// if not already recorded.
+///
+/// Fails if the rain reaches a cell where the unique lowest altitude requirement is violated,
+/// returning the coordinates of that cell.
+fn merge_strip_flow(
+    flows: &Grid<Result<StripFlow, CellCoordinates>>,
+    cell: CellCoordinates,
+    basins: &mut Grid<Option<Basin>>,
+) -> Result<Basin, CellCoordinates> {
+    if let Some(basin) = basins[cell] {
+        return Ok(basin);
+    }
+    let basin = match flows[cell]? {
+        StripFlow::Sink(basin) => basin,
+        StripFlow::Exit(next) => merge_strip_flow(flows, next, basins)?,
+    };
+    basins[cell] = Some(basin);
+    Ok(basin)
+}
+
 /// Returns the coordinates of the cell of lowest altitude
 /// between the cell at the given coordinates and its neighbors.
 ///
@@ -146,20 +384,46 @@ fn locally_lowest_cell(
     region: &Region,
     cell: CellCoordinates,
 ) -> Result<CellCoordinates, CellCoordinates> {
-    let neighborhood = neighborhood_coordinates(cell)
+    unique_lowest_altitude_cell(neighborhood(region, cell)).ok_or(cell)
+}
+
+/// Returns the coordinates of the cell of lowest altitude
+/// between the cell at the given coordinates and its neighbors,
+/// using `tiebreaker` to choose between the cells of lowest altitude if there are multiple.
+///
+/// Panics if the tiebreaker returns coordinates that aren't among the cells passed to it.
+fn locally_lowest_cell_with_tiebreaker(
+    region: &Region,
+    cell: CellCoordinates,
+    tiebreaker: impl Fn(&[CellCoordinates]) -> CellCoordinates,
+) -> CellCoordinates {
+    let mut lowest: Vec<CellCoordinates> = neighborhood(region, cell)
+        .min_set_by_key(|&(_coordinates, altitude)| altitude)
         .into_iter()
-        .filter_map(|coordinates| {
-            let &altitude = region.get(coordinates)?;
-            Some((coordinates, altitude))
-        });
-    unique_lowest_altitude_cell(neighborhood).ok_or(cell)
+        .map(|(coordinates, _altitude)| coordinates)
+        .collect();
+    if let [only] = lowest[..] {
+        return only;
+    }
+    lowest.sort_by_key(|&(x, y)| (y, x));
+    let chosen = tiebreaker(&lowest);
+    assert!(
+        lowest.contains(&chosen),
+        "the tiebreaker chose {chosen:?}, which isn't one of {lowest:?}"
+    );
+    chosen
 }
 
-/// Given the coordinates of a cell, returns the possible coordinates of the cell and its neighbors.
-/// Will return out-of-bounds coordinates for cells on the edge of the region.
-fn neighborhood_coordinates(cell: CellCoordinates) -> [CellCoordinates; 5] {
-    let (x, y) = cell;
-    [(x, y), (x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+/// Returns the `(coordinates, altitude)` pairs for the cell at the given coordinates
+/// and its neighbors.
+fn neighborhood(
+    region: &Region,
+    cell: CellCoordinates,
+) -> impl Iterator<Item = (CellCoordinates, Altitude)> + '_ {
+    region
+        .orthogonal_neighbors(cell)
+        .chain([(cell, &region[cell])])
+        .map(|(coordinates, &altitude)| (coordinates, altitude))
 }
 
 /// Returns the coordinates of the cell of lowest altitude
@@ -181,6 +445,7 @@ mod tests {
     use crate::rainfall::*;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
+    use std::collections::{HashMap, HashSet};
 
     #[rstest]
     #[case(0, 0)]
@@ -192,6 +457,38 @@ mod tests {
         let placeholder_basin = Basin { sink: (0, 0) };
         let expected = Ok(Grid::filled(placeholder_basin, dimensions));
         assert_eq!(actual, expected);
+        assert_eq!(
+            identify_basins_parallel(&Grid::filled(0, dimensions)),
+            expected
+        );
+        for strip_count in 1..=4 {
+            let actual = identify_basins_in_strips(&Grid::filled(0, dimensions), strip_count);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    /// The region from the problem description.
+    #[rustfmt::skip]
+    fn region() -> Region {
+        Grid::from_2d_array([
+            [3, 1, 4, 2, 5, 9],
+            [2, 6, 5, 3, 5, 8],
+            [9, 7, 9, 3, 1, 3],
+        ])
+    }
+
+    /// Asserts that identifying the basins in strips gives the same result as `identify_basins`
+    /// for various numbers of strips.
+    fn assert_strips_match(region: &Region) {
+        let expected = identify_basins(region);
+        assert_eq!(identify_basins_parallel(region), expected);
+        for strip_count in 1..=region.height() + 1 {
+            assert_eq!(
+                identify_basins_in_strips(region, strip_count),
+                expected,
+                "{strip_count} strips"
+            );
+        }
     }
 
     /// Test case macro for Rainfall.
@@ -230,6 +527,11 @@ mod tests {
                 let actual = identify_basins(&region);
                 let expected = Ok(Grid::from_2d_array($basins));
                 assert_eq!(actual, expected);
+                let (_, sinks) = identify_basins_with_sinks(&region).unwrap();
+                let expected_sinks = [$($sink),*];
+                assert_eq!(sinks.len(), expected_sinks.len());
+                assert_eq!(HashSet::from_iter(sinks), HashSet::from(expected_sinks));
+                assert_strips_match(&region);
             }
         };
         ($name:ident: $region:expr => err) => {
@@ -237,11 +539,13 @@ mod tests {
             fn $name() {
                 let region = Grid::from_2d_array($region);
                 let cell = identify_basins(&region).unwrap_err();
-                let lowest_altitudes = neighborhood_coordinates(cell)
-                    .into_iter()
-                    .filter_map(|neighbor_cell| region.get(neighbor_cell))
+                let lowest_altitudes = region
+                    .orthogonal_neighbors(cell)
+                    .map(|(_, altitude)| altitude)
+                    .chain([&region[cell]])
                     .min_set();
                 assert!(lowest_altitudes.len() > 1, "no violation at {cell:?}");
+                assert_strips_match(&region);
             }
         };
     }
@@ -370,4 +674,255 @@ mod tests {
         [0, 0, 0],
         [0, 0, 0],
     ] => err}
+
+    #[rstest]
+    #[case::problem_description_example(
+        region(),
+        vec![(1, 0), (3, 0), (0, 1), (4, 2)],
+    )]
+    #[case::single(Grid::from_2d_array([[0]]), vec![(0, 0)])]
+    // The sink at the bottom left is found first, from the cell at the top left.
+    #[case::first_cells(Grid::from_2d_array([[2, 3, 0], [1, 4, 5]]), vec![(0, 1), (2, 0)])]
+    #[case::empty(Grid::filled(0, (3, 0)), vec![])]
+    fn test_sinks(#[case] region: Region, #[case] expected: Vec<CellCoordinates>) {
+        let (basins, sinks) = identify_basins_with_sinks(&region).unwrap();
+        assert_eq!(sinks, expected);
+        assert_eq!(Ok(basins), identify_basins(&region));
+    }
+
+    #[test]
+    fn test_permissive_all_equal() {
+        // Rain flows up and then left, all the way to the top left corner.
+        let region = Grid::filled(0, (3, 3));
+        let basins = identify_basins_permissive(&region, TOPMOST_LEFTMOST_TIEBREAKER);
+        assert_eq!(basins, Grid::filled(Basin { sink: (0, 0) }, (3, 3)));
+        assert_eq!(
+            identify_basins_permissive(&region, TOPMOST_LEFTMOST_TIEBREAKER),
+            basins
+        );
+    }
+
+    #[rstest]
+    #[case::problem_description_example(region())]
+    #[case::corner_sinks(Grid::from_2d_array([
+        [0, 1, 1, 0],
+        [2, 3, 2, 3],
+        [1, 2, 3, 2],
+        [0, 3, 1, 0],
+    ]))]
+    #[case::empty(Grid::filled(0, (0, 2)))]
+    fn test_permissive_valid(#[case] region: Region) {
+        // The tiebreaker isn't needed for valid regions.
+        let basins = identify_basins_permissive(&region, |cells| panic!("tie at {cells:?}"));
+        assert_eq!(Ok(basins), identify_basins(&region));
+    }
+
+    #[test]
+    fn test_permissive_ties() {
+        // Rain on the 5 can flow either way,
+        // and rain on the last 1 can either collect there or flow left.
+        let region = Grid::from_2d_array([[0, 5, 0, 1, 1]]);
+        let a = Basin { sink: (0, 0) };
+        let b = Basin { sink: (2, 0) };
+        let c = Basin { sink: (4, 0) };
+        let leftmost = identify_basins_permissive(&region, TOPMOST_LEFTMOST_TIEBREAKER);
+        assert_eq!(leftmost, Grid::from_2d_array([[a, a, b, b, b]]));
+        let rightmost =
+            identify_basins_permissive(&region, |cells: &[CellCoordinates]| cells[cells.len() - 1]);
+        assert_eq!(rightmost, Grid::from_2d_array([[a, b, b, b, c]]));
+    }
+
+    #[test]
+    #[should_panic(expected = "the tiebreaker made rain flow in a cycle")]
+    fn test_permissive_cycle() {
+        // Each tiebreaker call gets the three corners of the square other than the one opposite
+        // the current cell, and picks the next corner clockwise from the current cell,
+        // so rain keeps going around the square.
+        let corners = [(0, 0), (1, 0), (1, 1), (0, 1)];
+        let clockwise = |cells: &[CellCoordinates]| {
+            let missing = corners.iter().position(|corner| !cells.contains(corner));
+            corners[(missing.unwrap() + 3) % 4]
+        };
+        identify_basins_permissive(&Grid::filled(0, (2, 2)), clockwise);
+    }
+
+    #[test]
+    #[should_panic(expected = "the tiebreaker chose (2, 0), which isn't one of [(0, 0), (1, 0)]")]
+    fn test_permissive_invalid_choice() {
+        identify_basins_permissive(&Grid::filled(0, (3, 1)), |_| (2, 0));
+    }
+
+    #[test]
+    fn test_basin_altitudes() {
+        // Every sink is the lowest cell in its basin.
+        let region = region();
+        let basins = identify_basins(&region).unwrap();
+        let mut lowest = HashMap::new();
+        for &(altitude, basin) in region.clone().zip(basins).as_flat_slice() {
+            let entry = lowest.entry(basin.sink).or_insert(altitude);
+            *entry = altitude.min(*entry);
+        }
+        let expected = HashMap::from([((1, 0), 1), ((3, 0), 2), ((0, 1), 2), ((4, 2), 1)]);
+        assert_eq!(lowest, expected);
+        for (sink, altitude) in lowest {
+            assert_eq!(region[sink], altitude);
+        }
+    }
+
+    #[test]
+    fn test_flow_direction_grid() {
+        let region = region();
+        let directions = flow_direction_grid(&region).unwrap();
+        let expected = Grid::from_2d_array([
+            [
+                Some((1, 0)),
+                None,
+                Some((1, 0)),
+                None,
+                Some((3, 0)),
+                Some((4, 0)),
+            ],
+            [
+                None,
+                Some((1, 0)),
+                Some((3, 1)),
+                Some((3, 0)),
+                Some((4, 2)),
+                Some((5, 2)),
+            ],
+            [
+                Some((0, 1)),
+                Some((1, 1)),
+                Some((3, 2)),
+                Some((4, 2)),
+                None,
+                Some((4, 2)),
+            ],
+        ]);
+        assert_eq!(directions, expected);
+        // Following the directions from a cell gives its drainage path.
+        for (cell, _) in region.enumerate::<CellCoordinates>() {
+            let mut path = vec![cell];
+            while let Some(next) = directions[path[path.len() - 1]] {
+                path.push(next);
+            }
+            assert_eq!(drainage_path(&region, cell), Ok(path));
+        }
+    }
+
+    #[rstest]
+    #[case::single(Grid::from_2d_array([[0]]))]
+    #[case::empty(Grid::filled(0, (0, 3)))]
+    fn test_flow_direction_grid_trivial(#[case] region: Region) {
+        let expected = Grid::filled(None, region.dimensions());
+        assert_eq!(flow_direction_grid(&region), Ok(expected));
+    }
+
+    #[test]
+    fn test_flow_direction_grid_invalid() {
+        // There are violations at every cell from (1, 1) onwards except (0, 2),
+        // and the one at (1, 1) comes first.
+        let region = Grid::from_2d_array([[1, 2, 3], [2, 3, 1], [3, 1, 1]]);
+        assert_eq!(flow_direction_grid(&region), Err((1, 1)));
+    }
+
+    #[rstest]
+    #[case::problem_description_example((2, 1), vec![(2, 1), (3, 1), (3, 0)])]
+    #[case::sink((4, 2), vec![(4, 2)])]
+    #[case::longer((5, 1), vec![(5, 1), (5, 2), (4, 2)])]
+    #[case::corner((0, 2), vec![(0, 2), (0, 1)])]
+    fn test_drainage_path(#[case] start: CellCoordinates, #[case] expected: Vec<CellCoordinates>) {
+        let region = region();
+        let path = drainage_path(&region, start).unwrap();
+        assert_eq!(path, expected);
+        let basins = identify_basins(&region).unwrap();
+        assert_eq!(basins[start].sink, path[path.len() - 1]);
+    }
+
+    #[test]
+    fn test_drainage_path_sinks() {
+        let region = Grid::from_2d_array([[0, 1, 1, 0], [2, 3, 2, 3], [1, 2, 3, 2], [0, 3, 1, 0]]);
+        let (_, sinks) = identify_basins_with_sinks(&region).unwrap();
+        for sink in sinks {
+            assert_eq!(drainage_path(&region, sink), Ok(vec![sink]));
+        }
+    }
+
+    #[test]
+    fn test_drainage_path_invalid() {
+        // Rain from the top left flows into the cell at (1, 1), which is as low as its right neighbor.
+        let region = Grid::from_2d_array([[4, 3, 2], [5, 1, 1], [6, 6, 6]]);
+        assert_eq!(drainage_path(&region, (0, 0)), Err((1, 1)));
+        assert_eq!(drainage_path(&region, (1, 1)), Err((1, 1)));
+        // A path that doesn't reach the violation still succeeds.
+        let region = Grid::from_2d_array([[0, 1, 2, 2]]);
+        assert_eq!(drainage_path(&region, (1, 0)), Ok(vec![(1, 0), (0, 0)]));
+        assert_eq!(drainage_path(&region, (3, 0)), Err((3, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_drainage_path_out_of_bounds() {
+        _ = drainage_path(&Grid::from_2d_array([[0, 1]]), (2, 0));
+    }
+
+    #[test]
+    fn test_basin_sizes() {
+        let region = region();
+        let sizes: HashMap<CellCoordinates, usize> =
+            basin_sizes(&identify_basins(&region).unwrap())
+                .into_iter()
+                .map(|(basin, size)| (basin.sink, size))
+                .collect();
+        let expected = HashMap::from([((1, 0), 5), ((3, 0), 5), ((0, 1), 2), ((4, 2), 6)]);
+        assert_eq!(sizes, expected);
+    }
+
+    #[rstest]
+    #[case::single(Grid::from_2d_array([[0]]))]
+    #[case::single_sink(Grid::from_2d_array([[0, 1, 3], [2, 4, 5]]))]
+    fn test_basin_sizes_single_sink(#[case] region: Region) {
+        let basins = identify_basins(&region).unwrap();
+        let cell_count = region.width() * region.height();
+        assert_eq!(
+            basin_sizes(&basins),
+            HashMap::from([(Basin { sink: (0, 0) }, cell_count)])
+        );
+    }
+
+    #[test]
+    fn test_basin_sizes_empty() {
+        let basins = identify_basins(&Grid::filled(0, (0, 2))).unwrap();
+        assert_eq!(basin_sizes(&basins), HashMap::new());
+    }
+
+    #[test]
+    fn test_parallel_large_region() {
+        // Bowls around a few centers, with ties between neighbors broken by the position of each
+        // cell modulo 3, which is different for every cell in a neighborhood.
+        let centers: [(i32, i32); 4] = [(120, 80), (400, 150), (250, 300), (60, 450)];
+        let mut region = Grid::filled(0, (500, 500));
+        for (x, y) in (0..500).cartesian_product(0..500) {
+            let distance = centers
+                .iter()
+                .map(|&(cx, cy)| (x - cx).pow(2) + (y - cy).pow(2))
+                .min()
+                .unwrap();
+            region[(x, y)] = 9 * distance + x % 3 + 3 * (y % 3);
+        }
+        let expected = identify_basins(&region);
+        let sinks: HashSet<_> = expected
+            .as_ref()
+            .unwrap()
+            .enumerate::<CellCoordinates>()
+            .map(|(_, basin)| basin.sink)
+            .collect();
+        assert_eq!(sinks.len(), centers.len());
+        // Timing on multicore hardware isn't reliable enough to assert on,
+        // so only the results are compared here.
+        assert_eq!(identify_basins_parallel(&region), expected);
+        for strip_count in [2, 7, 64] {
+            assert_eq!(identify_basins_in_strips(&region, strip_count), expected);
+        }
+    }
 }
@@ -0,0 +1,360 @@
+//! The Gray-Scott model is a reaction-diffusion system:
+//! two chemicals, U and V, diffuse across a surface while reacting with each other.
+//! V converts U into more V (`U + 2V -> 3V`), U is fed in at a constant rate,
+//! and V is removed at a constant rate.
+//! Depending on the feed and kill rates, this can produce spots, stripes,
+//! and other lifelike patterns, or simply let V die out.
+//!
+//! Problem: simulate the Gray-Scott model on a grid.
+//!
+//! With concentrations `u` and `v`, feed rate `F`, kill rate `k`,
+//! and diffusion rates `Du` and `Dv`, the model is:
+//! ```text
+//! du/dt = Du * ∇²u - u * v^2 + F * (1 - u)
+//! dv/dt = Dv * ∇²v + u * v^2 - (F + k) * v
+//! ```
+//! The simulation approximates this with discrete time steps (Euler's method),
+//! using the 5-point stencil to approximate the Laplacian `∇²`:
+//! ```text
+//!  0  1  0
+//!  1 -4  1
+//!  0  1  0
+//! ```
+//! Cells beyond the edges of the grid are handled according to a boundary condition:
+//! either the grid wraps around (periodic), or nothing flows across the edges (zero-flux).
+
+use crate::grid::Grid;
+
+/// How to treat cells beyond the edges of the grid when computing the Laplacian.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryCondition {
+    /// The grid wraps around, so the cell beyond one edge is the cell at the opposite edge.
+    Periodic,
+    /// Nothing flows across the edges, so the cell beyond an edge has the same value
+    /// as the cell at the edge.
+    ZeroFlux,
+}
+
+/// The concentrations of U and V in each cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrayScottState {
+    pub u: Grid<f64>,
+    pub v: Grid<f64>,
+}
+
+impl GrayScottState {
+    /// Creates a state with the given dimensions where `u = 1` and `v = 0` everywhere,
+    /// except for a square in the center, about a quarter of the grid across,
+    /// where `u = 0.5` and `v = 0.25`.
+    pub fn seeded(dimensions: (usize, usize)) -> GrayScottState {
+        let (width, height) = dimensions;
+        let mut u = Grid::filled(1.0, dimensions);
+        let mut v = Grid::filled(0.0, dimensions);
+        let size = (width.min(height) / 4).max(1);
+        let (left, top) = (
+            width.saturating_sub(size) / 2,
+            height.saturating_sub(size) / 2,
+        );
+        for y in top..(top + size).min(height) {
+            for x in left..(left + size).min(width) {
+                u[(x, y)] = 0.5;
+                v[(x, y)] = 0.25;
+            }
+        }
+        GrayScottState { u, v }
+    }
+}
+
+/// The parameters of a simulation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GrayScottParams {
+    /// The dimensions of the grid.
+    pub dimensions: (usize, usize),
+    /// The feed rate `F`.
+    pub feed: f64,
+    /// The kill rate `k`.
+    pub kill: f64,
+    /// The diffusion rate of U.
+    pub du: f64,
+    /// The diffusion rate of V.
+    pub dv: f64,
+    /// The length of each time step.
+    pub dt: f64,
+    /// How to treat cells beyond the edges of the grid.
+    pub boundary: BoundaryCondition,
+}
+
+/// Returns the discrete Laplacian of the given grid, using the 5-point stencil.
+pub fn laplacian(grid: &Grid<f64>, boundary: BoundaryCondition) -> Grid<f64> {
+    let (width, height) = (grid.width() as i32, grid.height() as i32);
+    let mut result = Grid::filled(0.0, grid.dimensions());
+    for ((x, y), &center) in grid.enumerate::<(i32, i32)>() {
+        let neighbor = |nx: i32, ny: i32| match grid.get((nx, ny)) {
+            Some(&value) => value,
+            None => match boundary {
+                BoundaryCondition::Periodic => grid[(nx.rem_euclid(width), ny.rem_euclid(height))],
+                BoundaryCondition::ZeroFlux => center,
+            },
+        };
+        result[(x, y)] =
+            neighbor(x - 1, y) + neighbor(x + 1, y) + neighbor(x, y - 1) + neighbor(x, y + 1)
+                - 4.0 * center;
+    }
+    result
+    /*
+        Time complexity analysis:
+        This function completes in `O(c)` time in the worst case,
+        where `c` is the number of cells in the grid.
+    */
+}
+
+/// Returns the state after one time step of the Gray-Scott model,
+/// with the given feed rate, kill rate, diffusion rates, time step, and boundary condition.
+///
+/// Panics if `u` and `v` have different dimensions.
+pub fn step(
+    state: &GrayScottState,
+    feed: f64,
+    kill: f64,
+    du: f64,
+    dv: f64,
+    dt: f64,
+    boundary: BoundaryCondition,
+) -> GrayScottState {
+    let GrayScottState { u, v } = state;
+    assert_eq!(
+        u.dimensions(),
+        v.dimensions(),
+        "dimensions of u and v don't match"
+    );
+    let (laplacian_u, laplacian_v) = (laplacian(u, boundary), laplacian(v, boundary));
+    let mut next = state.clone();
+    for (index, &u) in u.enumerate::<(usize, usize)>() {
+        let v = state.v[index];
+        let reaction = u * v * v;
+        next.u[index] = u + dt * (du * laplacian_u[index] - reaction + feed * (1.0 - u));
+        next.v[index] = v + dt * (dv * laplacian_v[index] + reaction - (feed + kill) * v);
+    }
+    next
+    /*
+        Time complexity analysis:
+        This function completes in `O(c)` time in the worst case,
+        where `c` is the number of cells in the grid.
+    */
+}
+
+/// Returns the state after the given number of time steps with the given parameters,
+/// starting from a seeded state (see [`GrayScottState::seeded`]).
+pub fn run(steps: usize, params: GrayScottParams) -> GrayScottState {
+    let GrayScottParams {
+        dimensions,
+        feed,
+        kill,
+        du,
+        dv,
+        dt,
+        boundary,
+    } = params;
+    let mut state = GrayScottState::seeded(dimensions);
+    for _ in 0..steps {
+        state = step(&state, feed, kill, du, dv, dt, boundary);
+    }
+    state
+    /*
+        Time complexity analysis:
+        This function completes in `O(s * c)` time in the worst case,
+        where `s` is the number of steps and `c` is the number of cells in the grid.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reaction_diffusion::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Returns the sum of the entries of the given grid.
+    fn sum(grid: &Grid<f64>) -> f64 {
+        grid.enumerate::<(usize, usize)>().map(|(_, &x)| x).sum()
+    }
+
+    /// Returns the largest absolute difference between an entry of the grid and `value`.
+    fn max_difference(grid: &Grid<f64>, value: f64) -> f64 {
+        grid.enumerate::<(usize, usize)>()
+            .map(|(_, &x)| (x - value).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[rstest]
+    #[case::empty_0_0((0, 0))]
+    #[case::empty_3_0((3, 0))]
+    #[case::empty_0_3((0, 3))]
+    fn test_empty(
+        #[case] dimensions: (usize, usize),
+        #[values(BoundaryCondition::Periodic, BoundaryCondition::ZeroFlux)]
+        boundary: BoundaryCondition,
+    ) {
+        let state = GrayScottState::seeded(dimensions);
+        assert_eq!(state.u, Grid::filled(1.0, dimensions));
+        assert_eq!(step(&state, 0.037, 0.06, 0.2, 0.1, 1.0, boundary), state);
+    }
+
+    #[test]
+    fn test_seeded() {
+        let state = GrayScottState::seeded((12, 8));
+        for ((x, y), &u) in state.u.enumerate::<(usize, usize)>() {
+            let is_seed = (5..7).contains(&x) && (3..5).contains(&y);
+            assert_eq!(u, if is_seed { 0.5 } else { 1.0 });
+            assert_eq!(state.v[(x, y)], if is_seed { 0.25 } else { 0.0 });
+        }
+    }
+
+    #[rstest]
+    #[case::periodic(BoundaryCondition::Periodic)]
+    #[case::zero_flux(BoundaryCondition::ZeroFlux)]
+    fn test_laplacian_center(#[case] boundary: BoundaryCondition) {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0],
+        ]);
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [0.0,  1.0, 0.0],
+            [1.0, -4.0, 1.0],
+            [0.0,  1.0, 0.0],
+        ]);
+        assert_eq!(laplacian(&grid, boundary), expected);
+    }
+
+    #[rstest]
+    // The neighbors beyond the edges are on the opposite edges.
+    #[case::periodic(BoundaryCondition::Periodic, Grid::from_2d_array([
+        [-4.0, 1.0, 1.0],
+        [ 1.0, 0.0, 0.0],
+        [ 1.0, 0.0, 0.0],
+    ]))]
+    // The neighbors beyond the edges have the same value as the corner itself.
+    #[case::zero_flux(BoundaryCondition::ZeroFlux, Grid::from_2d_array([
+        [-2.0, 1.0, 0.0],
+        [ 1.0, 0.0, 0.0],
+        [ 0.0, 0.0, 0.0],
+    ]))]
+    fn test_laplacian_corner(#[case] boundary: BoundaryCondition, #[case] expected: Grid<f64>) {
+        #[rustfmt::skip]
+        let grid = Grid::from_2d_array([
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0],
+        ]);
+        assert_eq!(laplacian(&grid, boundary), expected);
+    }
+
+    #[rstest]
+    #[case::periodic(BoundaryCondition::Periodic)]
+    #[case::zero_flux(BoundaryCondition::ZeroFlux)]
+    fn test_laplacian_linear(#[case] boundary: BoundaryCondition) {
+        // The Laplacian of a linear function is 0, except where the boundary condition interferes.
+        let grid = Grid::from_2d_array([[1.0, 2.0, 3.0, 4.0]]);
+        let expected = match boundary {
+            BoundaryCondition::Periodic => Grid::from_2d_array([[4.0, 0.0, 0.0, -4.0]]),
+            BoundaryCondition::ZeroFlux => Grid::from_2d_array([[1.0, 0.0, 0.0, -1.0]]),
+        };
+        assert_eq!(laplacian(&grid, boundary), expected);
+    }
+
+    #[test]
+    fn test_step_uniform() {
+        // Without any differences between cells, there's no diffusion.
+        let state = GrayScottState {
+            u: Grid::filled(0.5, (3, 2)),
+            v: Grid::filled(0.5, (3, 2)),
+        };
+        let next = step(&state, 0.1, 0.2, 1.0, 1.0, 0.5, BoundaryCondition::Periodic);
+        // u: 0.5 + 0.5 * (-0.125 + 0.05); v: 0.5 + 0.5 * (0.125 - 0.15)
+        assert!(max_difference(&next.u, 0.4625) < 1e-12);
+        assert!(max_difference(&next.v, 0.4875) < 1e-12);
+    }
+
+    #[rstest]
+    #[case::periodic(BoundaryCondition::Periodic)]
+    #[case::zero_flux(BoundaryCondition::ZeroFlux)]
+    fn test_mass_conservation(#[case] boundary: BoundaryCondition) {
+        // With no feeding or killing, the reaction only converts U into V,
+        // and diffusion only moves chemicals around, so the total amount stays the same.
+        let params = GrayScottParams {
+            dimensions: (12, 10),
+            feed: 0.0,
+            kill: 0.0,
+            du: 0.2,
+            dv: 0.1,
+            dt: 1.0,
+            boundary,
+        };
+        let initial = GrayScottState::seeded(params.dimensions);
+        let initial_total = sum(&initial.u) + sum(&initial.v);
+        for steps in [1, 10, 100, 500] {
+            let state = run(steps, params);
+            let total = sum(&state.u) + sum(&state.v);
+            assert!(
+                (total - initial_total).abs() < 1e-9,
+                "{total} != {initial_total}"
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::periodic(BoundaryCondition::Periodic)]
+    #[case::zero_flux(BoundaryCondition::ZeroFlux)]
+    fn test_steady_state(#[case] boundary: BoundaryCondition) {
+        // With these rates, a small seed of V dies out,
+        // leaving the trivial steady state where `u = 1` and `v = 0` everywhere.
+        let params = GrayScottParams {
+            dimensions: (8, 8),
+            feed: 0.037,
+            kill: 0.06,
+            du: 0.2,
+            dv: 0.1,
+            dt: 1.0,
+            boundary,
+        };
+        let state = run(1000, params);
+        assert!(max_difference(&state.u, 1.0) < 1e-9);
+        assert!(max_difference(&state.v, 0.0) < 1e-9);
+    }
+
+    #[test]
+    fn test_pattern_persists() {
+        // With a large enough seed, V doesn't die out.
+        let params = GrayScottParams {
+            dimensions: (32, 32),
+            feed: 0.037,
+            kill: 0.06,
+            du: 0.2,
+            dv: 0.1,
+            dt: 1.0,
+            boundary: BoundaryCondition::Periodic,
+        };
+        let state = run(2000, params);
+        assert!(max_difference(&state.v, 0.0) > 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions of u and v don't match")]
+    fn test_step_mismatched_dimensions() {
+        let state = GrayScottState {
+            u: Grid::filled(1.0, (3, 2)),
+            v: Grid::filled(0.0, (2, 3)),
+        };
+        step(
+            &state,
+            0.037,
+            0.06,
+            0.2,
+            0.1,
+            1.0,
+            BoundaryCondition::Periodic,
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Ripple Effect is a number puzzle played on a rectangular grid divided into rooms,
+//! with some cells given.
+//!
+//! Problem: fill each room of `n` cells with the numbers from 1 to `n`, each used exactly once,
+//! such that every given cell keeps its number,
+//! and whenever two cells in the same row or column contain the same number `k`,
+//! there are at least `k` cells between them.
+//!
+//! For example, in the following puzzle (rooms on the left, given cells on the right,
+//! with `.` for empty cells):
+//! ```text
+//! 0 0 0 1 2    . . . . .
+//! 0 3 1 1 2    . . . 1 .
+//! 3 3 1 1 2    . . . . .
+//! 4 4 4 5 6    . 2 . . .
+//! 4 4 7 6 6    4 . . . .
+//! ```
+//! the unique solution is:
+//! ```text
+//! 1 2 4 3 2
+//! 3 1 2 1 3
+//! 2 3 5 4 1
+//! 1 2 3 1 2
+//! 4 5 1 3 1
+//! ```
+//!
+//! The solver is the one from [`crate::candidate_numbers`], shared with Suguru,
+//! where each pair of constrained cells forbids a set of numbers from appearing in both cells
+//! (every number for cells in the same room,
+//! and every number at least as large as their distance for other cells in the same row or column).
+
+use crate::candidate_numbers::{self, Arc, Numbers};
+use crate::grid::{Grid, ORTHOGONAL_OFFSETS};
+use std::collections::HashMap;
+
+type CellIndex = (usize, usize);
+
+/// The largest number of cells a room can have.
+pub const MAX_ROOM_SIZE: usize = candidate_numbers::MAX_REGION_SIZE;
+
+/// A Ripple Effect puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RippleBoard {
+    grid: Grid<Option<u8>>,
+    rooms: Grid<usize>,
+}
+
+impl RippleBoard {
+    /// Creates a puzzle from its given cells and the ID of the room containing each cell.
+    ///
+    /// Returns Err(cell) if `cell` is in a room with more than [`MAX_ROOM_SIZE`] cells,
+    /// or is given a number that its room can't contain.
+    /// If there are multiple such cells, the first one in row-major order is returned.
+    ///
+    /// Panics if the dimensions of `grid` and `rooms` don't match.
+    pub fn new(grid: Grid<Option<u8>>, rooms: Grid<usize>) -> Result<RippleBoard, CellIndex> {
+        assert_eq!(
+            grid.dimensions(),
+            rooms.dimensions(),
+            "dimensions of grid and rooms don't match"
+        );
+        if let Some(cell) = candidate_numbers::first_invalid_cell(&grid, &rooms) {
+            return Err(cell);
+        }
+        Ok(RippleBoard { grid, rooms })
+    }
+
+    /// The given cells of the puzzle.
+    pub fn grid(&self) -> &Grid<Option<u8>> {
+        &self.grid
+    }
+
+    /// The ID of the room containing each cell.
+    pub fn rooms(&self) -> &Grid<usize> {
+        &self.rooms
+    }
+}
+
+/// Returns a solution to the given puzzle, or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &RippleBoard) -> Option<Grid<u8>> {
+    let RippleBoard { grid, rooms } = board;
+    candidate_numbers::solve_regions(grid, rooms, &arcs(rooms))
+    /*
+        Time complexity analysis:
+        Finding a solution can take exponential time in the number of cells in the worst case.
+        Let `c` be the number of cells and `r` be the size of the largest room.
+        Each cell has `O(r)` arcs (up to `r` in each direction along its row and column,
+        plus the rest of its room), so there are `O(c * r)` arcs in total,
+        and building them takes `O(c^2)` time.
+        By the analysis of `candidate_numbers::solve_candidates`
+        (with the rooms as the groups, so `g = c` and `n = r`),
+        each guess takes `O(c^2 * r^2)` time to propagate in the very worst case.
+    */
+}
+
+/// Returns the arcs starting from each cell of a puzzle with the given rooms,
+/// each with the numbers that its two cells can't both contain.
+fn arcs(rooms: &Grid<usize>) -> Grid<Vec<Arc<Numbers>>> {
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for (_, &id) in rooms.enumerate::<CellIndex>() {
+        *sizes.entry(id).or_default() += 1;
+    }
+    let max_size = sizes.values().copied().max().unwrap_or(0);
+    let all_numbers: Numbers = !1;
+    let mut result = Grid::filled(vec![], rooms.dimensions());
+    for (from, &id) in rooms.enumerate::<CellIndex>() {
+        let mut forbidden: HashMap<CellIndex, Numbers> = HashMap::new();
+        // Cells in the same row or column at distance `d` can't both contain a number `k >= d`.
+        // Cells further away than the size of the largest room can't conflict.
+        let (x, y) = (from.0 as i32, from.1 as i32);
+        for distance in 1..=max_size as i32 {
+            let numbers = all_numbers & !((1 << distance) - 1);
+            for (dx, dy) in ORTHOGONAL_OFFSETS {
+                let (other_x, other_y) = (x + dx * distance, y + dy * distance);
+                if rooms.get((other_x, other_y)).is_some() {
+                    forbidden.insert((other_x as usize, other_y as usize), numbers);
+                }
+            }
+        }
+        for (other, &other_id) in rooms.enumerate::<CellIndex>() {
+            if other_id == id && other != from {
+                forbidden.insert(other, all_numbers);
+            }
+        }
+        let mut arcs: Vec<Arc<Numbers>> = forbidden
+            .into_iter()
+            .map(|(to, numbers)| (from, to, numbers))
+            .collect();
+        arcs.sort();
+        result[from] = arcs;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ripple_effect::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates the given cells of a Ripple Effect puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// given![
+    ///     [<`.` for an empty cell, or a number> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! given {
+        (@cell .) => {None};
+        (@cell $number:literal) => {Some($number)};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(given!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &RippleBoard, solution: &Grid<u8>) {
+        assert_eq!(solution.dimensions(), board.grid().dimensions());
+        for (cell, &number) in board.grid().enumerate::<CellIndex>() {
+            if let Some(number) = number {
+                assert_eq!(solution[cell], number, "given number at {cell:?} changed");
+            }
+        }
+        let mut room_numbers: HashMap<usize, Vec<u8>> = HashMap::new();
+        for (cell, &id) in board.rooms().enumerate::<CellIndex>() {
+            room_numbers.entry(id).or_default().push(solution[cell]);
+        }
+        for (id, mut numbers) in room_numbers {
+            numbers.sort();
+            let expected: Vec<u8> = (1..=numbers.len() as u8).collect();
+            assert_eq!(
+                numbers, expected,
+                "room {id} doesn't contain each number once"
+            );
+        }
+        for ((x, y), &number) in solution.enumerate::<(i32, i32)>() {
+            for distance in 1..=i32::from(number) {
+                for other in [(x + distance, y), (x, y + distance)] {
+                    assert_ne!(
+                        solution.get(other),
+                        Some(&number),
+                        "{:?} and {other:?} contain the same number with too few cells between them",
+                        (x, y),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        #[rustfmt::skip]
+        let rooms = Grid::from_2d_array([
+            [0, 0, 0, 1, 2],
+            [0, 3, 1, 1, 2],
+            [3, 3, 1, 1, 2],
+            [4, 4, 4, 5, 6],
+            [4, 4, 7, 6, 6],
+        ]);
+        let given = given![
+            [. . . . .]
+            [. . . 1 .]
+            [. . . . .]
+            [. 2 . . .]
+            [4 . . . .]
+        ];
+        let board = RippleBoard::new(given, rooms).unwrap();
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 2, 4, 3, 2],
+            [3, 1, 2, 1, 3],
+            [2, 3, 5, 4, 1],
+            [1, 2, 3, 1, 2],
+            [4, 5, 1, 3, 1],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_5_by_5() {
+        #[rustfmt::skip]
+        let rooms = Grid::from_2d_array([
+            [0, 0, 1, 1, 2],
+            [0, 0, 1, 2, 2],
+            [3, 3, 1, 2, 2],
+            [4, 4, 1, 5, 5],
+            [6, 4, 4, 4, 5],
+        ]);
+        let given = given![
+            [1 2 . . .]
+            [. . . . 5]
+            [. . . . .]
+            [. . . . 1]
+            [. . . 5 .]
+        ];
+        let board = RippleBoard::new(given, rooms).unwrap();
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 2, 1, 4, 3],
+            [3, 4, 2, 1, 5],
+            [2, 1, 3, 2, 4],
+            [4, 2, 5, 3, 1],
+            [1, 3, 1, 5, 2],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::single_room(Grid::filled(0, (2, 2)), Grid::filled(None, (2, 2)))]
+    // The two 1s are far enough apart, with a cell between them.
+    #[case::one_row(Grid::from_2d_array([[0, 0, 1]]), given![[. 2 .]])]
+    #[case::no_givens(
+        Grid::from_2d_array([[0, 1, 1], [0, 2, 1], [0, 2, 2]]),
+        Grid::filled(None, (3, 3)),
+    )]
+    fn test_solve(#[case] rooms: Grid<usize>, #[case] given: Grid<Option<u8>>) {
+        let board = RippleBoard::new(given, rooms).unwrap();
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    // Two single-cell rooms next to each other would both contain 1.
+    #[case::adjacent_ones(Grid::from_2d_array([[0, 1]]), given![[. .]])]
+    // Two 2s in the same column need at least 2 cells between them, not 1.
+    #[case::column(
+        Grid::from_2d_array([[0], [0], [1], [1]]),
+        given![[2] [.] [2] [.]],
+    )]
+    // Whichever way the first room is filled, one of the second room's numbers is too close.
+    #[case::no_givens(Grid::from_2d_array([[0, 0, 1, 1]]), given![[. . . .]])]
+    fn test_unsolvable(#[case] rooms: Grid<usize>, #[case] given: Grid<Option<u8>>) {
+        let board = RippleBoard::new(given, rooms).unwrap();
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case::too_large(Grid::from_2d_array([[0, 0], [1, 1]]), given![[. .] [3 .]], (0, 1))]
+    #[case::zero(Grid::from_2d_array([[0, 1]]), given![[. 0]], (1, 0))]
+    #[case::first_invalid(Grid::from_2d_array([[0, 1]]), given![[2 2]], (0, 0))]
+    fn test_invalid_board(
+        #[case] rooms: Grid<usize>,
+        #[case] given: Grid<Option<u8>>,
+        #[case] expected: CellIndex,
+    ) {
+        assert_eq!(RippleBoard::new(given, rooms), Err(expected));
+    }
+
+    #[test]
+    fn test_room_too_large() {
+        let rooms = Grid::filled(0, (MAX_ROOM_SIZE + 1, 1));
+        let given = Grid::filled(None, (MAX_ROOM_SIZE + 1, 1));
+        assert_eq!(RippleBoard::new(given, rooms), Err((0, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions of grid and rooms don't match")]
+    fn test_mismatched_dimensions() {
+        let _ = RippleBoard::new(Grid::filled(None, (2, 3)), Grid::filled(0, (3, 2)));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = RippleBoard::new(
+            Grid::filled(None, (width, height)),
+            Grid::filled(0, (width, height)),
+        )
+        .unwrap();
+        assert_eq!(solve(&board), Some(Grid::filled(0, (width, height))));
+    }
+}
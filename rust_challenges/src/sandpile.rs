@@ -0,0 +1,278 @@
+//! The abelian sandpile model is a cellular automaton where each cell of a grid holds some grains of sand.
+//! A cell with at least 4 grains is unstable, and _fires_ (or topples):
+//! it loses 4 grains, and each of its orthogonal neighbors gains 1.
+//! This may make its neighbors unstable in turn,
+//! and the process repeats until every cell is stable.
+//!
+//! Problem: simulate the sandpile, firing every unstable cell at once in each step.
+//!
+//! Grains sent beyond the edges of the grid are handled in one of two ways:
+//! either the boundary acts as a sink, and the grains are lost,
+//! or the grid wraps around (periodic), and the grains land on the opposite edge.
+//!
+//! For example, with a sink at the boundary, a single cell with 4 grains in the middle of a 3×3 grid
+//! fires once and stabilizes to a cross:
+//! ```text
+//! 0 0 0    0 1 0
+//! 0 4 0 -> 1 0 1
+//! 0 0 0    0 1 0
+//! ```
+//!
+//! With a sink, every grid eventually stabilizes, and the final result doesn't depend
+//! on the order in which cells fire (hence "abelian").
+//! Adding two stable grids cell by cell and stabilizing the result makes the _recurrent_ grids
+//! into a group, whose identity element has a surprisingly intricate pattern on larger grids.
+
+use crate::grid::{Grid, ORTHOGONAL_OFFSETS};
+
+/// The number of grains at which a cell fires,
+/// which is also the number of orthogonal neighbors of each cell.
+const THRESHOLD: u32 = 4;
+
+/// The number of grains of sand in each cell of a grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SandpileGrid(pub Grid<u32>);
+
+impl SandpileGrid {
+    /// Returns whether every cell has fewer than 4 grains.
+    pub fn is_stable(&self) -> bool {
+        let SandpileGrid(grid) = self;
+        grid.enumerate::<(usize, usize)>()
+            .all(|(_, &grains)| grains < THRESHOLD)
+    }
+
+    /// The total number of grains of sand in the grid.
+    pub fn total_grains(&self) -> u64 {
+        let SandpileGrid(grid) = self;
+        grid.enumerate::<(usize, usize)>()
+            .map(|(_, &grains)| u64::from(grains))
+            .sum()
+    }
+}
+
+/// Returns the grid after firing every cell with at least 4 grains at once,
+/// along with whether any cells fired.
+///
+/// If `boundary_sink` is true, grains sent beyond the edges of the grid are lost;
+/// otherwise, the grid wraps around.
+pub fn fire_step(grid: &SandpileGrid, boundary_sink: bool) -> (SandpileGrid, bool) {
+    let SandpileGrid(grid) = grid;
+    let (width, height) = (grid.width() as i32, grid.height() as i32);
+    let mut result = grid.clone();
+    let mut fired = false;
+    for ((x, y), &grains) in grid.enumerate::<(i32, i32)>() {
+        if grains < THRESHOLD {
+            continue;
+        }
+        fired = true;
+        result[(x, y)] -= THRESHOLD;
+        for (dx, dy) in ORTHOGONAL_OFFSETS {
+            let (neighbor_x, neighbor_y) = (x + dx, y + dy);
+            let neighbor = if boundary_sink {
+                (neighbor_x, neighbor_y)
+            } else {
+                (neighbor_x.rem_euclid(width), neighbor_y.rem_euclid(height))
+            };
+            if let Some(neighbor_grains) = result.get_mut(neighbor) {
+                *neighbor_grains += 1;
+            }
+        }
+    }
+    (SandpileGrid(result), fired)
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid.
+        Each cell is looked at once, and a firing cell updates 4 neighbors,
+        so this function completes in `O(c)` time.
+    */
+}
+
+/// Returns the grid after repeatedly firing cells until every cell is stable.
+///
+/// If `boundary_sink` is true, grains sent beyond the edges of the grid are lost;
+/// otherwise, the grid wraps around, the total number of grains never changes,
+/// and the grid might never stabilize.
+///
+/// Panics if `boundary_sink` is false and there are more than 3 grains per cell on average,
+/// since that many grains can't fit in a stable grid.
+/// With between 2 and 3 grains per cell on average, a periodic grid might still cycle forever,
+/// in which case this function never returns.
+pub fn stabilize(grid: SandpileGrid, boundary_sink: bool) -> SandpileGrid {
+    if !boundary_sink {
+        let SandpileGrid(cells) = &grid;
+        let max_stable_grains = u64::from(THRESHOLD - 1) * (cells.width() * cells.height()) as u64;
+        assert!(
+            grid.total_grains() <= max_stable_grains,
+            "{} grains can't fit in a stable grid with dimensions {:?}",
+            grid.total_grains(),
+            cells.dimensions()
+        );
+    }
+    let mut grid = grid;
+    loop {
+        let (next, fired) = fire_step(&grid, boundary_sink);
+        if !fired {
+            return next;
+        }
+        grid = next;
+    }
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid.
+        Each step takes `O(c)` time.
+        With a sink at the boundary, the number of steps is finite,
+        and is bounded by a polynomial in `c` and the total number of grains,
+        though it's much smaller than that bound in practice.
+        Without a sink, there's no bound in general, since the grid might never stabilize.
+    */
+}
+
+/// Returns the result of adding the grains in two grids cell by cell, then stabilizing,
+/// with a sink at the boundary.
+///
+/// Panics if the dimensions of the grids don't match.
+pub fn add(first: &SandpileGrid, second: &SandpileGrid) -> SandpileGrid {
+    let (SandpileGrid(first), SandpileGrid(second)) = (first, second);
+    assert_eq!(
+        first.dimensions(),
+        second.dimensions(),
+        "dimensions of grids don't match"
+    );
+    let mut sum = first.clone();
+    for (index, &grains) in second.enumerate::<(usize, usize)>() {
+        sum[index] += grains;
+    }
+    stabilize(SandpileGrid(sum), true)
+}
+
+/// Returns the identity element for adding recurrent grids with the given dimensions:
+/// the recurrent grid `e` such that `add(g, e) == g` for every recurrent grid `g`.
+///
+/// It's computed as `(6 - (6)°)°`, where `6` is the grid with 6 grains in every cell
+/// and `°` means stabilizing with a sink at the boundary.
+pub fn identity(dimensions: (usize, usize)) -> SandpileGrid {
+    let sixes = Grid::filled(2 * (THRESHOLD - 1), dimensions);
+    let SandpileGrid(stabilized) = stabilize(SandpileGrid(sixes.clone()), true);
+    let mut difference = sixes;
+    for (index, &grains) in stabilized.enumerate::<(usize, usize)>() {
+        difference[index] -= grains;
+    }
+    stabilize(SandpileGrid(difference), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sandpile::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_problem_description_example() {
+        let grid = SandpileGrid(Grid::from_2d_array([[0, 0, 0], [0, 4, 0], [0, 0, 0]]));
+        let expected = SandpileGrid(Grid::from_2d_array([[0, 1, 0], [1, 0, 1], [0, 1, 0]]));
+        let (fired, any_fired) = fire_step(&grid, true);
+        assert_eq!((&fired, any_fired), (&expected, true));
+        assert_eq!(fire_step(&fired, true), (expected.clone(), false));
+        assert_eq!(stabilize(grid, true), expected);
+    }
+
+    #[test]
+    fn test_fire_step_simultaneous() {
+        // Both cells fire at once, each sending a grain to the other.
+        let grid = SandpileGrid(Grid::from_2d_array([[4, 5]]));
+        let (fired, any_fired) = fire_step(&grid, true);
+        assert!(any_fired);
+        assert_eq!(fired, SandpileGrid(Grid::from_2d_array([[1, 2]])));
+    }
+
+    #[test]
+    fn test_stabilize_corner() {
+        let grid = SandpileGrid(Grid::from_2d_array([[0, 0, 0], [0, 0, 0], [0, 0, 16]]));
+        let expected = SandpileGrid(Grid::from_2d_array([[0, 0, 1], [0, 2, 0], [1, 0, 2]]));
+        let stabilized = stabilize(grid, true);
+        assert!(stabilized.is_stable());
+        assert_eq!(stabilized, expected);
+    }
+
+    #[rstest]
+    #[case::three_by_three((3, 3), Grid::from_2d_array([[2, 1, 2], [1, 0, 1], [2, 1, 2]]))]
+    #[case::two_by_two((2, 2), Grid::filled(2, (2, 2)))]
+    #[case::four_by_three(
+        (4, 3),
+        Grid::from_2d_array([[0, 2, 2, 0], [3, 3, 3, 3], [0, 2, 2, 0]]),
+    )]
+    fn test_identity(#[case] dimensions: (usize, usize), #[case] expected: Grid<u32>) {
+        let identity = identity(dimensions);
+        assert_eq!(identity, SandpileGrid(expected));
+        // Adding the identity to itself, or to the maximal stable grid, changes nothing.
+        assert_eq!(add(&identity, &identity), identity);
+        let threes = SandpileGrid(Grid::filled(3, dimensions));
+        assert_eq!(add(&threes, &identity), threes);
+    }
+
+    #[test]
+    fn test_boundary_vs_periodic() {
+        let grid = SandpileGrid(Grid::from_2d_array([[4, 0, 0], [0, 0, 0]]));
+        // With a sink, the two grains sent beyond the edges are lost.
+        let (with_sink, _) = fire_step(&grid, true);
+        assert_eq!(
+            with_sink,
+            SandpileGrid(Grid::from_2d_array([[0, 1, 0], [1, 0, 0]]))
+        );
+        assert_eq!(with_sink.total_grains(), 2);
+        // Otherwise, the grain sent left lands on the right edge,
+        // and the one sent up wraps around to the bottom row, on top of the one sent down.
+        let (periodic, _) = fire_step(&grid, false);
+        assert_eq!(
+            periodic,
+            SandpileGrid(Grid::from_2d_array([[0, 1, 1], [2, 0, 0]]))
+        );
+        assert_eq!(periodic.total_grains(), 4);
+    }
+
+    #[test]
+    fn test_stabilize_periodic() {
+        let grid = SandpileGrid(Grid::from_2d_array([[8, 0, 0], [0, 0, 0], [0, 0, 0]]));
+        let stabilized = stabilize(grid.clone(), false);
+        assert!(stabilized.is_stable());
+        assert_eq!(stabilized.total_grains(), 8);
+        assert_eq!(
+            stabilized,
+            SandpileGrid(Grid::from_2d_array([[0, 2, 2], [2, 0, 0], [2, 0, 0]]))
+        );
+        // With a sink, half of the grains are lost along the way.
+        assert_eq!(
+            stabilize(grid, true),
+            SandpileGrid(Grid::from_2d_array([[0, 2, 0], [2, 0, 0], [0, 0, 0]]))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "37 grains can't fit in a stable grid with dimensions (3, 4)")]
+    fn test_stabilize_periodic_too_many_grains() {
+        let mut grid = Grid::filled(3, (3, 4));
+        grid[(0, 0)] = 4;
+        stabilize(SandpileGrid(grid), false);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions of grids don't match")]
+    fn test_add_mismatched_dimensions() {
+        add(
+            &SandpileGrid(Grid::filled(0, (2, 3))),
+            &SandpileGrid(Grid::filled(0, (3, 2))),
+        );
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_grids(#[case] width: usize, #[case] height: usize) {
+        let grid = SandpileGrid(Grid::filled(0, (width, height)));
+        assert_eq!(fire_step(&grid, true), (grid.clone(), false));
+        assert_eq!(fire_step(&grid, false), (grid.clone(), false));
+        assert_eq!(stabilize(grid.clone(), false), grid);
+        assert_eq!(identity((width, height)), grid);
+    }
+}
@@ -0,0 +1,541 @@
+//! Slitherlink is a puzzle played on a rectangular grid of cells, some of which contain numbers.
+//!
+//! Problem: draw a single closed loop along the edges of the cells,
+//! such that each numbered cell has exactly that many of its four edges in the loop.
+//! The loop can't cross or touch itself,
+//! so each corner of a cell must have either 0 or 2 of its edges in the loop.
+//!
+//! For example, the following puzzle (`.` for cells without numbers):
+//! ```text
+//! . 2 3 .
+//! 1 . . 1
+//! . . . 0
+//! 3 2 . .
+//! ```
+//! has the unique solution:
+//! ```text
+//! +   +   +---+   +
+//!       2 | 3 |
+//! +   +---+   +   +
+//!   1 |       | 1
+//! +   +   +---+   +
+//!     |   |     0
+//! +---+   +   +   +
+//! | 3   2 |
+//! +---+---+   +   +
+//! ```
+//!
+//! The solver keeps track of which edges are definitely in or out of the loop.
+//! It starts from some patterns that must hold at the corners of the grid,
+//! then repeatedly deduces more edges from the numbers, from the corners of the cells,
+//! and from the fact that the loop can't close early.
+//! When no more edges can be deduced, it guesses an edge (preferring one that extends the loop)
+//! and backtracks if that leads to a contradiction.
+
+use crate::grid::Grid;
+use crate::union_find::UnionFind;
+use std::collections::{HashMap, HashSet};
+
+type CellIndex = (usize, usize);
+
+/// An edge between two adjacent vertices, where vertex `(x, y)` is the top left corner of cell `(x, y)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Edge {
+    /// The edge from `(x, y)` to `(x + 1, y)`.
+    Horizontal(CellIndex),
+    /// The edge from `(x, y)` to `(x, y + 1)`.
+    Vertical(CellIndex),
+}
+
+impl Edge {
+    /// Returns the two vertices that this edge joins.
+    pub fn endpoints(self) -> [CellIndex; 2] {
+        match self {
+            Self::Horizontal((x, y)) => [(x, y), (x + 1, y)],
+            Self::Vertical((x, y)) => [(x, y), (x, y + 1)],
+        }
+    }
+
+    /// Returns the four edges of the given cell.
+    pub fn of_cell(cell: CellIndex) -> [Edge; 4] {
+        let (x, y) = cell;
+        [
+            Self::Horizontal((x, y)),
+            Self::Vertical((x, y)),
+            Self::Vertical((x + 1, y)),
+            Self::Horizontal((x, y + 1)),
+        ]
+    }
+}
+
+/// The edges of a board, numbered so that the state of each edge can be stored in a `Vec`.
+#[derive(Debug)]
+struct Layout {
+    /// All the edges of the board.
+    edges: Vec<Edge>,
+    /// The indices of the edges touching each vertex.
+    vertex_edges: Grid<Vec<usize>>,
+    /// The indices of the edges of each cell.
+    cell_edges: Grid<[usize; 4]>,
+}
+
+impl Layout {
+    /// Numbers the edges of a board with the given dimensions.
+    fn new(dimensions: (usize, usize)) -> Self {
+        let (width, height) = dimensions;
+        let horizontal =
+            (0..=height).flat_map(|y| (0..width).map(move |x| Edge::Horizontal((x, y))));
+        let vertical = (0..height).flat_map(|y| (0..=width).map(move |x| Edge::Vertical((x, y))));
+        let edges: Vec<Edge> = horizontal.chain(vertical).collect();
+        let mut vertex_edges = Grid::filled(vec![], (width + 1, height + 1));
+        for (index, edge) in edges.iter().enumerate() {
+            for vertex in edge.endpoints() {
+                vertex_edges[vertex].push(index);
+            }
+        }
+        let index_of = |edge: Edge| match edge {
+            Edge::Horizontal((x, y)) => y * width + x,
+            Edge::Vertical((x, y)) => width * (height + 1) + y * (width + 1) + x,
+        };
+        let mut cell_edges = Grid::filled([0; 4], dimensions);
+        for y in 0..height {
+            for x in 0..width {
+                cell_edges[(x, y)] = Edge::of_cell((x, y)).map(index_of);
+            }
+        }
+        Self {
+            edges,
+            vertex_edges,
+            cell_edges,
+        }
+    }
+
+    /// Returns the edges that are in the loop in the given partial solution.
+    fn edges_in_loop(&self, state: &[Option<bool>]) -> HashSet<Edge> {
+        self.edges
+            .iter()
+            .zip(state)
+            .filter(|&(_, &in_loop)| in_loop == Some(true))
+            .map(|(&edge, _)| edge)
+            .collect()
+    }
+}
+
+/// Returns whether the given edges form a single closed loop
+/// that satisfies all the numbers in the given puzzle.
+pub fn is_solution(board: &Grid<Option<u8>>, edges: &HashSet<Edge>) -> bool {
+    let (width, height) = board.dimensions();
+    let in_bounds = |edge: &Edge| match *edge {
+        Edge::Horizontal((x, y)) => x < width && y <= height,
+        Edge::Vertical((x, y)) => x <= width && y < height,
+    };
+    let numbers_satisfied = board.enumerate::<CellIndex>().all(|(cell, number)| {
+        let num_edges = Edge::of_cell(cell)
+            .iter()
+            .filter(|edge| edges.contains(edge))
+            .count();
+        number.is_none_or(|number| num_edges == usize::from(number))
+    });
+    edges.iter().all(in_bounds) && numbers_satisfied && is_single_loop(edges)
+}
+
+/// Returns whether the given edges form a single closed loop.
+fn is_single_loop(edges: &HashSet<Edge>) -> bool {
+    let mut degrees = HashMap::new();
+    for edge in edges {
+        for vertex in edge.endpoints() {
+            *degrees.entry(vertex).or_insert(0) += 1;
+        }
+    }
+    if degrees.values().any(|&degree| degree != 2) {
+        return false;
+    }
+    let Some(&start) = edges.iter().next() else {
+        return false;
+    };
+    // Walk along the loop, and check that it passes through every edge.
+    let mut visited = HashSet::from([start]);
+    let mut vertex = start.endpoints()[1];
+    while let Some(&next) = edges
+        .iter()
+        .find(|edge| !visited.contains(*edge) && edge.endpoints().contains(&vertex))
+    {
+        visited.insert(next);
+        let [first, second] = next.endpoints();
+        vertex = if first == vertex { second } else { first };
+    }
+    visited.len() == edges.len()
+}
+
+/// Returns the edges of a single closed loop solving the given puzzle,
+/// or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &Grid<Option<u8>>) -> Option<HashSet<Edge>> {
+    let layout = Layout::new(board.dimensions());
+    let mut state = vec![None; layout.edges.len()];
+    apply_corner_patterns(&mut state, board, &layout);
+    let state = search(state, board, &layout)?;
+    Some(layout.edges_in_loop(&state))
+    /*
+        Time complexity analysis:
+        The puzzle is NP-complete in general,
+        and the search can take exponential time in the number of edges in the worst case.
+        Let `e` be the number of edges, which is proportional to the number of cells.
+        Each round of deductions takes `O(e)` time (with a near-constant factor for union-find),
+        and each round other than the last decides at least one edge,
+        so propagation takes `O(e^2)` time per guess.
+    */
+}
+
+/// Decides the edges at the corners of the grid for some numbers in the corner cells.
+///
+/// Each corner vertex of the grid only has two edges, both belonging to the corner cell.
+/// If the loop passes through the corner, the cell has both edges in the loop, otherwise neither.
+/// So a 1 can't have the loop passing through the corner, and a 3 must have it.
+fn apply_corner_patterns(state: &mut [Option<bool>], board: &Grid<Option<u8>>, layout: &Layout) {
+    let (width, height) = board.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let corners = [
+        ((0, 0), (0, 0)),
+        ((width - 1, 0), (width, 0)),
+        ((0, height - 1), (0, height)),
+        ((width - 1, height - 1), (width, height)),
+    ];
+    for (cell, vertex) in corners {
+        let in_loop = match board[cell] {
+            Some(1) => false,
+            Some(3) => true,
+            _ => continue,
+        };
+        for &index in &layout.vertex_edges[vertex] {
+            state[index] = Some(in_loop);
+        }
+    }
+}
+
+/// Tries to complete the given partial solution
+/// (where `Some(true)` is an edge in the loop, `Some(false)` is an edge not in the loop,
+/// and None is undecided).
+///
+/// Returns None if there is no solution.
+fn search(
+    mut state: Vec<Option<bool>>,
+    board: &Grid<Option<u8>>,
+    layout: &Layout,
+) -> Option<Vec<Option<bool>>> {
+    if !propagate(&mut state, board, layout) {
+        return None;
+    }
+    // Prefer extending the end of a path, since that's where the loop is most constrained.
+    let extension = layout
+        .vertex_edges
+        .enumerate::<CellIndex>()
+        .find_map(|(_, indices)| {
+            let num_in_loop = indices
+                .iter()
+                .filter(|&&index| state[index] == Some(true))
+                .count();
+            let mut undecided = indices
+                .iter()
+                .copied()
+                .filter(|&index| state[index].is_none());
+            undecided.next().filter(|_| num_in_loop == 1)
+        });
+    let Some(guess) = extension.or_else(|| state.iter().position(Option::is_none)) else {
+        return is_single_loop(&layout.edges_in_loop(&state)).then_some(state);
+    };
+    [true, false].into_iter().find_map(|in_loop| {
+        let mut state = state.clone();
+        state[guess] = Some(in_loop);
+        search(state, board, layout)
+    })
+}
+
+/// Sets all the undecided edges with the given indices to the given value.
+fn fill(state: &mut [Option<bool>], indices: &[usize], in_loop: bool, changed: &mut bool) {
+    for &index in indices {
+        if state[index].is_none() {
+            state[index] = Some(in_loop);
+            *changed = true;
+        }
+    }
+}
+
+/// Decides edges of the given partial solution until no more edges can be decided:
+/// - a numbered cell has all its undecided edges in the loop if they're needed to reach the number,
+///   and none of them if the number has been reached;
+/// - a vertex with two edges in the loop has no others, a vertex at the end of a path
+///   with only one way to continue must continue that way,
+///   and a vertex with no edges in the loop and only one undecided edge can't use that edge; and
+/// - an edge that would close a loop while leaving other edges out of it isn't in the loop.
+///
+/// Returns false if the partial solution is found to break some rule.
+fn propagate(state: &mut [Option<bool>], board: &Grid<Option<u8>>, layout: &Layout) -> bool {
+    let count = |state: &[Option<bool>], indices: &[usize]| {
+        let num_in_loop = indices
+            .iter()
+            .filter(|&&index| state[index] == Some(true))
+            .count();
+        let num_undecided = indices
+            .iter()
+            .filter(|&&index| state[index].is_none())
+            .count();
+        (num_in_loop, num_undecided)
+    };
+    let vertex_id = |(x, y): CellIndex| y * layout.vertex_edges.width() + x;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (cell, &number) in board.enumerate::<CellIndex>() {
+            let Some(number) = number else {
+                continue;
+            };
+            let number = usize::from(number);
+            let indices = &layout.cell_edges[cell];
+            let (num_in_loop, num_undecided) = count(state, indices);
+            if num_in_loop > number || num_in_loop + num_undecided < number {
+                return false;
+            }
+            if num_in_loop == number {
+                fill(state, indices, false, &mut changed);
+            } else if num_in_loop + num_undecided == number {
+                fill(state, indices, true, &mut changed);
+            }
+        }
+        for (_, indices) in layout.vertex_edges.enumerate::<CellIndex>() {
+            match count(state, indices) {
+                (3.., _) | (1, 0) => return false,
+                (2, _) | (0, 1) => fill(state, indices, false, &mut changed),
+                (1, 1) => fill(state, indices, true, &mut changed),
+                _ => {}
+            }
+        }
+        // Group the vertices into paths, and count the edges in each path.
+        let mut forest = UnionFind::new(layout.vertex_edges.width() * layout.vertex_edges.height());
+        let mut num_edges = vec![0; forest.len()];
+        for (&edge, _) in layout
+            .edges
+            .iter()
+            .zip(&*state)
+            .filter(|&(_, &in_loop)| in_loop == Some(true))
+        {
+            let [first, second] = edge.endpoints().map(vertex_id);
+            let (first, second) = (forest.find(first), forest.find(second));
+            if forest.union(first, second) {
+                num_edges[second] += num_edges[first];
+            }
+            num_edges[second] += 1;
+        }
+        let total_edges = state
+            .iter()
+            .filter(|&&in_loop| in_loop == Some(true))
+            .count();
+        for (index, &edge) in layout.edges.iter().enumerate() {
+            if state[index].is_some() {
+                continue;
+            }
+            let [first, second] = edge.endpoints().map(vertex_id);
+            let root = forest.find(first);
+            if root == forest.find(second) && num_edges[root] < total_edges {
+                state[index] = Some(false);
+                changed = true;
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slitherlink::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a Slitherlink puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// board![
+    ///     [<`.` for no number, or a digit> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! board {
+        (@cell .) => {None};
+        (@cell $number:literal) => {Some($number)};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(board!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Returns the edges around the given cells, which must form a region without holes.
+    fn boundary(cells: &[CellIndex]) -> HashSet<Edge> {
+        let mut edges = HashSet::new();
+        for &cell in cells {
+            for edge in Edge::of_cell(cell) {
+                // Edges shared by two cells in the region are inside the region.
+                if !edges.remove(&edge) {
+                    edges.insert(edge);
+                }
+            }
+        }
+        edges
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &Grid<Option<u8>>, edges: &HashSet<Edge>) {
+        let mut degrees = HashMap::new();
+        for edge in edges {
+            for vertex in edge.endpoints() {
+                *degrees.entry(vertex).or_insert(0) += 1;
+            }
+        }
+        for (vertex, degree) in degrees {
+            assert_eq!(
+                degree, 2,
+                "vertex {vertex:?} has {degree} edges in the loop"
+            );
+        }
+        assert!(is_single_loop(edges), "edges don't form a single loop");
+        for (cell, &number) in board.enumerate::<CellIndex>() {
+            if let Some(number) = number {
+                let num_edges = Edge::of_cell(cell)
+                    .iter()
+                    .filter(|edge| edges.contains(edge))
+                    .count();
+                assert_eq!(
+                    num_edges,
+                    usize::from(number),
+                    "wrong number of edges around {cell:?}"
+                );
+            }
+        }
+        assert!(is_solution(board, edges));
+    }
+
+    #[test]
+    fn test_boundary() {
+        let expected = HashSet::from([
+            Edge::Horizontal((0, 0)),
+            Edge::Horizontal((1, 0)),
+            Edge::Vertical((0, 0)),
+            Edge::Vertical((2, 0)),
+            Edge::Horizontal((0, 1)),
+            Edge::Horizontal((1, 1)),
+        ]);
+        assert_eq!(boundary(&[(0, 0), (1, 0)]), expected);
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let board = board![
+            [. 2 3 .]
+            [1 . . 1]
+            [. . . 0]
+            [3 2 . .]
+        ];
+        let expected = boundary(&[(2, 0), (1, 1), (2, 1), (1, 2), (0, 3), (1, 3)]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_4_by_4() {
+        let board = board![
+            [. . . .]
+            [0 2 . 3]
+            [. . 2 .]
+            [. 3 . 1]
+        ];
+        let expected = boundary(&[(1, 0), (2, 0), (2, 1), (3, 1), (2, 2), (1, 3), (2, 3)]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_5_by_4() {
+        let board = board![
+            [2 2 2 . 1]
+            [. . . . 0]
+            [. . 3 . .]
+            [3 . . 2 .]
+        ];
+        #[rustfmt::skip]
+        let expected = boundary(&[
+            (1, 0), (2, 0), (3, 0),
+            (0, 1), (1, 1),
+            (1, 2), (3, 2),
+            (0, 3), (1, 3), (2, 3), (3, 3),
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::no_numbers(board![
+        [. . .]
+        [. . .]
+    ])]
+    #[case::single_cell(board![[4]])]
+    #[case::corner_threes(board![
+        [3 . . 3]
+        [. . . .]
+        [3 . . 3]
+    ])]
+    fn test_solve(#[case] board: Grid<Option<u8>>) {
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    #[case::number_too_large(board![
+        [5 .]
+        [. .]
+    ])]
+    // A cell with 4 edges is a loop by itself, so the other 4 can't be part of it.
+    #[case::two_fours(board![[4 . 4]])]
+    // The 0 forces the other three edges of the 3 into the loop, and their ends can't be joined up.
+    #[case::three_next_to_zero(board![
+        [. 0 .]
+        [. 3 .]
+    ])]
+    #[case::all_zeros(board![
+        [0 0]
+        [0 0]
+    ])]
+    fn test_unsolvable(#[case] board: Grid<Option<u8>>) {
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case::no_edges(HashSet::new())]
+    #[case::two_loops(boundary(&[(0, 0), (2, 0)]))]
+    #[case::wrong_number(boundary(&[(1, 0)]))]
+    #[case::open_path(HashSet::from([Edge::Horizontal((0, 0)), Edge::Horizontal((1, 0))]))]
+    #[case::out_of_bounds(boundary(&[(1, 0), (1, 1)]))]
+    fn test_invalid_solution(#[case] edges: HashSet<Edge>) {
+        let board = board![[. 2 .]];
+        assert!(!is_solution(&board, &edges));
+    }
+
+    #[test]
+    fn test_valid_solution() {
+        let board = board![[. 2 .]];
+        assert!(is_solution(&board, &boundary(&[(0, 0), (1, 0), (2, 0)])));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        assert_eq!(solve(&Grid::filled(None, (width, height))), None);
+    }
+}
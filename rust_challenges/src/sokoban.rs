@@ -0,0 +1,419 @@
+//! Sokoban is a puzzle game about pushing boxes around a warehouse.
+//!
+//! The player walks orthogonally around a level made up of floor tiles and walls.
+//! Walking into a box pushes the box one tile further in the same direction,
+//! provided that the tile behind the box is neither a wall nor another box.
+//! Boxes can only be pushed, never pulled.
+//! The level is complete once every box is resting on a target tile.
+//!
+//! Problem: model Sokoban and find the shortest solution (in number of moves) to a level.
+//!
+//! For example, in the following level
+//! (`#` wall, `-` floor, `.` target, `$` box, `@` player):
+//! ```text
+//! # # # # #
+//! # @ - - #
+//! # - $ - #
+//! # - - . #
+//! # # # # #
+//! ```
+//! the box needs to be pushed both right and down,
+//! and one shortest solution is `Down, Right, Up, Right, Down`.
+
+use crate::grid::Grid;
+use std::collections::{
+    hash_map::{Entry, HashMap},
+    VecDeque,
+};
+
+/// A tile of a Sokoban level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SokobanTile {
+    /// Floor that the player and boxes can move across.
+    Floor,
+    /// An impassable wall.
+    Wall,
+    /// Floor that a box needs to be pushed onto.
+    Target,
+}
+
+/// Something occupying a tile, used in specifying the starting state of a level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Occupant {
+    Player,
+    Box,
+}
+
+pub type Position = (usize, usize);
+
+/// A direction in which the player can move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+pub const DIRECTIONS: [Direction; 4] = [
+    Direction::Left,
+    Direction::Right,
+    Direction::Up,
+    Direction::Down,
+];
+
+impl Direction {
+    /// Returns the position one tile away from the given position in this direction,
+    /// or None if that position would have a negative coordinate.
+    fn step_from(self, position: Position) -> Option<Position> {
+        let (x, y) = position;
+        match self {
+            Direction::Left => Some((x.checked_sub(1)?, y)),
+            Direction::Right => Some((x + 1, y)),
+            Direction::Up => Some((x, y.checked_sub(1)?)),
+            Direction::Down => Some((x, y + 1)),
+        }
+    }
+}
+
+/// The fixed terrain of a Sokoban level.
+pub struct SokobanBoard(pub Grid<SokobanTile>);
+
+impl SokobanBoard {
+    /// The tile at the given position.
+    ///
+    /// Out-of-bounds positions are treated as containing walls.
+    pub fn tile_at(&self, position: Position) -> SokobanTile {
+        let SokobanBoard(grid) = self;
+        grid.get(position).copied().unwrap_or(SokobanTile::Wall)
+    }
+
+    /// Returns whether the tile at the given position is a wall.
+    fn is_wall(&self, position: Option<Position>) -> bool {
+        position.is_none_or(|position| self.tile_at(position) == SokobanTile::Wall)
+    }
+}
+
+/// The positions of the player and the boxes in a Sokoban level.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SokobanState {
+    pub player: Position,
+    pub boxes: Vec<Position>,
+}
+
+impl SokobanState {
+    /// Returns the result of the player moving once in the given direction on the given board,
+    /// pushing a box if there is one in the way.
+    ///
+    /// Returns None if the move is illegal:
+    /// the player would walk into a wall, or push a box into a wall or another box.
+    pub fn make_move(&self, board: &SokobanBoard, direction: Direction) -> Option<SokobanState> {
+        let player = direction.step_from(self.player)?;
+        if board.is_wall(Some(player)) {
+            return None;
+        }
+        let mut boxes = self.boxes.clone();
+        if let Some(pushed) = boxes.iter_mut().find(|&&mut position| position == player) {
+            let destination = direction.step_from(player).filter(|&destination| {
+                !board.is_wall(Some(destination)) && !self.boxes.contains(&destination)
+            })?;
+            *pushed = destination;
+        }
+        Some(SokobanState { player, boxes })
+    }
+
+    /// Returns whether every box is on a target tile of the given board.
+    pub fn is_solved(&self, board: &SokobanBoard) -> bool {
+        self.boxes
+            .iter()
+            .all(|&position| board.tile_at(position) == SokobanTile::Target)
+    }
+
+    /// Returns an equivalent state with the boxes listed in sorted order,
+    /// so that states differing only in the order of their boxes compare equal.
+    fn normalized(mut self) -> SokobanState {
+        self.boxes.sort();
+        self
+    }
+
+    /// Returns whether any box is stuck in a corner that isn't a target --
+    /// such a box can never be moved again, so the level can no longer be completed.
+    fn has_cornered_box(&self, board: &SokobanBoard) -> bool {
+        use Direction::*;
+        let is_wall =
+            |position: Position, direction: Direction| board.is_wall(direction.step_from(position));
+        self.boxes.iter().any(|&position| {
+            board.tile_at(position) != SokobanTile::Target
+                && (is_wall(position, Left) || is_wall(position, Right))
+                && (is_wall(position, Up) || is_wall(position, Down))
+        })
+    }
+}
+
+/// Returns the shortest list of moves needed to solve the given level,
+/// or None if the level is unsolvable.
+///
+/// If there are multiple shortest solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &SokobanBoard, initial: &SokobanState) -> Option<Vec<Direction>> {
+    let initial = initial.clone().normalized();
+    let mut queue = VecDeque::from([initial.clone()]);
+    // Map from a state to a (move from previous state to current state, previous state) tuple
+    // (or None if there is no previous state)
+    // so that the solution can be reconstructed once the level is solved.
+    let mut visited = HashMap::from([(initial, None)]);
+    while let Some(curr) = queue.pop_front() {
+        if curr.is_solved(board) {
+            return Some(trace_moves(&visited, curr));
+        }
+        for &direction in &DIRECTIONS {
+            let Some(next) = curr.make_move(board, direction) else {
+                continue;
+            };
+            let next = next.normalized();
+            if next.has_cornered_box(board) {
+                continue;
+            }
+            if let Entry::Vacant(entry_for_next) = visited.entry(next.clone()) {
+                queue.push_back(next);
+                entry_for_next.insert(Some((direction, curr.clone())));
+            }
+        }
+    }
+    None
+}
+
+/// Reconstructs the moves needed to get to the given state, based on the map of visited states.
+fn trace_moves(
+    visited: &HashMap<SokobanState, Option<(Direction, SokobanState)>>,
+    final_state: SokobanState,
+) -> Vec<Direction> {
+    let mut result = VecDeque::new();
+    let mut curr = &final_state;
+    while let Some((direction, prev)) = &visited[curr] {
+        result.push_front(*direction);
+        curr = prev;
+    }
+    result.into()
+}
+
+/// Splits a grid of tiles and their occupants into a board and a starting state.
+///
+/// Panics if the grid doesn't contain exactly one player.
+pub fn level_from_squares(
+    squares: Grid<(SokobanTile, Option<Occupant>)>,
+) -> (SokobanBoard, SokobanState) {
+    let mut players = vec![];
+    let mut boxes = vec![];
+    for (position, &(_, occupant)) in squares.enumerate() {
+        match occupant {
+            Some(Occupant::Player) => players.push(position),
+            Some(Occupant::Box) => boxes.push(position),
+            None => {}
+        }
+    }
+    let [player] = players[..] else {
+        panic!("expected exactly one player, found {}", players.len());
+    };
+    let board = SokobanBoard(squares.map(|(tile, _)| tile));
+    (board, SokobanState { player, boxes })
+}
+
+/// Creates a board and starting state for a Sokoban level.
+///
+/// Syntax:
+/// ```text
+/// sokoban_level![
+///     [<square> ...]
+///     ...
+/// ]
+/// ```
+/// with the following symbols for squares: \
+/// `#` Wall \
+/// `-` Floor \
+/// `.` Target \
+/// `@` Player on floor \
+/// `+` Player on target \
+/// `$` Box on floor \
+/// `*` Box on target
+#[macro_export]
+macro_rules! sokoban_level {
+    (@square #) => {(SokobanTile::Wall, None)};
+    (@square -) => {(SokobanTile::Floor, None)};
+    (@square .) => {(SokobanTile::Target, None)};
+    (@square @) => {(SokobanTile::Floor, Some(Occupant::Player))};
+    (@square +) => {(SokobanTile::Target, Some(Occupant::Player))};
+    (@square $) => {(SokobanTile::Floor, Some(Occupant::Box))};
+    (@square *) => {(SokobanTile::Target, Some(Occupant::Box))};
+    ($([$($square:tt)*])*) => {
+        {
+            use $crate::sokoban::{Occupant, SokobanTile};
+            $crate::sokoban::level_from_squares($crate::grid::Grid::from_2d_array(
+                [$([$(sokoban_level!(@square $square)),*]),*]
+            ))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sokoban::*;
+    use rstest::rstest;
+    use Direction::{Down as D, Left as L, Right as R, Up as U};
+
+    /// Returns the result of making multiple moves in the given directions.
+    ///
+    /// Panics if any of the moves is illegal.
+    fn play(
+        board: &SokobanBoard,
+        mut state: SokobanState,
+        directions: &[Direction],
+    ) -> SokobanState {
+        for (i, &direction) in directions.iter().enumerate() {
+            state = state
+                .make_move(board, direction)
+                .unwrap_or_else(|| panic!("illegal move: move {i} of {directions:?}"));
+        }
+        state
+    }
+
+    #[test]
+    fn test_level_from_squares() {
+        let (SokobanBoard(grid), state) = sokoban_level![
+            [# # # #]
+            [# + $ #]
+            [# * . #]
+            [# # # #]
+        ];
+        use SokobanTile::*;
+        #[rustfmt::skip]
+        let expected_grid = Grid::from_2d_array([
+            [Wall, Wall,   Wall,   Wall],
+            [Wall, Target, Floor,  Wall],
+            [Wall, Target, Target, Wall],
+            [Wall, Wall,   Wall,   Wall],
+        ]);
+        assert_eq!(grid, expected_grid);
+        assert_eq!(state.player, (1, 1));
+        assert_eq!(state.boxes, [(2, 1), (1, 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected exactly one player, found 2")]
+    fn test_level_from_squares_two_players() {
+        sokoban_level![[@ - @]];
+    }
+
+    fn crowded_level() -> (SokobanBoard, SokobanState) {
+        sokoban_level![
+            [# # # # # #]
+            [# - - - - #]
+            [# - - $ - #]
+            [# $ $ @ - #]
+            [# - - $ - #]
+            [# - - # - #]
+            [# # # # # #]
+        ]
+    }
+
+    #[rstest]
+    #[case::walk(R, Some(((4, 3), vec![(3, 2), (1, 3), (2, 3), (3, 4)])))]
+    #[case::push(U, Some(((3, 2), vec![(3, 1), (1, 3), (2, 3), (3, 4)])))]
+    #[case::push_into_box(L, None)]
+    #[case::push_into_wall(D, None)]
+    fn test_make_move(
+        #[case] direction: Direction,
+        #[case] expected: Option<(Position, Vec<Position>)>,
+    ) {
+        let (board, state) = crowded_level();
+        let expected = expected.map(|(player, boxes)| SokobanState { player, boxes });
+        assert_eq!(state.make_move(&board, direction), expected);
+    }
+
+    #[rstest]
+    #[case::off_left(L, None)]
+    #[case::off_top(U, None)]
+    #[case::walk(R, Some((1, 0)))]
+    #[case::off_bottom(D, None)]
+    fn test_make_move_out_of_bounds(
+        #[case] direction: Direction,
+        #[case] expected: Option<Position>,
+    ) {
+        let (board, state) = sokoban_level![[@ -]];
+        let actual = state.make_move(&board, direction).map(|state| state.player);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::trivial(sokoban_level![
+        [@ $ .]
+        [- - -]
+        [- - -]
+    ], Some(1))]
+    #[case::already_solved(sokoban_level![
+        [# # # #]
+        [# @ * #]
+        [# # # #]
+    ], Some(0))]
+    #[case::around_corner(sokoban_level![
+        [# # # # #]
+        [# @ - - #]
+        [# - $ - #]
+        [# - - . #]
+        [# # # # #]
+    ], Some(5))]
+    #[case::two_boxes(sokoban_level![
+        [# # # # # # #]
+        [# . $ @ $ . #]
+        [# # # # # # #]
+    ], Some(3))]
+    #[case::walk_around(sokoban_level![
+        [# # # # # #]
+        [# - - - - #]
+        [# - # # - #]
+        [# @ $ - . #]
+        [# # # # # #]
+    ], Some(2))]
+    #[case::reposition(sokoban_level![
+        [# # # # # #]
+        [# . - - - #]
+        [# - $ - @ #]
+        [# - - - - #]
+        [# # # # # #]
+    ], Some(5))]
+    #[case::cornered(sokoban_level![
+        [# # # # #]
+        [# $ - - #]
+        [# - @ . #]
+        [# # # # #]
+    ], None)]
+    #[case::against_wall(sokoban_level![
+        [# # # # # #]
+        [# - $ - - #]
+        [# - - . - #]
+        [# @ - - - #]
+        [# # # # # #]
+    ], None)]
+    #[case::too_many_boxes(sokoban_level![
+        [@ $ $ .]
+    ], None)]
+    fn test_solve(
+        #[case] level: (SokobanBoard, SokobanState),
+        #[case] optimal_solution_length: Option<usize>,
+    ) {
+        let (board, initial) = level;
+        match optimal_solution_length {
+            Some(length) => {
+                let solution = solve(&board, &initial).unwrap();
+                assert_eq!(solution.len(), length, "incorrect length: {solution:?}");
+                let final_state = play(&board, initial, &solution);
+                assert!(final_state.is_solved(&board), "not solved: {solution:?}");
+            }
+            None => {
+                if let Some(solution) = solve(&board, &initial) {
+                    panic!("expected no solution, got solution {solution:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! A spanning tree of a connected graph is a set of its edges that connects all of its vertices
+//! without forming any cycles; a minimum spanning tree is one with the smallest total weight.
+//!
+//! Problem: given a grid of values, where each cell is connected to its adjacent cells
+//! by an edge weighing the sum of the two cells' values, find a minimum spanning tree.
+//!
+//! For example, in the grid
+//! ```text
+//! 1 2
+//! 3 4
+//! ```
+//! with orthogonal adjacency, the edges weigh 3 (top), 4 (left), 6 (right), and 7 (bottom),
+//! and the minimum spanning tree is made of the first three edges, weighing 13 in total.
+//!
+//! The tree is found with Kruskal's algorithm:
+//! going through the edges from lightest to heaviest,
+//! each edge is added to the tree unless its cells are already connected by the tree so far,
+//! which is tracked with a union-find forest of the cells.
+
+use crate::flood_fill::Connectivity;
+use crate::grid::Grid;
+use crate::union_find::UnionFind;
+use std::ops::Add;
+
+type CellIndex = (usize, usize);
+
+/// Returns the edges of a minimum spanning tree of the given grid,
+/// with each cell connected to its adjacent cells according to `connectivity`,
+/// and each edge weighing the sum of the values of its two cells.
+///
+/// The tree has one edge fewer than the grid has cells (or no edges for an empty grid).
+/// If there are multiple minimum spanning trees, one of them will be returned;
+/// it's left unspecified which specific tree is returned.
+pub fn minimum_spanning_tree<W: Add<Output = W> + Copy + Ord>(
+    grid: &Grid<W>,
+    connectivity: Connectivity,
+) -> Vec<(CellIndex, CellIndex)> {
+    let (width, height) = grid.dimensions();
+    let mut edges = vec![];
+    for ((x, y), &value) in grid.enumerate::<CellIndex>() {
+        for &(dx, dy) in connectivity.displacements() {
+            // Only take each edge once, from the cell that comes first in row-major order.
+            if (dy, dx) < (0, 0) {
+                continue;
+            }
+            let Some(neighbor) = x
+                .checked_add_signed(dx as isize)
+                .zip(y.checked_add_signed(dy as isize))
+            else {
+                continue;
+            };
+            if let Some(&neighbor_value) = grid.get(neighbor) {
+                edges.push((value + neighbor_value, (x, y), neighbor));
+            }
+        }
+    }
+    edges.sort_by_key(|&(weight, _, _)| weight);
+    let cell_count = width * height;
+    let mut forest = UnionFind::new(cell_count);
+    let mut result = Vec::with_capacity(cell_count.saturating_sub(1));
+    for (_, a, b) in edges {
+        if result.len() + 1 >= cell_count {
+            break;
+        }
+        if forest.union(a.1 * width + a.0, b.1 * width + b.0) {
+            result.push((a, b));
+        }
+    }
+    result
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid.
+        There are `O(c)` edges (at most 4 per cell), which take `O(c log c)` time to sort,
+        and going through them takes `O(c log c)` time in the worst case
+        (with path compression in the union-find forest, but no union by rank),
+        so this function completes in `O(c log c)` time.
+    */
+}
+
+/// Returns the total weight of the given edges,
+/// with each edge weighing the sum of the values of its two cells in the given grid,
+/// starting from `W::default()` (which is 0 for numbers).
+///
+/// Panics if some edge has a cell out of bounds.
+pub fn spanning_tree_weight<W: Add<Output = W> + Copy + Default>(
+    grid: &Grid<W>,
+    tree: &[(CellIndex, CellIndex)],
+) -> W {
+    tree.iter()
+        .fold(W::default(), |total, &(a, b)| total + grid[a] + grid[b])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spanning_tree::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Checks that the given edges form a spanning tree of a grid with the given dimensions,
+    /// with each edge connecting adjacent cells according to `connectivity`.
+    fn assert_spanning_tree(
+        dimensions: (usize, usize),
+        tree: &[(CellIndex, CellIndex)],
+        connectivity: Connectivity,
+    ) {
+        let (width, height) = dimensions;
+        assert_eq!(tree.len(), (width * height).saturating_sub(1));
+        let mut forest = UnionFind::new(width * height);
+        for &(a, b) in tree {
+            assert!(a.0 < width && a.1 < height && b.0 < width && b.1 < height);
+            let displacement = (b.0 as i32 - a.0 as i32, b.1 as i32 - a.1 as i32);
+            let reverse = (-displacement.0, -displacement.1);
+            assert!(
+                connectivity.displacements().contains(&displacement)
+                    || connectivity.displacements().contains(&reverse),
+                "{a:?} and {b:?} aren't adjacent"
+            );
+            assert!(
+                forest.union(a.1 * width + a.0, b.1 * width + b.0),
+                "adding {a:?} to {b:?} forms a cycle"
+            );
+        }
+        // With one edge fewer than the number of cells and no cycles, every cell is connected.
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let grid = Grid::from_2d_array([[1, 2], [3, 4]]);
+        let tree = minimum_spanning_tree(&grid, Connectivity::Four);
+        assert_spanning_tree((2, 2), &tree, Connectivity::Four);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree, [((0, 0), (1, 0)), ((0, 0), (0, 1)), ((1, 0), (1, 1))]);
+        assert_eq!(spanning_tree_weight(&grid, &tree), 13);
+    }
+
+    #[test]
+    fn test_diagonals() {
+        // The diagonals weigh 5, which is less than the right edge.
+        let grid = Grid::from_2d_array([[1, 2], [3, 4]]);
+        let tree = minimum_spanning_tree(&grid, Connectivity::Eight);
+        assert_spanning_tree((2, 2), &tree, Connectivity::Eight);
+        assert_eq!(spanning_tree_weight(&grid, &tree), 12);
+    }
+
+    #[rstest]
+    #[case::four(Connectivity::Four, 82)]
+    #[case::eight(Connectivity::Eight, 62)]
+    fn test_minimum_weight(#[case] connectivity: Connectivity, #[case] expected: i32) {
+        let grid = Grid::from_2d_array([[5, 1, 4, 2], [3, 9, 1, 7], [8, 2, 6, 3]]);
+        let tree = minimum_spanning_tree(&grid, connectivity);
+        assert_spanning_tree(grid.dimensions(), &tree, connectivity);
+        assert_eq!(spanning_tree_weight(&grid, &tree), expected);
+    }
+
+    #[test]
+    fn test_weight_is_sum_of_edges() {
+        let grid = Grid::from_2d_array([[5, 1, 4, 2], [3, 9, 1, 7], [8, 2, 6, 3]]);
+        let tree = minimum_spanning_tree(&grid, Connectivity::Four);
+        let edge_weights: i32 = tree.iter().map(|&(a, b)| grid[a] + grid[b]).sum();
+        assert_eq!(spanning_tree_weight(&grid, &tree), edge_weights);
+        assert_eq!(spanning_tree_weight(&grid, &[((0, 0), (0, 1))]), 8);
+        assert_eq!(spanning_tree_weight(&grid, &[]), 0);
+    }
+
+    #[rstest]
+    #[case::four(Connectivity::Four)]
+    #[case::eight(Connectivity::Eight)]
+    fn test_uniform_weights(#[case] connectivity: Connectivity) {
+        // Every spanning tree is minimal, so any one of them will do.
+        let grid = Grid::filled(3u64, (7, 5));
+        let tree = minimum_spanning_tree(&grid, connectivity);
+        assert_spanning_tree((7, 5), &tree, connectivity);
+        assert_eq!(spanning_tree_weight(&grid, &tree), 34 * 6);
+    }
+
+    #[rstest]
+    #[case((1, 1))]
+    #[case((5, 1))]
+    #[case((1, 4))]
+    #[case((0, 0))]
+    #[case((3, 0))]
+    fn test_thin_grids(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(1, dimensions);
+        let tree = minimum_spanning_tree(&grid, Connectivity::Eight);
+        assert_spanning_tree(dimensions, &tree, Connectivity::Eight);
+    }
+}
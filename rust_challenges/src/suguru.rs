@@ -0,0 +1,307 @@
+//! Suguru (also known as Tectonic) is a number puzzle played on a rectangular grid
+//! divided into regions, with some cells given.
+//!
+//! Problem: fill each region of `n` cells with the numbers from 1 to `n`, each used exactly once,
+//! such that every given cell keeps its number,
+//! and no two cells that touch (orthogonally or diagonally) contain the same number.
+//!
+//! For example, in the following puzzle (regions on the left, given cells on the right,
+//! with `.` for empty cells):
+//! ```text
+//! 0 0 1 1 2    . . . . .
+//! 0 1 1 2 2    . . . . .
+//! 3 3 4 5 2    . 3 . . 5
+//! 3 3 4 5 2    . 4 . . .
+//! 6 3 5 5 5    . . . . .
+//! ```
+//! the unique solution is:
+//! ```text
+//! 3 1 3 1 3
+//! 2 4 2 4 2
+//! 1 3 1 3 5
+//! 2 4 2 4 1
+//! 1 5 1 5 2
+//! ```
+//!
+//! The solver (from [`crate::candidate_numbers`], shared with Ripple Effect)
+//! keeps track of the numbers that each cell could still contain,
+//! and makes every pair of constrained cells (in the same region, or touching) _arc consistent_
+//! using the AC-3 algorithm: every candidate for one cell of the pair
+//! must be compatible with some candidate for the other cell.
+//! Since each region must use each of its numbers,
+//! a number that only one cell of a region can contain is also placed in that cell.
+//! With the candidates consistent, the solver guesses a number for the most constrained cell,
+//! and backtracks if the guess leads to a contradiction.
+
+use crate::candidate_numbers::{self, Arc};
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// The largest number of cells a region can have.
+pub const MAX_REGION_SIZE: usize = candidate_numbers::MAX_REGION_SIZE;
+
+/// A Suguru puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuguruBoard {
+    grid: Grid<Option<u8>>,
+    regions: Grid<usize>,
+}
+
+impl SuguruBoard {
+    /// Creates a puzzle from its given cells and the ID of the region containing each cell.
+    ///
+    /// Returns Err(cell) if `cell` is in a region with more than [`MAX_REGION_SIZE`] cells,
+    /// or is given a number that its region can't contain.
+    /// If there are multiple such cells, the first one in row-major order is returned.
+    ///
+    /// Panics if the dimensions of `grid` and `regions` don't match.
+    pub fn new(grid: Grid<Option<u8>>, regions: Grid<usize>) -> Result<SuguruBoard, CellIndex> {
+        assert_eq!(
+            grid.dimensions(),
+            regions.dimensions(),
+            "dimensions of grid and regions don't match"
+        );
+        if let Some(cell) = candidate_numbers::first_invalid_cell(&grid, &regions) {
+            return Err(cell);
+        }
+        Ok(SuguruBoard { grid, regions })
+    }
+
+    /// The given cells of the puzzle.
+    pub fn grid(&self) -> &Grid<Option<u8>> {
+        &self.grid
+    }
+
+    /// The ID of the region containing each cell.
+    pub fn regions(&self) -> &Grid<usize> {
+        &self.regions
+    }
+}
+
+/// Returns a solution to the given puzzle, or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &SuguruBoard) -> Option<Grid<u8>> {
+    let SuguruBoard { grid, regions } = board;
+    candidate_numbers::solve_regions(grid, regions, &arcs(regions))
+    /*
+        Time complexity analysis:
+        Finding a solution can take exponential time in the number of cells in the worst case.
+        Let `c` be the number of cells and `r` be the size of the largest region.
+        Each cell has `O(r)` arcs (up to 8 for touching cells, plus the rest of its region),
+        so there are `O(c * r)` arcs in total, and building them takes `O(c^2)` time.
+        By the analysis of `candidate_numbers::solve_candidates`
+        (with the regions as the groups, so `g = c` and `n = r`),
+        each guess takes `O(c^2 * r^2)` time to propagate in the very worst case.
+    */
+}
+
+/// Returns the arcs starting from each cell of a puzzle with the given regions,
+/// between cells that can't contain the same number.
+fn arcs(regions: &Grid<usize>) -> Grid<Vec<Arc<()>>> {
+    let mut result = Grid::filled(vec![], regions.dimensions());
+    for (from, &id) in regions.enumerate::<CellIndex>() {
+        let (x, y) = (from.0 as i32, from.1 as i32);
+        let touching = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (x + dx, y + dy)))
+            .filter(|&(other_x, other_y)| regions.get((other_x, other_y)).is_some())
+            .map(|(other_x, other_y)| (other_x as usize, other_y as usize));
+        let same_region = regions
+            .enumerate::<CellIndex>()
+            .filter(|&(_, &other_id)| other_id == id)
+            .map(|(other, _)| other);
+        let mut others: Vec<CellIndex> = touching
+            .chain(same_region)
+            .filter(|&to| to != from)
+            .collect();
+        others.sort();
+        others.dedup();
+        result[from] = others.into_iter().map(|to| (from, to, ())).collect();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::suguru::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    /// Creates the given cells of a Suguru puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// given![
+    ///     [<`.` for an empty cell, or a number> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! given {
+        (@cell .) => {None};
+        (@cell $number:literal) => {Some($number)};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(given!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &SuguruBoard, solution: &Grid<u8>) {
+        assert_eq!(solution.dimensions(), board.grid().dimensions());
+        for (cell, &number) in board.grid().enumerate::<CellIndex>() {
+            if let Some(number) = number {
+                assert_eq!(solution[cell], number, "given number at {cell:?} changed");
+            }
+        }
+        let mut region_numbers: HashMap<usize, Vec<u8>> = HashMap::new();
+        for (cell, &id) in board.regions().enumerate::<CellIndex>() {
+            region_numbers.entry(id).or_default().push(solution[cell]);
+        }
+        for (id, mut numbers) in region_numbers {
+            numbers.sort();
+            let expected: Vec<u8> = (1..=numbers.len() as u8).collect();
+            assert_eq!(
+                numbers, expected,
+                "region {id} doesn't contain each number once"
+            );
+        }
+        for ((x, y), &number) in solution.enumerate::<(i32, i32)>() {
+            for (dx, dy) in [(1, -1), (1, 0), (1, 1), (0, 1)] {
+                assert_ne!(
+                    solution.get((x + dx, y + dy)),
+                    Some(&number),
+                    "{:?} and {:?} touch and contain the same number",
+                    (x, y),
+                    (x + dx, y + dy)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        #[rustfmt::skip]
+        let regions = Grid::from_2d_array([
+            [0, 0, 1, 1, 2],
+            [0, 1, 1, 2, 2],
+            [3, 3, 4, 5, 2],
+            [3, 3, 4, 5, 2],
+            [6, 3, 5, 5, 5],
+        ]);
+        let given = given![
+            [. . . . .]
+            [. . . . .]
+            [. 3 . . 5]
+            [. 4 . . .]
+            [. . . . .]
+        ];
+        let board = SuguruBoard::new(given, regions).unwrap();
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [3, 1, 3, 1, 3],
+            [2, 4, 2, 4, 2],
+            [1, 3, 1, 3, 5],
+            [2, 4, 2, 4, 1],
+            [1, 5, 1, 5, 2],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_5_by_5() {
+        #[rustfmt::skip]
+        let regions = Grid::from_2d_array([
+            [0, 1, 1, 1, 1],
+            [0, 2, 2, 2, 1],
+            [3, 2, 2, 4, 5],
+            [6, 6, 7, 4, 4],
+            [6, 6, 7, 4, 4],
+        ]);
+        let given = given![
+            [. . . 5 2]
+            [. . . . 4]
+            [. . . . .]
+            [. . . . .]
+            [. 3 . 3 .]
+        ];
+        let board = SuguruBoard::new(given, regions).unwrap();
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [1, 3, 1, 5, 2],
+            [2, 4, 2, 3, 4],
+            [1, 5, 1, 5, 1],
+            [2, 4, 2, 4, 2],
+            [1, 3, 1, 3, 1],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[rstest]
+    #[case::single_region(Grid::filled(0, (2, 2)), Grid::filled(None, (2, 2)))]
+    #[case::no_givens(
+        Grid::from_2d_array([[0, 0, 1, 1], [0, 0, 1, 1]]),
+        Grid::filled(None, (4, 2)),
+    )]
+    #[case::one_row(Grid::from_2d_array([[0, 0, 0, 1, 1]]), given![[. . . . 1]])]
+    fn test_solve(#[case] regions: Grid<usize>, #[case] given: Grid<Option<u8>>) {
+        let board = SuguruBoard::new(given, regions).unwrap();
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[rstest]
+    // Two single-cell regions next to each other would both contain 1.
+    #[case::touching_ones(Grid::from_2d_array([[0, 1]]), given![[. .]])]
+    // Cells touching diagonally can't both contain 2.
+    #[case::diagonal(Grid::from_2d_array([[0, 0], [1, 1]]), given![[2 .] [. 2]])]
+    // The 1 in the middle touches every cell of the other region.
+    #[case::no_room(Grid::from_2d_array([[0, 1, 0], [0, 0, 0]]), given![[. 1 .] [. . .]])]
+    fn test_unsolvable(#[case] regions: Grid<usize>, #[case] given: Grid<Option<u8>>) {
+        let board = SuguruBoard::new(given, regions).unwrap();
+        assert_eq!(solve(&board), None);
+    }
+
+    #[rstest]
+    #[case::too_large(Grid::from_2d_array([[0, 0], [1, 1]]), given![[. .] [3 .]], (0, 1))]
+    #[case::zero(Grid::from_2d_array([[0, 1]]), given![[. 0]], (1, 0))]
+    #[case::first_invalid(Grid::from_2d_array([[0, 1]]), given![[2 2]], (0, 0))]
+    fn test_invalid_board(
+        #[case] regions: Grid<usize>,
+        #[case] given: Grid<Option<u8>>,
+        #[case] expected: CellIndex,
+    ) {
+        assert_eq!(SuguruBoard::new(given, regions), Err(expected));
+    }
+
+    #[test]
+    fn test_region_too_large() {
+        let regions = Grid::filled(0, (MAX_REGION_SIZE + 1, 1));
+        let given = Grid::filled(None, (MAX_REGION_SIZE + 1, 1));
+        assert_eq!(SuguruBoard::new(given, regions), Err((0, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "dimensions of grid and regions don't match")]
+    fn test_mismatched_dimensions() {
+        let _ = SuguruBoard::new(Grid::filled(None, (2, 3)), Grid::filled(0, (3, 2)));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_boards(#[case] width: usize, #[case] height: usize) {
+        let board = SuguruBoard::new(
+            Grid::filled(None, (width, height)),
+            Grid::filled(0, (width, height)),
+        )
+        .unwrap();
+        assert_eq!(solve(&board), Some(Grid::filled(0, (width, height))));
+    }
+}
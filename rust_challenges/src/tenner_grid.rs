@@ -0,0 +1,386 @@
+//! Tenner Grid (also known as Zehnergitter) is a number puzzle played on a grid with 10 columns,
+//! with some cells given and a clue below each column.
+//!
+//! Problem: fill every cell with a digit from 0 to 9,
+//! such that every given cell keeps its digit,
+//! each row contains each digit exactly once,
+//! the digits in each column add up to the column's clue,
+//! and no two cells in consecutive rows that touch (orthogonally or diagonally)
+//! contain the same digit.
+//!
+//! For example, in the following puzzle (with `.` for empty cells, and the clues in the last row):
+//! ```text
+//!  .  .  .  .  .  4  .  .  .  .
+//!  6  9  .  .  .  .  .  .  .  .
+//! 13 17  5 13  5  4  3  3 14 13
+//! ```
+//! the unique solution is:
+//! ```text
+//!  7  8  1  5  3  4  2  0  9  6
+//!  6  9  4  8  2  0  1  3  5  7
+//! ```
+//!
+//! Since each row adds up to 45, the clues must add up to 45 times the number of rows.
+//! Beyond that, the solver keeps track of the digits that each cell could still contain,
+//! eliminating digits that are already decided for another cell in the same row or a touching cell,
+//! and digits that would make a column's sum too small or too large
+//! given the smallest and largest candidates for the column's other cells.
+//! A digit that only one cell of a row can contain is also placed in that cell.
+//! When no more digits can be eliminated, it guesses a digit for the most constrained cell,
+//! and backtracks if the guess leads to a contradiction.
+
+use crate::grid::Grid;
+
+type CellIndex = (usize, usize);
+
+/// The number of columns in a Tenner Grid puzzle.
+pub const COLUMNS: usize = 10;
+
+/// A Tenner Grid puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TennerBoard {
+    rows: usize,
+    givens: Grid<Option<u8>>,
+    col_sums: Vec<u32>,
+}
+
+impl TennerBoard {
+    /// Creates a puzzle from its given cells and the clue for each column.
+    ///
+    /// Returns Err(cell) if `cell` is given a number that isn't a digit.
+    /// If there are multiple such cells, the first one in row-major order is returned.
+    ///
+    /// Panics if `givens` doesn't have 10 columns, or there aren't 10 clues.
+    pub fn new(givens: Grid<Option<u8>>, col_sums: Vec<u32>) -> Result<TennerBoard, CellIndex> {
+        assert_eq!(
+            givens.width(),
+            COLUMNS,
+            "grid doesn't have {COLUMNS} columns"
+        );
+        assert_eq!(
+            col_sums.len(),
+            COLUMNS,
+            "there aren't {COLUMNS} column clues"
+        );
+        let invalid = givens
+            .enumerate::<CellIndex>()
+            .find(|&(_, &digit)| digit.is_some_and(|digit| digit > 9));
+        if let Some((cell, _)) = invalid {
+            return Err(cell);
+        }
+        Ok(TennerBoard {
+            rows: givens.height(),
+            givens,
+            col_sums,
+        })
+    }
+
+    /// The number of rows in the puzzle.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The given cells of the puzzle.
+    pub fn givens(&self) -> &Grid<Option<u8>> {
+        &self.givens
+    }
+
+    /// The clue for each column: the sum of the digits in the column.
+    pub fn col_sums(&self) -> &[u32] {
+        &self.col_sums
+    }
+}
+
+/// A set of digits from 0 to 9, where bit `d` is set if digit `d` is in the set.
+type Digits = u16;
+
+/// The set of all digits from 0 to 9.
+const ALL_DIGITS: Digits = 0b11_1111_1111;
+
+/// Returns the digits in the given set, in increasing order.
+fn digits_in(digits: Digits) -> impl Iterator<Item = u8> {
+    (0..=9).filter(move |&d| digits & (1 << d) != 0)
+}
+
+/// Returns a solution to the given puzzle, or None if the puzzle has no solution.
+///
+/// If there are multiple solutions, one of them will be returned;
+/// it's left unspecified which specific solution is returned.
+pub fn solve(board: &TennerBoard) -> Option<Grid<u8>> {
+    // Each row adds up to 0 + 1 + ... + 9 = 45.
+    if board.col_sums.iter().sum::<u32>() != 45 * board.rows as u32 {
+        return None;
+    }
+    let candidates = board
+        .givens
+        .clone()
+        .map(|digit| digit.map_or(ALL_DIGITS, |digit| 1 << digit));
+    let candidates = search(candidates, &board.col_sums)?;
+    Some(candidates.map(|digits| digits.trailing_zeros() as u8))
+    /*
+        Time complexity analysis:
+        Finding a solution can take exponential time in the number of rows in the worst case.
+        Let `r` be the number of rows.
+        Each round of propagation takes `O(r)` time, since there are 10 columns and 10 digits,
+        and each round except the last eliminates at least one of the `O(r)` candidates,
+        so each guess takes `O(r^2)` time to propagate.
+    */
+}
+
+/// Tries to narrow down the given candidates to a single digit for each cell,
+/// such that every rule is satisfied.
+///
+/// Returns the narrowed-down candidates, or None if that's impossible.
+fn search(mut candidates: Grid<Digits>, col_sums: &[u32]) -> Option<Grid<Digits>> {
+    if !propagate(&mut candidates, col_sums) {
+        return None;
+    }
+    // Guess a digit for the cell with the fewest remaining candidates (besides 1).
+    let undecided = candidates
+        .enumerate::<CellIndex>()
+        .filter(|&(_, &digits)| digits.count_ones() > 1)
+        .min_by_key(|&(_, &digits)| digits.count_ones());
+    let Some((index, &digits)) = undecided else {
+        return Some(candidates);
+    };
+    digits_in(digits).find_map(|digit| {
+        let mut candidates = candidates.clone();
+        candidates[index] = 1 << digit;
+        search(candidates, col_sums)
+    })
+}
+
+/// Returns the cells constrained to contain a different digit from the given cell:
+/// the rest of its row, and the touching cells in the rows above and below it.
+fn peers(cell: CellIndex, height: usize) -> impl Iterator<Item = CellIndex> {
+    let (x, y) = cell;
+    let same_row = (0..COLUMNS).map(move |other_x| (other_x, y));
+    let touching = [y.checked_sub(1), Some(y + 1).filter(|&y| y < height)]
+        .into_iter()
+        .flatten()
+        .flat_map(move |other_y| {
+            (x.saturating_sub(1)..=(x + 1).min(COLUMNS - 1)).map(move |other_x| (other_x, other_y))
+        });
+    same_row.chain(touching).filter(move |&other| other != cell)
+}
+
+/// Eliminates digits from the candidates for each cell until no more digits can be eliminated,
+/// and places digits that only one cell of their row can contain.
+///
+/// Returns false if some cell has no candidates left, or some row can't contain all the digits.
+/// When every cell is narrowed down to a single digit and this returns true,
+/// the candidates form a solution.
+fn propagate(candidates: &mut Grid<Digits>, col_sums: &[u32]) -> bool {
+    let height = candidates.height();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        // Digits that are already decided can't appear in any peer.
+        for y in 0..height {
+            for x in 0..COLUMNS {
+                let digits = candidates[(x, y)];
+                if digits.count_ones() != 1 {
+                    continue;
+                }
+                for peer in peers((x, y), height) {
+                    if candidates[peer] & digits != 0 {
+                        candidates[peer] &= !digits;
+                        changed = true;
+                        if candidates[peer] == 0 {
+                            return false;
+                        }
+                    }
+                }
+            }
+        }
+        // Each row must contain each digit somewhere.
+        for y in 0..height {
+            for digit in 0..=9 {
+                let mut possible = (0..COLUMNS).filter(|&x| candidates[(x, y)] & (1 << digit) != 0);
+                match (possible.next(), possible.next()) {
+                    (None, _) => return false,
+                    (Some(x), None) if candidates[(x, y)] != 1 << digit => {
+                        candidates[(x, y)] = 1 << digit;
+                        changed = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        // Each digit must leave room for the rest of its column to reach the clue.
+        for (x, &sum) in col_sums.iter().enumerate() {
+            let smallest = |digits: Digits| digits.trailing_zeros();
+            let largest = |digits: Digits| Digits::BITS - 1 - digits.leading_zeros();
+            let min_sum: u32 = (0..height).map(|y| smallest(candidates[(x, y)])).sum();
+            let max_sum: u32 = (0..height).map(|y| largest(candidates[(x, y)])).sum();
+            if !(min_sum..=max_sum).contains(&sum) {
+                return false;
+            }
+            for y in 0..height {
+                let digits = candidates[(x, y)];
+                let others_min = min_sum - smallest(digits);
+                let others_max = max_sum - largest(digits);
+                let narrowed = digits_in(digits)
+                    .filter(|&digit| {
+                        let digit = u32::from(digit);
+                        others_min + digit <= sum && others_max + digit >= sum
+                    })
+                    .fold(0, |acc, digit| acc | 1 << digit);
+                if narrowed == 0 {
+                    return false;
+                }
+                if narrowed != digits {
+                    candidates[(x, y)] = narrowed;
+                    changed = true;
+                    // The bounds for the rest of the column are out of date, so move on.
+                    break;
+                }
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tenner_grid::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates the given cells of a Tenner Grid puzzle.
+    ///
+    /// Syntax:
+    /// ```text
+    /// given![
+    ///     [<`.` for an empty cell, or a digit> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! given {
+        (@cell .) => {None};
+        (@cell $digit:literal) => {Some($digit)};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(given!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Checks each rule separately for the given solution to the given puzzle.
+    fn assert_valid(board: &TennerBoard, solution: &Grid<u8>) {
+        assert_eq!(solution.dimensions(), (COLUMNS, board.rows()));
+        for (cell, &digit) in board.givens().enumerate::<CellIndex>() {
+            if let Some(digit) = digit {
+                assert_eq!(solution[cell], digit, "given digit at {cell:?} changed");
+            }
+        }
+        for y in 0..board.rows() {
+            let mut row: Vec<u8> = (0..COLUMNS).map(|x| solution[(x, y)]).collect();
+            row.sort();
+            assert_eq!(
+                row,
+                (0..=9).collect::<Vec<u8>>(),
+                "row {y} isn't a permutation of the digits"
+            );
+        }
+        for (x, &sum) in board.col_sums().iter().enumerate() {
+            let actual: u32 = (0..board.rows()).map(|y| u32::from(solution[(x, y)])).sum();
+            assert_eq!(actual, sum, "column {x} has the wrong sum");
+        }
+        for ((x, y), &digit) in solution.enumerate::<(i32, i32)>() {
+            for dx in -1..=1 {
+                assert_ne!(
+                    solution.get((x + dx, y + 1)),
+                    Some(&digit),
+                    "{:?} and {:?} touch and contain the same digit",
+                    (x, y),
+                    (x + dx, y + 1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_problem_description_example() {
+        let givens = given![
+            [. . . . . 4 . . . .]
+            [6 9 . . . . . . . .]
+        ];
+        let col_sums = vec![13, 17, 5, 13, 5, 4, 3, 3, 14, 13];
+        let board = TennerBoard::new(givens, col_sums).unwrap();
+        let expected = Grid::from_2d_array([
+            [7, 8, 1, 5, 3, 4, 2, 0, 9, 6],
+            [6, 9, 4, 8, 2, 0, 1, 3, 5, 7],
+        ]);
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+        assert_eq!(solution, expected);
+    }
+
+    #[test]
+    fn test_5_rows() {
+        let givens = given![
+            [8 3 . 4 . 0 9 . . .]
+            [9 . . 6 . . . . 4 .]
+            [. . 4 . . . 0 3 . .]
+            [8 . . 5 3 . 7 . . 0]
+            [. . . 7 . . 0 8 . 5]
+        ];
+        let col_sums = vec![37, 11, 25, 29, 16, 27, 18, 30, 19, 13];
+        let board = TennerBoard::new(givens, col_sums).unwrap();
+        let solution = solve(&board).unwrap();
+        assert_valid(&board, &solution);
+    }
+
+    #[test]
+    fn test_one_row() {
+        let col_sums = vec![3, 1, 4, 0, 5, 9, 2, 6, 8, 7];
+        let board = TennerBoard::new(Grid::filled(None, (COLUMNS, 1)), col_sums).unwrap();
+        let expected = Grid::from_2d_array([[3, 1, 4, 0, 5, 9, 2, 6, 8, 7]]);
+        assert_eq!(solve(&board), Some(expected));
+    }
+
+    #[rstest]
+    // Two rows can't add up to more than 18 in a column.
+    #[case::column_too_large(
+        Grid::filled(None, (COLUMNS, 2)),
+        vec![19, 8, 9, 9, 9, 9, 9, 9, 9, 0],
+    )]
+    // Each row adds up to 45, so the clues must add up to 90.
+    #[case::wrong_total(Grid::filled(None, (COLUMNS, 2)), vec![10; COLUMNS])]
+    // The two 5s touch diagonally.
+    #[case::touching_givens(
+        given![[5 . . . . . . . . .] [. 5 . . . . . . . .]],
+        vec![9, 9, 9, 9, 9, 9, 9, 9, 9, 9],
+    )]
+    // A single row must be a permutation of the digits, so it can't contain two 0s.
+    #[case::repeated_digit(Grid::filled(None, (COLUMNS, 1)), vec![0, 0, 1, 2, 3, 4, 5, 6, 7, 9])]
+    fn test_unsolvable(#[case] givens: Grid<Option<u8>>, #[case] col_sums: Vec<u32>) {
+        let board = TennerBoard::new(givens, col_sums).unwrap();
+        assert_eq!(solve(&board), None);
+    }
+
+    #[test]
+    fn test_invalid_board() {
+        let givens = given![[. . . 10 . . . . 11 .]];
+        assert_eq!(TennerBoard::new(givens, vec![0; COLUMNS]), Err((3, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "grid doesn't have 10 columns")]
+    fn test_wrong_width() {
+        let _ = TennerBoard::new(Grid::filled(None, (9, 2)), vec![0; COLUMNS]);
+    }
+
+    #[test]
+    #[should_panic(expected = "there aren't 10 column clues")]
+    fn test_wrong_number_of_clues() {
+        let _ = TennerBoard::new(Grid::filled(None, (COLUMNS, 2)), vec![9; 9]);
+    }
+
+    #[rstest]
+    #[case::zero_sums(vec![0; COLUMNS], Some(Grid::filled(0, (COLUMNS, 0))))]
+    #[case::nonzero_sum(vec![0, 0, 0, 0, 1, 0, 0, 0, 0, 0], None)]
+    fn test_empty_board(#[case] col_sums: Vec<u32>, #[case] expected: Option<Grid<u8>>) {
+        let board = TennerBoard::new(Grid::filled(None, (COLUMNS, 0)), col_sums).unwrap();
+        assert_eq!(solve(&board), expected);
+    }
+}
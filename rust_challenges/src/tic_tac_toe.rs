@@ -0,0 +1,445 @@
+//! Problem: model a game of tic-tac-toe, and find optimal moves for a player.
+//!
+//! Two players take turns placing their marks (`X` or `O`) in empty cells of a 3×3 board.
+//! The first player to get three marks in a row -- horizontally, vertically, or diagonally -- wins;
+//! if the board fills up without either player doing so, the game is a draw.
+//!
+//! For example, on the following board (`.` for empty cells), with `X` to move:
+//! ```text
+//! X O .
+//! . X O
+//! . . .
+//! ```
+//! `X` wins immediately by playing in the bottom right corner.
+//!
+//! Optimal moves are found with minimax search (with alpha-beta pruning) over the full game tree.
+//! Among winning moves, moves that win sooner are preferred;
+//! among losing moves, moves that lose later are preferred.
+
+use crate::grid::Grid;
+
+/// One of the two players.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Player {
+    X,
+    O,
+}
+
+impl Player {
+    /// The other player.
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+}
+
+/// The result of a game, or the lack thereof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    Win(Player),
+    Draw,
+    Ongoing,
+}
+
+/// The side length of the board.
+const SIZE: usize = 3;
+
+/// A tic-tac-toe board, where `None` indicates an empty cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TTTBoard(pub Grid<Option<Player>>);
+
+impl TTTBoard {
+    /// Creates an empty 3×3 board.
+    pub fn new() -> TTTBoard {
+        TTTBoard(Grid::filled(None, (SIZE, SIZE)))
+    }
+}
+
+impl Default for TTTBoard {
+    fn default() -> TTTBoard {
+        TTTBoard::new()
+    }
+}
+
+type CellIndex = (usize, usize);
+
+#[rustfmt::skip]
+const LINES: [[CellIndex; 3]; 8] = [
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(2, 0), (1, 1), (0, 2)],
+];
+
+/// Returns the result of `player` placing a mark at the given cell,
+/// or None if the cell is occupied or out of bounds.
+pub fn make_move(board: &TTTBoard, col: usize, row: usize, player: Player) -> Option<TTTBoard> {
+    let TTTBoard(grid) = board;
+    if grid.get((col, row))?.is_some() {
+        return None;
+    }
+    let mut grid = grid.clone();
+    grid[(col, row)] = Some(player);
+    Some(TTTBoard(grid))
+}
+
+/// Returns the result of the game on the given board.
+///
+/// If both players have three in a row (which can't happen in a real game),
+/// it's left unspecified which player is considered to have won.
+pub fn game_result(board: &TTTBoard) -> GameResult {
+    let TTTBoard(grid) = board;
+    for line in LINES {
+        let [first, rest @ ..] = line.map(|index| grid[index]);
+        if let Some(player) = first {
+            if rest.iter().all(|&cell| cell == Some(player)) {
+                return GameResult::Win(player);
+            }
+        }
+    }
+    if grid
+        .enumerate::<CellIndex>()
+        .all(|(_, cell)| cell.is_some())
+    {
+        GameResult::Draw
+    } else {
+        GameResult::Ongoing
+    }
+}
+
+/// Returns the empty cells of the given board, in row-major order.
+fn empty_cells(board: &TTTBoard) -> Vec<CellIndex> {
+    let TTTBoard(grid) = board;
+    grid.enumerate()
+        .filter(|(_, cell)| cell.is_none())
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns the minimax value of the given board from the perspective of `maximizer`,
+/// with `to_move` about to move.
+///
+/// A win is worth more the more empty cells remain, and a loss is worth correspondingly less.
+/// The result is exact if it lies strictly between `alpha` and `beta`;
+/// otherwise, it's only a bound in the corresponding direction.
+fn minimax(
+    board: &TTTBoard,
+    to_move: Player,
+    maximizer: Player,
+    mut alpha: i32,
+    mut beta: i32,
+) -> i32 {
+    let empty_cells = empty_cells(board);
+    match game_result(board) {
+        GameResult::Win(player) => {
+            let value = 1 + empty_cells.len() as i32;
+            return if player == maximizer { value } else { -value };
+        }
+        GameResult::Draw => return 0,
+        GameResult::Ongoing => {}
+    }
+    let is_maximizing = to_move == maximizer;
+    let mut best = if is_maximizing { i32::MIN } else { i32::MAX };
+    for (col, row) in empty_cells {
+        let child = make_move(board, col, row, to_move).unwrap();
+        let value = minimax(&child, to_move.opponent(), maximizer, alpha, beta);
+        if is_maximizing {
+            best = best.max(value);
+            alpha = alpha.max(value);
+        } else {
+            best = best.min(value);
+            beta = beta.min(value);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// Returns an optimal move for `player` on the given board,
+/// as the `(col, row)` coordinates of the cell to place a mark in,
+/// or None if the game is already over.
+///
+/// If multiple moves are equally good, the first of them in row-major order is returned.
+pub fn best_move(board: &TTTBoard, player: Player) -> Option<CellIndex> {
+    if game_result(board) != GameResult::Ongoing {
+        return None;
+    }
+    let mut best: Option<(CellIndex, i32)> = None;
+    for (col, row) in empty_cells(board) {
+        let child = make_move(board, col, row, player).unwrap();
+        // Only moves that are strictly better than the best so far matter.
+        let alpha = best.map_or(i32::MIN, |(_, value)| value);
+        let value = minimax(&child, player.opponent(), player, alpha, i32::MAX);
+        if best.is_none_or(|(_, best_value)| value > best_value) {
+            best = Some(((col, row), value));
+        }
+    }
+    best.map(|(index, _)| index)
+    /*
+        Time complexity analysis:
+        The board has 9 cells, so the game tree has at most 9! leaves,
+        and this function completes in constant time.
+        More generally, for a board with `c` empty cells,
+        the search visits `O(c!)` positions, each taking `O(c)` time to process.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tic_tac_toe::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::collections::{HashMap, HashSet};
+
+    /// Creates a tic-tac-toe board.
+    ///
+    /// Syntax:
+    /// ```text
+    /// ttt![
+    ///     [<`X`, `O`, or `.` for empty> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! ttt {
+        (@cell X) => {Some(Player::X)};
+        (@cell O) => {Some(Player::O)};
+        (@cell .) => {None};
+        ($([$($cell:tt)*])*) => {
+            TTTBoard(Grid::from_2d_array([$([$(ttt!(@cell $cell)),*]),*]))
+        };
+    }
+
+    #[test]
+    fn test_make_move() {
+        let board = ttt![
+            [X . .]
+            [. O .]
+            [. . .]
+        ];
+        let expected = ttt![
+            [X . .]
+            [. O .]
+            [. X .]
+        ];
+        assert_eq!(make_move(&board, 1, 2, Player::X), Some(expected));
+    }
+
+    #[rstest]
+    #[case::occupied(1, 1)]
+    #[case::occupied_by_self(0, 0)]
+    #[case::out_of_bounds(3, 0)]
+    fn test_make_move_invalid(#[case] col: usize, #[case] row: usize) {
+        let board = ttt![
+            [X . .]
+            [. O .]
+            [. . .]
+        ];
+        assert_eq!(make_move(&board, col, row, Player::X), None);
+    }
+
+    #[rstest]
+    #[case::row(ttt![
+        [. . .]
+        [O O O]
+        [X X .]
+    ], GameResult::Win(Player::O))]
+    #[case::column(ttt![
+        [X O .]
+        [X O .]
+        [X . .]
+    ], GameResult::Win(Player::X))]
+    #[case::diagonal(ttt![
+        [X O .]
+        [O X .]
+        [. . X]
+    ], GameResult::Win(Player::X))]
+    #[case::anti_diagonal(ttt![
+        [X X O]
+        [. O .]
+        [O X .]
+    ], GameResult::Win(Player::O))]
+    #[case::full_board_win(ttt![
+        [X O X]
+        [O X O]
+        [O X X]
+    ], GameResult::Win(Player::X))]
+    #[case::draw(ttt![
+        [X O X]
+        [X O O]
+        [O X X]
+    ], GameResult::Draw)]
+    #[case::ongoing(ttt![
+        [X O .]
+        [. X O]
+        [. . .]
+    ], GameResult::Ongoing)]
+    #[case::empty(TTTBoard::new(), GameResult::Ongoing)]
+    fn test_game_result(#[case] board: TTTBoard, #[case] expected: GameResult) {
+        assert_eq!(game_result(&board), expected);
+    }
+
+    #[test]
+    fn test_best_move_empty_board() {
+        let corners_and_center = [(0, 0), (2, 0), (1, 1), (0, 2), (2, 2)];
+        let actual = best_move(&TTTBoard::new(), Player::X).unwrap();
+        assert!(
+            corners_and_center.contains(&actual),
+            "unexpected move {actual:?}"
+        );
+    }
+
+    #[rstest]
+    #[case::problem_description_example(ttt![
+        [X O .]
+        [. X O]
+        [. . .]
+    ], Player::X, (2, 2))]
+    // Winning immediately is preferred over blocking.
+    #[case::win_over_block(ttt![
+        [X X .]
+        [O O .]
+        [X . .]
+    ], Player::O, (2, 1))]
+    #[case::block(ttt![
+        [X X .]
+        [. O .]
+        [. . .]
+    ], Player::O, (2, 0))]
+    // After X takes opposite corners, O must play on an edge to avoid a fork.
+    #[case::avoid_fork(ttt![
+        [X . .]
+        [. O .]
+        [. . X]
+    ], Player::O, (1, 0))]
+    #[case::last_cell(ttt![
+        [X O X]
+        [X O O]
+        [O X .]
+    ], Player::X, (2, 2))]
+    fn test_best_move(
+        #[case] board: TTTBoard,
+        #[case] player: Player,
+        #[case] expected: CellIndex,
+    ) {
+        assert_eq!(best_move(&board, player), Some(expected));
+    }
+
+    #[rstest]
+    #[case::won(ttt![
+        [X X X]
+        [O O .]
+        [. . .]
+    ])]
+    #[case::draw(ttt![
+        [X O X]
+        [X O O]
+        [O X X]
+    ])]
+    fn test_best_move_game_over(#[case] board: TTTBoard) {
+        assert_eq!(best_move(&board, Player::O), None);
+    }
+
+    /// Plays out every possible sequence of moves by the opponent from the given position,
+    /// with `ai` always choosing its moves using `best_move`,
+    /// and asserts that the opponent never wins.
+    fn assert_never_loses(board: &TTTBoard, to_move: Player, ai: Player) {
+        match game_result(board) {
+            GameResult::Win(player) => assert_eq!(player, ai, "lost on {board:?}"),
+            GameResult::Draw => {}
+            GameResult::Ongoing if to_move == ai => {
+                let (col, row) = best_move(board, ai).unwrap();
+                let child = make_move(board, col, row, ai).unwrap();
+                assert_never_loses(&child, ai.opponent(), ai);
+            }
+            GameResult::Ongoing => {
+                for (col, row) in empty_cells(board) {
+                    let child = make_move(board, col, row, to_move).unwrap();
+                    assert_never_loses(&child, ai, ai);
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::first(Player::X)]
+    #[case::second(Player::O)]
+    fn test_never_loses(#[case] ai: Player) {
+        assert_never_loses(&TTTBoard::new(), Player::X, ai);
+    }
+
+    /// Returns the contents of each cell of the given board, in row-major order.
+    fn cells(board: &TTTBoard) -> Vec<Option<Player>> {
+        let TTTBoard(grid) = board;
+        grid.enumerate::<CellIndex>()
+            .map(|(_, &cell)| cell)
+            .collect()
+    }
+
+    /// Returns the game-theoretic outcome of the given position from the perspective of `player`
+    /// (1 for a win, 0 for a draw, -1 for a loss), with `to_move` about to move,
+    /// using plain minimax without pruning (memoized in `memo`).
+    fn outcome(
+        board: &TTTBoard,
+        to_move: Player,
+        player: Player,
+        memo: &mut HashMap<(Vec<Option<Player>>, Player), i32>,
+    ) -> i32 {
+        match game_result(board) {
+            GameResult::Win(winner) => return if winner == player { 1 } else { -1 },
+            GameResult::Draw => return 0,
+            GameResult::Ongoing => {}
+        }
+        let key = (cells(board), to_move);
+        if let Some(&result) = memo.get(&key) {
+            // Memoized outcomes are from the perspective of the player to move.
+            return if to_move == player { result } else { -result };
+        }
+        let result = empty_cells(board)
+            .into_iter()
+            .map(|(col, row)| {
+                let child = make_move(board, col, row, to_move).unwrap();
+                outcome(&child, to_move.opponent(), to_move, memo)
+            })
+            .max()
+            .unwrap();
+        memo.insert(key, result);
+        if to_move == player {
+            result
+        } else {
+            -result
+        }
+    }
+
+    #[test]
+    fn test_best_move_optimal_everywhere() {
+        // From every reachable position, the chosen move preserves the best achievable outcome.
+        let mut memo = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut stack = vec![(TTTBoard::new(), Player::X)];
+        while let Some((board, to_move)) = stack.pop() {
+            if game_result(&board) != GameResult::Ongoing || !seen.insert(cells(&board)) {
+                continue;
+            }
+            let (col, row) = best_move(&board, to_move).unwrap();
+            let child = make_move(&board, col, row, to_move).unwrap();
+            assert_eq!(
+                outcome(&child, to_move.opponent(), to_move, &mut memo),
+                outcome(&board, to_move, to_move, &mut memo),
+                "suboptimal move {:?} on {board:?}",
+                (col, row)
+            );
+            for (col, row) in empty_cells(&board) {
+                let child = make_move(&board, col, row, to_move).unwrap();
+                stack.push((child, to_move.opponent()));
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! An L-tromino is a shape made of three squares in an L shape:
+//! a 2×2 square with one of its cells removed.
+//!
+//! Problem: given `n` and a cell of a `2^n`×`2^n` board,
+//! cover every cell of the board except the given one with non-overlapping L-trominoes.
+//!
+//! This is always possible, by divide and conquer:
+//! split the board into four `2^(n-1)`×`2^(n-1)` quadrants,
+//! one of which contains the missing cell.
+//! Placing a tromino on the three center cells belonging to the other quadrants
+//! leaves each quadrant with exactly one cell that doesn't need to be covered,
+//! so each quadrant can then be tiled in the same way.
+//!
+//! For example, with `n = 2` and the missing cell at the top left
+//! (`.` for the missing cell, and letters for trominoes):
+//! ```text
+//! . B C C
+//! B B A C
+//! D A A E
+//! D D E E
+//! ```
+
+type CellIndex = (usize, usize);
+
+/// An L-tromino placed on the board, covering the given cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrominoPlacement {
+    pub cells: [CellIndex; 3],
+}
+
+/// Tiles the square with the given top left corner and size (a power of 2),
+/// except for the given missing cell, adding the placed trominoes to `placements`.
+fn tile(
+    corner: CellIndex,
+    size: usize,
+    missing: CellIndex,
+    placements: &mut Vec<TrominoPlacement>,
+) {
+    if size == 1 {
+        return;
+    }
+    let half = size / 2;
+    let (x, y) = corner;
+    let (center_x, center_y) = (x + half, y + half);
+    // The quadrants in row-major order, each with the cell closest to the center of the square.
+    let quadrants = [
+        ((x, y), (center_x - 1, center_y - 1)),
+        ((center_x, y), (center_x, center_y - 1)),
+        ((x, center_y), (center_x - 1, center_y)),
+        ((center_x, center_y), (center_x, center_y)),
+    ];
+    let contains = |(qx, qy): CellIndex, (mx, my): CellIndex| {
+        (qx..qx + half).contains(&mx) && (qy..qy + half).contains(&my)
+    };
+    let quadrant_missing = quadrants.map(|(quadrant, center_cell)| {
+        if contains(quadrant, missing) {
+            missing
+        } else {
+            center_cell
+        }
+    });
+    let center_cells: Vec<_> = quadrants
+        .iter()
+        .filter(|&&(quadrant, _)| !contains(quadrant, missing))
+        .map(|&(_, center_cell)| center_cell)
+        .collect();
+    placements.push(TrominoPlacement {
+        cells: center_cells.try_into().unwrap(),
+    });
+    for ((quadrant, _), quadrant_missing) in quadrants.into_iter().zip(quadrant_missing) {
+        tile(quadrant, half, quadrant_missing, placements);
+    }
+}
+
+/// Returns a tiling of a `2^n`×`2^n` board with L-trominoes,
+/// covering every cell except for `missing`.
+///
+/// The cells of each tromino are listed in row-major order.
+///
+/// Panics if `missing` is out of bounds.
+pub fn tile_board(n: usize, missing: CellIndex) -> Vec<TrominoPlacement> {
+    let size = 1 << n;
+    let (x, y) = missing;
+    if x >= size || y >= size {
+        panic!(
+            "cell {missing:?} out of bounds for dimensions {:?}",
+            (size, size)
+        );
+    }
+    let mut placements = vec![];
+    tile((0, 0), size, missing, &mut placements);
+    placements
+    /*
+        Time complexity analysis:
+        This function completes in `O(4^n)` time in the worst case,
+        which is linear in the number of cells.
+        - Tiling a square of size `2^k` places one tromino
+          and then tiles four squares of size `2^(k-1)`,
+          so the number of calls to `tile` is `1 + 4 + ... + 4^n = O(4^n)`.
+        - Each call takes `O(1)` time, apart from the recursive calls.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+    use crate::tromino_tiling::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Checks that the given placements are L-trominoes
+    /// that together cover every cell of the `2^n`×`2^n` board except for `missing` exactly once.
+    fn assert_valid(placements: &[TrominoPlacement], n: usize, missing: CellIndex) {
+        let size = 1 << n;
+        assert_eq!(placements.len(), (size * size - 1) / 3);
+        let mut num_covers = Grid::filled(0, (size, size));
+        for placement in placements {
+            let [a, b, c] = placement.cells;
+            assert!(a != b && b != c && a != c, "repeated cell: {placement:?}");
+            // Three distinct cells in a 2×2 square always form an L shape.
+            let xs = placement.cells.map(|(x, _)| x);
+            let ys = placement.cells.map(|(_, y)| y);
+            let width = xs.iter().max().unwrap() - xs.iter().min().unwrap();
+            let height = ys.iter().max().unwrap() - ys.iter().min().unwrap();
+            assert!(width == 1 && height == 1, "not an L shape: {placement:?}");
+            for cell in placement.cells {
+                num_covers[cell] += 1;
+            }
+        }
+        for (index, &count) in num_covers.enumerate::<CellIndex>() {
+            let expected = if index == missing { 0 } else { 1 };
+            assert_eq!(
+                count, expected,
+                "wrong number of trominoes covering {index:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_n_0() {
+        assert_eq!(tile_board(0, (0, 0)), vec![]);
+    }
+
+    #[rstest]
+    #[case::top_left((0, 0), [(1, 0), (0, 1), (1, 1)])]
+    #[case::top_right((1, 0), [(0, 0), (0, 1), (1, 1)])]
+    #[case::bottom_left((0, 1), [(0, 0), (1, 0), (1, 1)])]
+    #[case::bottom_right((1, 1), [(0, 0), (1, 0), (0, 1)])]
+    fn test_n_1(#[case] missing: CellIndex, #[case] expected: [CellIndex; 3]) {
+        assert_eq!(
+            tile_board(1, missing),
+            vec![TrominoPlacement { cells: expected }]
+        );
+    }
+
+    #[test]
+    fn test_n_2_example() {
+        let expected = [
+            [(2, 1), (1, 2), (2, 2)],
+            [(1, 0), (0, 1), (1, 1)],
+            [(2, 0), (3, 0), (3, 1)],
+            [(0, 2), (0, 3), (1, 3)],
+            [(3, 2), (2, 3), (3, 3)],
+        ];
+        let expected: Vec<_> = expected
+            .into_iter()
+            .map(|cells| TrominoPlacement { cells })
+            .collect();
+        assert_eq!(tile_board(2, (0, 0)), expected);
+    }
+
+    #[rstest]
+    fn test_every_missing_cell(#[values(1, 2, 3)] n: usize) {
+        let size = 1 << n;
+        for y in 0..size {
+            for x in 0..size {
+                let placements = tile_board(n, (x, y));
+                assert_valid(&placements, n, (x, y));
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::n_2(2, (3, 1), 5)]
+    #[case::n_4(4, (5, 11), 85)]
+    #[case::n_6(6, (63, 0), 1365)]
+    fn test_tile_board(#[case] n: usize, #[case] missing: CellIndex, #[case] num_trominoes: usize) {
+        let placements = tile_board(n, missing);
+        assert_eq!(placements.len(), num_trominoes);
+        assert_valid(&placements, n, missing);
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (2, 0) out of bounds for dimensions (2, 2)")]
+    fn test_out_of_bounds() {
+        tile_board(1, (2, 0));
+    }
+}
@@ -0,0 +1,114 @@
+//! Helper module for keeping track of which vertices of a graph are connected to each other,
+//! as edges are added to the graph.
+//!
+//! The vertices are stored as a union-find forest:
+//! each vertex points at a parent in the same tree (or at itself, for the root of a tree),
+//! and two vertices are connected if and only if their trees have the same root.
+//! Finding a root points every vertex on the way directly at the root (path compression),
+//! so that later lookups are faster.
+
+/// A union-find forest of the vertices `0..len`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Creates a forest of `len` vertices, none of which are connected to each other.
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..len).collect(),
+        }
+    }
+
+    /// The number of vertices in the forest.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns whether the forest has no vertices.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the root of the tree containing the given vertex,
+    /// pointing every vertex on the way directly at the root.
+    ///
+    /// Panics if the vertex is out of bounds.
+    pub fn find(&mut self, vertex: usize) -> usize {
+        let mut root = vertex;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = vertex;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Connects the given vertices, returning false if they were already connected.
+    ///
+    /// The root of `b`'s tree becomes the root of the merged tree.
+    ///
+    /// Panics if either vertex is out of bounds.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+        self.parents[root_a] = root_b;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::union_find::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_new() {
+        let mut forest = UnionFind::new(3);
+        assert_eq!(forest.len(), 3);
+        assert!(!forest.is_empty());
+        assert_eq!(
+            (0..3).map(|v| forest.find(v)).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert!(UnionFind::new(0).is_empty());
+    }
+
+    #[test]
+    fn test_union() {
+        let mut forest = UnionFind::new(5);
+        assert!(forest.union(0, 1));
+        assert!(forest.union(2, 3));
+        assert!(forest.union(1, 3));
+        assert!(!forest.union(0, 2));
+        assert_eq!(forest.find(0), forest.find(3));
+        assert_ne!(forest.find(0), forest.find(4));
+        // The root of the second vertex's tree is kept.
+        assert!(forest.union(4, 0));
+        assert_eq!(forest.find(4), 3);
+    }
+
+    #[test]
+    fn test_path_compression() {
+        let mut forest = UnionFind::new(4);
+        for vertex in 0..3 {
+            forest.union(vertex, vertex + 1);
+        }
+        assert_eq!(forest.parents, [1, 2, 3, 3]);
+        assert_eq!(forest.find(0), 3);
+        assert_eq!(forest.parents, [3, 3, 3, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn test_out_of_bounds() {
+        UnionFind::new(2).find(2);
+    }
+}
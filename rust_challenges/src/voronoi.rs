@@ -0,0 +1,291 @@
+//! Problem: given a grid with some cells marked as _seeds_,
+//! compute the grid's Voronoi diagram --
+//! assign each cell to the seed that is closest to it.
+//!
+//! Distances are measured as the number of orthogonal steps between cells
+//! (equivalently, the Manhattan distance).
+//! Ties are broken in favor of the seed with the lexicographically smaller `(x, y)` coordinates.
+//!
+//! For example, with seeds `a` at `(0, 0)` and `b` at `(4, 2)`:
+//! ```text
+//! a . . . .
+//! . . . . .
+//! . . . . b
+//! ```
+//! the Voronoi diagram is:
+//! ```text
+//! a a a a b
+//! a a a b b
+//! a a b b b
+//! ```
+//! Note that cells such as `(3, 0)` are equally close to both seeds, and are assigned to `a`.
+
+use crate::flood_fill::Connectivity;
+use crate::grid::Grid;
+use std::collections::VecDeque;
+
+type SeedCoordinates = (usize, usize);
+
+/// Computes the Voronoi diagram for the seeds in the given grid,
+/// where a cell is a seed if its element satisfies `is_seed`.
+///
+/// Returns a grid that maps the coordinates of each cell
+/// to the coordinates of the closest seed, or None if there are no seeds.
+pub fn voronoi_diagram<T>(
+    grid: &Grid<T>,
+    is_seed: impl Fn(&T) -> bool,
+) -> Grid<Option<SeedCoordinates>> {
+    // Multi-source breadth-first search: all seeds start out in the queue at distance 0.
+    let mut diagram: Grid<Option<SeedCoordinates>> = Grid::filled(None, grid.dimensions());
+    let mut distances = Grid::filled(None, grid.dimensions());
+    let mut queue = VecDeque::new();
+    for ((x, y), element) in grid.enumerate::<(usize, usize)>() {
+        if is_seed(element) {
+            diagram[(x, y)] = Some((x, y));
+            distances[(x, y)] = Some(0);
+            queue.push_back((x as i32, y as i32));
+        }
+    }
+    while let Some((x, y)) = queue.pop_front() {
+        // By the time a cell is taken off the queue, every cell at the previous distance
+        // has been processed, so the seed for the cell is final.
+        let seed = diagram[(x, y)];
+        let distance = distances[(x, y)].unwrap() + 1;
+        for (dx, dy) in Connectivity::Four.displacements() {
+            let neighbor = (x + dx, y + dy);
+            let Some(&neighbor_distance) = distances.get(neighbor) else {
+                continue;
+            };
+            match neighbor_distance {
+                None => {
+                    distances[neighbor] = Some(distance);
+                    diagram[neighbor] = seed;
+                    queue.push_back(neighbor);
+                }
+                // Another seed at the same distance from the neighbor: keep the smaller one.
+                Some(neighbor_distance) if neighbor_distance == distance => {
+                    diagram[neighbor] = diagram[neighbor].min(seed);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    diagram
+    /*
+        Time complexity analysis:
+        Let `c` be the number of cells in the grid.
+        This function completes in `O(c)` time in the worst case,
+        assuming that `is_seed` completes in `O(1)` time.
+        - Creating the grids and finding the seeds takes `O(c)` time.
+        - Each cell is added to the queue at most once,
+          and processing a cell from the queue takes `O(1)` time.
+    */
+}
+
+/// Returns a grid indicating which cells of the given Voronoi diagram lie on a boundary:
+/// cells with at least one orthogonal neighbor assigned to a different seed.
+pub fn voronoi_boundaries(diagram: &Grid<Option<SeedCoordinates>>) -> Grid<bool> {
+    let mut boundaries = Grid::filled(false, diagram.dimensions());
+    for ((x, y), seed) in diagram.enumerate::<(i32, i32)>() {
+        boundaries[(x, y)] = Connectivity::Four
+            .displacements()
+            .iter()
+            .filter_map(|(dx, dy)| diagram.get((x + dx, y + dy)))
+            .any(|neighbor_seed| neighbor_seed != seed);
+    }
+    boundaries
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voronoi::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a grid of seed markers, with `a` to `d` marking seeds.
+    ///
+    /// Syntax:
+    /// ```text
+    /// seeds![
+    ///     [<`.` for a regular cell, `a`, `b`, `c`, or `d` for a seed> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! seeds {
+        (@cell .) => {None};
+        (@cell $seed:ident) => {Some(stringify!($seed))};
+        ($([$($cell:tt)*])*) => {
+            Grid::<Option<&str>>::from_2d_array([$([$(seeds!(@cell $cell)),*]),*])
+        };
+    }
+
+    /// Test case macro for Voronoi diagrams.
+    ///
+    /// Syntax:
+    /// ```text
+    /// test! {<test name>: <seeds grid> => [
+    ///     [<seed name>, ...],
+    ///     ...
+    /// ], seeds {
+    ///     <seed name>: <coordinates of seed>,
+    ///     ...
+    /// }}
+    /// ```
+    macro_rules! test {
+        ($name:ident: $seeds:expr => $diagram:expr, seeds {$($var:ident: $seed:expr,)*}) => {
+            #[test]
+            fn $name() {
+                $(let $var = Some($seed);)*
+                let actual = voronoi_diagram(&$seeds, |seed| seed.is_some());
+                let expected = Grid::from_2d_array($diagram);
+                assert_eq!(actual, expected);
+            }
+        };
+    }
+
+    test! {single_seed: seeds![
+        [. . . .]
+        [. . a .]
+        [. . . .]
+    ] => [
+        [a, a, a, a],
+        [a, a, a, a],
+        [a, a, a, a],
+    ], seeds {
+        a: (2, 1),
+    }}
+
+    test! {two_seeds: seeds![
+        [a . . . . .]
+        [. . . . . .]
+        [. . . . . b]
+    ] => [
+        [a, a, a, a, b, b],
+        [a, a, a, b, b, b],
+        [a, a, b, b, b, b],
+    ], seeds {
+        a: (0, 0),
+        b: (5, 2),
+    }}
+
+    test! {problem_description_example: seeds![
+        [a . . . .]
+        [. . . . .]
+        [. . . . b]
+    ] => [
+        [a, a, a, a, b],
+        [a, a, a, b, b],
+        [a, a, b, b, b],
+    ], seeds {
+        a: (0, 0),
+        b: (4, 2),
+    }}
+
+    // Cells in the middle column are equally close to both seeds.
+    test! {tie_in_row: seeds![
+        [b . . . a]
+    ] => [
+        [b, b, b, a, a],
+    ], seeds {
+        a: (4, 0),
+        b: (0, 0),
+    }}
+
+    // Cells on the main diagonal are equally close to both seeds, and `b` is at (0, 2) < (2, 0).
+    test! {tie_on_diagonal: seeds![
+        [. . a]
+        [. . .]
+        [b . .]
+    ] => [
+        [b, a, a],
+        [b, b, a],
+        [b, b, b],
+    ], seeds {
+        a: (2, 0),
+        b: (0, 2),
+    }}
+
+    test! {four_corners: seeds![
+        [a . . . b]
+        [. . . . .]
+        [. . . . .]
+        [c . . . d]
+    ] => [
+        [a, a, a, b, b],
+        [a, a, a, b, b],
+        [c, c, c, d, d],
+        [c, c, c, d, d],
+    ], seeds {
+        a: (0, 0),
+        b: (4, 0),
+        c: (0, 3),
+        d: (4, 3),
+    }}
+
+    test! {all_seeds: seeds![
+        [a b]
+        [c d]
+    ] => [
+        [a, b],
+        [c, d],
+    ], seeds {
+        a: (0, 0),
+        b: (1, 0),
+        c: (0, 1),
+        d: (1, 1),
+    }}
+
+    #[test]
+    fn test_no_seeds() {
+        let grid = seeds![
+            [. . .]
+            [. . .]
+        ];
+        let actual = voronoi_diagram(&grid, |seed| seed.is_some());
+        assert_eq!(actual, Grid::filled(None, (3, 2)));
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 0)]
+    #[case(0, 3)]
+    fn test_empty_grids(#[case] width: usize, #[case] height: usize) {
+        let actual = voronoi_diagram(&Grid::filled(true, (width, height)), |&seed| seed);
+        assert_eq!(actual, Grid::filled(None, (width, height)));
+        assert_eq!(
+            voronoi_boundaries(&actual),
+            Grid::filled(false, (width, height))
+        );
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let grid = seeds![
+            [a . . . . .]
+            [. . . . . .]
+            [. . . . . b]
+        ];
+        let diagram = voronoi_diagram(&grid, |seed| seed.is_some());
+        // Regions:
+        // a a a a b b
+        // a a a b b b
+        // a a b b b b
+        #[rustfmt::skip]
+        let expected = Grid::from_2d_array([
+            [false, false, false, true,  true,  false],
+            [false, false, true,  true,  false, false],
+            [false, true,  true,  false, false, false],
+        ]);
+        assert_eq!(voronoi_boundaries(&diagram), expected);
+    }
+
+    #[test]
+    fn test_boundaries_single_region() {
+        let grid = seeds![
+            [. . .]
+            [. a .]
+        ];
+        let diagram = voronoi_diagram(&grid, |seed| seed.is_some());
+        assert_eq!(voronoi_boundaries(&diagram), Grid::filled(false, (3, 2)));
+    }
+}
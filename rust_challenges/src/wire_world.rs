@@ -0,0 +1,207 @@
+//! WireWorld is a cellular automaton for simulating electronic circuits.
+//! Each cell is empty, a conductor, or part of an electron (its head or its tail).
+//! At each step, every cell changes according to these rules:
+//! - an empty cell stays empty,
+//! - an electron head becomes an electron tail,
+//! - an electron tail becomes a conductor, and
+//! - a conductor becomes an electron head if exactly 1 or 2 of its 8 neighbors are electron heads,
+//!   and stays a conductor otherwise.
+//!
+//! Problem: simulate WireWorld.
+//!
+//! For example (`.` for empty, `#` for conductor, `H` for electron head, `t` for electron tail),
+//! an electron moves one cell per step along a wire:
+//! ```text
+//! t H # # #
+//! # t H # #
+//! # # t H #
+//! ```
+//! Electrons can also be combined with wires in interesting shapes to make logic gates.
+//! For example, in this XOR gate, an electron arriving from either input on the left
+//! reaches the output on the right, but when electrons arrive from both inputs at once,
+//! the cell where the inputs meet sees three electron heads and doesn't let either through:
+//! ```text
+//! # # # # # . . .
+//! . . . . # # # #
+//! # # # # # . . .
+//! ```
+
+use crate::flood_fill::Connectivity;
+use crate::grid::Grid;
+
+/// A cell in WireWorld.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WireCell {
+    Empty,
+    ElectronHead,
+    ElectronTail,
+    Conductor,
+}
+
+/// Returns the result of a single step of the simulation on the given grid.
+pub fn step(grid: &Grid<WireCell>) -> Grid<WireCell> {
+    let mut result = grid.clone();
+    for ((x, y), &cell) in grid.enumerate::<(i32, i32)>() {
+        result[(x, y)] = match cell {
+            WireCell::Empty => WireCell::Empty,
+            WireCell::ElectronHead => WireCell::ElectronTail,
+            WireCell::ElectronTail => WireCell::Conductor,
+            WireCell::Conductor => {
+                let num_heads = Connectivity::Eight
+                    .displacements()
+                    .iter()
+                    .filter(|(dx, dy)| grid.get((x + dx, y + dy)) == Some(&WireCell::ElectronHead))
+                    .count();
+                if matches!(num_heads, 1 | 2) {
+                    WireCell::ElectronHead
+                } else {
+                    WireCell::Conductor
+                }
+            }
+        }
+    }
+    result
+    /*
+        Time complexity analysis:
+        This function completes in `O(c)` time in the worst case,
+        where `c` is the number of cells in the grid,
+        since each cell has at most 8 neighbors to check.
+    */
+}
+
+/// Returns the result of the given number of steps of the simulation on the given grid.
+pub fn run(grid: Grid<WireCell>, steps: usize) -> Grid<WireCell> {
+    (0..steps).fold(grid, |grid, _| step(&grid))
+    /*
+        Time complexity analysis:
+        This function completes in `O(s * c)` time in the worst case,
+        where `s` is the number of steps and `c` is the number of cells in the grid.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wire_world::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a grid of WireWorld cells.
+    ///
+    /// Syntax:
+    /// ```text
+    /// wire_world![
+    ///     [<`.` for empty, `#` for conductor, `H` for electron head, `t` for electron tail> ...]
+    ///     ...
+    /// ]
+    /// ```
+    macro_rules! wire_world {
+        (@cell .) => {WireCell::Empty};
+        (@cell #) => {WireCell::Conductor};
+        (@cell H) => {WireCell::ElectronHead};
+        (@cell t) => {WireCell::ElectronTail};
+        ($([$($cell:tt)*])*) => {
+            Grid::from_2d_array([$([$(wire_world!(@cell $cell)),*]),*])
+        };
+    }
+
+    #[rstest]
+    #[case::empty_0_0((0, 0))]
+    #[case::empty_3_0((3, 0))]
+    #[case::empty_0_3((0, 3))]
+    fn test_empty(#[case] dimensions: (usize, usize)) {
+        let grid = Grid::filled(WireCell::Empty, dimensions);
+        assert_eq!(step(&grid), grid);
+    }
+
+    #[rstest]
+    #[case::each_cell(wire_world![[. H t #]], wire_world![[. t # #]])]
+    #[case::one_head(wire_world![[H #] [# #]], wire_world![[t H] [H H]])]
+    #[case::two_heads(wire_world![[H # H]], wire_world![[t H t]])]
+    #[case::three_heads(wire_world![[H H] [H #]], wire_world![[t t] [t #]])]
+    fn test_step(#[case] grid: Grid<WireCell>, #[case] expected: Grid<WireCell>) {
+        assert_eq!(step(&grid), expected);
+    }
+
+    #[test]
+    fn test_wire() {
+        let mut grid = wire_world![[t H # # #]];
+        let expected = [
+            wire_world![[# t H # #]],
+            wire_world![[# # t H #]],
+            wire_world![[# # # t H]],
+            wire_world![[# # # # t]],
+            wire_world![[# # # # #]],
+            wire_world![[# # # # #]],
+        ];
+        for expected in expected {
+            grid = step(&grid);
+            assert_eq!(grid, expected);
+        }
+    }
+
+    #[test]
+    fn test_diode() {
+        let forward = wire_world![
+            [. . # # . . .]
+            [t H # . # # #]
+            [. . # # . . .]
+        ];
+        let expected = wire_world![
+            [. . # # . . .]
+            [# # # . # t H]
+            [. . # # . . .]
+        ];
+        assert_eq!(run(forward, 5), expected);
+
+        // In the other direction, electrons get stuck where the wire splits into three.
+        let backward = wire_world![
+            [. . # # . . .]
+            [# # # . # H t]
+            [. . # # . . .]
+        ];
+        let stuck = wire_world![
+            [. . # # . . .]
+            [# # # . # # #]
+            [. . # # . . .]
+        ];
+        for steps in 5..10 {
+            assert_eq!(run(backward.clone(), steps), stuck);
+        }
+    }
+
+    /// Returns the XOR gate from the module documentation, with electrons at the inputs if given.
+    fn xor_gate(a: bool, b: bool) -> Grid<WireCell> {
+        let mut grid = wire_world![
+            [# # # # # . . . . . . .]
+            [. . . . # # # # # # # #]
+            [# # # # # . . . . . . .]
+        ];
+        for (y, input) in [(0, a), (2, b)] {
+            if input {
+                grid[(0, y)] = WireCell::ElectronTail;
+                grid[(1, y)] = WireCell::ElectronHead;
+            }
+        }
+        grid
+    }
+
+    #[rstest]
+    #[case::neither(false, false, false)]
+    #[case::a(true, false, true)]
+    #[case::b(false, true, true)]
+    #[case::both(true, true, false)]
+    fn test_xor_gate(#[case] a: bool, #[case] b: bool, #[case] expected: bool) {
+        let output = (11, 1);
+        let mut grid = xor_gate(a, b);
+        let mut num_outputs = 0;
+        for _ in 0..20 {
+            grid = step(&grid);
+            if grid[output] == WireCell::ElectronHead {
+                num_outputs += 1;
+            }
+        }
+        assert_eq!(num_outputs, usize::from(expected));
+        // Every electron eventually leaves the circuit.
+        assert_eq!(grid, xor_gate(false, false));
+    }
+}
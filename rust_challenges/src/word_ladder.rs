@@ -0,0 +1,248 @@
+//! A word ladder is a sequence of words, where each word is formed from the previous one
+//! by changing exactly one character.
+//!
+//! Problem: given a start word, an end word, and a dictionary,
+//! find a shortest word ladder from the start word to the end word
+//! using only words from the dictionary, and count how many shortest ladders there are.
+//!
+//! For example, with the dictionary `hit`, `hot`, `dot`, `dog`, `lot`, `log`, `cog`,
+//! there are two shortest ladders from `hit` to `cog`:
+//! ```text
+//! hit -> hot -> dot -> dog -> cog
+//! hit -> hot -> lot -> log -> cog
+//! ```
+//!
+//! Words are found with a breadth-first search from the start word.
+//! To find the neighbors of a word quickly, the dictionary words are grouped by _patterns_:
+//! a word with one of its characters left out. Two words are neighbors exactly when
+//! they share a pattern with the same character left out.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A word with one of its characters left out:
+/// the index of that character, and the other characters in order.
+type Pattern = (usize, Vec<char>);
+
+/// Returns every pattern of the given word.
+fn patterns(word: &str) -> impl Iterator<Item = Pattern> + '_ {
+    let chars: Vec<_> = word.chars().collect();
+    (0..chars.len()).map(move |i| {
+        let mut rest = chars.clone();
+        rest.remove(i);
+        (i, rest)
+    })
+}
+
+/// Information about a word found during the search.
+struct Visit<'a> {
+    /// The length of a shortest ladder from the start word to this word, minus 1.
+    distance: usize,
+    /// The number of shortest ladders from the start word to this word.
+    num_ladders: usize,
+    /// The previous word in one of the shortest ladders, or None for the start word.
+    previous: Option<&'a str>,
+}
+
+/// Searches outwards from `start` until every word as close to `start` as `end` has been found.
+///
+/// Returns None if `start` or `end` isn't in the dictionary, or they have different lengths.
+fn search<'a>(
+    start: &'a str,
+    end: &str,
+    dictionary: &'a HashSet<String>,
+) -> Option<HashMap<&'a str, Visit<'a>>> {
+    if !dictionary.contains(start)
+        || !dictionary.contains(end)
+        || start.chars().count() != end.chars().count()
+    {
+        return None;
+    }
+    let length = start.chars().count();
+    let mut words_by_pattern: HashMap<Pattern, Vec<&str>> = HashMap::new();
+    for word in dictionary
+        .iter()
+        .filter(|word| word.chars().count() == length)
+    {
+        for pattern in patterns(word) {
+            words_by_pattern.entry(pattern).or_default().push(word);
+        }
+    }
+    // Sorting makes the choice between equally short ladders deterministic.
+    for words in words_by_pattern.values_mut() {
+        words.sort();
+    }
+    let initial = Visit {
+        distance: 0,
+        num_ladders: 1,
+        previous: None,
+    };
+    let mut visited = HashMap::from([(start, initial)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(word) = queue.pop_front() {
+        let (distance, num_ladders) = (visited[word].distance, visited[word].num_ladders);
+        if visited
+            .get(end)
+            .is_some_and(|visit| visit.distance <= distance)
+        {
+            break;
+        }
+        for pattern in patterns(word) {
+            for &neighbor in &words_by_pattern[&pattern] {
+                match visited.get_mut(neighbor) {
+                    None => {
+                        let visit = Visit {
+                            distance: distance + 1,
+                            num_ladders,
+                            previous: Some(word),
+                        };
+                        visited.insert(neighbor, visit);
+                        queue.push_back(neighbor);
+                    }
+                    Some(visit) if visit.distance == distance + 1 => {
+                        visit.num_ladders += num_ladders;
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    Some(visited)
+}
+
+/// Returns a shortest word ladder from `start` to `end` (including both),
+/// where each word is in the dictionary
+/// and differs from the previous word in exactly one character.
+///
+/// Returns None if there is no such ladder,
+/// if `start` or `end` isn't in the dictionary, or if they have different lengths.
+/// If there are multiple shortest ladders, any one of them may be returned.
+pub fn word_ladder(start: &str, end: &str, dictionary: &HashSet<String>) -> Option<Vec<String>> {
+    let visited = search(start, end, dictionary)?;
+    let mut ladder = vec![end.to_string()];
+    let mut curr = visited.get(end)?;
+    while let Some(previous) = curr.previous {
+        ladder.push(previous.to_string());
+        curr = &visited[previous];
+    }
+    ladder.reverse();
+    Some(ladder)
+    /*
+        Time complexity analysis:
+        Let `w` be the number of words in the dictionary, and `l` the length of `start`.
+        This function completes in `O(w * l^2 * log(w) + e * l)` time in the worst case,
+        where `e` is the number of pairs of neighboring words (`e < w^2`),
+        assuming that hashing a pattern takes `O(l)` time.
+        - Creating and hashing the `l` patterns of each word takes `O(w * l^2)` time in total.
+        - Sorting the words for each pattern takes `O(w * l * log(w))` comparisons in total,
+          each taking `O(l)` time.
+        - The search looks at each word once, and at each pair of neighboring words
+          a constant number of times.
+          Finding the patterns of each word takes `O(l^2)` time,
+          and each neighbor found takes `O(l)` time to look up in `visited`.
+    */
+}
+
+/// Returns the number of distinct shortest word ladders from `start` to `end`,
+/// as described in [`word_ladder`].
+///
+/// Returns 0 in the same cases where `word_ladder` returns None.
+pub fn word_ladder_count(start: &str, end: &str, dictionary: &HashSet<String>) -> usize {
+    search(start, end, dictionary)
+        .and_then(|visited| visited.get(end).map(|visit| visit.num_ladders))
+        .unwrap_or(0)
+    /*
+        Time complexity analysis:
+        Same as `word_ladder`.
+    */
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::word_ladder::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    /// Creates a dictionary from the given words.
+    fn dictionary(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    /// Checks that the given ladder goes from `start` to `end`,
+    /// using only dictionary words that each differ from the previous word in exactly one character.
+    fn assert_valid(ladder: &[String], start: &str, end: &str, dictionary: &HashSet<String>) {
+        assert_eq!(ladder.first().map(String::as_str), Some(start));
+        assert_eq!(ladder.last().map(String::as_str), Some(end));
+        for word in ladder {
+            assert!(dictionary.contains(word), "{word} not in dictionary");
+        }
+        for pair in ladder.windows(2) {
+            let num_differences = pair[0]
+                .chars()
+                .zip(pair[1].chars())
+                .filter(|(a, b)| a != b)
+                .count();
+            assert_eq!(num_differences, 1, "invalid step: {pair:?}");
+        }
+    }
+
+    const HIT_COG: [&str; 7] = ["hit", "hot", "dot", "dog", "lot", "log", "cog"];
+
+    #[test]
+    fn test_hit_cog() {
+        let dictionary = dictionary(&HIT_COG);
+        let ladder = word_ladder("hit", "cog", &dictionary).unwrap();
+        assert_valid(&ladder, "hit", "cog", &dictionary);
+        assert_eq!(ladder.len(), 5);
+        assert_eq!(word_ladder_count("hit", "cog", &dictionary), 2);
+    }
+
+    #[rstest]
+    #[case::same_word(&["cat"], "cat", "cat", &["cat"])]
+    #[case::one_step(&["cat", "cot"], "cat", "cot", &["cat", "cot"])]
+    #[case::unique(&["cold", "cord", "card", "ward", "warm", "worm"], "cold", "warm",
+        &["cold", "cord", "card", "ward", "warm"])]
+    // The ladder can't take a shortcut through a word that isn't in the dictionary.
+    #[case::detour(&["ab", "eb", "ed", "cd"], "ab", "cd", &["ab", "eb", "ed", "cd"])]
+    #[case::unicode(&["née", "nie", "pie"], "née", "pie", &["née", "nie", "pie"])]
+    fn test_word_ladder(
+        #[case] words: &[&str],
+        #[case] start: &str,
+        #[case] end: &str,
+        #[case] expected: &[&str],
+    ) {
+        let dictionary = dictionary(words);
+        let expected: Vec<_> = expected.iter().map(|word| word.to_string()).collect();
+        assert_eq!(word_ladder(start, end, &dictionary), Some(expected));
+        assert_eq!(word_ladder_count(start, end, &dictionary), 1);
+    }
+
+    #[rstest]
+    #[case::start_not_in_dictionary(&HIT_COG[1..], "hit", "cog")]
+    #[case::end_not_in_dictionary(&HIT_COG[..6], "hit", "cog")]
+    #[case::different_lengths(&["cat", "cats"], "cat", "cats")]
+    #[case::unreachable(&["cat", "cot", "dog"], "cat", "dog")]
+    #[case::same_word_not_in_dictionary(&[], "cat", "cat")]
+    fn test_no_ladder(#[case] words: &[&str], #[case] start: &str, #[case] end: &str) {
+        let dictionary = dictionary(words);
+        assert_eq!(word_ladder(start, end, &dictionary), None);
+        assert_eq!(word_ladder_count(start, end, &dictionary), 0);
+    }
+
+    #[rstest]
+    #[case::square(&["aa", "ab", "ba", "bb"], "aa", "bb", 2)]
+    // Every choice of which character to change first gives a different ladder.
+    #[case::cube(&["aaa", "aab", "aba", "abb", "baa", "bab", "bba", "bbb"], "aaa", "bbb", 6)]
+    // Longer ladders aren't counted.
+    #[case::longer_ignored(&["aa", "ab", "bb", "bc", "ac", "cc"], "aa", "cc", 1)]
+    fn test_word_ladder_count(
+        #[case] words: &[&str],
+        #[case] start: &str,
+        #[case] end: &str,
+        #[case] expected: usize,
+    ) {
+        let dictionary = dictionary(words);
+        assert_eq!(word_ladder_count(start, end, &dictionary), expected);
+        let ladder = word_ladder(start, end, &dictionary).unwrap();
+        assert_valid(&ladder, start, end, &dictionary);
+    }
+}